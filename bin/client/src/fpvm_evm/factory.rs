@@ -105,3 +105,59 @@ where
         OpEvm::new(revm_evm, true)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fpvm_evm::precompiles::test_utils::test_accelerated_precompile;
+    use alloy_evm::Evm;
+    use alloy_primitives::{Address, Bytes, U256};
+    use kona_preimage::{BidirectionalChannel, HintWriter, OracleReader};
+    use revm::{context::CfgEnv, database::EmptyDB, precompile::u64_to_address};
+
+    /// A `modexp` call whose base is one byte longer than EIP-7823 allows.
+    fn oversized_modexp_input() -> Bytes {
+        let mut input = Vec::new();
+        input.extend_from_slice(&U256::from(1025).to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+        input.extend_from_slice(&[2u8; 1025]);
+        input.extend_from_slice(&[3, 5]);
+        input.into()
+    }
+
+    fn call_modexp<H, O>(factory: &FpvmOpEvmFactory<H, O>, spec: OpSpecId) -> bool
+    where
+        H: HintWriterClient + Clone + Send + Sync + 'static,
+        O: PreimageOracleClient + Clone + Send + Sync + 'static,
+    {
+        let env = EvmEnv::new(CfgEnv::new().with_spec(spec), BlockEnv::default());
+        let mut evm = factory.create_evm(EmptyDB::new(), env);
+        evm.transact_system_call(Address::ZERO, u64_to_address(5), oversized_modexp_input())
+            .unwrap()
+            .result
+            .is_success()
+    }
+
+    #[test]
+    fn test_osaka_evm_rejects_oversized_modexp() {
+        let (hint_chan, preimage_chan) =
+            (BidirectionalChannel::new().unwrap(), BidirectionalChannel::new().unwrap());
+        let factory = FpvmOpEvmFactory::new(
+            HintWriter::new(hint_chan.client),
+            OracleReader::new(preimage_chan.client),
+        );
+
+        // The call fails in the VM, without reaching out to the host.
+        assert!(!call_modexp(&factory, OpSpecId::OSAKA));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_isthmus_evm_accepts_oversized_modexp() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let factory = FpvmOpEvmFactory::new(hint_writer.clone(), oracle_reader.clone());
+            assert!(call_modexp(&factory, OpSpecId::ISTHMUS));
+        })
+        .await;
+    }
+}
@@ -16,7 +16,9 @@ mod bls12_pair;
 mod bn128_pair;
 mod ecrecover;
 mod kzg_point_eval;
+mod modexp;
+mod p256_verify;
 mod utils;
 
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
@@ -0,0 +1,249 @@
+//! Contains the accelerated version of the `modexp` precompile.
+
+use crate::fpvm_evm::precompiles::utils::precompile_run;
+use alloc::string::ToString;
+use alloy_primitives::U256;
+use kona_preimage::{HintWriterClient, PreimageOracleClient};
+use revm::precompile::{PrecompileError, PrecompileOutput, PrecompileResult, modexp};
+
+/// The length of the `modexp` input header, holding the lengths of the base, exponent and modulus.
+const HEADER_LEN: usize = 96;
+
+/// The maximum length of the base, exponent and modulus from Osaka onwards, per EIP-7823.
+const EIP7823_INPUT_SIZE_LIMIT: usize = 1024;
+
+/// Runs the FPVM-accelerated `modexp` precompile call, with the gas pricing of EIP-2565.
+pub(crate) fn fpvm_modexp<H, O>(
+    input: &[u8],
+    gas_limit: u64,
+    hint_writer: &H,
+    oracle_reader: &O,
+) -> PrecompileResult
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    fpvm_modexp_inner(input, gas_limit, hint_writer, oracle_reader, false)
+}
+
+/// Runs the FPVM-accelerated `modexp` precompile call, with the input limits of EIP-7823 and the
+/// gas pricing of EIP-7883.
+pub(crate) fn fpvm_modexp_osaka<H, O>(
+    input: &[u8],
+    gas_limit: u64,
+    hint_writer: &H,
+    oracle_reader: &O,
+) -> PrecompileResult
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    fpvm_modexp_inner(input, gas_limit, hint_writer, oracle_reader, true)
+}
+
+/// Runs the FPVM-accelerated `modexp` precompile call, with the rules of Osaka if `osaka` is set,
+/// or of Berlin otherwise.
+fn fpvm_modexp_inner<H, O>(
+    input: &[u8],
+    gas_limit: u64,
+    hint_writer: &H,
+    oracle_reader: &O,
+    osaka: bool,
+) -> PrecompileResult
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    let precompile = if osaka { modexp::OSAKA } else { modexp::BERLIN };
+
+    // Calls with lengths that do not fit in a `usize`, or that exceed the limits of EIP-7823, fail,
+    // and calls with an empty base and modulus return no data. None of them require any
+    // computation, so they are executed in the VM.
+    let (Some(base_len), Some(exp_len), Some(mod_len)) =
+        (read_len(input, 0), read_len(input, 32), read_len(input, 64))
+    else {
+        return precompile.execute(input, gas_limit);
+    };
+    if osaka && [base_len, exp_len, mod_len].iter().any(|&len| len > EIP7823_INPUT_SIZE_LIMIT) {
+        return precompile.execute(input, gas_limit);
+    }
+    if base_len == 0 && mod_len == 0 {
+        return precompile.execute(input, gas_limit);
+    }
+
+    // The first 32 bytes of the exponent, used to price the call.
+    let exp_start = HEADER_LEN.saturating_add(base_len);
+    let exp_highp_len = exp_len.min(32);
+    let mut exp_highp = [0u8; 32];
+    for (i, byte) in exp_highp[32 - exp_highp_len..].iter_mut().enumerate() {
+        *byte = input.get(exp_start.saturating_add(i)).copied().unwrap_or_default();
+    }
+
+    let gas_calc = if osaka { modexp::osaka_gas_calc } else { modexp::berlin_gas_calc };
+    let gas_used =
+        gas_calc(base_len as u64, exp_len as u64, mod_len as u64, &U256::from_be_bytes(exp_highp));
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    // Bytes past the modulus are ignored by the precompile, so they are not sent to the host.
+    let input_len = exp_start.saturating_add(exp_len).saturating_add(mod_len);
+    let truncated_input = &input[..input.len().min(input_len)];
+
+    let result_data = kona_proof::block_on(precompile_run! {
+        hint_writer,
+        oracle_reader,
+        &[precompile.address().as_slice(), &gas_used.to_be_bytes(), truncated_input]
+    })
+    .map_err(|e| PrecompileError::Other(e.to_string().into()))?;
+
+    Ok(PrecompileOutput::new(gas_used, result_data.into()))
+}
+
+/// Reads the 32-byte big-endian length at `offset` in the input header, padding the input with
+/// zeros. Returns `None` if the length does not fit in a `usize`.
+fn read_len(input: &[u8], offset: usize) -> Option<usize> {
+    let mut word = [0u8; 32];
+    let bytes = input.get(offset..).unwrap_or_default();
+    let len = bytes.len().min(32);
+    word[..len].copy_from_slice(&bytes[..len]);
+    usize::try_from(U256::from_be_bytes(word)).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fpvm_evm::precompiles::test_utils::{
+        execute_native_precompile, test_accelerated_precompile,
+    };
+    use alloy_primitives::{Bytes, hex};
+
+    // 3^(p-1) mod p, for the prime p = 2^256 - 2^32 - 977.
+    const TEST_INPUT: [u8; 161] = hex!(
+        "00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000002003fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2efffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+    );
+    const EXPECTED_RESULT: [u8; 32] =
+        hex!("0000000000000000000000000000000000000000000000000000000000000001");
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let accelerated_result =
+                fpvm_modexp(&TEST_INPUT, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(*modexp::BERLIN.address(), TEST_INPUT, u64::MAX).unwrap();
+
+            assert_eq!(accelerated_result.bytes.as_ref(), EXPECTED_RESULT.as_ref());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp_with_extra_bytes() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let mut input = TEST_INPUT.to_vec();
+            input.extend_from_slice(&[0xFF; 100]);
+
+            let accelerated_result =
+                fpvm_modexp(&input, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(*modexp::BERLIN.address(), input, u64::MAX).unwrap();
+
+            assert_eq!(accelerated_result.bytes.as_ref(), EXPECTED_RESULT.as_ref());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp_short_input() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            // The modulus is cut short, so it is padded with zeros.
+            let input = &TEST_INPUT[..150];
+
+            let accelerated_result =
+                fpvm_modexp(input, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(*modexp::BERLIN.address(), input.to_vec(), u64::MAX)
+                    .unwrap();
+
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp_empty() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let accelerated_result =
+                fpvm_modexp(&[], u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(*modexp::BERLIN.address(), Bytes::new(), u64::MAX)
+                    .unwrap();
+
+            assert!(accelerated_result.bytes.is_empty());
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp_osaka() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let accelerated_result =
+                fpvm_modexp_osaka(&TEST_INPUT, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result = modexp::OSAKA.execute(&TEST_INPUT, u64::MAX).unwrap();
+            let berlin_result =
+                execute_native_precompile(*modexp::BERLIN.address(), TEST_INPUT, u64::MAX).unwrap();
+
+            assert_eq!(accelerated_result.bytes.as_ref(), EXPECTED_RESULT.as_ref());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+            assert!(accelerated_result.gas_used > berlin_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp_osaka_input_limit() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            // A 1025 byte base exceeds the limit of EIP-7823.
+            let mut input = TEST_INPUT.to_vec();
+            input[30..32].copy_from_slice(&1025u16.to_be_bytes());
+            input.splice(96..97, [0u8; 1025]);
+
+            let accelerated_result =
+                fpvm_modexp_osaka(&input, u64::MAX, hint_writer, oracle_reader).unwrap_err();
+            let native_result = modexp::OSAKA.execute(&input, u64::MAX).unwrap_err();
+            assert_eq!(accelerated_result.to_string(), native_result.to_string());
+
+            // The same call succeeds before Osaka.
+            assert!(fpvm_modexp(&input, u64::MAX, hint_writer, oracle_reader).is_ok());
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_modexp_out_of_gas() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let gas_used =
+                execute_native_precompile(*modexp::BERLIN.address(), TEST_INPUT, u64::MAX)
+                    .unwrap()
+                    .gas_used;
+
+            let accelerated_result =
+                fpvm_modexp(&TEST_INPUT, gas_used - 1, hint_writer, oracle_reader).unwrap_err();
+            let native_result =
+                execute_native_precompile(*modexp::BERLIN.address(), TEST_INPUT, gas_used - 1)
+                    .unwrap_err();
+
+            assert!(matches!(accelerated_result, PrecompileError::OutOfGas));
+            assert!(matches!(native_result, PrecompileError::OutOfGas));
+        })
+        .await;
+    }
+}
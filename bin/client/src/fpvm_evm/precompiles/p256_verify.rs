@@ -0,0 +1,181 @@
+//! Contains the accelerated version of the RIP-7212 `p256Verify` precompile.
+
+use crate::fpvm_evm::precompiles::utils::precompile_run;
+use alloc::string::ToString;
+use alloy_primitives::{Address, Bytes};
+use kona_preimage::{HintWriterClient, PreimageOracleClient};
+use revm::precompile::{PrecompileError, PrecompileOutput, PrecompileResult};
+
+/// Address of the `p256Verify` precompile.
+pub(crate) const P256_VERIFY_ADDR: Address = revm::precompile::u64_to_address(0x100);
+
+/// The gas cost of a `p256Verify` call, per RIP-7212.
+const P256_VERIFY_BASE: u64 = 3_450;
+
+/// The gas cost of a `p256Verify` call from Osaka onwards, per EIP-7951.
+const P256_VERIFY_BASE_OSAKA: u64 = 6_900;
+
+/// Runs the FPVM-accelerated `p256Verify` precompile call.
+pub(crate) fn fpvm_p256_verify<H, O>(
+    input: &[u8],
+    gas_limit: u64,
+    hint_writer: &H,
+    oracle_reader: &O,
+) -> PrecompileResult
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    fpvm_p256_verify_inner(input, gas_limit, hint_writer, oracle_reader, P256_VERIFY_BASE)
+}
+
+/// Runs the FPVM-accelerated `p256Verify` precompile call, with the gas cost of EIP-7951.
+pub(crate) fn fpvm_p256_verify_osaka<H, O>(
+    input: &[u8],
+    gas_limit: u64,
+    hint_writer: &H,
+    oracle_reader: &O,
+) -> PrecompileResult
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    fpvm_p256_verify_inner(input, gas_limit, hint_writer, oracle_reader, P256_VERIFY_BASE_OSAKA)
+}
+
+/// Runs the FPVM-accelerated `p256Verify` precompile call, charging `base_gas` for it.
+fn fpvm_p256_verify_inner<H, O>(
+    input: &[u8],
+    gas_limit: u64,
+    hint_writer: &H,
+    oracle_reader: &O,
+    base_gas: u64,
+) -> PrecompileResult
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    const P256_VERIFY_INPUT_LEN: usize = 160;
+
+    if base_gas > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    // Inputs of any other length fail verification, which returns no data.
+    if input.len() != P256_VERIFY_INPUT_LEN {
+        return Ok(PrecompileOutput::new(base_gas, Bytes::new()));
+    }
+
+    let result_data = kona_proof::block_on(precompile_run! {
+        hint_writer,
+        oracle_reader,
+        &[P256_VERIFY_ADDR.as_slice(), &base_gas.to_be_bytes(), input]
+    })
+    .map_err(|e| PrecompileError::Other(e.to_string().into()))?;
+
+    Ok(PrecompileOutput::new(base_gas, result_data.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fpvm_evm::precompiles::test_utils::{
+        execute_native_precompile, test_accelerated_precompile,
+    };
+    use alloy_primitives::hex;
+
+    const TEST_INPUT: [u8; 160] = hex!(
+        "4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4da73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d604aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff37618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e"
+    );
+    const EXPECTED_RESULT: [u8; 32] =
+        hex!("0000000000000000000000000000000000000000000000000000000000000001");
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_p256_verify() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let accelerated_result =
+                fpvm_p256_verify(&TEST_INPUT, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(P256_VERIFY_ADDR, TEST_INPUT, u64::MAX).unwrap();
+
+            assert_eq!(accelerated_result.bytes.as_ref(), EXPECTED_RESULT.as_ref());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_p256_verify_invalid_signature() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let mut input = TEST_INPUT;
+            input[0] ^= 0xFF;
+
+            let accelerated_result =
+                fpvm_p256_verify(&input, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(P256_VERIFY_ADDR, input, u64::MAX).unwrap();
+
+            assert!(accelerated_result.bytes.is_empty());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_p256_verify_bad_input_len() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let input = &TEST_INPUT[..159];
+
+            let accelerated_result =
+                fpvm_p256_verify(input, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result =
+                execute_native_precompile(P256_VERIFY_ADDR, input.to_vec(), u64::MAX).unwrap();
+
+            assert!(accelerated_result.bytes.is_empty());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_p256_verify_out_of_gas() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let accelerated_result =
+                fpvm_p256_verify(&TEST_INPUT, 3_449, hint_writer, oracle_reader).unwrap_err();
+            let native_result =
+                execute_native_precompile(P256_VERIFY_ADDR, TEST_INPUT, 3_449).unwrap_err();
+
+            assert!(matches!(accelerated_result, PrecompileError::OutOfGas));
+            assert!(matches!(native_result, PrecompileError::OutOfGas));
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accelerated_p256_verify_osaka() {
+        test_accelerated_precompile(|hint_writer, oracle_reader| {
+            let accelerated_result =
+                fpvm_p256_verify_osaka(&TEST_INPUT, u64::MAX, hint_writer, oracle_reader).unwrap();
+            let native_result = revm::precompile::secp256r1::P256VERIFY_OSAKA
+                .execute(&TEST_INPUT, u64::MAX)
+                .unwrap();
+
+            assert_eq!(accelerated_result.bytes.as_ref(), EXPECTED_RESULT.as_ref());
+            assert_eq!(accelerated_result.bytes, native_result.bytes);
+            assert_eq!(accelerated_result.gas_used, native_result.gas_used);
+
+            let accelerated_result = fpvm_p256_verify_osaka(
+                &TEST_INPUT,
+                P256_VERIFY_BASE_OSAKA - 1,
+                hint_writer,
+                oracle_reader,
+            )
+            .unwrap_err();
+            assert!(matches!(accelerated_result, PrecompileError::OutOfGas));
+        })
+        .await;
+    }
+}
@@ -1,7 +1,7 @@
 //! [`PrecompileProvider`] for FPVM-accelerated OP Stack precompiles.
 
 use crate::fpvm_evm::precompiles::{
-    ecrecover::ECRECOVER_ADDR, kzg_point_eval::KZG_POINT_EVAL_ADDR,
+    ecrecover::ECRECOVER_ADDR, kzg_point_eval::KZG_POINT_EVAL_ADDR, p256_verify::P256_VERIFY_ADDR,
 };
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use alloy_primitives::{Address, Bytes};
@@ -14,7 +14,9 @@ use revm::{
     context::{Cfg, ContextTr},
     handler::{EthPrecompiles, PrecompileProvider},
     interpreter::{CallInputs, Gas, InstructionResult, InterpreterResult},
-    precompile::{PrecompileError, PrecompileResult, Precompiles, bls12_381_const, bn254},
+    precompile::{
+        PrecompileError, PrecompileResult, Precompiles, bls12_381_const, bn254, modexp, secp256r1,
+    },
     primitives::{hardfork::SpecId, hash_map::HashMap},
 };
 
@@ -48,19 +50,20 @@ where
             OpSpecId::ECOTONE) => Precompiles::new(spec.into_eth_spec().into()),
             OpSpecId::FJORD => fjord(),
             OpSpecId::GRANITE | OpSpecId::HOLOCENE => granite(),
-            OpSpecId::ISTHMUS | OpSpecId::INTEROP | OpSpecId::OSAKA | OpSpecId::JOVIAN => isthmus(),
+            OpSpecId::ISTHMUS | OpSpecId::INTEROP | OpSpecId::JOVIAN => isthmus(),
+            OpSpecId::OSAKA => osaka(),
         };
 
         let accelerated_precompiles = match spec {
             OpSpecId::BEDROCK | OpSpecId::REGOLITH | OpSpecId::CANYON => {
                 accelerated_bedrock::<H, O>()
             }
-            OpSpecId::ECOTONE | OpSpecId::FJORD => accelerated_ecotone::<H, O>(),
+            OpSpecId::ECOTONE => accelerated_ecotone::<H, O>(),
+            OpSpecId::FJORD => accelerated_fjord::<H, O>(),
             OpSpecId::GRANITE | OpSpecId::HOLOCENE => accelerated_granite::<H, O>(),
-            OpSpecId::ISTHMUS | OpSpecId::INTEROP | OpSpecId::OSAKA => {
-                accelerated_isthmus::<H, O>()
-            }
+            OpSpecId::ISTHMUS | OpSpecId::INTEROP => accelerated_isthmus::<H, O>(),
             OpSpecId::JOVIAN => accelerated_jovian::<H, O>(),
+            OpSpecId::OSAKA => accelerated_osaka::<H, O>(),
         };
 
         Self {
@@ -159,6 +162,17 @@ where
     }
 }
 
+/// Returns the precompiles for the osaka spec: the isthmus precompiles, with the `modexp` input
+/// limits and repricing of EIP-7823 and EIP-7883, and the `p256Verify` repricing of EIP-7951.
+fn osaka() -> &'static Precompiles {
+    static INSTANCE: spin::Once<Precompiles> = spin::Once::new();
+    INSTANCE.call_once(|| {
+        let mut precompiles = isthmus().clone();
+        precompiles.extend([modexp::OSAKA, secp256r1::P256VERIFY_OSAKA]);
+        precompiles
+    })
+}
+
 /// A precompile function that can be accelerated by the FPVM.
 type AcceleratedPrecompileFn<H, O> = fn(&[u8], u64, &H, &O) -> PrecompileResult;
 
@@ -189,6 +203,7 @@ where
             bn254::pair::ADDRESS,
             super::bn128_pair::fpvm_bn128_pair::<H, O>,
         ),
+        AcceleratedPrecompile::new(*modexp::BERLIN.address(), super::modexp::fpvm_modexp::<H, O>),
     ]
 }
 
@@ -206,13 +221,27 @@ where
     base
 }
 
+/// The accelerated precompiles for the fjord spec.
+fn accelerated_fjord<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    let mut base = accelerated_ecotone::<H, O>();
+    base.push(AcceleratedPrecompile::new(
+        P256_VERIFY_ADDR,
+        super::p256_verify::fpvm_p256_verify::<H, O>,
+    ));
+    base
+}
+
 /// The accelerated precompiles for the granite spec.
 fn accelerated_granite<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
 where
     H: HintWriterClient + Send + Sync,
     O: PreimageOracleClient + Send + Sync,
 {
-    let mut base = accelerated_ecotone::<H, O>();
+    let mut base = accelerated_fjord::<H, O>();
     base.push(AcceleratedPrecompile::new(
         bn254::pair::ADDRESS,
         super::bn128_pair::fpvm_bn128_pair_granite::<H, O>,
@@ -294,6 +323,29 @@ where
     base
 }
 
+/// The accelerated precompiles for the osaka spec.
+fn accelerated_osaka<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
+where
+    H: HintWriterClient + Send + Sync,
+    O: PreimageOracleClient + Send + Sync,
+{
+    let mut base = accelerated_jovian::<H, O>();
+
+    // Replace `modexp` and `p256Verify` with the repriced Osaka versions.
+    base.retain(|p| p.address != *modexp::OSAKA.address() && p.address != P256_VERIFY_ADDR);
+
+    base.push(AcceleratedPrecompile::new(
+        *modexp::OSAKA.address(),
+        super::modexp::fpvm_modexp_osaka::<H, O>,
+    ));
+    base.push(AcceleratedPrecompile::new(
+        P256_VERIFY_ADDR,
+        super::p256_verify::fpvm_p256_verify_osaka::<H, O>,
+    ));
+
+    base
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -438,6 +490,30 @@ mod test {
         assert_eq!(interpreter_result.result, InstructionResult::PrecompileOOG);
     }
 
+    #[test]
+    fn test_run_osaka_p256_verify_repriced() {
+        let (hint_chan, preimage_chan) = (
+            kona_preimage::BidirectionalChannel::new().unwrap(),
+            kona_preimage::BidirectionalChannel::new().unwrap(),
+        );
+        let hint_writer = kona_preimage::HintWriter::new(hint_chan.client);
+        let oracle_reader = kona_preimage::OracleReader::new(preimage_chan.client);
+
+        let mut ctx = create_test_context();
+
+        let mut precompiles =
+            OpFpvmPrecompiles::new_with_spec(OpSpecId::OSAKA, hint_writer, oracle_reader);
+
+        // The pre-Osaka cost of `p256Verify` no longer covers the call.
+        let call_inputs = create_call_inputs(P256_VERIFY_ADDR, Bytes::from(vec![0u8; 160]), 3_450);
+
+        let result = precompiles.run(&mut ctx, &call_inputs).unwrap();
+        assert!(result.is_some());
+
+        let interpreter_result = result.unwrap();
+        assert_eq!(interpreter_result.result, InstructionResult::PrecompileOOG);
+    }
+
     #[test]
     fn test_run_with_shared_buffer_empty() {
         let (hint_chan, preimage_chan) = (
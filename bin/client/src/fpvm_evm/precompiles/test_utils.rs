@@ -37,14 +37,13 @@ pub(crate) async fn test_accelerated_precompile(
     });
 }
 
-/// Executes a precompile on [`revm`].
+/// Executes a precompile on [`revm`], using the precompiles of the Isthmus hardfork.
 pub(crate) fn execute_native_precompile<T: Into<Bytes>>(
     address: Address,
     input: T,
     gas: u64,
 ) -> PrecompileResult {
-    let precompiles = revm::handler::EthPrecompiles::default();
-    let Some(precompile) = precompiles.precompiles.get(&address) else {
+    let Some(precompile) = op_revm::precompiles::isthmus().get(&address) else {
         panic!("Precompile not found");
     };
     precompile.execute(&input.into(), gas)
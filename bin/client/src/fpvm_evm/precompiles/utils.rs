@@ -10,6 +10,8 @@
 macro_rules! precompile_run {
     ($hint_writer:expr, $oracle_reader:expr, $hint_data:expr) => {
         async move {
+            let _phase = kona_proof::profile::phase("precompiles");
+
             use alloc::{string::ToString, vec::Vec};
             use kona_preimage::{PreimageKey, PreimageKeyType, errors::PreimageOracleError};
             use kona_proof::{HintType, errors::OracleProviderError};
@@ -37,5 +37,17 @@ fn main() -> Result<(), String> {
             .expect("Failed to set tracing subscriber");
     }
 
-    kona_proof::block_on(kona_client::interop::run(ORACLE_READER, HINT_WRITER))
+    #[cfg(feature = "client-profiling")]
+    kona_proof::profile::set_clock(
+        kona_std_fpvm::profile::clock,
+        kona_std_fpvm::profile::CLOCK_UNIT,
+    );
+
+    let result = kona_proof::block_on(kona_client::interop::run(ORACLE_READER, HINT_WRITER));
+
+    // Report the profile of the run to the host, regardless of its outcome.
+    #[cfg(feature = "client-profiling")]
+    let _ = kona_proof::block_on(kona_proof::profile::send_report(&HINT_WRITER));
+
+    result
 }
@@ -1,22 +1,28 @@
 //! Single-chain fault proof program entrypoint.
 
 use crate::fpvm_evm::FpvmOpEvmFactory;
-use alloc::sync::Arc;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_consensus::Sealed;
-use alloy_primitives::B256;
+use alloy_primitives::{B256, Bytes};
+use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{EthereumDataSource, PipelineErrorKind};
-use kona_driver::{Driver, DriverError};
-use kona_executor::{ExecutorError, TrieDBProvider};
+use kona_driver::{ClaimProvider, ClaimedBlock, Driver, DriverError};
+use kona_executor::{
+    BlockBuildingOutcome, ExecutorError, TraceConfig, TransactionTrace, TrieDBProvider,
+};
 use kona_preimage::{CommsClient, HintWriterClient, PreimageKey, PreimageOracleClient};
 use kona_proof::{
     BootInfo, CachingOracle, HintType,
+    boot::IntermediateClaim,
     errors::OracleProviderError,
     executor::KonaExecutor,
     l1::{OracleBlobProvider, OracleL1ChainProvider, OraclePipeline},
     l2::OracleL2ChainProvider,
+    profile,
     sync::new_oracle_pipeline_cursor,
 };
+use kona_protocol::L2BlockInfo;
 use thiserror::Error;
 use tracing::{error, info};
 
@@ -35,6 +41,81 @@ pub enum FaultProofProgramError {
     /// An error occurred in the driver.
     #[error(transparent)]
     Driver(#[from] DriverError<ExecutorError>),
+    /// An intermediate claim is outside of the range of L2 blocks derived by the program.
+    #[error("Intermediate claim for block {0} is outside of the derived range ({1}, {2}]")]
+    IntermediateClaimOutOfRange(u64, u64, u64),
+}
+
+/// The L2 block derived and executed by the fault proof program, along with the claim it is
+/// checked against.
+#[derive(Debug, Clone)]
+pub struct DerivedClaim {
+    /// The claimed L2 block number.
+    pub block_number: u64,
+    /// The L2 safe head reached by derivation.
+    pub safe_head: L2BlockInfo,
+    /// The output root of the L2 safe head.
+    pub output_root: B256,
+    /// The claimed output root.
+    pub claimed_output_root: B256,
+    /// The execution artifacts and raw transactions of the L2 safe head, if any block was
+    /// executed.
+    pub artifacts: Option<(BlockBuildingOutcome, Vec<Bytes>)>,
+    /// The traces of the transactions executed since the previous claim, if tracing is enabled.
+    pub traces: Vec<TransactionTrace>,
+}
+
+impl DerivedClaim {
+    /// Returns `true` if the derived output root matches the claimed output root.
+    pub fn is_valid(&self) -> bool {
+        self.output_root == self.claimed_output_root
+    }
+}
+
+/// Speculative parallel execution settings for the client program.
+///
+/// When set, the driver derives the payload attributes for a window of blocks up front, executes
+/// them concurrently on top of the intermediate blocks served by the [ClaimProvider], and falls
+/// back to sequential execution from the first block that does not match its claim. This mode is
+/// only available when running natively, with the `parallel-execution` feature enabled.
+#[derive(Debug)]
+pub struct ParallelExecution<C> {
+    /// The provider of the claimed intermediate blocks.
+    pub claims: C,
+    /// The maximum number of blocks to execute speculatively at once.
+    pub window: usize,
+}
+
+/// A [ClaimProvider] that never has a claim, used as the type of an absent [ParallelExecution].
+#[derive(Debug)]
+enum NoClaims {}
+
+/// The options of a [derive_claims] run.
+#[derive(Debug)]
+struct DeriveOptions<C> {
+    /// Whether to load the [IntermediateClaim]s and derive them alongside the disputed claim.
+    intermediate: bool,
+    /// Whether to retain the state changes of the executed blocks in their artifacts.
+    retain_bundle: bool,
+    /// The configuration of the transaction tracer, if tracing is enabled.
+    trace: Option<TraceConfig>,
+    /// The speculative parallel execution settings, if enabled.
+    parallel: Option<ParallelExecution<C>>,
+}
+
+impl Default for DeriveOptions<NoClaims> {
+    fn default() -> Self {
+        Self { intermediate: false, retain_bundle: false, trace: None, parallel: None }
+    }
+}
+
+#[async_trait]
+impl ClaimProvider for NoClaims {
+    type Error = core::convert::Infallible;
+
+    async fn claimed_block(&mut self, _: u64) -> Result<ClaimedBlock, Self::Error> {
+        match *self {}
+    }
 }
 
 /// Executes the fault proof program with the given [PreimageOracleClient] and [HintWriterClient].
@@ -43,6 +124,146 @@ pub async fn run<P, H>(oracle_client: P, hint_client: H) -> Result<(), FaultProo
 where
     P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
     H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    let Some(derived) = derive_claim(oracle_client, hint_client).await? else {
+        return Ok(());
+    };
+    check_claim(&derived)
+}
+
+/// Executes the fault proof program as [run], executing the L2 blocks speculatively in parallel.
+#[cfg(feature = "parallel-execution")]
+pub async fn run_parallel<P, H, C>(
+    oracle_client: P,
+    hint_client: H,
+    parallel: ParallelExecution<C>,
+) -> Result<(), FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+    C: ClaimProvider + Send,
+{
+    let options = DeriveOptions {
+        intermediate: false,
+        retain_bundle: false,
+        trace: None,
+        parallel: Some(parallel),
+    };
+    let Some(derived) = derive_claims(oracle_client, hint_client, options)
+        .await?
+        .and_then(|mut claims| claims.pop())
+    else {
+        return Ok(());
+    };
+    check_claim(&derived)
+}
+
+/// Checks the derived output root against the claimed output root.
+fn check_claim(derived: &DerivedClaim) -> Result<(), FaultProofProgramError> {
+    if derived.output_root != derived.claimed_output_root {
+        error!(
+            target: "client",
+            number = derived.block_number,
+            output_root = ?derived.output_root,
+            claimed_output_root = ?derived.claimed_output_root,
+            "Failed to validate L2 block",
+        );
+        return Err(FaultProofProgramError::InvalidClaim(
+            derived.output_root,
+            derived.claimed_output_root,
+        ));
+    }
+
+    info!(
+        target: "client",
+        number = derived.block_number,
+        output_root = ?derived.output_root,
+        "Successfully validated L2 block",
+    );
+
+    Ok(())
+}
+
+/// Derives and executes the L2 chain up to the claimed L2 block, without checking the claim.
+///
+/// Returns `None` if trace extension is detected, in which case there is nothing to derive.
+pub async fn derive_claim<P, H>(
+    oracle_client: P,
+    hint_client: H,
+) -> Result<Option<DerivedClaim>, FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    Ok(derive_claims(oracle_client, hint_client, DeriveOptions::default())
+        .await?
+        .and_then(|mut claims| claims.pop()))
+}
+
+/// Derives and executes the L2 chain up to the claimed L2 block as [derive_claim], retaining the
+/// state changes made by the claimed L2 block in its [DerivedClaim::artifacts].
+pub async fn derive_claim_with_bundle<P, H>(
+    oracle_client: P,
+    hint_client: H,
+) -> Result<Option<DerivedClaim>, FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    let options = DeriveOptions { retain_bundle: true, ..Default::default() };
+    Ok(derive_claims(oracle_client, hint_client, options)
+        .await?
+        .and_then(|mut claims| claims.pop()))
+}
+
+/// Derives and executes the L2 chain up to the claimed L2 block as [derive_claim], tracing the
+/// executed transactions with the given [TraceConfig] into the [DerivedClaim::traces].
+pub async fn derive_claim_traced<P, H>(
+    oracle_client: P,
+    hint_client: H,
+    config: TraceConfig,
+) -> Result<Option<DerivedClaim>, FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    let options = DeriveOptions { trace: Some(config), ..Default::default() };
+    Ok(derive_claims(oracle_client, hint_client, options)
+        .await?
+        .and_then(|mut claims| claims.pop()))
+}
+
+/// Derives and executes the L2 chain up to the claimed L2 block in a single pass, stopping at each
+/// of the [IntermediateClaim]s along the way, without checking the claims.
+///
+/// Returns the derived claims ordered by block number, including the disputed claim. This mode is
+/// only available when running natively, as the intermediate claims are not served by the FPVM.
+pub async fn derive_intermediate_claims<P, H>(
+    oracle_client: P,
+    hint_client: H,
+) -> Result<Vec<DerivedClaim>, FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+{
+    let options = DeriveOptions { intermediate: true, ..Default::default() };
+    Ok(derive_claims(oracle_client, hint_client, options).await?.unwrap_or_default())
+}
+
+/// Derives and executes the L2 chain up to the claimed L2 block with the given [DeriveOptions],
+/// returning the derived claims ordered by block number. Parallel execution is only used if the
+/// `parallel-execution` feature is enabled.
+///
+/// Returns `None` if trace extension is detected and there are no intermediate claims.
+async fn derive_claims<P, H, C>(
+    oracle_client: P,
+    hint_client: H,
+    options: DeriveOptions<C>,
+) -> Result<Option<Vec<DerivedClaim>>, FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone + 'static,
+    H: HintWriterClient + Send + Sync + Debug + Clone + 'static,
+    C: ClaimProvider + Send,
 {
     const ORACLE_LRU_SIZE: usize = 1024;
 
@@ -50,6 +271,7 @@ where
     //                          PROLOGUE                          //
     ////////////////////////////////////////////////////////////////
 
+    let boot_phase = profile::phase("boot");
     let oracle =
         Arc::new(CachingOracle::new(ORACLE_LRU_SIZE, oracle_client.clone(), hint_client.clone()));
     let boot = BootInfo::load(oracle.as_ref()).await?;
@@ -57,6 +279,10 @@ where
     let rollup_config = Arc::new(boot.rollup_config);
     let safe_head_hash = fetch_safe_head_hash(oracle.as_ref(), boot.agreed_l2_output_root).await?;
 
+    let DeriveOptions { intermediate, retain_bundle, trace, mut parallel } = options;
+    let mut claims =
+        if intermediate { IntermediateClaim::load(oracle.as_ref()).await? } else { Vec::new() };
+
     let mut l1_provider = OracleL1ChainProvider::new(boot.l1_head, oracle.clone());
     let mut l2_provider =
         OracleL2ChainProvider::new(safe_head_hash, rollup_config.clone(), oracle.clone());
@@ -66,6 +292,7 @@ where
     let safe_head = l2_provider
         .header_by_hash(safe_head_hash)
         .map(|header| Sealed::new_unchecked(header, safe_head_hash))?;
+    drop(boot_phase);
 
     // If the claimed L2 block number is less than the safe head of the L2 chain, the claim is
     // invalid.
@@ -82,14 +309,35 @@ where
         ));
     }
 
+    // Each intermediate claim must lie between the safe head and the claimed L2 block, as the
+    // driver cannot produce the output root of a block at or below its safe head.
+    if let Some(claim) = claims.iter().find(|claim| {
+        claim.block_number <= safe_head.number || claim.block_number > boot.claimed_l2_block_number
+    }) {
+        error!(
+            target: "client",
+            number = claim.block_number,
+            safe = safe_head.number,
+            claimed = boot.claimed_l2_block_number,
+            "Intermediate claim is outside of the derived range",
+        );
+        return Err(FaultProofProgramError::IntermediateClaimOutOfRange(
+            claim.block_number,
+            safe_head.number,
+            boot.claimed_l2_block_number,
+        ));
+    }
+    claims.push(IntermediateClaim::new(boot.claimed_l2_block_number, boot.claimed_l2_output_root));
+    claims.sort_by_key(|claim| claim.block_number);
+
     // In the case where the agreed upon L2 output root is the same as the claimed L2 output root,
     // trace extension is detected and we can skip the derivation and execution steps.
-    if boot.agreed_l2_output_root == boot.claimed_l2_output_root {
+    if claims.len() == 1 && boot.agreed_l2_output_root == boot.claimed_l2_output_root {
         info!(
             target: "client",
             "Trace extension detected. State transition is already agreed upon.",
         );
-        return Ok(());
+        return Ok(None);
     }
 
     ////////////////////////////////////////////////////////////////
@@ -124,44 +372,58 @@ where
     )
     .await?;
 
+    #[cfg(feature = "parallel-execution")]
+    let mut parallel_executor = kona_proof::executor::ParallelKonaExecutor::new(
+        rollup_config.clone(),
+        l2_provider.clone(),
+        l2_provider.clone(),
+        evm_factory.clone(),
+    );
     let executor = KonaExecutor::new(
         rollup_config.as_ref(),
         l2_provider.clone(),
         l2_provider,
         evm_factory,
         None,
-    );
+    )
+    .with_bundle_retention(retain_bundle);
+    let executor = match trace {
+        Some(config) => executor.with_tracing(config),
+        None => executor,
+    };
     let mut driver = Driver::new(cursor, executor, pipeline);
 
-    // Run the derivation pipeline until we are able to produce the output root of the claimed
-    // L2 block.
-    let (safe_head, output_root) = driver
-        .advance_to_target(rollup_config.as_ref(), Some(boot.claimed_l2_block_number))
-        .await?;
+    // Run the derivation pipeline until we are able to produce the output root of each claimed
+    // L2 block, continuing from the previous claim.
+    let mut derived = Vec::with_capacity(claims.len());
+    for claim in claims {
+        let (safe_head, output_root) = match parallel.as_mut() {
+            #[cfg(feature = "parallel-execution")]
+            Some(ParallelExecution { claims: claim_provider, window }) => {
+                driver
+                    .advance_to_target_parallel(
+                        rollup_config.as_ref(),
+                        claim.block_number,
+                        *window,
+                        claim_provider,
+                        &mut parallel_executor,
+                    )
+                    .await?
+            }
+            _ => driver.advance_to_target(rollup_config.as_ref(), Some(claim.block_number)).await?,
+        };
 
-    ////////////////////////////////////////////////////////////////
-    //                          EPILOGUE                          //
-    ////////////////////////////////////////////////////////////////
-
-    if output_root != boot.claimed_l2_output_root {
-        error!(
-            target: "client",
-            number = safe_head.block_info.number,
-            output_root = ?output_root,
-            claimed_output_root = ?boot.claimed_l2_output_root,
-            "Failed to validate L2 block",
-        );
-        return Err(FaultProofProgramError::InvalidClaim(output_root, boot.claimed_l2_output_root));
+        derived.push(DerivedClaim {
+            block_number: claim.block_number,
+            safe_head,
+            output_root,
+            claimed_output_root: claim.output_root,
+            artifacts: driver.safe_head_artifacts.take(),
+            traces: driver.executor.take_traces(),
+        });
     }
 
-    info!(
-        target: "client",
-        number = safe_head.block_info.number,
-        output_root = ?output_root,
-        "Successfully validated L2 block",
-    );
-
-    Ok(())
+    Ok(Some(derived))
 }
 
 /// Fetches the safe head hash of the L2 chain based on the agreed upon L2 output root in the
@@ -4,6 +4,10 @@ mod offline;
 pub use offline::OfflineHostBackend;
 
 mod online;
-pub use online::{HintHandler, OnlineHostBackend, OnlineHostBackendCfg};
+pub use online::{
+    DEFAULT_PREFETCH_CONCURRENCY, HintHandler, OnlineHostBackend, OnlineHostBackendCfg,
+};
+
+mod prefetch;
 
 pub(crate) mod util;
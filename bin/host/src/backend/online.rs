@@ -1,5 +1,6 @@
 //! Contains the [OnlineHostBackend] definition.
 
+use super::prefetch::HintFetches;
 use crate::SharedKeyValueStore;
 use anyhow::Result;
 use async_trait::async_trait;
@@ -8,9 +9,20 @@ use kona_preimage::{
     errors::{PreimageOracleError, PreimageOracleResult},
 };
 use kona_proof::{Hint, errors::HintParsingError};
-use std::{collections::HashSet, hash::Hash, str::FromStr, sync::Arc};
-use tokio::sync::RwLock;
-use tracing::{debug, error, trace};
+use std::{
+    collections::HashSet,
+    hash::Hash,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    sync::{RwLock, Semaphore},
+    task::JoinSet,
+};
+use tracing::{Instrument, debug, error, info_span, trace};
+
+/// The default maximum number of hints prefetched concurrently by the [OnlineHostBackend].
+pub const DEFAULT_PREFETCH_CONCURRENCY: usize = 8;
 
 /// The [OnlineHostBackendCfg] trait is used to define the type configuration for the
 /// [OnlineHostBackend].
@@ -36,12 +48,22 @@ pub trait HintHandler {
         providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
         kv: SharedKeyValueStore,
     ) -> Result<()>;
+
+    /// Returns the hints whose data is likely to be requested by the client program after the
+    /// data of the given hint, so that it can be prefetched. By default, no hints are predicted.
+    fn predict_hints(
+        _hint: &Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>,
+    ) -> Vec<Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>> {
+        Vec::new()
+    }
 }
 
 /// The [OnlineHostBackend] is a [HintRouter] and [PreimageFetcher] that is used to fetch data from
 /// remote sources in response to hints.
 ///
-/// [PreimageKey]: kona_preimage::PreimageKey
+/// Hints are prefetched in the background as they are received, along with the hints predicted by
+/// the [HintHandler], with bounded concurrency. Fetches of the same hint are deduplicated, so a
+/// [PreimageKey] request only waits on the last hint if its preimage is not yet available.
 #[allow(missing_debug_implementations)]
 pub struct OnlineHostBackend<C, H>
 where
@@ -49,15 +71,23 @@ where
     H: HintHandler,
 {
     /// The configuration that is used to route hints.
-    cfg: C,
+    cfg: Arc<C>,
     /// The key-value store that is used to store preimages.
     kv: SharedKeyValueStore,
     /// The providers that are used to fetch data in response to hints.
-    providers: C::Providers,
+    providers: Arc<C::Providers>,
     /// Hints that should be immediately executed by the host.
     proactive_hints: HashSet<C::HintType>,
     /// The last hint that was received.
     last_hint: Arc<RwLock<Option<Hint<C::HintType>>>>,
+    /// The in-flight and completed hint fetches.
+    fetches: Arc<HintFetches<C::HintType>>,
+    /// The permits bounding the number of concurrent prefetches.
+    prefetch_permits: Arc<Semaphore>,
+    /// The maximum number of concurrent prefetches. Prefetching is disabled if zero.
+    prefetch_concurrency: usize,
+    /// The running prefetch tasks, aborted when the backend is dropped.
+    prefetch_tasks: Mutex<JoinSet<()>>,
     /// Phantom marker for the [HintHandler].
     _hint_handler: std::marker::PhantomData<H>,
 }
@@ -71,11 +101,15 @@ where
     /// external configuration.
     pub fn new(cfg: C, kv: SharedKeyValueStore, providers: C::Providers, _: H) -> Self {
         Self {
-            cfg,
+            cfg: Arc::new(cfg),
             kv,
-            providers,
+            providers: Arc::new(providers),
             proactive_hints: HashSet::default(),
             last_hint: Arc::new(RwLock::new(None)),
+            fetches: Arc::new(HintFetches::default()),
+            prefetch_permits: Arc::new(Semaphore::new(DEFAULT_PREFETCH_CONCURRENCY)),
+            prefetch_concurrency: DEFAULT_PREFETCH_CONCURRENCY,
+            prefetch_tasks: Mutex::new(JoinSet::new()),
             _hint_handler: std::marker::PhantomData,
         }
     }
@@ -85,13 +119,21 @@ where
         self.proactive_hints.insert(hint_type);
         self
     }
+
+    /// Sets the maximum number of hints prefetched concurrently. A concurrency of zero disables
+    /// prefetching, so that hints are only fetched once the client requests a missing preimage.
+    pub fn with_prefetch_concurrency(mut self, concurrency: usize) -> Self {
+        self.prefetch_permits = Arc::new(Semaphore::new(concurrency));
+        self.prefetch_concurrency = concurrency;
+        self
+    }
 }
 
 #[async_trait]
 impl<C, H> HintRouter for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Set the last hint to be received.
     async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
@@ -102,10 +144,17 @@ where
             .map_err(|e| PreimageOracleError::HintParseFailed(e.to_string()))?;
         if self.proactive_hints.contains(&parsed_hint.ty) {
             debug!(target: "host_backend", "Proactive hint received; Immediately fetching {hint}");
-            H::fetch_hint(parsed_hint, &self.cfg, &self.providers, self.kv.clone())
+            let span = info_span!(target: "host_backend", "hint_fetch", hint = %hint);
+            self.fetch_hint(&parsed_hint)
+                .instrument(span)
                 .await
-                .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+                .map_err(PreimageOracleError::Other)?;
         } else {
+            if self.prefetch_concurrency > 0 {
+                self.prefetch(parsed_hint.clone());
+                H::predict_hints(&parsed_hint).into_iter().for_each(|hint| self.prefetch(hint));
+            }
+
             let mut hint_lock = self.last_hint.write().await;
             hint_lock.replace(parsed_hint);
         }
@@ -117,11 +166,29 @@ where
 #[async_trait]
 impl<C, H> PreimageFetcher for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Get the preimage for the given key.
     async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let span = info_span!(
+            target: "host_backend",
+            "preimage_request",
+            key = %key,
+            key_type = ?key.key_type(),
+        );
+        self.get_preimage_inner(key).instrument(span).await
+    }
+}
+
+impl<C, H> OnlineHostBackend<C, H>
+where
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
+{
+    /// Get the preimage for the given key, fetching it from the remote sources via the last
+    /// received hint if it is not yet in the key-value store.
+    async fn get_preimage_inner(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
         trace!(target: "host_backend", "Pre-image requested. Key: {key}");
 
         // Acquire a read lock on the key-value store.
@@ -131,22 +198,148 @@ where
         // Drop the read lock before beginning the retry loop.
         drop(kv_lock);
 
-        // Use a loop to keep retrying the prefetch as long as the key is not found
+        // Use a loop to keep retrying the fetch as long as the key is not found
         while preimage.is_none() {
-            if let Some(hint) = self.last_hint.read().await.as_ref() {
-                let value =
-                    H::fetch_hint(hint.clone(), &self.cfg, &self.providers, self.kv.clone()).await;
-
-                if let Err(e) = value {
+            let hint = self.last_hint.read().await.clone();
+            if let Some(hint) = hint {
+                // The hint fetch is a child of the preimage request span. If the hint is being
+                // prefetched, this waits on the prefetch rather than fetching it again.
+                let span = info_span!(target: "host_backend", "hint_fetch");
+                if let Err(e) = self.fetch_hint(&hint).instrument(span).await {
                     error!(target: "host_backend", "Failed to prefetch hint: {e}");
                     continue;
                 }
 
                 let kv_lock = self.kv.read().await;
                 preimage = kv_lock.get(key.into());
+
+                // The fetch completed without providing the preimage, so fetch the hint again.
+                if preimage.is_none() {
+                    self.fetches.invalidate(&hint);
+                }
             }
         }
 
         preimage.ok_or(PreimageOracleError::KeyNotFound)
     }
+
+    /// Fetches the given hint, unless it is already being fetched or was already fetched.
+    async fn fetch_hint(&self, hint: &Hint<C::HintType>) -> Result<(), String> {
+        self.fetches
+            .fetch(hint, || {
+                H::fetch_hint(hint.clone(), &self.cfg, &self.providers, self.kv.clone())
+            })
+            .await
+    }
+
+    /// Prefetches the given hint in the background, once a prefetch permit is available.
+    fn prefetch(&self, hint: Hint<C::HintType>) {
+        if self.fetches.is_fetched(&hint) {
+            return;
+        }
+
+        let (cfg, providers, kv) = (self.cfg.clone(), self.providers.clone(), self.kv.clone());
+        let (fetches, permits) = (self.fetches.clone(), self.prefetch_permits.clone());
+        let mut tasks = self.prefetch_tasks.lock().unwrap();
+        while tasks.try_join_next().is_some() {}
+        tasks.spawn(async move {
+            let Ok(_permit) = permits.acquire_owned().await else { return };
+            let result =
+                fetches.fetch(&hint, || H::fetch_hint(hint.clone(), &cfg, &providers, kv)).await;
+            if let Err(e) = result {
+                debug!(target: "host_backend", "Failed to prefetch hint: {e}");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use alloy_primitives::{B256, keccak256};
+    use kona_proof::HintType;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    struct MockCfg;
+
+    impl OnlineHostBackendCfg for MockCfg {
+        type HintType = HintType;
+        type Providers = AtomicUsize;
+    }
+
+    struct MockHintHandler;
+
+    #[async_trait]
+    impl HintHandler for MockHintHandler {
+        type Cfg = MockCfg;
+
+        async fn fetch_hint(
+            hint: Hint<HintType>,
+            _: &MockCfg,
+            calls: &AtomicUsize,
+            kv: SharedKeyValueStore,
+        ) -> Result<()> {
+            calls.fetch_add(1, Ordering::SeqCst);
+            kv.write().await.set(mock_key(&hint).into(), hint.data.to_vec())
+        }
+
+        fn predict_hints(hint: &Hint<HintType>) -> Vec<Hint<HintType>> {
+            match hint.ty {
+                HintType::L1BlockHeader => vec![Hint::new(HintType::L1Receipts, hint.data.clone())],
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    fn mock_key(hint: &Hint<HintType>) -> PreimageKey {
+        let hash = keccak256([hint.ty.to_string().as_bytes(), hint.data.as_ref()].concat());
+        PreimageKey::new_keccak256(*hash)
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_predicted_hints() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend =
+            OnlineHostBackend::new(MockCfg, kv.clone(), AtomicUsize::new(0), MockHintHandler);
+
+        let header = HintType::L1BlockHeader.with_data(&[B256::repeat_byte(0xFF).as_slice()]);
+        let receipts = Hint::new(HintType::L1Receipts, header.data.clone());
+        backend.route_hint(header.encode()).await.unwrap();
+
+        // Both the hinted and the predicted data are fetched in the background.
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while kv.read().await.get(mock_key(&receipts).into()).is_none() ||
+                kv.read().await.get(mock_key(&header).into()).is_none()
+            {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // The preimages are served without fetching the hints again.
+        assert_eq!(backend.get_preimage(mock_key(&header)).await.unwrap(), header.data.to_vec());
+        assert_eq!(backend.get_preimage(mock_key(&receipts)).await.unwrap(), header.data.to_vec());
+        assert_eq!(backend.providers.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_disabled() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend =
+            OnlineHostBackend::new(MockCfg, kv.clone(), AtomicUsize::new(0), MockHintHandler)
+                .with_prefetch_concurrency(0);
+
+        let header = HintType::L1BlockHeader.with_data(&[B256::repeat_byte(0xFF).as_slice()]);
+        backend.route_hint(header.encode()).await.unwrap();
+        assert_eq!(backend.providers.load(Ordering::SeqCst), 0);
+
+        // The hint is only fetched once the client requests its preimage.
+        assert_eq!(backend.get_preimage(mock_key(&header)).await.unwrap(), header.data.to_vec());
+        assert_eq!(backend.providers.load(Ordering::SeqCst), 1);
+    }
 }
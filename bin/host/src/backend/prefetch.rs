@@ -0,0 +1,144 @@
+//! Contains the [HintFetches] type, which deduplicates concurrent fetches of the same hint.
+
+use anyhow::Result;
+use kona_proof::Hint;
+use lru::LruCache;
+use std::{
+    hash::Hash,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::OnceCell;
+
+/// The result of a hint fetch, shared between all of its waiters.
+type SharedFetch = Arc<OnceCell<Result<(), String>>>;
+
+/// The default maximum number of hint fetches tracked by [HintFetches].
+const DEFAULT_HINT_FETCHES_CAPACITY: NonZeroUsize = NonZeroUsize::new(8192).unwrap();
+
+/// [HintFetches] tracks the fetches of hints, so that a hint that is being fetched, or was
+/// already fetched, is not fetched again.
+///
+/// Concurrent fetches of the same hint wait on the first one. Failed fetches are forgotten, so
+/// that they can be retried. At most `capacity` fetches are tracked, evicting the least recently
+/// used ones, so a long-running host does not grow without bound; an evicted hint is simply
+/// fetched again.
+#[derive(Debug)]
+pub(crate) struct HintFetches<T: Hash + Eq> {
+    fetches: Mutex<LruCache<Hint<T>, SharedFetch>>,
+}
+
+impl<T: Hash + Eq> Default for HintFetches<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_HINT_FETCHES_CAPACITY)
+    }
+}
+
+impl<T> HintFetches<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Creates a new [HintFetches] tracking at most `capacity` fetches.
+    pub(crate) fn new(capacity: NonZeroUsize) -> Self {
+        Self { fetches: Mutex::new(LruCache::new(capacity)) }
+    }
+
+    /// Fetches the given hint with `fetch`, unless it is already being fetched or was already
+    /// fetched, in which case the result of that fetch is returned.
+    pub(crate) async fn fetch<F, Fut>(&self, hint: &Hint<T>, fetch: F) -> Result<(), String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let cell =
+            self.fetches.lock().unwrap().get_or_insert(hint.clone(), SharedFetch::default).clone();
+        let result = cell
+            .get_or_init(|| async move { fetch().await.map_err(|e| e.to_string()) })
+            .await
+            .clone();
+
+        if result.is_err() {
+            self.forget(hint, &cell);
+        }
+        result
+    }
+
+    /// Returns `true` if the given hint was fetched successfully.
+    pub(crate) fn is_fetched(&self, hint: &Hint<T>) -> bool {
+        self.fetches
+            .lock()
+            .unwrap()
+            .peek(hint)
+            .is_some_and(|cell| cell.get().is_some_and(Result::is_ok))
+    }
+
+    /// Forgets the fetch of the given hint, so that it is fetched again next time.
+    pub(crate) fn invalidate(&self, hint: &Hint<T>) {
+        self.fetches.lock().unwrap().pop(hint);
+    }
+
+    /// Forgets the given fetch of the hint, unless it was already replaced by a newer fetch.
+    fn forget(&self, hint: &Hint<T>, cell: &SharedFetch) {
+        let mut fetches = self.fetches.lock().unwrap();
+        if fetches.peek(hint).is_some_and(|current| Arc::ptr_eq(current, cell)) {
+            fetches.pop(hint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use kona_proof::HintType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_hint_fetches_dedup() {
+        let fetches = HintFetches::default();
+        let hint = HintType::L1BlockHeader.with_data(&[&[0xFF; 32]]);
+        let calls = AtomicUsize::new(0);
+        let fetch = || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(())
+        };
+
+        let (a, b) = tokio::join!(fetches.fetch(&hint, fetch), fetches.fetch(&hint, fetch));
+        assert!(a.is_ok() && b.is_ok());
+        assert!(fetches.is_fetched(&hint));
+        fetches.fetch(&hint, fetch).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        fetches.invalidate(&hint);
+        fetches.fetch(&hint, fetch).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hint_fetches_retry_failed() {
+        let fetches = HintFetches::default();
+        let hint = HintType::L1BlockHeader.with_data(&[&[0xFF; 32]]);
+
+        let err = fetches.fetch(&hint, || async { Err(anyhow!("offline")) }).await.unwrap_err();
+        assert_eq!(err, "offline");
+        assert!(!fetches.is_fetched(&hint));
+        fetches.fetch(&hint, || async { Ok(()) }).await.unwrap();
+        assert!(fetches.is_fetched(&hint));
+    }
+
+    #[tokio::test]
+    async fn test_hint_fetches_bounded() {
+        let fetches = HintFetches::new(NonZeroUsize::new(2).unwrap());
+        let hints =
+            (0..3u8).map(|i| HintType::L1BlockHeader.with_data(&[&[i; 32]])).collect::<Vec<_>>();
+
+        for hint in &hints {
+            fetches.fetch(hint, || async { Ok(()) }).await.unwrap();
+        }
+        assert_eq!(fetches.fetches.lock().unwrap().len(), 2);
+        assert!(!fetches.is_fetched(&hints[0]));
+        assert!(fetches.is_fetched(&hints[1]));
+        assert!(fetches.is_fetched(&hints[2]));
+    }
+}
@@ -6,7 +6,7 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use kona_cli::{LogArgs, LogConfig, cli_styles};
+use kona_cli::{LogArgs, LogConfig, MetricsArgs, cli_styles};
 use serde::Serialize;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
@@ -26,6 +26,10 @@ pub struct HostCli {
     /// Logging arguments.
     #[command(flatten)]
     pub log_args: LogArgs,
+    /// Metrics arguments.
+    #[command(flatten)]
+    #[serde(skip)]
+    pub metrics: MetricsArgs,
     /// Host mode
     #[command(subcommand)]
     pub mode: HostMode,
@@ -38,27 +42,62 @@ pub enum HostMode {
     /// Run the host in single-chain mode.
     #[cfg(feature = "single")]
     Single(kona_host::single::SingleChainHost),
+    /// Bisect a disputed single-chain output root range to the first diverging L2 block.
+    #[cfg(feature = "single")]
+    Bisect(kona_host::single::BisectHost),
+    /// Re-run the client program natively and offline from a replay bundle.
+    #[cfg(feature = "single")]
+    Replay(kona_host::single::ReplayHost),
     /// Run the host in super-chain (interop) mode.
     #[cfg(feature = "interop")]
     Super(kona_host::interop::InteropHost),
+    /// Run the host as a proving service, accepting jobs over a JSON-RPC API.
+    #[cfg(feature = "interop")]
+    Service(kona_host::service::ServiceHost),
+}
+
+impl HostCli {
+    /// Runs the host in the selected mode.
+    async fn run(self) -> Result<()> {
+        self.metrics.init_metrics()?;
+
+        match self.mode {
+            #[cfg(feature = "single")]
+            HostMode::Single(cfg) => {
+                cfg.start().await?;
+            }
+            #[cfg(feature = "single")]
+            HostMode::Bisect(cfg) => {
+                cfg.start().await?;
+            }
+            #[cfg(feature = "single")]
+            HostMode::Replay(cfg) => {
+                cfg.start().await?;
+            }
+            #[cfg(feature = "interop")]
+            HostMode::Super(cfg) => {
+                cfg.start().await?;
+            }
+            #[cfg(feature = "interop")]
+            HostMode::Service(cfg) => {
+                cfg.start().await?;
+            }
+        }
+
+        info!(target: "host", "Exiting host program.");
+        Ok(())
+    }
 }
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> Result<()> {
     let cfg = HostCli::parse();
-    LogConfig::new(cfg.log_args).init_tracing_subscriber(None::<EnvFilter>)?;
+    LogConfig::new(cfg.log_args.clone()).init_tracing_subscriber(None::<EnvFilter>)?;
 
-    match cfg.mode {
-        #[cfg(feature = "single")]
-        HostMode::Single(cfg) => {
-            cfg.start().await?;
-        }
-        #[cfg(feature = "interop")]
-        HostMode::Super(cfg) => {
-            cfg.start().await?;
-        }
-    }
+    let result = cfg.run().await;
+
+    // Flush the OTLP exporters, if any, before exiting.
+    kona_cli::shutdown_otlp();
 
-    info!(target: "host", "Exiting host program.");
-    Ok(())
+    result
 }
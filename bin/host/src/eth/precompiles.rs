@@ -5,9 +5,16 @@ use anyhow::{Result, anyhow};
 use revm::precompile::{self, Precompile};
 
 /// List of precompiles that are accelerated by the host program.
+///
+/// The client sends the gas it charged for a call along with the call, which is at least the gas
+/// charged before Osaka, as the repricings of EIP-7883 and EIP-7951 only raise costs. The input
+/// limits of EIP-7823 are enforced by the client. The pre-Osaka versions of `modexp` and
+/// `p256Verify` therefore serve calls made under either set of rules.
 pub(crate) const ACCELERATED_PRECOMPILES: &[Precompile] = &[
     precompile::secp256k1::ECRECOVER,          // ecRecover
     precompile::bn254::pair::ISTANBUL,         // ecPairing
+    precompile::modexp::BERLIN,                // modexp
+    precompile::secp256r1::P256VERIFY,         // p256Verify (RIP-7212)
     precompile::bls12_381::g1_add::PRECOMPILE, // BLS12-381 G1 Point Addition
     precompile::bls12_381::g1_msm::PRECOMPILE, /* BLS12-381 G1 Point Multi-scalar
                                                 * Multiplication */
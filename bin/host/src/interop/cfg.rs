@@ -1,43 +1,65 @@
 //! This module contains all CLI-specific code for the interop entrypoint.
 
-use super::{InteropHintHandler, InteropLocalInputs};
+use super::{
+    InteropHintHandler, InteropLocalInputs, SupervisorProvider, pre_state_from_super_root,
+    transition_state_from_super_roots,
+};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::rpc_provider, server::PreimageServerError,
+    CachedKeyValueStore, DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, OfflineHostBackend,
+    OnlineHostBackend, OnlineHostBackendCfg, PreimageCache, PreimageServer, SharedKeyValueStore,
+    SplitKeyValueStore, eth::rpc_provider, server::PreimageServerError,
 };
+use alloy_eips::BlockNumHash;
 use alloy_primitives::{B256, Bytes};
 use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::Decodable;
 use clap::Parser;
 use kona_cli::cli_styles;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, HintReader, HintWriter, NativeChannel, OracleReader,
+    OracleServer, PreimageListener, SocketAddress,
+};
+use kona_proof_interop::{HintType, PreState};
+use kona_providers_alloy::{
+    BlobArchiverClient, BlobDirectory, FallbackBlobProvider, OnlineBeaconClient, OnlineBlobProvider,
 };
-use kona_proof_interop::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use kona_registry::ROLLUP_CONFIGS;
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
 use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::{error, info, warn};
 
 /// The interop host application.
-#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[derive(Default, Parser, Serialize, Deserialize, Clone, Debug)]
 #[command(styles = cli_styles())]
+#[serde(default)]
 pub struct InteropHost {
     /// Hash of the L1 head block, marking a static, trusted cutoff point for reading data from the
     /// L1 chain.
     #[arg(long, env)]
     pub l1_head: B256,
-    /// Agreed [PreState] to start from.
+    /// Agreed [PreState] to start from. Required unless a supervisor address is provided, in which
+    /// case the pre-state is constructed from the supervisor's super roots.
+    #[arg(
+        long,
+        visible_alias = "l2-pre-state",
+        value_parser = Bytes::from_str,
+        required_unless_present = "supervisor_address",
+        env
+    )]
+    pub agreed_l2_pre_state: Option<Bytes>,
+    /// Step of the agreed [TransitionState] to construct from the supervisor. If not provided, the
+    /// agreed pre-state is the super root at the timestamp preceding the claimed L2 timestamp.
     ///
-    /// [PreState]: kona_proof_interop::PreState
-    #[arg(long, visible_alias = "l2-pre-state", value_parser = Bytes::from_str, env)]
-    pub agreed_l2_pre_state: Bytes,
+    /// [TransitionState]: kona_proof_interop::TransitionState
+    #[arg(long, requires = "supervisor_address", conflicts_with = "agreed_l2_pre_state", env)]
+    pub agreed_l2_pre_state_step: Option<u64>,
     /// Claimed L2 post-state to validate.
     #[arg(long, visible_alias = "l2-claim", env)]
     pub claimed_l2_post_state: B256,
@@ -54,6 +76,11 @@ pub struct InteropHost {
         env
     )]
     pub l2_node_addresses: Option<Vec<String>>,
+    /// Address of the supervisor JSON-RPC endpoint to use. If provided, the agreed pre-state and
+    /// the chains in the dependency set are sourced from the supervisor, and the L2 endpoints are
+    /// only used for chain state.
+    #[arg(long, visible_alias = "supervisor", requires = "l2_node_addresses", env)]
+    pub supervisor_address: Option<String>,
     /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required)
     #[arg(
         long,
@@ -72,6 +99,14 @@ pub struct InteropHost {
         env
     )]
     pub l1_beacon_address: Option<String>,
+    /// Address of a blob archiver API endpoint, used to fetch blobs that were pruned by the L1
+    /// Beacon API endpoint.
+    #[arg(long, visible_alias = "blob-archiver", requires = "l1_beacon_address", env)]
+    pub l1_blob_archiver_address: Option<String>,
+    /// Path to a local blob directory, used to fetch blobs that were pruned by the L1 Beacon API
+    /// endpoint. Blobs fetched from the blob archiver are cached in the directory.
+    #[arg(long, visible_alias = "blob-dir", requires = "l1_beacon_address", env)]
+    pub l1_blob_dir: Option<PathBuf>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
@@ -81,6 +116,20 @@ pub struct InteropHost {
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// Directory of a persistent preimage cache shared between host runs. Content-addressed
+    /// preimages fetched by the host are stored in the cache and reused by later runs, while local
+    /// boot data is kept per run. The cache may be shared by several host processes at once.
+    #[arg(long, env)]
+    pub preimage_cache: Option<PathBuf>,
+    /// The maximum size of the preimage cache, in MiB. Once the cache grows past this size, the
+    /// least recently used preimages are evicted. Defaults to 8 GiB.
+    #[arg(
+        long,
+        requires = "preimage_cache",
+        value_parser = PreimageCache::parse_max_size_mib,
+        env
+    )]
+    pub preimage_cache_size: Option<u64>,
     /// Run the client program natively.
     #[arg(long, conflicts_with = "server", required_unless_present = "server")]
     pub native: bool,
@@ -88,6 +137,12 @@ pub struct InteropHost {
     /// host will run the client program in the host process.
     #[arg(long, conflicts_with = "native", required_unless_present = "native")]
     pub server: bool,
+    /// Serve the pre-image server over a stream socket instead of the pre-image pipes, accepting
+    /// concurrent client sessions. Accepts `tcp://<ip>:<port>`, `<ip>:<port>` or
+    /// `unix://<path>`. Each session is isolated in its own key-value store.
+    #[arg(long, requires = "server", env)]
+    #[serde(skip)]
+    pub listen: Option<SocketAddress>,
     /// Path to rollup configs. If provided, the host will use this config instead of attempting to
     /// look up the configs in the superchain registry.
     /// The rollup configs should be stored as serde-JSON serialized files.
@@ -124,6 +179,15 @@ pub enum InteropHostError {
     /// An error when no provider found for chain ID.
     #[error("No provider found for chain ID: {0}")]
     RootProviderError(u64),
+    /// An error when sourcing data from the supervisor.
+    #[error("Supervisor error: {0}")]
+    SupervisorError(anyhow::Error),
+    /// An error when the agreed L2 block of a chain was not cross-safe at the L1 head.
+    #[error("Agreed L2 block {1} of chain {0} is not cross-safe at the L1 head")]
+    NotCrossSafe(u64, u64),
+    /// An error opening the preimage cache.
+    #[error("Preimage cache error: {0}")]
+    PreimageCacheError(anyhow::Error),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -132,17 +196,69 @@ pub enum InteropHostError {
 impl InteropHost {
     /// Starts the [InteropHost] application.
     pub async fn start(self) -> Result<(), InteropHostError> {
-        if self.server {
+        let host = self.resolve_agreed_pre_state().await?;
+
+        if let Some(addr) = &host.listen {
+            host.start_socket_server(addr).await
+        } else if host.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
 
-            self.start_server(hint, preimage).await?.await?
+            host.start_server(hint, preimage).await?.await?
         } else {
-            self.start_native().await
+            host.start_native().await
         }
     }
 
+    /// Returns the agreed pre-state of the [InteropHost], which is empty until it has been
+    /// resolved with [Self::resolve_agreed_pre_state].
+    pub fn agreed_pre_state(&self) -> &[u8] {
+        self.agreed_l2_pre_state.as_deref().unwrap_or_default()
+    }
+
+    /// Resolves the agreed pre-state of the [InteropHost].
+    ///
+    /// If no agreed pre-state was provided, it is constructed from the supervisor's super root at
+    /// the timestamp preceding the claimed L2 timestamp. If an agreed pre-state step was provided,
+    /// the pre-state is the [TransitionState] at that step, with its pending progress sourced from
+    /// the supervisor's super root at the claimed L2 timestamp.
+    ///
+    /// [TransitionState]: kona_proof_interop::TransitionState
+    pub async fn resolve_agreed_pre_state(mut self) -> Result<Self, InteropHostError> {
+        if self.agreed_l2_pre_state.is_some() {
+            return Ok(self);
+        }
+
+        let supervisor = self
+            .supervisor_provider()?
+            .ok_or(InteropHostError::Other("Agreed pre-state or supervisor address must be set"))?;
+        let timestamp = self
+            .claimed_l2_timestamp
+            .checked_sub(1)
+            .ok_or(InteropHostError::Other("Claimed L2 timestamp must be non-zero"))?;
+
+        let output = supervisor.super_root_at_timestamp(timestamp).await?;
+        let pre_state = match self.agreed_l2_pre_state_step {
+            Some(step) => {
+                let post = supervisor.super_root_at_timestamp(self.claimed_l2_timestamp).await?;
+                transition_state_from_super_roots(&output, &post, step)
+            }
+            None => pre_state_from_super_root(&output),
+        }
+        .map_err(InteropHostError::SupervisorError)?;
+        info!(
+            target: "host",
+            timestamp,
+            step = ?self.agreed_l2_pre_state_step,
+            super_root = ?output.super_root,
+            "Constructed agreed pre-state from supervisor"
+        );
+
+        self.agreed_l2_pre_state = Some(alloy_rlp::encode(&pre_state).into());
+        Ok(self)
+    }
+
     /// Starts the preimage server, communicating with the client over the provided channels.
     async fn start_server<C>(
         &self,
@@ -153,7 +269,20 @@ impl InteropHost {
         C: Channel + Send + Sync + 'static,
     {
         let kv_store = self.create_key_value_store()?;
+        self.start_server_with_store(hint, preimage, kv_store).await
+    }
 
+    /// Starts the preimage server backed by the given key-value store, communicating with the
+    /// client over the provided channels.
+    pub async fn start_server_with_store<C>(
+        &self,
+        hint: C,
+        preimage: C,
+        kv_store: SharedKeyValueStore,
+    ) -> Result<JoinHandle<Result<(), InteropHostError>>, InteropHostError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
         let task_handle = if self.is_offline() {
             task::spawn(async {
                 PreimageServer::new(
@@ -190,22 +319,81 @@ impl InteropHost {
         Ok(task_handle)
     }
 
+    /// Starts the preimage server on a stream socket, serving each client session that connects
+    /// to the given address concurrently.
+    ///
+    /// In online mode, each session is backed by its own key-value store, in a subdirectory of the
+    /// data directory if one is set. In offline mode, all sessions read from the data directory.
+    async fn start_socket_server(&self, addr: &SocketAddress) -> Result<(), InteropHostError> {
+        let mut listener = PreimageListener::bind(addr).await?;
+        info!(target: "host", "Listening for preimage sessions on {addr}");
+
+        let offline_kv_store =
+            self.is_offline().then(|| self.create_key_value_store()).transpose()?;
+        loop {
+            let session = match listener.accept().await {
+                Ok(session) => session,
+                Err(e) => {
+                    warn!(target: "host", "Failed to accept preimage connection: {e}");
+                    continue;
+                }
+            };
+            info!(target: "host", "Accepted preimage session {}", session.id);
+
+            let kv_store = match &offline_kv_store {
+                Some(kv_store) => kv_store.clone(),
+                None => {
+                    let mut cfg = self.clone();
+                    cfg.data_dir =
+                        cfg.data_dir.map(|dir| dir.join(format!("session-{}", session.id)));
+                    cfg.create_key_value_store()?
+                }
+            };
+            let server =
+                self.start_server_with_store(session.hint, session.preimage, kv_store).await?;
+
+            let id = session.id;
+            task::spawn(async move {
+                match server.await {
+                    Ok(Ok(())) => info!(target: "host", "Preimage session {id} closed"),
+                    Ok(Err(e)) => error!(target: "host", "Preimage session {id} failed: {e}"),
+                    Err(e) => error!(target: "host", "Preimage session {id} panicked: {e}"),
+                }
+            });
+        }
+    }
+
     /// Starts the host in native mode, running both the client and preimage server in the same
     /// process.
     async fn start_native(&self) -> Result<(), InteropHostError> {
+        let kv_store = self.create_key_value_store()?;
+        let client_result = self.run_native(kv_store, kona_client::interop::run).await?;
+
+        // Bubble up the exit status of the client program if execution completes.
+        std::process::exit(client_result.is_err() as i32)
+    }
+
+    /// Runs the given client program natively, against a preimage server backed by the given
+    /// key-value store, and returns the program's result.
+    pub async fn run_native<F, Fut>(
+        &self,
+        kv_store: SharedKeyValueStore,
+        program: F,
+    ) -> Result<Fut::Output, InteropHostError>
+    where
+        F: FnOnce(OracleReader<NativeChannel>, HintWriter<NativeChannel>) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
         let hint = BidirectionalChannel::new()?;
         let preimage = BidirectionalChannel::new()?;
 
-        let server_task = self.start_server(hint.host, preimage.host).await?;
-        let client_task = task::spawn(kona_client::interop::run(
-            OracleReader::new(preimage.client),
-            HintWriter::new(hint.client),
-        ));
+        let server_task = self.start_server_with_store(hint.host, preimage.host, kv_store).await?;
+        let client_task =
+            task::spawn(program(OracleReader::new(preimage.client), HintWriter::new(hint.client)));
 
         let (_, client_result) = tokio::try_join!(server_task, client_task)?;
-
-        // Bubble up the exit status of the client program if execution completes.
-        std::process::exit(client_result.is_err() as i32)
+        Ok(client_result)
     }
 
     /// Returns `true` if the host is running in offline mode.
@@ -248,19 +436,47 @@ impl InteropHost {
             .map_err(|_| InteropHostError::Other("failed to parse L1 config"))
     }
 
+    /// Returns the rollup config of the given chain, from the provided rollup configs or the
+    /// superchain registry.
+    fn rollup_config(&self, chain_id: u64) -> Result<RollupConfig, InteropHostError> {
+        self.read_rollup_configs()
+            .transpose()?
+            .and_then(|mut configs| configs.remove(&chain_id))
+            .or_else(|| ROLLUP_CONFIGS.get(&chain_id).cloned())
+            .ok_or(InteropHostError::Other("No rollup config found for agreed chain"))
+    }
+
+    /// Creates the [SupervisorProvider], if a supervisor address is set.
+    fn supervisor_provider(&self) -> Result<Option<SupervisorProvider>, InteropHostError> {
+        self.supervisor_address
+            .as_deref()
+            .map(SupervisorProvider::new_http)
+            .transpose()
+            .map_err(InteropHostError::SupervisorError)
+    }
+
     /// Creates the key-value store for the host backend.
     fn create_key_value_store(&self) -> Result<SharedKeyValueStore, InteropHostError> {
         let local_kv_store = InteropLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
-            let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
-        } else {
-            let mem_kv_store = MemoryKeyValueStore::new();
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, mem_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
-        };
+        let mut remote_kv_store: Box<dyn KeyValueStore + Send + Sync> =
+            if let Some(ref data_dir) = self.data_dir {
+                Box::new(DiskKeyValueStore::new(data_dir.clone()))
+            } else {
+                Box::new(MemoryKeyValueStore::new())
+            };
+        if let Some(ref cache_dir) = self.preimage_cache {
+            let max_size = PreimageCache::max_size_from_mib(
+                self.preimage_cache_size.unwrap_or(PreimageCache::DEFAULT_MAX_SIZE_MIB),
+            )
+            .ok_or(InteropHostError::Other("Preimage cache size is too large"))?;
+            let cache = PreimageCache::open(cache_dir.clone(), max_size)
+                .map_err(InteropHostError::PreimageCacheError)?;
+            remote_kv_store = Box::new(CachedKeyValueStore::new(remote_kv_store, cache));
+        }
+
+        let split_kv_store = SplitKeyValueStore::new(local_kv_store, remote_kv_store);
+        let kv_store: SharedKeyValueStore = Arc::new(RwLock::new(split_kv_store));
 
         Ok(kv_store)
     }
@@ -272,12 +488,18 @@ impl InteropHost {
         )
         .await;
 
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
-            self.l1_beacon_address
-                .clone()
-                .ok_or(InteropHostError::Other("Beacon API URL must be set"))?,
-        ))
-        .await;
+        let blob_provider = FallbackBlobProvider::new(
+            OnlineBlobProvider::init(OnlineBeaconClient::new_http(
+                self.l1_beacon_address
+                    .clone()
+                    .ok_or(InteropHostError::Other("Beacon API URL must be set"))?,
+            ))
+            .await,
+        )
+        .with_archiver(self.l1_blob_archiver_address.clone().map(BlobArchiverClient::new_http))
+        .with_directory(
+            self.l1_blob_dir.clone().map(|dir| BlobDirectory::new(dir).with_write_through(true)),
+        );
 
         // Resolve all chain IDs to their corresponding providers.
         let l2_node_addresses = self
@@ -291,7 +513,58 @@ impl InteropHost {
             l2_providers.insert(chain_id, l2_provider);
         }
 
-        Ok(InteropProviders { l1: l1_provider, blobs: blob_provider, l2s: l2_providers })
+        // Discover the chains in the dependency set through the supervisor, and ensure that an L2
+        // provider is available for each of them.
+        let supervisor = self.supervisor_provider()?;
+        if let Some(supervisor) = &supervisor {
+            let l1_head = l1_provider
+                .get_header_by_hash(self.l1_head)
+                .await?
+                .ok_or(InteropHostError::Other("L1 head not found"))?;
+            let safe_heads = supervisor
+                .all_safe_derived_at(BlockNumHash::new(l1_head.number, self.l1_head))
+                .await?;
+
+            if let Some(chain_id) =
+                safe_heads.keys().find(|chain_id| !l2_providers.contains_key(*chain_id))
+            {
+                return Err(InteropHostError::RootProviderError(*chain_id));
+            }
+
+            // Ensure that the agreed L2 block of every chain was cross-safe at the L1 head, so that
+            // the proof does not start from a state that the L1 head does not commit to.
+            let super_root = match PreState::decode(&mut self.agreed_pre_state())
+                .map_err(|_| InteropHostError::Other("Invalid agreed pre-state"))?
+            {
+                PreState::SuperRoot(super_root) => super_root,
+                PreState::TransitionState(transition_state) => transition_state.pre_state,
+            };
+            for output_root in &super_root.output_roots {
+                let chain_id = output_root.chain_id;
+                let number =
+                    self.rollup_config(chain_id)?.block_number_from_timestamp(super_root.timestamp);
+                let block = l2_providers
+                    .get(&chain_id)
+                    .ok_or(InteropHostError::RootProviderError(chain_id))?
+                    .get_block_by_number(number.into())
+                    .await?
+                    .ok_or(InteropHostError::Other("Agreed L2 block not found"))?;
+
+                let source = supervisor
+                    .cross_derived_to_source(chain_id, BlockNumHash::new(number, block.header.hash))
+                    .await?;
+                if source.number > l1_head.number {
+                    return Err(InteropHostError::NotCrossSafe(chain_id, number));
+                }
+            }
+        }
+
+        Ok(InteropProviders {
+            l1: l1_provider,
+            blobs: blob_provider,
+            l2s: l2_providers,
+            supervisor,
+        })
     }
 }
 
@@ -306,9 +579,11 @@ pub struct InteropProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: FallbackBlobProvider<OnlineBeaconClient>,
     /// The L2 EL providers, keyed by chain ID.
     pub l2s: HashMap<u64, RootProvider<Optimism>>,
+    /// The supervisor provider, if one is configured.
+    pub supervisor: Option<SupervisorProvider>,
 }
 
 impl InteropProviders {
@@ -345,10 +620,132 @@ mod tests {
             "http://localhost:8547",
         ]);
         assert_eq!(host.l1_head, hash);
-        assert_eq!(host.agreed_l2_pre_state, Bytes::from(hash.0));
+        assert_eq!(host.agreed_l2_pre_state, Some(Bytes::from(hash.0)));
         assert_eq!(host.claimed_l2_post_state, hash);
         assert_eq!(host.claimed_l2_timestamp, 0);
         assert!(host.native);
+        assert!(host.supervisor_address.is_none());
+    }
+
+    #[test]
+    fn test_parse_interop_host_cli_supervisor() {
+        let hash = b256!("ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68");
+        let host = InteropHost::try_parse_from([
+            "interop-host",
+            "--l1-head",
+            &hash.to_string(),
+            "--claimed-l2-post-state",
+            &hash.to_string(),
+            "--claimed-l2-timestamp",
+            "1",
+            "--native",
+            "--supervisor",
+            "http://localhost:8548",
+            "--l2-node-addresses",
+            "http://localhost:8545",
+            "--l1-node-address",
+            "http://localhost:8546",
+            "--l1-beacon-address",
+            "http://localhost:8547",
+        ])
+        .unwrap();
+        assert!(host.agreed_l2_pre_state.is_none());
+        assert!(host.agreed_l2_pre_state_step.is_none());
+        assert_eq!(host.supervisor_address.as_deref(), Some("http://localhost:8548"));
+
+        // The supervisor is only used alongside the L2 endpoints.
+        assert!(
+            InteropHost::try_parse_from([
+                "interop-host",
+                "--l1-head",
+                &hash.to_string(),
+                "--claimed-l2-post-state",
+                &hash.to_string(),
+                "--claimed-l2-timestamp",
+                "1",
+                "--native",
+                "--supervisor",
+                "http://localhost:8548",
+            ])
+            .is_err()
+        );
+
+        // The agreed pre-state step selects a transition state sourced from the supervisor.
+        let host = InteropHost::try_parse_from([
+            "interop-host",
+            "--l1-head",
+            &hash.to_string(),
+            "--claimed-l2-post-state",
+            &hash.to_string(),
+            "--claimed-l2-timestamp",
+            "1",
+            "--agreed-l2-pre-state-step",
+            "2",
+            "--native",
+            "--supervisor",
+            "http://localhost:8548",
+            "--l2-node-addresses",
+            "http://localhost:8545",
+            "--l1-node-address",
+            "http://localhost:8546",
+            "--l1-beacon-address",
+            "http://localhost:8547",
+        ])
+        .unwrap();
+        assert_eq!(host.agreed_l2_pre_state_step, Some(2));
+    }
+
+    #[test]
+    fn test_parse_interop_host_cli_missing_pre_state() {
+        let hash = b256!("ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68");
+
+        // Without a supervisor, the agreed pre-state is required.
+        assert!(
+            InteropHost::try_parse_from([
+                "interop-host",
+                "--l1-head",
+                &hash.to_string(),
+                "--claimed-l2-post-state",
+                &hash.to_string(),
+                "--claimed-l2-timestamp",
+                "1",
+                "--native",
+                "--l2-node-addresses",
+                "http://localhost:8545",
+                "--l1-node-address",
+                "http://localhost:8546",
+                "--l1-beacon-address",
+                "http://localhost:8547",
+            ])
+            .is_err()
+        );
+
+        // The agreed pre-state step is only used when constructing the pre-state.
+        assert!(
+            InteropHost::try_parse_from([
+                "interop-host",
+                "--l1-head",
+                &hash.to_string(),
+                "--l2-pre-state",
+                &hash.to_string(),
+                "--claimed-l2-post-state",
+                &hash.to_string(),
+                "--claimed-l2-timestamp",
+                "1",
+                "--agreed-l2-pre-state-step",
+                "2",
+                "--native",
+                "--supervisor",
+                "http://localhost:8548",
+                "--l2-node-addresses",
+                "http://localhost:8545",
+                "--l1-node-address",
+                "http://localhost:8546",
+                "--l1-beacon-address",
+                "http://localhost:8547",
+            ])
+            .is_err()
+        );
     }
 
     #[test]
@@ -373,7 +770,7 @@ mod tests {
             "http://localhost:8547",
         ]);
         assert_eq!(host.l1_head, hash);
-        assert_eq!(host.agreed_l2_pre_state, Bytes::from([0xff]));
+        assert_eq!(host.agreed_l2_pre_state, Some(Bytes::from([0xff])));
         assert_eq!(host.claimed_l2_post_state, hash);
         assert_eq!(host.claimed_l2_timestamp, 0);
         assert!(host.native);
@@ -192,14 +192,14 @@ impl HintHandler for InteropHintHandler {
 
                 let hash: B256 = hint.data.as_ref().try_into()?;
 
-                if hash != keccak256(cfg.agreed_l2_pre_state.as_ref()) {
+                if hash != keccak256(cfg.agreed_pre_state()) {
                     anyhow::bail!("Agreed pre-state hash does not match.");
                 }
 
                 let mut kv_write_lock = kv.write().await;
                 kv_write_lock.set(
                     PreimageKey::new_keccak256(*hash).into(),
-                    cfg.agreed_l2_pre_state.clone().into(),
+                    cfg.agreed_pre_state().to_vec(),
                 )?;
             }
             HintType::L2OutputRoot => {
@@ -210,7 +210,7 @@ impl HintHandler for InteropHintHandler {
                 let l2_provider = providers.l2(&chain_id)?;
 
                 // Decode the pre-state to determine the timestamp of the block.
-                let pre = PreState::decode(&mut cfg.agreed_l2_pre_state.as_ref())?;
+                let pre = PreState::decode(&mut cfg.agreed_pre_state())?;
                 let timestamp = match pre {
                     PreState::SuperRoot(super_root) => super_root.timestamp,
                     PreState::TransitionState(transition_state) => {
@@ -218,6 +218,17 @@ impl HintHandler for InteropHintHandler {
                     }
                 };
 
+                // If a supervisor is configured, attempt to source the output root preimage from
+                // it before reconstructing it from the L2 chain state.
+                if let Some(supervisor) = &providers.supervisor &&
+                    let Some(output_root) =
+                        supervisor.output_root_preimage(timestamp, chain_id, hash).await?
+                {
+                    let mut kv_lock = kv.write().await;
+                    kv_lock.set(PreimageKey::new_keccak256(*hash).into(), output_root.into())?;
+                    return Ok(());
+                }
+
                 // Convert the timestamp to an L2 block number, using the rollup config for the
                 // chain ID embedded within the hint.
                 let rollup_config = cfg
@@ -613,4 +624,17 @@ impl HintHandler for InteropHintHandler {
 
         Ok(())
     }
+
+    fn predict_hints(
+        hint: &Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>,
+    ) -> Vec<Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>> {
+        match hint.ty {
+            // Derivation reads the receipts and transactions of each L1 block after its header.
+            HintType::L1BlockHeader => vec![
+                Hint::new(HintType::L1Receipts, hint.data.clone()),
+                Hint::new(HintType::L1Transactions, hint.data.clone()),
+            ],
+            _ => Vec::new(),
+        }
+    }
 }
@@ -29,9 +29,7 @@ impl KeyValueStore for InteropLocalInputs {
         let preimage_key = PreimageKey::try_from(*key).ok()?;
         match preimage_key.key_value() {
             L1_HEAD_KEY => Some(self.cfg.l1_head.to_vec()),
-            L2_AGREED_PRE_STATE_KEY => {
-                Some(keccak256(self.cfg.agreed_l2_pre_state.as_ref()).to_vec())
-            }
+            L2_AGREED_PRE_STATE_KEY => Some(keccak256(self.cfg.agreed_pre_state()).to_vec()),
             L2_CLAIMED_POST_STATE_KEY => Some(self.cfg.claimed_l2_post_state.to_vec()),
             L2_CLAIMED_TIMESTAMP_KEY => Some(self.cfg.claimed_l2_timestamp.to_be_bytes().to_vec()),
             L2_ROLLUP_CONFIG_KEY => {
@@ -8,3 +8,8 @@ pub use local_kv::InteropLocalInputs;
 
 mod handler;
 pub use handler::InteropHintHandler;
+
+mod supervisor;
+pub use supervisor::{
+    SupervisorProvider, pre_state_from_super_root, transition_state_from_super_roots,
+};
@@ -0,0 +1,257 @@
+//! A client for the subset of the supervisor API used by the [InteropHost].
+//!
+//! [InteropHost]: super::InteropHost
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{B256, Bytes, ChainId, U64, keccak256, map::HashMap};
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{RpcError, TransportErrorKind};
+use anyhow::{Result, anyhow, ensure};
+use kona_interop::{OutputRootWithChain, SuperRoot};
+use kona_proof_interop::{OptimisticBlock, PreState, TRANSITION_STATE_MAX_STEPS, TransitionState};
+use kona_protocol::{BlockInfo, OutputRoot};
+use kona_supervisor_rpc::SuperRootOutputRpc;
+
+/// A client for a `kona-supervisor` (or `op-supervisor`) JSON-RPC endpoint.
+///
+/// The supervisor tracks every chain in the dependency set, and is used by the [InteropHost] to
+/// construct the agreed pre-state and to discover the chains that take part in the proof.
+///
+/// [InteropHost]: super::InteropHost
+#[derive(Debug, Clone)]
+pub struct SupervisorProvider {
+    /// The inner RPC client.
+    client: RpcClient,
+}
+
+impl SupervisorProvider {
+    /// Creates a new [SupervisorProvider] over HTTP.
+    pub fn new_http(url: &str) -> Result<Self> {
+        let url = url.parse().map_err(|e| anyhow!("Invalid supervisor URL {url}: {e}"))?;
+        Ok(Self { client: RpcClient::new_http(url) })
+    }
+
+    /// Returns the super root output at the given timestamp, as seen by the supervisor.
+    pub async fn super_root_at_timestamp(
+        &self,
+        timestamp: u64,
+    ) -> Result<SuperRootOutputRpc, RpcError<TransportErrorKind>> {
+        self.client.request("supervisor_superRootAtTimestamp", (U64::from(timestamp),)).await
+    }
+
+    /// Returns the last block derived from the given L1 block, for each chain in the dependency
+    /// set.
+    pub async fn all_safe_derived_at(
+        &self,
+        derived_from: BlockNumHash,
+    ) -> Result<HashMap<ChainId, BlockNumHash>, RpcError<TransportErrorKind>> {
+        self.client.request("supervisor_allSafeDerivedAt", (derived_from,)).await
+    }
+
+    /// Returns the L1 block that the given L2 block on `chain_id` was cross-safe derived from.
+    pub async fn cross_derived_to_source(
+        &self,
+        chain_id: ChainId,
+        block_id: BlockNumHash,
+    ) -> Result<BlockInfo, RpcError<TransportErrorKind>> {
+        self.client
+            .request("supervisor_crossDerivedToSource", (U64::from(chain_id), block_id))
+            .await
+    }
+
+    /// Returns the output root preimage of `chain_id` at the given timestamp, if it hashes to
+    /// `output_root`.
+    ///
+    /// The supervisor only serves the pending output root preimage of each chain, which is not the
+    /// preimage of the canonical output root if the chain's block at the timestamp was replaced.
+    pub async fn output_root_preimage(
+        &self,
+        timestamp: u64,
+        chain_id: ChainId,
+        output_root: B256,
+    ) -> Result<Option<Bytes>> {
+        let super_root = self.super_root_at_timestamp(timestamp).await?;
+        let chain =
+            super_root.chains.into_iter().find(|chain| chain.chain_id == chain_id).ok_or(
+                anyhow!("Chain {chain_id} not found in super root at timestamp {timestamp}"),
+            )?;
+
+        Ok((keccak256(chain.pending.as_ref()) == output_root).then_some(chain.pending))
+    }
+}
+
+/// Builds the agreed [PreState] from the supervisor's super root output, checking that it commits
+/// to the super root reported by the supervisor.
+pub fn pre_state_from_super_root(output: &SuperRootOutputRpc) -> Result<PreState> {
+    super_root_from_output(output).map(PreState::SuperRoot)
+}
+
+/// Builds an agreed [TransitionState] pre-state at the given `step` of the transition from the
+/// super root output `pre` to the super root output `post`, one second later.
+///
+/// The pending progress holds the optimistic blocks of the first `step` chains of the pre-state,
+/// in chain ID order, sourced from the pending output roots of `post`.
+pub fn transition_state_from_super_roots(
+    pre: &SuperRootOutputRpc,
+    post: &SuperRootOutputRpc,
+    step: u64,
+) -> Result<PreState> {
+    ensure!(
+        step <= TRANSITION_STATE_MAX_STEPS,
+        "Transition step {step} exceeds the maximum of {TRANSITION_STATE_MAX_STEPS}"
+    );
+    ensure!(
+        post.timestamp == pre.timestamp + 1,
+        "Super root at timestamp {} does not follow the super root at timestamp {}",
+        post.timestamp,
+        pre.timestamp
+    );
+
+    let super_root = super_root_from_output(pre)?;
+    let pending_progress = super_root
+        .output_roots
+        .iter()
+        .take(step as usize)
+        .map(|output_root| {
+            let chain =
+                post.chains.iter().find(|chain| chain.chain_id == output_root.chain_id).ok_or(
+                    anyhow!(
+                        "Chain {} not found in super root at timestamp {}",
+                        output_root.chain_id,
+                        post.timestamp
+                    ),
+                )?;
+            ensure!(
+                chain.pending.len() == OutputRoot::ENCODED_LENGTH,
+                "Invalid pending output root preimage for chain {}",
+                chain.chain_id
+            );
+
+            Ok(OptimisticBlock::new(
+                B256::from_slice(&chain.pending[96..]),
+                keccak256(chain.pending.as_ref()),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(PreState::TransitionState(TransitionState::new(super_root, pending_progress, step)))
+}
+
+/// Builds the [SuperRoot] committed to by the supervisor's super root output, checking that it
+/// matches the super root reported by the supervisor.
+fn super_root_from_output(output: &SuperRootOutputRpc) -> Result<SuperRoot> {
+    let super_root = SuperRoot::new(
+        output.timestamp,
+        output
+            .chains
+            .iter()
+            .map(|chain| OutputRootWithChain::new(chain.chain_id, chain.canonical))
+            .collect(),
+    );
+
+    let super_root_hash = super_root.hash();
+    ensure!(
+        super_root_hash == output.super_root,
+        "Super root does not match supervisor. Expected: {}, got: {super_root_hash}",
+        output.super_root
+    );
+    Ok(super_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_supervisor_rpc::ChainRootInfoRpc;
+
+    fn super_root_output(timestamp: u64) -> SuperRootOutputRpc {
+        let chains = vec![
+            ChainRootInfoRpc {
+                chain_id: 902,
+                canonical: B256::repeat_byte(0x02),
+                pending: Bytes::from_static(&[0x02; 128]),
+            },
+            ChainRootInfoRpc {
+                chain_id: 901,
+                canonical: B256::repeat_byte(0x01),
+                pending: Bytes::from_static(&[0x01; 128]),
+            },
+        ];
+        let super_root = SuperRoot::new(
+            timestamp,
+            chains.iter().map(|c| OutputRootWithChain::new(c.chain_id, c.canonical)).collect(),
+        );
+
+        SuperRootOutputRpc {
+            cross_safe_derived_from: BlockNumHash::default(),
+            timestamp,
+            super_root: super_root.hash(),
+            version: 1,
+            chains,
+        }
+    }
+
+    #[test]
+    fn test_pre_state_from_super_root() {
+        let output = super_root_output(100);
+        let PreState::SuperRoot(super_root) = pre_state_from_super_root(&output).unwrap() else {
+            panic!("Expected a super root pre-state");
+        };
+
+        assert_eq!(super_root.timestamp, 100);
+        assert_eq!(super_root.hash(), output.super_root);
+        assert_eq!(
+            super_root.output_roots.iter().map(|r| r.chain_id).collect::<Vec<_>>(),
+            [901, 902]
+        );
+    }
+
+    #[test]
+    fn test_pre_state_from_super_root_mismatch() {
+        let mut output = super_root_output(100);
+        output.super_root = B256::ZERO;
+
+        assert!(pre_state_from_super_root(&output).is_err());
+    }
+
+    #[test]
+    fn test_transition_state_from_super_roots() {
+        let pre = super_root_output(100);
+        let post = super_root_output(101);
+        let PreState::TransitionState(transition_state) =
+            transition_state_from_super_roots(&pre, &post, 1).unwrap()
+        else {
+            panic!("Expected a transition state pre-state");
+        };
+
+        assert_eq!(transition_state.step, 1);
+        assert_eq!(transition_state.pre_state.hash(), pre.super_root);
+        assert_eq!(
+            transition_state.pending_progress,
+            [OptimisticBlock::new(B256::repeat_byte(0x01), keccak256([0x01; 128]))]
+        );
+
+        // Steps past the number of chains carry the progress of every chain.
+        let PreState::TransitionState(transition_state) =
+            transition_state_from_super_roots(&pre, &post, 5).unwrap()
+        else {
+            panic!("Expected a transition state pre-state");
+        };
+        assert_eq!(transition_state.step, 5);
+        assert_eq!(transition_state.pending_progress.len(), 2);
+    }
+
+    #[test]
+    fn test_transition_state_from_super_roots_invalid() {
+        let pre = super_root_output(100);
+
+        assert!(transition_state_from_super_roots(&pre, &super_root_output(102), 1).is_err());
+        assert!(
+            transition_state_from_super_roots(
+                &pre,
+                &super_root_output(101),
+                TRANSITION_STATE_MAX_STEPS + 1
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Contains a persistent, content-addressed preimage cache that is shared between host runs, and a
+//! [KeyValueStore] that layers it over the store of a single run.
+
+use super::KeyValueStore;
+use alloy_primitives::{B256, hex};
+use anyhow::{Result, anyhow};
+use kona_preimage::PreimageKeyType;
+use std::{
+    fs::{self, File},
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::SystemTime,
+};
+use tracing::{debug, warn};
+
+/// The name of the directory that preimages are staged in before being moved into the cache.
+const STAGING_DIR: &str = "staging";
+
+/// The fraction of the maximum cache size that this process may write before the cache is checked
+/// against its size limit.
+const EVICTION_INTERVAL_DIVISOR: u64 = 16;
+
+/// The fraction of the maximum cache size that the cache is trimmed down to when it is evicted.
+const EVICTION_TARGET_PERCENT: u64 = 90;
+
+/// A persistent preimage cache, storing content-addressed preimages on disk so that they can be
+/// reused across host runs.
+///
+/// Only preimages whose keys commit to their contents are cached (see
+/// [PreimageCache::is_cacheable]). Local preimages, which differ between runs, are never stored.
+///
+/// Every preimage is stored in its own file, which is written to a staging directory and atomically
+/// renamed into place. The cache can therefore be shared by several host processes at once, without
+/// any locking. Reading a preimage refreshes its modification time, and once the cache grows past
+/// its maximum size, the least recently used preimages are evicted.
+#[derive(Debug)]
+pub struct PreimageCache {
+    /// The root directory of the cache.
+    directory: PathBuf,
+    /// The maximum size of the cache, in bytes.
+    max_size: u64,
+    /// The number of bytes written by this process since the cache was last checked against its
+    /// size limit.
+    written: AtomicU64,
+    /// A counter used to give staged preimages unique names within this process.
+    staged: AtomicU64,
+    /// Serializes evictions within this process.
+    eviction: Mutex<()>,
+}
+
+impl PreimageCache {
+    /// The default maximum size of the cache, in MiB.
+    pub const DEFAULT_MAX_SIZE_MIB: u64 = 8 * 1024;
+
+    /// Converts a maximum cache size in MiB to bytes, returning `None` if it overflows.
+    pub const fn max_size_from_mib(mib: u64) -> Option<u64> {
+        mib.checked_mul(1 << 20)
+    }
+
+    /// Parses a maximum cache size in MiB, as passed on the command line, rejecting sizes that
+    /// overflow when converted to bytes.
+    pub fn parse_max_size_mib(s: &str) -> Result<u64, String> {
+        let mib = s.parse().map_err(|e| format!("Invalid preimage cache size {s}: {e}"))?;
+        Self::max_size_from_mib(mib)
+            .map(|_| mib)
+            .ok_or_else(|| format!("Preimage cache size of {mib} MiB is too large"))
+    }
+
+    /// Opens the [PreimageCache] in the given directory, creating it if it does not exist.
+    pub fn open(directory: PathBuf, max_size: u64) -> Result<Self> {
+        fs::create_dir_all(directory.join(STAGING_DIR))
+            .map_err(|e| anyhow!("Failed to create preimage cache at {directory:?}: {e}"))?;
+        Ok(Self {
+            directory,
+            max_size,
+            written: AtomicU64::new(0),
+            staged: AtomicU64::new(0),
+            eviction: Mutex::new(()),
+        })
+    }
+
+    /// Returns `true` if preimages with the given key are shared across runs. Keccak256, SHA256,
+    /// blob and precompile keys commit to their preimages, while local and global generic keys do
+    /// not.
+    pub fn is_cacheable(key: B256) -> bool {
+        matches!(
+            PreimageKeyType::try_from(key[0]),
+            Ok(PreimageKeyType::Keccak256 |
+                PreimageKeyType::Sha256 |
+                PreimageKeyType::Blob |
+                PreimageKeyType::Precompile)
+        )
+    }
+
+    /// Returns the preimage for the given key, if it is cached.
+    pub fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let path = self.path(key);
+        let value = fs::read(&path).ok()?;
+
+        // Mark the preimage as recently used. Failing to do so only affects eviction order.
+        if let Err(e) = File::options()
+            .append(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            debug!(target: "preimage_cache", "Failed to refresh {key}: {e}");
+        }
+
+        Some(value)
+    }
+
+    /// Stores the preimage for the given key in the cache. Preimages with keys that are not
+    /// [cacheable](Self::is_cacheable) are ignored.
+    pub fn set(&self, key: B256, value: &[u8]) -> Result<()> {
+        if !Self::is_cacheable(key) {
+            return Ok(());
+        }
+
+        let path = self.path(key);
+        if path.exists() {
+            return Ok(());
+        }
+
+        // Stage the preimage under a name that is unique to this process, and move it into place
+        // once it has been fully written, so that readers never observe a partial preimage.
+        let staged = self.directory.join(STAGING_DIR).join(format!(
+            "{}.{}.{}",
+            hex::encode(key),
+            std::process::id(),
+            self.staged.fetch_add(1, Ordering::Relaxed)
+        ));
+        let write = || -> std::io::Result<()> {
+            File::create(&staged)?.write_all(value)?;
+            fs::create_dir_all(path.parent().expect("cache paths have a parent"))?;
+            fs::rename(&staged, &path)
+        };
+        if let Err(e) = write() {
+            let _ = fs::remove_file(&staged);
+            return Err(anyhow!("Failed to cache preimage {key}: {e}"));
+        }
+
+        let written = self.written.fetch_add(value.len() as u64, Ordering::Relaxed);
+        if written + value.len() as u64 >= self.max_size / EVICTION_INTERVAL_DIVISOR {
+            self.written.store(0, Ordering::Relaxed);
+            if let Err(e) = self.evict() {
+                warn!(target: "preimage_cache", "Failed to evict preimages: {e}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evicts the least recently used preimages until the cache is below its target size. Returns
+    /// the number of evicted preimages.
+    pub fn evict(&self) -> Result<usize> {
+        let _guard = self.eviction.lock().map_err(|_| anyhow!("Eviction lock poisoned"))?;
+
+        let mut entries = Vec::new();
+        let mut size = 0;
+        for shard in fs::read_dir(&self.directory)? {
+            let shard = shard?;
+            if shard.file_name() == STAGING_DIR || !shard.file_type()?.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(shard.path())? {
+                // Entries may be evicted by other processes while the cache is being scanned.
+                let Ok(metadata) = entry.and_then(|e| Ok((e.path(), e.metadata()?))) else {
+                    continue;
+                };
+                size += metadata.1.len();
+                entries.push(metadata);
+            }
+        }
+
+        if size <= self.max_size {
+            return Ok(0);
+        }
+
+        entries.sort_by_key(|(_, metadata)| metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+
+        let target = self.max_size / 100 * EVICTION_TARGET_PERCENT;
+        let mut evicted = 0;
+        for (path, metadata) in entries {
+            if size <= target {
+                break;
+            }
+            match fs::remove_file(&path) {
+                Ok(()) => evicted += 1,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(anyhow!("Failed to evict {path:?}: {e}")),
+            }
+            size -= metadata.len();
+        }
+
+        debug!(target: "preimage_cache", "Evicted {evicted} preimages");
+        Ok(evicted)
+    }
+
+    /// Returns the path of the file that the preimage for the given key is stored in. Preimages are
+    /// sharded by the first byte of their key's hash, to keep directories small.
+    fn path(&self, key: B256) -> PathBuf {
+        let key = hex::encode(key);
+        self.directory.join(&key[2..4]).join(key)
+    }
+}
+
+/// A [KeyValueStore] that layers a shared [PreimageCache] over the store of a single run.
+///
+/// Preimages are read from the inner store first, and then from the cache. Written preimages are
+/// always kept in the inner store, so that a preimage evicted from the cache by another process
+/// remains available to the run, and [cacheable](PreimageCache::is_cacheable) preimages are
+/// additionally written to the cache. Preimages read from the cache are written through to the
+/// inner store, which therefore holds every preimage of the run, e.g. for offline re-runs.
+#[derive(Debug)]
+pub struct CachedKeyValueStore<S> {
+    /// The store of the current run, locked so that preimages read from the cache can be written
+    /// through to it.
+    store: Mutex<S>,
+    /// The shared preimage cache.
+    cache: PreimageCache,
+}
+
+impl<S> CachedKeyValueStore<S>
+where
+    S: KeyValueStore,
+{
+    /// Create a new [CachedKeyValueStore], layering the given [PreimageCache] over `store`.
+    pub const fn new(store: S, cache: PreimageCache) -> Self {
+        Self { store: Mutex::new(store), cache }
+    }
+}
+
+impl<S> KeyValueStore for CachedKeyValueStore<S>
+where
+    S: KeyValueStore,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let mut store = self.store.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(value) = store.get(key) {
+            return Some(value);
+        }
+
+        let value = self.cache.get(key)?;
+        if let Err(e) = store.set(key, value.clone()) {
+            warn!(target: "preimage_cache", "Failed to write through preimage {key}: {e}");
+        }
+        Some(value)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        if let Err(e) = self.cache.set(key, &value) {
+            warn!(target: "preimage_cache", "{e}");
+        }
+        self.store.get_mut().unwrap_or_else(PoisonError::into_inner).set(key, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::kv::MemoryKeyValueStore;
+    use kona_preimage::PreimageKey;
+    use std::time::Duration;
+
+    fn keccak_key(byte: u8) -> B256 {
+        PreimageKey::new([byte; 32], PreimageKeyType::Keccak256).into()
+    }
+
+    #[test]
+    fn test_cache_shared_across_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = keccak_key(1);
+        let local_key = PreimageKey::new_local(1).into();
+
+        let mut first = CachedKeyValueStore::new(
+            MemoryKeyValueStore::new(),
+            PreimageCache::open(dir.path().to_path_buf(), u64::MAX).unwrap(),
+        );
+        first.set(key, vec![0xaa; 32]).unwrap();
+        first.set(local_key, vec![0xbb; 32]).unwrap();
+        assert_eq!(first.get(local_key).unwrap(), [0xbb; 32]);
+
+        let second = CachedKeyValueStore::new(
+            MemoryKeyValueStore::new(),
+            PreimageCache::open(dir.path().to_path_buf(), u64::MAX).unwrap(),
+        );
+        assert_eq!(second.get(key).unwrap(), [0xaa; 32]);
+        assert!(second.get(local_key).is_none());
+
+        // Preimages read from the cache are written through to the store of the run.
+        assert_eq!(second.store.lock().unwrap().get(key).unwrap(), [0xaa; 32]);
+    }
+
+    #[test]
+    fn test_parse_max_size_mib() {
+        assert_eq!(PreimageCache::parse_max_size_mib("8192").unwrap(), 8192);
+        assert_eq!(PreimageCache::max_size_from_mib(8192), Some(8 << 30));
+        assert!(PreimageCache::parse_max_size_mib("not a size").is_err());
+        assert!(PreimageCache::parse_max_size_mib(&u64::MAX.to_string()).is_err());
+        assert!(PreimageCache::parse_max_size_mib(&(1u64 << 44).to_string()).is_err());
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PreimageCache::open(dir.path().to_path_buf(), u64::MAX).unwrap();
+        for byte in 1..=4 {
+            cache.set(keccak_key(byte), &[byte; 100]).unwrap();
+
+            // Spread out the modification times, with the first key being the least recently used.
+            let modified = SystemTime::now() - Duration::from_secs(100 - byte as u64);
+            File::options()
+                .append(true)
+                .open(cache.path(keccak_key(byte)))
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+
+        // Reading a preimage marks it as recently used.
+        assert!(cache.get(keccak_key(1)).is_some());
+
+        let cache = PreimageCache::open(dir.path().to_path_buf(), 300).unwrap();
+        assert_eq!(cache.evict().unwrap(), 2);
+        assert!(cache.get(keccak_key(1)).is_some());
+        assert!(cache.get(keccak_key(2)).is_none());
+        assert!(cache.get(keccak_key(3)).is_none());
+        assert!(cache.get(keccak_key(4)).is_some());
+    }
+}
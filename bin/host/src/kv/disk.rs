@@ -5,13 +5,17 @@ use super::{KeyValueStore, MemoryKeyValueStore};
 use alloy_primitives::B256;
 use anyhow::{Result, anyhow};
 use rocksdb::{DB, Options};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 /// A simple, synchronous key-value store that stores data on disk.
+///
+/// Unless it is marked as persistent with [DiskKeyValueStore::with_persist], the database is
+/// destroyed when the store is dropped.
 #[derive(Debug)]
 pub struct DiskKeyValueStore {
     data_directory: PathBuf,
     db: DB,
+    persist: bool,
 }
 
 impl DiskKeyValueStore {
@@ -20,7 +24,13 @@ impl DiskKeyValueStore {
         let db = DB::open(&Self::get_db_options(), data_directory.as_path())
             .unwrap_or_else(|e| panic!("Failed to open database at {data_directory:?}: {e}"));
 
-        Self { data_directory, db }
+        Self { data_directory, db, persist: false }
+    }
+
+    /// Sets whether the database is kept on disk when the store is dropped.
+    pub const fn with_persist(mut self, persist: bool) -> Self {
+        self.persist = persist;
+        self
     }
 
     /// Gets the [Options] for the underlying RocksDB instance.
@@ -42,8 +52,23 @@ impl KeyValueStore for DiskKeyValueStore {
     }
 }
 
+/// A shared [DiskKeyValueStore], allowing one store to back several preimage servers in turn. The
+/// underlying database is dropped once the last reference is dropped.
+impl KeyValueStore for Arc<DiskKeyValueStore> {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.db.get(*key).ok()?
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.db.put(*key, value).map_err(|e| anyhow!("Failed to set key-value pair: {e}"))
+    }
+}
+
 impl Drop for DiskKeyValueStore {
     fn drop(&mut self) {
+        if self.persist {
+            return;
+        }
         let _ = DB::destroy(&Self::get_db_options(), self.data_directory.as_path());
     }
 }
@@ -14,6 +14,9 @@ pub use disk::DiskKeyValueStore;
 mod split;
 pub use split::SplitKeyValueStore;
 
+mod cache;
+pub use cache::{CachedKeyValueStore, PreimageCache};
+
 /// A type alias for a shared key-value store.
 pub type SharedKeyValueStore = Arc<RwLock<dyn KeyValueStore + Send + Sync>>;
 
@@ -25,3 +28,16 @@ pub trait KeyValueStore {
     /// Set the value associated with the given key.
     fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()>;
 }
+
+impl<T> KeyValueStore for Box<T>
+where
+    T: KeyValueStore + ?Sized,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        (**self).get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        (**self).set(key, value)
+    }
+}
@@ -2,11 +2,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod server;
-pub use server::{PreimageServer, PreimageServerError};
+pub use server::{HintLog, PreimageServer, PreimageServerError};
 
 mod kv;
 pub use kv::{
-    DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    CachedKeyValueStore, DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, PreimageCache,
+    SharedKeyValueStore, SplitKeyValueStore,
 };
 
 mod backend;
@@ -19,3 +20,6 @@ pub mod single;
 
 #[cfg(feature = "interop")]
 pub mod interop;
+
+#[cfg(feature = "interop")]
+pub mod service;
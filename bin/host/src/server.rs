@@ -1,11 +1,15 @@
 //! This module contains the [PreimageServer] struct and its implementation.
 
+use alloy_primitives::hex;
+use async_trait::async_trait;
 use kona_preimage::{
-    HintReaderServer, PreimageOracleServer, PreimageServerBackend, errors::PreimageOracleError,
+    HintReaderServer, HintRouter, PreimageOracleServer, PreimageServerBackend,
+    errors::{PreimageOracleError, PreimageOracleResult},
 };
-use std::sync::Arc;
+use kona_proof::profile::{PROFILE_HINT, ProfileReport};
+use std::sync::{Arc, Mutex};
 use tokio::spawn;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// The [PreimageServer] is responsible for waiting for incoming preimage requests and
 /// serving them to the client.
@@ -17,8 +21,13 @@ pub struct PreimageServer<P, H, B> {
     hint_reader: H,
     /// [PreimageServerBackend] that routes hints and retrieves preimages.
     backend: Arc<B>,
+    /// An optional log that every received hint is appended to, in order.
+    hint_log: Option<HintLog>,
 }
 
+/// A shared, ordered log of the hints received by a [PreimageServer].
+pub type HintLog = Arc<Mutex<Vec<String>>>;
+
 /// An error that can occur when handling preimage requests
 #[derive(Debug, thiserror::Error)]
 pub enum PreimageServerError {
@@ -42,14 +51,21 @@ where
     /// Create a new [PreimageServer] with the given [PreimageOracleServer],
     /// [HintReaderServer], and [PreimageServerBackend].
     pub const fn new(oracle_server: P, hint_reader: H, backend: Arc<B>) -> Self {
-        Self { oracle_server, hint_reader, backend }
+        Self { oracle_server, hint_reader, backend, hint_log: None }
+    }
+
+    /// Sets the [HintLog] that every hint received by the [PreimageServer] is appended to.
+    pub fn with_hint_log(mut self, hint_log: Option<HintLog>) -> Self {
+        self.hint_log = hint_log;
+        self
     }
 
     /// Starts the [PreimageServer] and waits for incoming requests.
     pub async fn start(self) -> Result<(), PreimageServerError> {
         // Create the futures for the oracle server and hint router.
         let server = spawn(Self::start_oracle_server(self.oracle_server, self.backend.clone()));
-        let hint_router = spawn(Self::start_hint_router(self.hint_reader, self.backend.clone()));
+        let hint_router =
+            spawn(Self::start_hint_router(self.hint_reader, self.backend.clone(), self.hint_log));
 
         // Race the two futures to completion, returning the result of the first one to finish.
         tokio::select! {
@@ -81,12 +97,17 @@ where
 
     /// Starts the hint router, which waits for incoming hints and routes them to the appropriate
     /// handler.
-    async fn start_hint_router(hint_reader: H, backend: Arc<B>) -> Result<(), PreimageServerError> {
+    async fn start_hint_router(
+        hint_reader: H,
+        backend: Arc<B>,
+        hint_log: Option<HintLog>,
+    ) -> Result<(), PreimageServerError> {
         info!(target: "host_server", "Starting hint router");
+        let router = HostHintRouter { inner: backend.as_ref(), hint_log };
         loop {
             // Route the next hint. This `await` will yield to the runtime if no progress can be
             // made.
-            match hint_reader.next_hint(backend.as_ref()).await {
+            match hint_reader.next_hint(&router).await {
                 Ok(_) => continue,
                 Err(PreimageOracleError::IOError(_)) => return Ok(()),
                 Err(e) => {
@@ -97,3 +118,36 @@ where
         }
     }
 }
+
+/// A [HintRouter] that appends hints to the [HintLog], if any, logs the profile reports sent by the
+/// client through the [PROFILE_HINT], and forwards all other hints to the inner router.
+#[derive(Debug)]
+struct HostHintRouter<'a, R> {
+    /// The inner hint router.
+    inner: &'a R,
+    /// The log to append received hints to.
+    hint_log: Option<HintLog>,
+}
+
+#[async_trait]
+impl<R> HintRouter for HostHintRouter<'_, R>
+where
+    R: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        if let Some(hint_log) = &self.hint_log {
+            hint_log.lock().expect("hint log poisoned").push(hint.clone());
+        }
+
+        let Some(data) = hint.strip_prefix(PROFILE_HINT).and_then(|rest| rest.strip_prefix(' '))
+        else {
+            return self.inner.route_hint(hint).await;
+        };
+
+        match hex::decode(data).ok().as_deref().and_then(ProfileReport::decode) {
+            Some(report) => info!(target: "host_server", "Client profile:\n{report}"),
+            None => warn!(target: "host_server", "Received malformed profile report"),
+        }
+        Ok(())
+    }
+}
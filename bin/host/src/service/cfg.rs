@@ -0,0 +1,48 @@
+//! This module contains all CLI-specific code for the proving service entrypoint.
+
+use super::{ProvingService, ProvingServiceApiServer};
+use anyhow::Result;
+use clap::Parser;
+use jsonrpsee::server::ServerBuilder;
+use kona_cli::cli_styles;
+use serde::Serialize;
+use std::{net::SocketAddr, path::PathBuf};
+use tracing::info;
+
+/// The proving service CLI arguments.
+///
+/// Runs the host as a long-running service, accepting single-chain and interop jobs over a
+/// JSON-RPC API and running them natively on a pool of workers.
+#[derive(Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct ServiceHost {
+    /// The address to serve the JSON-RPC API on.
+    #[arg(long, default_value = "127.0.0.1:9546", env)]
+    pub rpc_addr: SocketAddr,
+    /// The number of jobs to run concurrently.
+    #[arg(long, default_value_t = 1, env)]
+    pub workers: usize,
+    /// The data directory, holding the job state and the preimage store shared by all jobs.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+}
+
+impl ServiceHost {
+    /// Starts the proving service, and serves its JSON-RPC API until the server stops.
+    pub async fn start(self) -> Result<()> {
+        let service = ProvingService::new(&self.data_dir)?;
+        service.start_workers(self.workers);
+
+        let server = ServerBuilder::default().build(self.rpc_addr).await?;
+        let handle = server.start(service.into_rpc());
+        info!(
+            target: "host_service",
+            addr = %self.rpc_addr,
+            workers = self.workers,
+            "Proving service started"
+        );
+
+        handle.stopped().await;
+        Ok(())
+    }
+}
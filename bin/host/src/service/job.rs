@@ -0,0 +1,121 @@
+//! Contains the types describing a proving service job.
+
+use crate::{interop::InteropHost, single::SingleChainHost};
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The identifier of a [Job].
+pub type JobId = u64;
+
+/// The parameters of a [Job], which are the same as the parameters of the [SingleChainHost] and
+/// [InteropHost] modes.
+///
+/// The `native`, `server`, `listen` and `data_dir` parameters are ignored, as jobs always run
+/// natively against the preimage store of the service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+#[allow(clippy::large_enum_variant)]
+pub enum JobParams {
+    /// A single-chain job.
+    Single(SingleChainHost),
+    /// A super-chain (interop) job.
+    Super(InteropHost),
+}
+
+/// The status of a [Job].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    /// The job is waiting for a worker.
+    Queued,
+    /// The job is running.
+    Running,
+    /// The client program ran to completion and validated the claim.
+    Passed,
+    /// The client program rejected the claim, or failed to run.
+    Failed,
+    /// The job was cancelled.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns `true` if the job is finished and will not change status anymore.
+    pub const fn is_finished(&self) -> bool {
+        matches!(self, Self::Passed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// A proving service job, along with its results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    /// The identifier of the job.
+    pub id: JobId,
+    /// The parameters of the job.
+    pub params: JobParams,
+    /// The status of the job.
+    pub status: JobStatus,
+    /// The time the job was submitted at, in milliseconds since the unix epoch.
+    pub submitted_at: u64,
+    /// The time the job started running at, in milliseconds since the unix epoch.
+    pub started_at: Option<u64>,
+    /// The time the job finished at, in milliseconds since the unix epoch.
+    pub finished_at: Option<u64>,
+    /// The output root computed by the client program. For interop jobs, this is the claimed
+    /// post-state if the claim was validated.
+    pub output_root: Option<B256>,
+    /// The error the job failed with, if any.
+    pub error: Option<String>,
+    /// The number of preimages served to the client program.
+    pub preimages_served: u64,
+    /// The number of preimages fetched from the RPC endpoints and added to the preimage store.
+    pub preimages_fetched: u64,
+}
+
+impl Job {
+    /// Creates a new queued [Job] with the given identifier and parameters.
+    pub fn new(id: JobId, params: JobParams) -> Self {
+        Self {
+            id,
+            params,
+            status: JobStatus::Queued,
+            submitted_at: unix_millis(),
+            started_at: None,
+            finished_at: None,
+            output_root: None,
+            error: None,
+            preimages_served: 0,
+            preimages_fetched: 0,
+        }
+    }
+
+    /// Returns the time the job ran for, in milliseconds, if it finished running.
+    pub fn duration_ms(&self) -> Option<u64> {
+        Some(self.finished_at?.saturating_sub(self.started_at?))
+    }
+}
+
+/// Returns the current time, in milliseconds since the unix epoch.
+pub(crate) fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_params_serde() {
+        let params: JobParams = serde_json::from_str(
+            r#"{"mode":"single","l2_chain_id":10,"claimed_l2_block_number":100}"#,
+        )
+        .unwrap();
+        let JobParams::Single(host) = &params else { panic!("expected a single-chain job") };
+        assert_eq!(host.l2_chain_id, Some(10));
+        assert_eq!(host.claimed_l2_block_number, 100);
+
+        let value = serde_json::to_value(&params).unwrap();
+        assert_eq!(value["mode"], "single");
+    }
+}
@@ -0,0 +1,345 @@
+//! Contains the [ProvingService], which queues proving jobs and runs them on a worker pool.
+
+use super::{
+    Job, JobId, JobParams, JobStatus, JobStore,
+    job::unix_millis,
+    runner::{JobOutcome, run_job},
+};
+use crate::DiskKeyValueStore;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::{self, JoinHandle},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+/// The [ProvingService] queues single-chain and interop jobs, runs them natively on a pool of
+/// workers, and persists their state and results in a [JobStore].
+///
+/// All jobs share one on-disk preimage store, so preimages fetched by one job are reused by the
+/// next. Jobs that were queued or running when the service stopped are queued again on restart.
+#[derive(Debug, Clone)]
+pub struct ProvingService {
+    inner: Arc<ServiceState>,
+}
+
+/// The shared state of the [ProvingService].
+#[derive(Debug)]
+struct ServiceState {
+    /// The store persisting the jobs.
+    store: JobStore,
+    /// The preimage store shared by all jobs.
+    preimages: Arc<DiskKeyValueStore>,
+    /// The directory of the shared preimage store.
+    preimage_dir: PathBuf,
+    /// All known jobs, along with the tokens used to cancel them.
+    jobs: Mutex<BTreeMap<JobId, (Job, CancellationToken)>>,
+    /// The identifier of the next submitted job.
+    next_id: AtomicU64,
+    /// The sender of the job queue.
+    queue: UnboundedSender<JobId>,
+    /// The receiver of the job queue, shared by the workers.
+    receiver: tokio::sync::Mutex<UnboundedReceiver<JobId>>,
+}
+
+/// An error returned by the [ProvingService].
+#[derive(Debug, thiserror::Error)]
+pub enum ProvingServiceError {
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// A JSON (de)serialization error.
+    #[error("Failed (de)serializing job: {0}")]
+    ParseError(#[from] serde_json::Error),
+    /// The job does not exist.
+    #[error("Job {0} not found")]
+    JobNotFound(JobId),
+}
+
+impl ProvingService {
+    /// Creates a new [ProvingService] storing its jobs and preimages in `data_dir`, and loads the
+    /// jobs persisted by previous runs. Workers must be started with
+    /// [ProvingService::start_workers] for jobs to run.
+    pub fn new(data_dir: &Path) -> Result<Self, ProvingServiceError> {
+        let store = JobStore::new(data_dir.join("jobs"))?;
+        let preimage_dir = data_dir.join("preimages");
+        let preimages = Arc::new(DiskKeyValueStore::new(preimage_dir.clone()).with_persist(true));
+        let (queue, receiver) = mpsc::unbounded_channel();
+
+        let mut jobs = BTreeMap::new();
+        for mut job in store.load()? {
+            if !job.status.is_finished() {
+                // The job was interrupted by a restart, so it is run again from scratch.
+                job.status = JobStatus::Queued;
+                job.started_at = None;
+                store.save(&job)?;
+                let _ = queue.send(job.id);
+            }
+            jobs.insert(job.id, (job, CancellationToken::new()));
+        }
+        let next_id = jobs.last_key_value().map_or(0, |(id, _)| id + 1);
+
+        Ok(Self {
+            inner: Arc::new(ServiceState {
+                store,
+                preimages,
+                preimage_dir,
+                jobs: Mutex::new(jobs),
+                next_id: AtomicU64::new(next_id),
+                queue,
+                receiver: tokio::sync::Mutex::new(receiver),
+            }),
+        })
+    }
+
+    /// Starts `count` workers, each running one job at a time.
+    pub fn start_workers(&self, count: usize) -> Vec<JoinHandle<()>> {
+        (0..count)
+            .map(|_| {
+                let service = self.clone();
+                task::spawn(async move {
+                    loop {
+                        let Some(id) = service.inner.receiver.lock().await.recv().await else {
+                            return;
+                        };
+                        if let Err(e) = service.run(id).await {
+                            error!(target: "host_service", "Failed to run job {id}: {e}");
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Submits a new job with the given parameters, returning its identifier.
+    pub fn submit(&self, params: JobParams) -> Result<JobId, ProvingServiceError> {
+        let id = self.inner.next_id.fetch_add(1, Ordering::SeqCst);
+        let job = Job::new(id, params);
+        self.inner.store.save(&job)?;
+        self.inner.jobs.lock().unwrap().insert(id, (job, CancellationToken::new()));
+        let _ = self.inner.queue.send(id);
+
+        info!(target: "host_service", "Queued job {id}");
+        Ok(id)
+    }
+
+    /// Returns the job with the given identifier, if it exists.
+    pub fn job(&self, id: JobId) -> Option<Job> {
+        self.inner.jobs.lock().unwrap().get(&id).map(|(job, _)| job.clone())
+    }
+
+    /// Returns all jobs, ordered by identifier.
+    pub fn jobs(&self) -> Vec<Job> {
+        self.inner.jobs.lock().unwrap().values().map(|(job, _)| job.clone()).collect()
+    }
+
+    /// Cancels the job with the given identifier. Returns `false` if the job already finished.
+    pub fn cancel(&self, id: JobId) -> Result<bool, ProvingServiceError> {
+        let mut jobs = self.inner.jobs.lock().unwrap();
+        let (job, cancel) = jobs.get_mut(&id).ok_or(ProvingServiceError::JobNotFound(id))?;
+        match job.status {
+            JobStatus::Queued => {
+                // The job is skipped once a worker takes it from the queue.
+                job.status = JobStatus::Cancelled;
+                job.finished_at = Some(unix_millis());
+                self.inner.store.save(job)?;
+            }
+            // The worker running the job records the cancellation.
+            JobStatus::Running => cancel.cancel(),
+            _ => return Ok(false),
+        }
+
+        info!(target: "host_service", "Cancelled job {id}");
+        Ok(true)
+    }
+
+    /// Runs the queued job with the given identifier, and records its outcome.
+    async fn run(&self, id: JobId) -> Result<(), ProvingServiceError> {
+        let (params, cancel) = {
+            let mut jobs = self.inner.jobs.lock().unwrap();
+            let (job, cancel) = jobs.get_mut(&id).ok_or(ProvingServiceError::JobNotFound(id))?;
+            if job.status != JobStatus::Queued {
+                return Ok(());
+            }
+            job.status = JobStatus::Running;
+            job.started_at = Some(unix_millis());
+            self.inner.store.save(job)?;
+            (job.params.clone(), cancel.clone())
+        };
+
+        info!(target: "host_service", "Running job {id}");
+        let outcome =
+            run_job(params, self.inner.preimages.clone(), &self.inner.preimage_dir, &cancel).await;
+
+        let mut jobs = self.inner.jobs.lock().unwrap();
+        let (job, _) = jobs.get_mut(&id).ok_or(ProvingServiceError::JobNotFound(id))?;
+        let JobOutcome { status, output_root, error, preimages_served, preimages_fetched } =
+            outcome;
+        job.status = status;
+        job.finished_at = Some(unix_millis());
+        job.output_root = output_root;
+        job.error = error;
+        job.preimages_served = preimages_served;
+        job.preimages_fetched = preimages_fetched;
+        self.inner.store.save(job)?;
+
+        info!(
+            target: "host_service",
+            status = ?job.status,
+            duration_ms = job.duration_ms(),
+            "Finished job {id}"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KeyValueStore, single::SingleChainHost};
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
+    use kona_preimage::PreimageKey;
+    use kona_protocol::OutputRoot;
+    use std::time::Duration;
+
+    fn single_job() -> JobParams {
+        JobParams::Single(SingleChainHost {
+            claimed_l2_block_number: 1,
+            l2_chain_id: Some(10),
+            ..Default::default()
+        })
+    }
+
+    /// Waits for the job with the given identifier to finish.
+    async fn finished_job(service: &ProvingService, id: JobId) -> Job {
+        tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                let job = service.job(id).unwrap();
+                if job.status.is_finished() {
+                    return job;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProvingService::new(dir.path()).unwrap();
+
+        let id = service.submit(single_job()).unwrap();
+        assert_eq!(service.job(id).unwrap().status, JobStatus::Queued);
+
+        assert!(service.cancel(id).unwrap());
+        assert_eq!(service.job(id).unwrap().status, JobStatus::Cancelled);
+        assert!(!service.cancel(id).unwrap());
+        assert!(matches!(service.cancel(id + 1), Err(ProvingServiceError::JobNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_jobs_persist_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProvingService::new(dir.path()).unwrap();
+        let cancelled = service.submit(single_job()).unwrap();
+        let queued = service.submit(single_job()).unwrap();
+        service.cancel(cancelled).unwrap();
+        drop(service);
+
+        let service = ProvingService::new(dir.path()).unwrap();
+        let jobs = service.jobs();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].status, JobStatus::Cancelled);
+        assert_eq!(jobs[1].status, JobStatus::Queued);
+        assert_eq!(jobs[1].id, queued);
+        assert_eq!(service.submit(single_job()).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_preimages_persist_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProvingService::new(dir.path()).unwrap();
+        let key = PreimageKey::new_keccak256([1; 32]).into();
+        service.inner.preimages.clone().set(key, vec![0xaa]).unwrap();
+        drop(service);
+
+        let service = ProvingService::new(dir.path()).unwrap();
+        assert_eq!(service.inner.preimages.get(key), Some(vec![0xaa]));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_offline_job_missing_preimages() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProvingService::new(dir.path()).unwrap();
+        service.start_workers(2);
+
+        // The shared preimage store is empty, so the offline backend fails to serve the agreed
+        // output root preimage after the boot info was served from the local inputs.
+        let id = service.submit(single_job()).unwrap();
+        let job = finished_job(&service, id).await;
+
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.error.is_some());
+        assert!(job.preimages_served > 0);
+        assert_eq!(job.preimages_fetched, 0);
+        assert!(job.duration_ms().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_offline_job_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProvingService::new(dir.path()).unwrap();
+
+        // Seed the shared preimage store with the agreed safe head and its output root. The
+        // claim equals the agreed output root, so the client program detects trace extension
+        // and accepts it without deriving any block.
+        let safe_head = Header { number: 1, ..Default::default() };
+        let output_root = OutputRoot::from_parts(
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            safe_head.hash_slow(),
+        );
+        let mut preimages = service.inner.preimages.clone();
+        preimages
+            .set(
+                PreimageKey::new_keccak256(*safe_head.hash_slow()).into(),
+                alloy_rlp::encode(&safe_head),
+            )
+            .unwrap();
+        preimages
+            .set(
+                PreimageKey::new_keccak256(*output_root.hash()).into(),
+                output_root.encode().to_vec(),
+            )
+            .unwrap();
+
+        service.start_workers(1);
+        let id = service
+            .submit(JobParams::Single(SingleChainHost {
+                agreed_l2_output_root: output_root.hash(),
+                claimed_l2_output_root: output_root.hash(),
+                claimed_l2_block_number: 1,
+                l2_chain_id: Some(10),
+                ..Default::default()
+            }))
+            .unwrap();
+        let job = finished_job(&service, id).await;
+
+        assert_eq!(job.status, JobStatus::Passed);
+        assert_eq!(job.error, None);
+        assert!(job.preimages_served > 0);
+        assert_eq!(job.preimages_fetched, 0);
+        assert!(job.duration_ms().is_some());
+    }
+}
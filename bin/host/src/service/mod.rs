@@ -0,0 +1,19 @@
+//! This module contains the proving service mode for the host, which runs single-chain and
+//! interop jobs on a worker pool and serves them over a JSON-RPC API.
+
+mod cfg;
+pub use cfg::ServiceHost;
+
+mod job;
+pub use job::{Job, JobId, JobParams, JobStatus};
+
+mod store;
+pub use store::JobStore;
+
+mod runner;
+
+mod manager;
+pub use manager::{ProvingService, ProvingServiceError};
+
+mod rpc;
+pub use rpc::ProvingServiceApiServer;
@@ -0,0 +1,58 @@
+//! Contains the JSON-RPC API of the [ProvingService].
+
+use super::{Job, JobId, JobParams, ProvingService, ProvingServiceError};
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::{ErrorCode, ErrorObject, ErrorObjectOwned},
+};
+
+/// The JSON-RPC API of the [ProvingService], served under the `host` namespace.
+#[rpc(server, namespace = "host")]
+pub trait ProvingServiceApi {
+    /// Submits a job, returning its identifier.
+    #[method(name = "submitJob")]
+    async fn submit_job(&self, params: JobParams) -> RpcResult<JobId>;
+
+    /// Returns the job with the given identifier.
+    #[method(name = "getJob")]
+    async fn get_job(&self, id: JobId) -> RpcResult<Job>;
+
+    /// Returns all jobs, ordered by identifier.
+    #[method(name = "listJobs")]
+    async fn list_jobs(&self) -> RpcResult<Vec<Job>>;
+
+    /// Cancels the job with the given identifier. Returns `false` if the job already finished.
+    #[method(name = "cancelJob")]
+    async fn cancel_job(&self, id: JobId) -> RpcResult<bool>;
+}
+
+#[async_trait]
+impl ProvingServiceApiServer for ProvingService {
+    async fn submit_job(&self, params: JobParams) -> RpcResult<JobId> {
+        Ok(self.submit(params)?)
+    }
+
+    async fn get_job(&self, id: JobId) -> RpcResult<Job> {
+        Ok(self.job(id).ok_or(ProvingServiceError::JobNotFound(id))?)
+    }
+
+    async fn list_jobs(&self) -> RpcResult<Vec<Job>> {
+        Ok(self.jobs())
+    }
+
+    async fn cancel_job(&self, id: JobId) -> RpcResult<bool> {
+        Ok(self.cancel(id)?)
+    }
+}
+
+impl From<ProvingServiceError> for ErrorObjectOwned {
+    fn from(e: ProvingServiceError) -> Self {
+        let code = match e {
+            ProvingServiceError::JobNotFound(_) => ErrorCode::InvalidParams,
+            _ => ErrorCode::InternalError,
+        };
+        ErrorObject::owned(code.code(), e.to_string(), None::<()>)
+    }
+}
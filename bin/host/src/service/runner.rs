@@ -0,0 +1,220 @@
+//! Runs proving service jobs natively, against the shared preimage store.
+
+use super::{JobParams, JobStatus};
+use crate::{
+    DiskKeyValueStore, KeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    interop::{InteropHost, InteropLocalInputs},
+    single::{SingleChainHost, SingleChainLocalInputs},
+};
+use alloy_primitives::B256;
+use anyhow::Result;
+use kona_preimage::{BidirectionalChannel, HintWriter, OracleReader};
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tokio::{
+    sync::RwLock,
+    task::{self, JoinHandle},
+};
+use tokio_util::sync::CancellationToken;
+
+/// The outcome of a job run.
+#[derive(Debug)]
+pub(crate) struct JobOutcome {
+    /// The final status of the job.
+    pub(crate) status: JobStatus,
+    /// The output root computed by the client program.
+    pub(crate) output_root: Option<B256>,
+    /// The error the job failed with, if any.
+    pub(crate) error: Option<String>,
+    /// The number of preimages served to the client program.
+    pub(crate) preimages_served: u64,
+    /// The number of preimages added to the preimage store.
+    pub(crate) preimages_fetched: u64,
+}
+
+/// Runs the job with the given parameters natively, backed by the shared `preimages` store at
+/// `preimage_dir`. The client program is aborted if `cancel` is cancelled.
+pub(crate) async fn run_job(
+    params: JobParams,
+    preimages: Arc<DiskKeyValueStore>,
+    preimage_dir: &Path,
+    cancel: &CancellationToken,
+) -> JobOutcome {
+    let counts = Arc::new(PreimageCounts::default());
+    let result = match params {
+        JobParams::Single(host) => {
+            run_single(host, preimages, preimage_dir, counts.clone(), cancel).await
+        }
+        JobParams::Super(host) => {
+            run_super(host, preimages, preimage_dir, counts.clone(), cancel).await
+        }
+    };
+
+    let (status, output_root, error) = match result {
+        Ok(Some(outcome)) => outcome,
+        Ok(None) => (JobStatus::Cancelled, None, None),
+        Err(e) => (JobStatus::Failed, None, Some(e.to_string())),
+    };
+    JobOutcome {
+        status,
+        output_root,
+        error,
+        preimages_served: counts.served.load(Ordering::Relaxed),
+        preimages_fetched: counts.fetched.load(Ordering::Relaxed),
+    }
+}
+
+/// The status, computed output root and error of a job run to completion.
+type RunResult = (JobStatus, Option<B256>, Option<String>);
+
+/// Runs a single-chain job, returning `None` if it was cancelled.
+async fn run_single(
+    host: SingleChainHost,
+    preimages: Arc<DiskKeyValueStore>,
+    preimage_dir: &Path,
+    counts: Arc<PreimageCounts>,
+    cancel: &CancellationToken,
+) -> Result<Option<RunResult>> {
+    let host = SingleChainHost {
+        data_dir: Some(preimage_dir.to_path_buf()),
+        native: true,
+        server: false,
+        listen: None,
+        ..host
+    };
+    let local_kv_store = SingleChainLocalInputs::new(host.clone());
+    let kv_store: SharedKeyValueStore = Arc::new(RwLock::new(CountingKeyValueStore::new(
+        SplitKeyValueStore::new(local_kv_store, preimages),
+        counts,
+    )));
+
+    let hint = BidirectionalChannel::new()?;
+    let preimage = BidirectionalChannel::new()?;
+    let server_task = host.start_server_with_store(hint.host, preimage.host, kv_store).await?;
+    let client_task = task::spawn(kona_client::single::derive_claim(
+        OracleReader::new(preimage.client),
+        HintWriter::new(hint.client),
+    ));
+
+    let Some(result) = await_client(server_task, client_task, cancel).await? else {
+        return Ok(None);
+    };
+    Ok(Some(match result {
+        Ok(Some(derived)) if derived.output_root == derived.claimed_output_root => {
+            (JobStatus::Passed, Some(derived.output_root), None)
+        }
+        Ok(Some(derived)) => (
+            JobStatus::Failed,
+            Some(derived.output_root),
+            Some(format!(
+                "Invalid claim. Expected {}, actual {}",
+                derived.output_root, derived.claimed_output_root
+            )),
+        ),
+        // Trace extension was detected, so the claim is trivially valid.
+        Ok(None) => (JobStatus::Passed, None, None),
+        Err(e) => (JobStatus::Failed, None, Some(e.to_string())),
+    }))
+}
+
+/// Runs a super-chain (interop) job, returning `None` if it was cancelled.
+async fn run_super(
+    host: InteropHost,
+    preimages: Arc<DiskKeyValueStore>,
+    preimage_dir: &Path,
+    counts: Arc<PreimageCounts>,
+    cancel: &CancellationToken,
+) -> Result<Option<RunResult>> {
+    let host = InteropHost {
+        data_dir: Some(preimage_dir.to_path_buf()),
+        native: true,
+        server: false,
+        listen: None,
+        ..host
+    }
+    .resolve_agreed_pre_state()
+    .await?;
+    let local_kv_store = InteropLocalInputs::new(host.clone());
+    let kv_store: SharedKeyValueStore = Arc::new(RwLock::new(CountingKeyValueStore::new(
+        SplitKeyValueStore::new(local_kv_store, preimages),
+        counts,
+    )));
+
+    let hint = BidirectionalChannel::new()?;
+    let preimage = BidirectionalChannel::new()?;
+    let server_task = host.start_server_with_store(hint.host, preimage.host, kv_store).await?;
+    let client_task = task::spawn(kona_client::interop::run(
+        OracleReader::new(preimage.client),
+        HintWriter::new(hint.client),
+    ));
+
+    let Some(result) = await_client(server_task, client_task, cancel).await? else {
+        return Ok(None);
+    };
+    Ok(Some(match result {
+        Ok(()) => (JobStatus::Passed, Some(host.claimed_l2_post_state), None),
+        Err(e) => (JobStatus::Failed, None, Some(e.to_string())),
+    }))
+}
+
+/// Waits for the client program to finish, returning its result, or aborts it and returns `None`
+/// if `cancel` is cancelled first. The preimage server stops once the client's channels close.
+async fn await_client<S, T>(
+    server_task: JoinHandle<S>,
+    client_task: JoinHandle<T>,
+    cancel: &CancellationToken,
+) -> Result<Option<T>> {
+    let client_abort = client_task.abort_handle();
+    tokio::select! {
+        result = async { tokio::try_join!(server_task, client_task) } => {
+            let (_, client_result) = result?;
+            Ok(Some(client_result))
+        }
+        _ = cancel.cancelled() => {
+            client_abort.abort();
+            Ok(None)
+        }
+    }
+}
+
+/// The number of preimages read from and written to a [CountingKeyValueStore].
+#[derive(Debug, Default)]
+struct PreimageCounts {
+    served: AtomicU64,
+    fetched: AtomicU64,
+}
+
+/// A [KeyValueStore] that counts the preimages read from and written to the wrapped store.
+#[derive(Debug)]
+struct CountingKeyValueStore<KV> {
+    inner: KV,
+    counts: Arc<PreimageCounts>,
+}
+
+impl<KV> CountingKeyValueStore<KV> {
+    /// Creates a new [CountingKeyValueStore] wrapping `inner`, recording into `counts`.
+    const fn new(inner: KV, counts: Arc<PreimageCounts>) -> Self {
+        Self { inner, counts }
+    }
+}
+
+impl<KV: KeyValueStore> KeyValueStore for CountingKeyValueStore<KV> {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let value = self.inner.get(key);
+        if value.is_some() {
+            self.counts.served.fetch_add(1, Ordering::Relaxed);
+        }
+        value
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.inner.set(key, value)?;
+        self.counts.fetched.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Contains the [JobStore], which persists proving service jobs on disk.
+
+use super::{Job, JobId, ProvingServiceError};
+use std::path::PathBuf;
+
+/// A [JobStore] persists each [Job] as a JSON file in a directory, so that job state and results
+/// survive restarts of the proving service.
+#[derive(Debug, Clone)]
+pub struct JobStore {
+    directory: PathBuf,
+}
+
+impl JobStore {
+    /// Creates a new [JobStore] in the given directory, creating the directory if it does not
+    /// exist.
+    pub fn new(directory: PathBuf) -> Result<Self, ProvingServiceError> {
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    /// Persists the given [Job], replacing its previous state.
+    pub fn save(&self, job: &Job) -> Result<(), ProvingServiceError> {
+        // Write to a temporary file first, so that a crash never leaves a truncated job behind.
+        let path = self.path(job.id);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(job)?)?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Loads all persisted [Job]s, ordered by identifier.
+    pub fn load(&self) -> Result<Vec<Job>, ProvingServiceError> {
+        let mut jobs = Vec::new();
+        for entry in std::fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                jobs.push(serde_json::from_slice::<Job>(&std::fs::read(path)?)?);
+            }
+        }
+        jobs.sort_by_key(|job| job.id);
+        Ok(jobs)
+    }
+
+    /// Returns the path of the file holding the [Job] with the given identifier.
+    fn path(&self, id: JobId) -> PathBuf {
+        self.directory.join(format!("{id}.json"))
+    }
+}
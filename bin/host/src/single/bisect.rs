@@ -0,0 +1,490 @@
+//! Bisection of a disputed output root range down to the first diverging L2 block.
+
+use super::{SingleChainHost, SingleChainLocalInputs};
+use crate::{DiskKeyValueStore, SharedKeyValueStore, SplitKeyValueStore, eth::rpc_provider};
+use alloy_consensus::Header;
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{Address, B256, Bytes, U256, map::HashMap};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::Decodable;
+use anyhow::{Result, ensure};
+use clap::Parser;
+use kona_cli::cli_styles;
+use kona_client::single::{DerivedClaim, derive_claim, derive_claim_with_bundle};
+use kona_protocol::L2BlockInfo;
+use op_alloy_consensus::OpReceiptEnvelope;
+use op_alloy_network::Optimism;
+use revm::{database::BundleState, primitives::KECCAK_EMPTY};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// The bisect CLI arguments.
+///
+/// Bisects the L2 block range between an agreed output root and a claimed L2 block number, running
+/// the client program natively over sub-ranges to find the first L2 block whose output root, as
+/// computed by kona, disagrees with the output root reported by the rollup node's
+/// `optimism_outputAtBlock`. All runs share one on-disk preimage store, so preimages fetched for
+/// one sub-range are reused by the next.
+#[derive(Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct BisectHost {
+    /// Hash of the L1 head block. Derivation stops after this block is processed.
+    #[arg(long, env)]
+    pub l1_head: B256,
+    /// Hash of the agreed upon safe L2 block committed to by `--agreed-l2-output-root`.
+    #[arg(long, visible_alias = "l2-head", env)]
+    pub agreed_l2_head_hash: B256,
+    /// Agreed safe L2 Output Root to start bisecting from.
+    #[arg(long, visible_alias = "l2-output-root", env)]
+    pub agreed_l2_output_root: B256,
+    /// Number of the last L2 block of the disputed range.
+    #[arg(long, visible_alias = "l2-block-number", env)]
+    pub claimed_l2_block_number: u64,
+    /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required).
+    #[arg(long, visible_alias = "l2", env)]
+    pub l2_node_address: String,
+    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required)
+    #[arg(long, visible_alias = "l1", env)]
+    pub l1_node_address: String,
+    /// Address of the L1 Beacon API endpoint to use.
+    #[arg(long, visible_alias = "beacon", env)]
+    pub l1_beacon_address: String,
+    /// Address of the rollup node RPC endpoint serving `optimism_outputAtBlock`.
+    #[arg(long, visible_alias = "rollup", env)]
+    pub rollup_node_address: String,
+    /// The Data Directory for preimage data storage, shared between all runs.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+    /// The L2 chain ID of a supported chain. If provided, the host will look for the corresponding
+    /// rollup config in the superchain registry.
+    #[arg(
+        long,
+        conflicts_with = "rollup_config_path",
+        required_unless_present = "rollup_config_path",
+        env
+    )]
+    pub l2_chain_id: Option<u64>,
+    /// Path to rollup config. If provided, the host will use this config instead of attempting to
+    /// look up the config in the superchain registry.
+    #[arg(
+        long,
+        alias = "rollup-cfg",
+        conflicts_with = "l2_chain_id",
+        required_unless_present = "l2_chain_id",
+        env
+    )]
+    pub rollup_config_path: Option<PathBuf>,
+    /// Path to l1 config. If provided, the host will use this config instead of attempting to
+    /// look up the config in the known l1 configs.
+    #[arg(long, alias = "l1-cfg", env)]
+    pub l1_config_path: Option<PathBuf>,
+    /// Optionally enables the use of `debug_executePayload` to collect the execution witness from
+    /// the execution layer.
+    #[arg(long, env)]
+    pub enable_experimental_witness_endpoint: bool,
+    /// The file to write the report to. If not provided, the report is written to stdout.
+    #[arg(long, short = 'o')]
+    pub report: Option<PathBuf>,
+}
+
+/// An output of the L2 chain, as reported by the rollup node's `optimism_outputAtBlock`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeOutput {
+    /// The output root hash.
+    pub output_root: B256,
+    /// A reference to the L2 block.
+    pub block_ref: L2BlockInfo,
+}
+
+/// A difference in a single field between kona's and the L2 node's view of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDiff {
+    /// The path to the field.
+    pub path: String,
+    /// The value computed by kona.
+    pub kona: Value,
+    /// The value reported by the L2 node.
+    pub node: Value,
+}
+
+/// The report of a bisection.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectReport {
+    /// The number of the first L2 block whose output root kona disagrees with, if any.
+    pub diverging_block: Option<u64>,
+    /// The output root of the diverging block as computed by kona.
+    pub kona_output_root: Option<B256>,
+    /// The output root of the diverging block as reported by the rollup node.
+    pub node_output_root: Option<B256>,
+    /// The error returned by the client program for the diverging block, if it failed before
+    /// producing an output root.
+    pub error: Option<String>,
+    /// The differing header fields of the diverging block.
+    pub header: Vec<FieldDiff>,
+    /// The differing receipt fields of the diverging block.
+    pub receipts: Vec<FieldDiff>,
+    /// The differing account and storage values after the diverging block.
+    pub state: Vec<FieldDiff>,
+}
+
+impl BisectHost {
+    /// Runs the bisection and writes the report.
+    pub async fn start(self) -> Result<()> {
+        let report = self.bisect().await?;
+
+        let serialized = serde_json::to_string_pretty(&report)?;
+        match self.report.as_ref() {
+            Some(path) => std::fs::write(path, serialized)?,
+            None => println!("{serialized}"),
+        }
+        Ok(())
+    }
+
+    /// Bisects the disputed range, returning the [BisectReport] for the first diverging block.
+    pub async fn bisect(&self) -> Result<BisectReport> {
+        let l2 = rpc_provider::<Optimism>(&self.l2_node_address).await;
+        let rollup = rpc_provider::<Optimism>(&self.rollup_node_address).await;
+        let kv_store = Arc::new(DiskKeyValueStore::new(self.data_dir.clone()));
+
+        let agreed_header = raw_header(&l2, self.agreed_l2_head_hash).await?;
+        ensure!(
+            self.claimed_l2_block_number > agreed_header.number,
+            "Claimed L2 block number must be greater than the agreed L2 block number"
+        );
+        let mut good = output_at_block(&rollup, agreed_header.number).await?;
+        ensure!(
+            good.output_root == self.agreed_l2_output_root &&
+                good.block_ref.block_info.hash == self.agreed_l2_head_hash,
+            "Agreed output root does not match the rollup node's output at block {}",
+            agreed_header.number
+        );
+
+        let mut bad = output_at_block(&rollup, self.claimed_l2_block_number).await?;
+        if self.agrees(&kv_store, &good, &bad).await? {
+            info!(
+                target: "bisect",
+                from = good.block_ref.block_info.number,
+                to = bad.block_ref.block_info.number,
+                "No divergence found in range"
+            );
+            return Ok(BisectReport::default());
+        }
+
+        while bad.block_ref.block_info.number - good.block_ref.block_info.number > 1 {
+            let (lo, hi) = (good.block_ref.block_info.number, bad.block_ref.block_info.number);
+            let mid = output_at_block(&rollup, lo + (hi - lo) / 2).await?;
+            if self.agrees(&kv_store, &good, &mid).await? {
+                good = mid;
+            } else {
+                bad = mid;
+            }
+            info!(
+                target: "bisect",
+                good = good.block_ref.block_info.number,
+                bad = bad.block_ref.block_info.number,
+                "Narrowed disputed range"
+            );
+        }
+
+        let number = bad.block_ref.block_info.number;
+        info!(target: "bisect", number, "Found first diverging block");
+
+        // Re-run the diverging block on its own to collect kona's execution artifacts. All of its
+        // preimages are already in the store.
+        let mut report = BisectReport {
+            diverging_block: Some(number),
+            node_output_root: Some(bad.output_root),
+            ..Default::default()
+        };
+        let derived = match self.derive(&kv_store, &good, &bad, true).await? {
+            Ok(derived) => derived,
+            Err(e) => {
+                report.error = Some(e);
+                return Ok(report);
+            }
+        };
+        report.kona_output_root = Some(derived.output_root);
+
+        let Some((outcome, _)) = derived.artifacts else {
+            report.error = Some("No block was executed".to_string());
+            return Ok(report);
+        };
+        let node_hash = bad.block_ref.block_info.hash;
+        let node_header = raw_header(&l2, node_hash).await?;
+        diff_values(
+            "header",
+            &serde_json::to_value(outcome.header.inner())?,
+            &serde_json::to_value(&node_header)?,
+            &mut report.header,
+        );
+
+        let raw_receipts: Vec<Bytes> =
+            l2.client().request("debug_getRawReceipts", [node_hash]).await?;
+        let node_receipts = raw_receipts
+            .iter()
+            .map(|r| OpReceiptEnvelope::decode_2718(&mut r.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        diff_values(
+            "receipts",
+            &serde_json::to_value(&outcome.execution_result.receipts)?,
+            &serde_json::to_value(&node_receipts)?,
+            &mut report.receipts,
+        );
+
+        let Some(bundle) = outcome.bundle.as_ref() else {
+            report.error = Some("No state changes were retained".to_string());
+            return Ok(report);
+        };
+        let (kona_state, node_state) =
+            state_views(&l2, bundle, node_hash, node_header.parent_hash).await?;
+        diff_values("state", &kona_state, &node_state, &mut report.state);
+
+        Ok(report)
+    }
+
+    /// Runs the client program from `agreed` to `claimed`, returning whether kona's output root of
+    /// the claimed block matches the rollup node's.
+    async fn agrees(
+        &self,
+        kv_store: &Arc<DiskKeyValueStore>,
+        agreed: &NodeOutput,
+        claimed: &NodeOutput,
+    ) -> Result<bool> {
+        match self.derive(kv_store, agreed, claimed, false).await? {
+            Ok(derived) => Ok(derived.output_root == claimed.output_root),
+            Err(e) => {
+                warn!(
+                    target: "bisect",
+                    from = agreed.block_ref.block_info.number,
+                    to = claimed.block_ref.block_info.number,
+                    "Client program failed: {e}"
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Derives and executes the range from `agreed` to `claimed` natively, retaining the state
+    /// changes of the claimed block if `retain_bundle` is set. The outer result holds host errors,
+    /// while the inner result holds errors returned by the client program.
+    async fn derive(
+        &self,
+        kv_store: &Arc<DiskKeyValueStore>,
+        agreed: &NodeOutput,
+        claimed: &NodeOutput,
+        retain_bundle: bool,
+    ) -> Result<Result<DerivedClaim, String>> {
+        let host = self.single_chain_host(agreed, claimed);
+        let local_kv_store = SingleChainLocalInputs::new(host.clone());
+        let kv_store: SharedKeyValueStore =
+            Arc::new(RwLock::new(SplitKeyValueStore::new(local_kv_store, kv_store.clone())));
+
+        info!(
+            target: "bisect",
+            from = agreed.block_ref.block_info.number,
+            to = claimed.block_ref.block_info.number,
+            "Running client program"
+        );
+        let derived = if retain_bundle {
+            host.run_native(kv_store, derive_claim_with_bundle).await?
+        } else {
+            host.run_native(kv_store, derive_claim).await?
+        };
+        Ok(match derived {
+            Ok(Some(derived)) => Ok(derived),
+            Ok(None) => Err("Trace extension detected".to_string()),
+            Err(e) => Err(e.to_string()),
+        })
+    }
+
+    /// Returns the [SingleChainHost] configuration for a native run from `agreed` to `claimed`.
+    fn single_chain_host(&self, agreed: &NodeOutput, claimed: &NodeOutput) -> SingleChainHost {
+        SingleChainHost {
+            l1_head: self.l1_head,
+            agreed_l2_head_hash: agreed.block_ref.block_info.hash,
+            agreed_l2_output_root: agreed.output_root,
+            claimed_l2_output_root: claimed.output_root,
+            claimed_l2_block_number: claimed.block_ref.block_info.number,
+            l2_node_address: Some(self.l2_node_address.clone()),
+            l1_node_address: Some(self.l1_node_address.clone()),
+            l1_beacon_address: Some(self.l1_beacon_address.clone()),
+            l1_blob_archiver_address: None,
+            l1_blob_dir: None,
+            data_dir: Some(self.data_dir.clone()),
+            preimage_cache: None,
+            preimage_cache_size: None,
+            native: true,
+            profile: false,
+            server: false,
+            listen: None,
+            l2_chain_id: self.l2_chain_id,
+            rollup_config_path: self.rollup_config_path.clone(),
+            l1_config_path: self.l1_config_path.clone(),
+            enable_experimental_witness_endpoint: self.enable_experimental_witness_endpoint,
+            intermediate_claims: None,
+            parallel_execution_window: None,
+            trace_output: None,
+            trace_struct_logs: false,
+            replay_bundle: None,
+            client_log: None,
+        }
+    }
+}
+
+/// Fetches the output at the given L2 block number from the rollup node.
+async fn output_at_block(rollup: &RootProvider<Optimism>, number: u64) -> Result<NodeOutput> {
+    Ok(rollup.client().request("optimism_outputAtBlock", [format!("0x{number:x}")]).await?)
+}
+
+/// Fetches the header of the L2 block with the given hash.
+async fn raw_header(l2: &RootProvider<Optimism>, hash: B256) -> Result<Header> {
+    let raw_header: Bytes = l2.client().request("debug_getRawHeader", [hash]).await?;
+    Ok(Header::decode(&mut raw_header.as_ref())?)
+}
+
+/// Builds kona's and the L2 node's views of every account and storage slot touched by the block,
+/// as JSON objects keyed by address.
+///
+/// The touched accounts and slots are the union of the ones changed in kona's [BundleState] and
+/// the ones reported by the node's `prestateTracer` in diff mode. Accounts and slots that kona did
+/// not change hold their value at the parent block.
+async fn state_views(
+    l2: &RootProvider<Optimism>,
+    bundle: &BundleState,
+    block_hash: B256,
+    parent_hash: B256,
+) -> Result<(Value, Value)> {
+    let mut touched: HashMap<Address, BTreeSet<B256>> = HashMap::default();
+    for (address, account) in &bundle.state {
+        touched.entry(*address).or_default().extend(account.storage.keys().map(|k| B256::from(*k)));
+    }
+
+    let traces: Value = l2
+        .client()
+        .request(
+            "debug_traceBlockByHash",
+            (
+                block_hash,
+                json!({ "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }),
+            ),
+        )
+        .await?;
+    for diff in traces.as_array().into_iter().flatten() {
+        for side in ["pre", "post"] {
+            let Some(accounts) = diff["result"][side].as_object() else { continue };
+            for (address, account) in accounts {
+                let slots = touched.entry(address.parse()?).or_default();
+                for slot in account["storage"].as_object().into_iter().flat_map(|s| s.keys()) {
+                    slots.insert(slot.parse()?);
+                }
+            }
+        }
+    }
+
+    let (mut kona, mut node) = (Map::new(), Map::new());
+    for (address, slots) in touched {
+        let slots = slots.into_iter().collect::<Vec<_>>();
+        let post = l2.get_proof(address, slots.clone()).block_id(block_hash.into()).await?;
+        let pre = l2.get_proof(address, slots.clone()).block_id(parent_hash.into()).await?;
+        let code_hash = |hash: B256| if hash.is_zero() { KECCAK_EMPTY } else { hash };
+
+        let node_storage = post
+            .storage_proof
+            .iter()
+            .map(|p| (p.key.as_b256().to_string(), json!(p.value)))
+            .collect::<Map<_, _>>();
+        node.insert(
+            address.to_string(),
+            json!({
+                "balance": post.balance,
+                "nonce": post.nonce,
+                "codeHash": code_hash(post.code_hash),
+                "storage": node_storage,
+            }),
+        );
+
+        let account = bundle.state.get(&address);
+        let kona_storage = pre
+            .storage_proof
+            .iter()
+            .map(|p| {
+                let slot = U256::from_be_bytes(p.key.as_b256().0);
+                let value =
+                    account.and_then(|a| a.storage.get(&slot)).map_or(p.value, |s| s.present_value);
+                (p.key.as_b256().to_string(), json!(value))
+            })
+            .collect::<Map<_, _>>();
+        let (balance, nonce, hash) = match account {
+            Some(account) => account.info.as_ref().map_or((U256::ZERO, 0, KECCAK_EMPTY), |info| {
+                (info.balance, info.nonce, info.code_hash)
+            }),
+            None => (pre.balance, pre.nonce, code_hash(pre.code_hash)),
+        };
+        kona.insert(
+            address.to_string(),
+            json!({ "balance": balance, "nonce": nonce, "codeHash": hash, "storage": kona_storage }),
+        );
+    }
+
+    Ok((Value::Object(kona), Value::Object(node)))
+}
+
+/// Recursively collects the differences between two JSON values into `diffs`.
+fn diff_values(path: &str, kona: &Value, node: &Value, diffs: &mut Vec<FieldDiff>) {
+    match (kona, node) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                diff_values(
+                    &format!("{path}.{key}"),
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    diffs,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(
+                    &format!("{path}[{i}]"),
+                    a.get(i).unwrap_or(&Value::Null),
+                    b.get(i).unwrap_or(&Value::Null),
+                    diffs,
+                );
+            }
+        }
+        (a, b) if a != b => {
+            diffs.push(FieldDiff { path: path.to_string(), kona: a.clone(), node: b.clone() })
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FieldDiff, diff_values};
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_values() {
+        let kona = json!({ "a": 1, "b": { "c": [1, 2] }, "d": "x" });
+        let node = json!({ "a": 1, "b": { "c": [1, 3, 4] }, "e": "y" });
+
+        let mut diffs = Vec::new();
+        diff_values("root", &kona, &node, &mut diffs);
+        assert_eq!(
+            diffs,
+            vec![
+                FieldDiff { path: "root.b.c[1]".into(), kona: json!(2), node: json!(3) },
+                FieldDiff { path: "root.b.c[2]".into(), kona: json!(null), node: json!(4) },
+                FieldDiff { path: "root.d".into(), kona: json!("x"), node: json!(null) },
+                FieldDiff { path: "root.e".into(), kona: json!(null), node: json!("y") },
+            ]
+        );
+    }
+}
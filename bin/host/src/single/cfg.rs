@@ -1,33 +1,48 @@
 //! This module contains all CLI-specific code for the single chain entrypoint.
 
-use super::{SingleChainHintHandler, SingleChainLocalInputs};
+use super::{
+    L2ClaimProvider, RecordingKeyValueStore, ReplayManifest, ReplayRecorder,
+    SingleChainHintHandler, SingleChainLocalInputs,
+};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::rpc_provider, server::PreimageServerError,
+    CachedKeyValueStore, DiskKeyValueStore, HintLog, KeyValueStore, MemoryKeyValueStore,
+    OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg, PreimageCache, PreimageServer,
+    SharedKeyValueStore, SplitKeyValueStore, eth::rpc_provider, server::PreimageServerError,
 };
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
 use clap::Parser;
 use kona_cli::cli_styles;
+use kona_client::single::{DerivedClaim, FaultProofProgramError, ParallelExecution};
+use kona_executor::TraceConfig;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, HintReader, HintWriter, NativeChannel, OracleReader,
+    OracleServer, PreimageListener, SocketAddress,
+};
+use kona_proof::{HintType, boot::IntermediateClaim, profile};
+use kona_providers_alloy::{
+    BlobArchiverClient, BlobDirectory, FallbackBlobProvider, OnlineBeaconClient, OnlineBlobProvider,
 };
-use kona_proof::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
-use serde::Serialize;
-use std::{path::PathBuf, sync::Arc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::{error, info, instrument::WithSubscriber, level_filters::LevelFilter, warn};
 
 /// The host binary CLI application arguments.
-#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[derive(Default, Parser, Serialize, Deserialize, Clone, Debug)]
 #[command(styles = cli_styles())]
+#[serde(default)]
 pub struct SingleChainHost {
     /// Hash of the L1 head block. Derivation stops after this block is processed.
     #[arg(long, env)]
@@ -71,6 +86,14 @@ pub struct SingleChainHost {
         env
     )]
     pub l1_beacon_address: Option<String>,
+    /// Address of a blob archiver API endpoint, used to fetch blobs that were pruned by the L1
+    /// Beacon API endpoint.
+    #[arg(long, visible_alias = "blob-archiver", requires = "l1_beacon_address", env)]
+    pub l1_blob_archiver_address: Option<String>,
+    /// Path to a local blob directory, used to fetch blobs that were pruned by the L1 Beacon API
+    /// endpoint. Blobs fetched from the blob archiver are cached in the directory.
+    #[arg(long, visible_alias = "blob-dir", requires = "l1_beacon_address", env)]
+    pub l1_blob_dir: Option<PathBuf>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
@@ -80,13 +103,38 @@ pub struct SingleChainHost {
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// Directory of a persistent preimage cache shared between host runs. Content-addressed
+    /// preimages fetched by the host are stored in the cache and reused by later runs, while local
+    /// boot data is kept per run. The cache may be shared by several host processes at once.
+    #[arg(long, env)]
+    pub preimage_cache: Option<PathBuf>,
+    /// The maximum size of the preimage cache, in MiB. Once the cache grows past this size, the
+    /// least recently used preimages are evicted. Defaults to 8 GiB.
+    #[arg(
+        long,
+        requires = "preimage_cache",
+        value_parser = PreimageCache::parse_max_size_mib,
+        env
+    )]
+    pub preimage_cache_size: Option<u64>,
     /// Run the client program natively.
     #[arg(long, conflicts_with = "server", required_unless_present = "server")]
     pub native: bool,
+    /// Profile the phases of the natively run client program, logging a per-phase report of the
+    /// wall-clock time spent in each once the program exits.
+    #[arg(long, requires = "native")]
+    #[serde(skip)]
+    pub profile: bool,
     /// Run in pre-image server mode without executing any client program. If not provided, the
     /// host will run the client program in the host process.
     #[arg(long, conflicts_with = "native", required_unless_present = "native")]
     pub server: bool,
+    /// Serve the pre-image server over a stream socket instead of the pre-image pipes, accepting
+    /// concurrent client sessions. Accepts `tcp://<ip>:<port>`, `<ip>:<port>` or
+    /// `unix://<path>`. Each session is isolated in its own key-value store.
+    #[arg(long, requires = "server", env)]
+    #[serde(skip)]
+    pub listen: Option<SocketAddress>,
     /// The L2 chain ID of a supported chain. If provided, the host will look for the corresponding
     /// rollup config in the superchain registry.
     #[arg(
@@ -114,6 +162,59 @@ pub struct SingleChainHost {
     /// the execution layer.
     #[arg(long, env)]
     pub enable_experimental_witness_endpoint: bool,
+    /// Intermediate output root claims to check in the same derivation pass as the claimed output
+    /// root, formatted as `<block_number>:<output_root>`. The validity of each claim is reported,
+    /// and the host exits with a non-zero status if any claim is invalid. Only available when
+    /// running the client program natively.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = parse_intermediate_claim,
+        requires = "native",
+        env
+    )]
+    pub intermediate_claims: Option<Vec<IntermediateClaim>>,
+    /// Execute the derived L2 blocks speculatively in parallel, in windows of up to this many
+    /// blocks, building on the intermediate blocks reported by the L2 execution layer. Blocks
+    /// that do not match the execution layer are re-executed sequentially, so the outcome does not
+    /// depend on the execution layer. Only available when running the client program natively.
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u64).range(1..),
+        requires = "native",
+        requires = "l2_node_address",
+        conflicts_with = "intermediate_claims",
+        env
+    )]
+    #[serde(skip)]
+    pub parallel_execution_window: Option<u64>,
+    /// Trace the transactions executed by the natively run client program, writing the call
+    /// trace of each transaction to this path as JSON lines.
+    #[arg(
+        long,
+        requires = "native",
+        conflicts_with_all = ["intermediate_claims", "parallel_execution_window"],
+        env
+    )]
+    #[serde(skip)]
+    pub trace_output: Option<PathBuf>,
+    /// Include a log of every executed opcode in the transaction traces.
+    #[arg(long, requires = "trace_output", env)]
+    #[serde(skip)]
+    pub trace_struct_logs: bool,
+    /// Write a replay bundle to this path, holding every preimage served to the client program and
+    /// the hints it sent, which `kona-host replay` re-runs offline. When running natively, the
+    /// bundle is only written if the client program fails. In server mode, the host cannot observe
+    /// the outcome of the client program, and writes the bundle once the session ends.
+    #[arg(long, conflicts_with = "listen", env)]
+    #[serde(skip)]
+    pub replay_bundle: Option<PathBuf>,
+    /// A file holding the output of the client program, e.g. the redirected stderr of the fault
+    /// proof VM, to include in the replay bundle. When running natively, the tracing output of the
+    /// client program is captured into the bundle instead, unless this file is given.
+    #[arg(long, requires = "replay_bundle", env)]
+    #[serde(skip)]
+    pub client_log: Option<PathBuf>,
 }
 
 /// An error that can occur when handling single chain hosts
@@ -137,6 +238,18 @@ pub enum SingleChainHostError {
     /// No l1 config found.
     #[error("No l1 config found")]
     NoL1Config,
+    /// An error opening the preimage cache.
+    #[error("Preimage cache error: {0}")]
+    PreimageCacheError(anyhow::Error),
+    /// An error recording the replay bundle.
+    #[error("Replay bundle error: {0}")]
+    ReplayBundleError(anyhow::Error),
+    /// The client program failed.
+    #[error("Client program failed: {0}")]
+    ClientError(#[from] FaultProofProgramError),
+    /// One or more of the derived claims are invalid.
+    #[error("Invalid claims")]
+    InvalidClaims,
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -145,11 +258,16 @@ pub enum SingleChainHostError {
 impl SingleChainHost {
     /// Starts the [SingleChainHost] application.
     pub async fn start(self) -> Result<(), SingleChainHostError> {
-        if self.server {
+        if let Some(addr) = &self.listen {
+            self.start_socket_server(addr).await
+        } else if self.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
 
+            if self.replay_bundle.is_some() {
+                return self.start_recorded_server(hint, preimage).await;
+            }
             self.start_server(hint, preimage).await?.await?
         } else {
             self.start_native().await
@@ -166,7 +284,64 @@ impl SingleChainHost {
         C: Channel + Send + Sync + 'static,
     {
         let kv_store = self.create_key_value_store()?;
+        self.start_server_with_store(hint, preimage, kv_store).await
+    }
+
+    /// Starts the preimage server, communicating with the client over the provided channels and
+    /// recording the session in a replay bundle, which is written once the session ends or the
+    /// host is interrupted.
+    async fn start_recorded_server<C>(
+        &self,
+        hint: C,
+        preimage: C,
+    ) -> Result<(), SingleChainHostError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
+        let recorder = Arc::new(
+            self.create_replay_recorder()?
+                .ok_or(SingleChainHostError::Other("Replay bundle path must be set"))?,
+        );
+        let kv_store = self.create_recorded_key_value_store(recorder.clone())?;
+        let server = self.spawn_server(hint, preimage, kv_store, Some(recorder.hint_log())).await?;
+
+        let result = tokio::select! {
+            result = server => result?,
+            _ = tokio::signal::ctrl_c() => {
+                info!(target: "host", "Interrupted, writing replay bundle");
+                Ok(())
+            }
+        };
+        self.write_replay_bundle(&recorder, None);
+        result
+    }
 
+    /// Starts the preimage server backed by the given key-value store, communicating with the
+    /// client over the provided channels.
+    pub async fn start_server_with_store<C>(
+        &self,
+        hint: C,
+        preimage: C,
+        kv_store: SharedKeyValueStore,
+    ) -> Result<JoinHandle<Result<(), SingleChainHostError>>, SingleChainHostError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
+        self.spawn_server(hint, preimage, kv_store, None).await
+    }
+
+    /// Spawns the preimage server backed by the given key-value store, appending the received
+    /// hints to the [HintLog], if one is given.
+    async fn spawn_server<C>(
+        &self,
+        hint: C,
+        preimage: C,
+        kv_store: SharedKeyValueStore,
+        hint_log: Option<HintLog>,
+    ) -> Result<JoinHandle<Result<(), SingleChainHostError>>, SingleChainHostError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
         let task_handle = if self.is_offline() {
             task::spawn(async {
                 PreimageServer::new(
@@ -174,6 +349,7 @@ impl SingleChainHost {
                     HintReader::new(hint),
                     Arc::new(OfflineHostBackend::new(kv_store)),
                 )
+                .with_hint_log(hint_log)
                 .start()
                 .await
                 .map_err(SingleChainHostError::from)
@@ -194,6 +370,7 @@ impl SingleChainHost {
                     HintReader::new(hint),
                     Arc::new(backend),
                 )
+                .with_hint_log(hint_log)
                 .start()
                 .await
                 .map_err(SingleChainHostError::from)
@@ -203,22 +380,231 @@ impl SingleChainHost {
         Ok(task_handle)
     }
 
+    /// Starts the preimage server on a stream socket, serving each client session that connects
+    /// to the given address concurrently.
+    ///
+    /// In online mode, each session is backed by its own key-value store, in a subdirectory of the
+    /// data directory if one is set. In offline mode, all sessions read from the data directory.
+    async fn start_socket_server(&self, addr: &SocketAddress) -> Result<(), SingleChainHostError> {
+        let mut listener = PreimageListener::bind(addr).await?;
+        info!(target: "host", "Listening for preimage sessions on {addr}");
+
+        let offline_kv_store =
+            self.is_offline().then(|| self.create_key_value_store()).transpose()?;
+        loop {
+            let session = match listener.accept().await {
+                Ok(session) => session,
+                Err(e) => {
+                    warn!(target: "host", "Failed to accept preimage connection: {e}");
+                    continue;
+                }
+            };
+            info!(target: "host", "Accepted preimage session {}", session.id);
+
+            let kv_store = match &offline_kv_store {
+                Some(kv_store) => kv_store.clone(),
+                None => {
+                    let mut cfg = self.clone();
+                    cfg.data_dir =
+                        cfg.data_dir.map(|dir| dir.join(format!("session-{}", session.id)));
+                    cfg.create_key_value_store()?
+                }
+            };
+            let server =
+                self.start_server_with_store(session.hint, session.preimage, kv_store).await?;
+
+            let id = session.id;
+            task::spawn(async move {
+                match server.await {
+                    Ok(Ok(())) => info!(target: "host", "Preimage session {id} closed"),
+                    Ok(Err(e)) => error!(target: "host", "Preimage session {id} failed: {e}"),
+                    Err(e) => error!(target: "host", "Preimage session {id} panicked: {e}"),
+                }
+            });
+        }
+    }
+
     /// Starts the host in native mode, running both the client and preimage server in the same
     /// process.
     async fn start_native(&self) -> Result<(), SingleChainHostError> {
+        let recorder = self.create_replay_recorder()?.map(Arc::new);
+        let kv_store = match &recorder {
+            Some(recorder) => self.create_recorded_key_value_store(recorder.clone())?,
+            None => self.create_key_value_store()?,
+        };
+        let recorder_ref = recorder.as_deref();
+
+        if self.profile {
+            profile::set_clock(kona_std_fpvm::profile::clock, kona_std_fpvm::profile::CLOCK_UNIT);
+        }
+
+        if self.intermediate_claims.is_some() {
+            let result = match self
+                .run_native_recorded(
+                    kv_store,
+                    recorder_ref,
+                    kona_client::single::derive_intermediate_claims,
+                )
+                .await?
+            {
+                Ok(claims) if report_claims(&claims) => Ok(()),
+                Ok(_) => Err(SingleChainHostError::InvalidClaims),
+                Err(e) => {
+                    error!(target: "host", "Failed to derive intermediate claims: {e}");
+                    if let Some(recorder) = &recorder {
+                        self.write_replay_bundle(recorder, Some(e.to_string()));
+                    }
+                    Err(e.into())
+                }
+            };
+            self.report_profile();
+            return result;
+        }
+
+        let client_result = match (&self.trace_output, self.parallel_execution_window) {
+            (Some(path), _) => self.run_native_traced(kv_store, recorder_ref, path).await?,
+            (None, Some(window)) => {
+                let parallel = ParallelExecution {
+                    claims: L2ClaimProvider::new(self.create_l2_provider().await?),
+                    window: window as usize,
+                };
+                self.run_native_recorded(kv_store, recorder_ref, |oracle, hint| {
+                    kona_client::single::run_parallel(oracle, hint, parallel)
+                })
+                .await?
+            }
+            (None, None) => {
+                self.run_native_recorded(kv_store, recorder_ref, kona_client::single::run).await?
+            }
+        };
+        self.report_profile();
+        if let (Some(recorder), Err(e)) = (&recorder, &client_result) {
+            self.write_replay_bundle(recorder, Some(e.to_string()));
+        }
+
+        // Bubble up the error of the client program if execution completes.
+        client_result.map_err(Into::into)
+    }
+
+    /// Runs the given client program natively, against a preimage server backed by the given
+    /// key-value store, and returns the program's result.
+    pub async fn run_native<F, Fut>(
+        &self,
+        kv_store: SharedKeyValueStore,
+        program: F,
+    ) -> Result<Fut::Output, SingleChainHostError>
+    where
+        F: FnOnce(OracleReader<NativeChannel>, HintWriter<NativeChannel>) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.run_native_recorded(kv_store, None, program).await
+    }
+
+    /// Runs the given client program natively, as [Self::run_native], recording the hints it sends
+    /// and its tracing output in the [ReplayRecorder], if one is given.
+    async fn run_native_recorded<F, Fut>(
+        &self,
+        kv_store: SharedKeyValueStore,
+        recorder: Option<&ReplayRecorder>,
+        program: F,
+    ) -> Result<Fut::Output, SingleChainHostError>
+    where
+        F: FnOnce(OracleReader<NativeChannel>, HintWriter<NativeChannel>) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
         let hint = BidirectionalChannel::new()?;
         let preimage = BidirectionalChannel::new()?;
 
-        let server_task = self.start_server(hint.host, preimage.host).await?;
-        let client_task = task::spawn(kona_client::single::run(
-            OracleReader::new(preimage.client),
-            HintWriter::new(hint.client),
-        ));
+        let hint_log = recorder.map(ReplayRecorder::hint_log);
+        let server_task = self.spawn_server(hint.host, preimage.host, kv_store, hint_log).await?;
+        let client = program(OracleReader::new(preimage.client), HintWriter::new(hint.client));
+        let client_task = match recorder {
+            Some(recorder) => task::spawn(
+                client.with_subscriber(recorder.client_log().dispatch(LevelFilter::current())),
+            ),
+            None => task::spawn(client),
+        };
 
         let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+        Ok(client_result)
+    }
 
-        // Bubble up the exit status of the client program if execution completes.
-        std::process::exit(client_result.is_err() as i32)
+    /// Runs the client program natively, tracing the transactions it executes and writing their
+    /// traces to `path` as JSON lines. Returns the result of checking the derived claim.
+    async fn run_native_traced(
+        &self,
+        kv_store: SharedKeyValueStore,
+        recorder: Option<&ReplayRecorder>,
+        path: &Path,
+    ) -> Result<Result<(), FaultProofProgramError>, SingleChainHostError> {
+        let config = TraceConfig { calls: true, struct_logs: self.trace_struct_logs, stack: false };
+        let derived = match self
+            .run_native_recorded(kv_store, recorder, move |oracle, hint| {
+                kona_client::single::derive_claim_traced(oracle, hint, config)
+            })
+            .await?
+        {
+            Ok(Some(derived)) => derived,
+            Ok(None) => return Ok(Ok(())),
+            Err(e) => return Ok(Err(e)),
+        };
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        for trace in &derived.traces {
+            serde_json::to_writer(&mut writer, trace).map_err(std::io::Error::from)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        info!(target: "host", "Wrote {} transaction traces to {path:?}", derived.traces.len());
+
+        Ok(if report_claims(std::slice::from_ref(&derived)) {
+            Ok(())
+        } else {
+            Err(FaultProofProgramError::InvalidClaim(
+                derived.output_root,
+                derived.claimed_output_root,
+            ))
+        })
+    }
+
+    /// Creates the [ReplayRecorder] that streams the session into the replay bundle, if a replay
+    /// bundle path is set.
+    fn create_replay_recorder(&self) -> Result<Option<ReplayRecorder>, SingleChainHostError> {
+        self.replay_bundle
+            .as_deref()
+            .map(ReplayRecorder::new)
+            .transpose()
+            .map_err(SingleChainHostError::ReplayBundleError)
+    }
+
+    /// Finishes the session recorded by the [ReplayRecorder], writing the replay bundle along with
+    /// the error returned by the client program, if known, and the client log, if any. The client
+    /// log is read from the configured file, or taken from the tracing output captured by the
+    /// recorder when running natively.
+    fn write_replay_bundle(&self, recorder: &ReplayRecorder, error: Option<String>) {
+        let client_log = match self.client_log.as_ref().map(std::fs::read).transpose() {
+            Ok(client_log) => client_log,
+            Err(e) => {
+                warn!(target: "host", "Failed to read client log: {e}");
+                None
+            }
+        }
+        .or_else(|| recorder.client_log().contents());
+
+        let manifest = ReplayManifest::new(self.clone(), error);
+        match recorder.finish(&manifest, client_log.as_deref()) {
+            Ok(path) => info!(target: "host", "Wrote replay bundle to {path:?}"),
+            Err(e) => error!(target: "host", "Failed to write replay bundle: {e}"),
+        }
+    }
+
+    /// Logs the client program's [profile::ProfileReport], if profiling is enabled.
+    fn report_profile(&self) {
+        if self.profile {
+            info!(target: "host", "Client profile:\n{}", profile::report());
+        }
     }
 
     /// Returns `true` if the host is running in offline mode.
@@ -254,19 +640,46 @@ impl SingleChainHost {
 
     /// Creates the key-value store for the host backend.
     pub fn create_key_value_store(&self) -> Result<SharedKeyValueStore, SingleChainHostError> {
+        Ok(Arc::new(RwLock::new(self.create_split_key_value_store()?)))
+    }
+
+    /// Creates the key-value store for the host backend, recording every preimage read from it in
+    /// the given [ReplayRecorder].
+    pub fn create_recorded_key_value_store(
+        &self,
+        recorder: Arc<ReplayRecorder>,
+    ) -> Result<SharedKeyValueStore, SingleChainHostError> {
+        let kv_store = RecordingKeyValueStore::new(self.create_split_key_value_store()?, recorder);
+        Ok(Arc::new(RwLock::new(kv_store)))
+    }
+
+    /// Creates the [SplitKeyValueStore] that serves local inputs from the host configuration, and
+    /// all other preimages from the data directory, or memory if no data directory is set.
+    fn create_split_key_value_store(
+        &self,
+    ) -> Result<
+        SplitKeyValueStore<SingleChainLocalInputs, Box<dyn KeyValueStore + Send + Sync>>,
+        SingleChainHostError,
+    > {
         let local_kv_store = SingleChainLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
-            let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
-        } else {
-            let mem_kv_store = MemoryKeyValueStore::new();
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, mem_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
-        };
+        let mut remote_kv_store: Box<dyn KeyValueStore + Send + Sync> =
+            if let Some(ref data_dir) = self.data_dir {
+                Box::new(DiskKeyValueStore::new(data_dir.clone()))
+            } else {
+                Box::new(MemoryKeyValueStore::new())
+            };
+        if let Some(ref cache_dir) = self.preimage_cache {
+            let max_size = PreimageCache::max_size_from_mib(
+                self.preimage_cache_size.unwrap_or(PreimageCache::DEFAULT_MAX_SIZE_MIB),
+            )
+            .ok_or(SingleChainHostError::Other("Preimage cache size is too large"))?;
+            let cache = PreimageCache::open(cache_dir.clone(), max_size)
+                .map_err(SingleChainHostError::PreimageCacheError)?;
+            remote_kv_store = Box::new(CachedKeyValueStore::new(remote_kv_store, cache));
+        }
 
-        Ok(kv_store)
+        Ok(SplitKeyValueStore::new(local_kv_store, remote_kv_store))
     }
 
     /// Creates the providers required for the host backend.
@@ -277,21 +690,68 @@ impl SingleChainHost {
                 .ok_or(SingleChainHostError::Other("Provider must be set"))?,
         )
         .await;
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
-            self.l1_beacon_address
-                .clone()
-                .ok_or(SingleChainHostError::Other("Beacon API URL must be set"))?,
-        ))
-        .await;
-        let l2_provider = rpc_provider::<Optimism>(
+        let blob_provider = FallbackBlobProvider::new(
+            OnlineBlobProvider::init(OnlineBeaconClient::new_http(
+                self.l1_beacon_address
+                    .clone()
+                    .ok_or(SingleChainHostError::Other("Beacon API URL must be set"))?,
+            ))
+            .await,
+        )
+        .with_archiver(self.l1_blob_archiver_address.clone().map(BlobArchiverClient::new_http))
+        .with_directory(
+            self.l1_blob_dir.clone().map(|dir| BlobDirectory::new(dir).with_write_through(true)),
+        );
+        let l2_provider = self.create_l2_provider().await?;
+
+        Ok(SingleChainProviders { l1: l1_provider, blobs: blob_provider, l2: l2_provider })
+    }
+
+    /// Creates the L2 EL provider.
+    async fn create_l2_provider(&self) -> Result<RootProvider<Optimism>, SingleChainHostError> {
+        Ok(rpc_provider::<Optimism>(
             self.l2_node_address
                 .as_ref()
                 .ok_or(SingleChainHostError::Other("L2 node address must be set"))?,
         )
-        .await;
+        .await)
+    }
+}
 
-        Ok(SingleChainProviders { l1: l1_provider, blobs: blob_provider, l2: l2_provider })
+/// Parses an [IntermediateClaim] formatted as `<block_number>:<output_root>`.
+fn parse_intermediate_claim(s: &str) -> Result<IntermediateClaim, String> {
+    let (block_number, output_root) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Expected <block_number>:<output_root>, got {s}"))?;
+
+    Ok(IntermediateClaim::new(
+        block_number.parse().map_err(|e| format!("Invalid block number {block_number}: {e}"))?,
+        output_root.parse().map_err(|e| format!("Invalid output root {output_root}: {e}"))?,
+    ))
+}
+
+/// Reports the validity of each [DerivedClaim], returning `true` if all claims are valid.
+fn report_claims(claims: &[DerivedClaim]) -> bool {
+    for claim in claims {
+        if claim.is_valid() {
+            info!(
+                target: "host",
+                number = claim.block_number,
+                output_root = ?claim.output_root,
+                "Claim is valid"
+            );
+        } else {
+            error!(
+                target: "host",
+                number = claim.block_number,
+                output_root = ?claim.output_root,
+                claimed_output_root = ?claim.claimed_output_root,
+                "Claim is invalid"
+            );
+        }
     }
+
+    claims.iter().all(DerivedClaim::is_valid)
 }
 
 impl OnlineHostBackendCfg for SingleChainHost {
@@ -305,20 +765,39 @@ pub struct SingleChainProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: FallbackBlobProvider<OnlineBeaconClient>,
     /// The L2 EL provider.
     pub l2: RootProvider<Optimism>,
 }
 
 #[cfg(test)]
 mod test {
+    use super::report_claims;
     use crate::single::SingleChainHost;
     use alloy_primitives::B256;
     use clap::Parser;
+    use kona_client::single::DerivedClaim;
+    use kona_proof::boot::IntermediateClaim;
+
+    #[test]
+    fn test_report_claims() {
+        let claim = |block_number, claimed_output_root| DerivedClaim {
+            block_number,
+            safe_head: Default::default(),
+            output_root: B256::ZERO,
+            claimed_output_root,
+            artifacts: None,
+            traces: Vec::new(),
+        };
+
+        assert!(report_claims(&[claim(1, B256::ZERO), claim(2, B256::ZERO)]));
+        assert!(!report_claims(&[claim(1, B256::ZERO), claim(2, B256::repeat_byte(0x01))]));
+    }
 
     #[test]
     fn test_flags() {
         let zero_hash_str = &B256::ZERO.to_string();
+        let claims = &format!("1:{zero_hash_str},2:{zero_hash_str}");
         let default_flags = [
             "single",
             "--l1-head",
@@ -366,7 +845,66 @@ mod test {
                 .as_slice(),
                 true,
             ),
+            (
+                [
+                    "--server",
+                    "--listen",
+                    "127.0.0.1:7000",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--intermediate-claims",
+                    claims,
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--parallel-execution-window",
+                    "8",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--trace-output",
+                    "traces.jsonl",
+                    "--trace-struct-logs",
+                ]
+                .as_slice(),
+                true,
+            ),
             // invalid
+            (["--native", "--listen", "127.0.0.1:7000", "--l2-chain-id", "0"].as_slice(), false),
+            (["--server", "--listen", "dummy", "--l2-chain-id", "0"].as_slice(), false),
             (["--server", "--native", "--l2-chain-id", "0"].as_slice(), false),
             (["--l2-chain-id", "0", "--rollup-config-path", "dummy", "--server"].as_slice(), false),
             (["--server"].as_slice(), false),
@@ -376,6 +914,95 @@ mod test {
             (["--l1-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l2-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l1-beacon-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--intermediate-claims",
+                    claims,
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--intermediate-claims",
+                    "1",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--parallel-execution-window",
+                    "8",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--parallel-execution-window",
+                    "0",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--trace-output",
+                    "traces.jsonl",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                ["--native", "--l2-chain-id", "0", "--data-dir", "dummy", "--trace-struct-logs"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--trace-output",
+                    "traces.jsonl",
+                    "--intermediate-claims",
+                    claims,
+                ]
+                .as_slice(),
+                false,
+            ),
             ([].as_slice(), false),
         ];
 
@@ -386,4 +1013,36 @@ mod test {
             assert_eq!(parsed.is_ok(), valid);
         }
     }
+
+    #[test]
+    fn test_intermediate_claims_flag() {
+        let zero_hash_str = &B256::ZERO.to_string();
+        let root = B256::repeat_byte(0x01);
+        let host = SingleChainHost::try_parse_from([
+            "single",
+            "--l1-head",
+            zero_hash_str,
+            "--l2-head",
+            zero_hash_str,
+            "--l2-output-root",
+            zero_hash_str,
+            "--l2-claim",
+            zero_hash_str,
+            "--l2-block-number",
+            "10",
+            "--native",
+            "--l2-chain-id",
+            "0",
+            "--data-dir",
+            "dummy",
+            "--intermediate-claims",
+            &format!("5:{root},8:{root}"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            host.intermediate_claims.unwrap(),
+            [IntermediateClaim::new(5, root), IntermediateClaim::new(8, root)]
+        );
+    }
 }
@@ -0,0 +1,66 @@
+//! A [ClaimProvider] backed by the L2 execution layer.
+
+use alloy_primitives::Sealable;
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport::TransportError;
+use async_trait::async_trait;
+use kona_driver::{ClaimProvider, ClaimedBlock};
+use kona_protocol::{OutputRoot, Predeploys};
+use op_alloy_network::Optimism;
+
+/// An error returned by the [L2ClaimProvider].
+#[derive(Debug, thiserror::Error)]
+pub enum L2ClaimProviderError {
+    /// The L2 execution layer does not have the requested block.
+    #[error("Block {0} not found")]
+    BlockNotFound(u64),
+    /// A request to the L2 execution layer failed.
+    #[error("RPC error: {0}")]
+    Rpc(#[from] TransportError),
+}
+
+/// A [ClaimProvider] serving the blocks and output roots reported by the L2 execution layer as the
+/// claims that speculative parallel execution in the client program builds on.
+///
+/// The claims are not trusted: the driver checks every speculatively executed block against its
+/// claim, and falls back to sequential execution on the first mismatch.
+#[derive(Debug, Clone)]
+pub struct L2ClaimProvider {
+    /// The L2 EL provider.
+    l2: RootProvider<Optimism>,
+}
+
+impl L2ClaimProvider {
+    /// Creates a new [L2ClaimProvider] with the given L2 EL provider.
+    pub const fn new(l2: RootProvider<Optimism>) -> Self {
+        Self { l2 }
+    }
+}
+
+#[async_trait]
+impl ClaimProvider for L2ClaimProvider {
+    type Error = L2ClaimProviderError;
+
+    async fn claimed_block(&mut self, number: u64) -> Result<ClaimedBlock, Self::Error> {
+        let block = self
+            .l2
+            .get_block_by_number(number.into())
+            .await?
+            .ok_or(L2ClaimProviderError::BlockNotFound(number))?;
+        let header = block.header.inner.seal_slow();
+
+        let l2_to_l1_message_passer = self
+            .l2
+            .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Default::default())
+            .block_id(header.hash().into())
+            .await?;
+        let output_root = OutputRoot::from_parts(
+            header.state_root,
+            l2_to_l1_message_passer.storage_hash,
+            header.hash(),
+        )
+        .hash();
+
+        Ok(ClaimedBlock { header, output_root })
+    }
+}
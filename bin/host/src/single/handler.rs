@@ -381,4 +381,17 @@ impl HintHandler for SingleChainHintHandler {
 
         Ok(())
     }
+
+    fn predict_hints(
+        hint: &Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>,
+    ) -> Vec<Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>> {
+        match hint.ty {
+            // Derivation reads the receipts and transactions of each L1 block after its header.
+            HintType::L1BlockHeader => vec![
+                Hint::new(HintType::L1Receipts, hint.data.clone()),
+                Hint::new(HintType::L1Transactions, hint.data.clone()),
+            ],
+            _ => Vec::new(),
+        }
+    }
 }
@@ -7,8 +7,8 @@ use alloy_primitives::B256;
 use anyhow::Result;
 use kona_preimage::PreimageKey;
 use kona_proof::boot::{
-    L1_CONFIG_KEY, L1_HEAD_KEY, L2_CHAIN_ID_KEY, L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY,
-    L2_OUTPUT_ROOT_KEY, L2_ROLLUP_CONFIG_KEY,
+    IntermediateClaim, L1_CONFIG_KEY, L1_HEAD_KEY, L2_CHAIN_ID_KEY, L2_CLAIM_BLOCK_NUMBER_KEY,
+    L2_CLAIM_KEY, L2_INTERMEDIATE_CLAIMS_KEY, L2_OUTPUT_ROOT_KEY, L2_ROLLUP_CONFIG_KEY,
 };
 
 /// A simple, synchronous key-value store that returns data from a [SingleChainHost] config.
@@ -47,6 +47,9 @@ impl KeyValueStore for SingleChainLocalInputs {
                 let serialized = serde_json::to_vec(&l1_config).ok()?;
                 Some(serialized)
             }
+            L2_INTERMEDIATE_CLAIMS_KEY => {
+                self.cfg.intermediate_claims.as_deref().map(IntermediateClaim::encode_list)
+            }
             _ => None,
         }
     }
@@ -8,3 +8,15 @@ pub use local_kv::SingleChainLocalInputs;
 
 mod handler;
 pub use handler::SingleChainHintHandler;
+
+mod claims;
+pub use claims::{L2ClaimProvider, L2ClaimProviderError};
+
+mod bisect;
+pub use bisect::{BisectHost, BisectReport, FieldDiff, NodeOutput};
+
+mod replay;
+pub use replay::{
+    ClientLog, REPLAY_BUNDLE_VERSION, RecordingKeyValueStore, ReplayBundle, ReplayHost,
+    ReplayManifest, ReplayRecorder,
+};
@@ -0,0 +1,513 @@
+//! Replay bundles, capturing everything needed to re-run a failed client program natively and
+//! offline, without access to the RPCs that it was originally run against.
+
+use super::SingleChainHost;
+use crate::{HintLog, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore};
+use alloy_primitives::B256;
+use anyhow::{Result, anyhow, ensure};
+use clap::Parser;
+use kona_cli::cli_styles;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, PoisonError},
+};
+use tempfile::NamedTempFile;
+use tokio::sync::RwLock;
+use tracing::{Dispatch, info, level_filters::LevelFilter, warn};
+use tracing_subscriber::layer::SubscriberExt;
+
+/// The version of the replay bundle format.
+pub const REPLAY_BUNDLE_VERSION: u8 = 1;
+
+/// The path of the [ReplayManifest] within a replay bundle.
+const MANIFEST_PATH: &str = "manifest.json";
+
+/// The path of the hint log within a replay bundle.
+const HINTS_PATH: &str = "hints.log";
+
+/// The path of the client program's output within a replay bundle.
+const CLIENT_LOG_PATH: &str = "client.log";
+
+/// The directory of the preimages within a replay bundle.
+const PREIMAGES_DIR: &str = "preimages/";
+
+/// The replay CLI arguments.
+///
+/// Re-runs the client program natively and offline, serving it the preimages captured in a replay
+/// bundle written by `single --replay-bundle`.
+#[derive(Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct ReplayHost {
+    /// Path to the replay bundle.
+    pub bundle: PathBuf,
+}
+
+impl ReplayHost {
+    /// Replays the bundle, returning an error if the client program fails.
+    pub async fn start(self) -> Result<()> {
+        let bundle = ReplayBundle::read(&self.bundle)?;
+        info!(
+            target: "replay",
+            "Replaying {} preimages and {} hints from {:?}",
+            bundle.preimages.len(),
+            bundle.hints.len(),
+            self.bundle
+        );
+        if let Some(error) = &bundle.manifest.error {
+            info!(target: "replay", "Recorded client program error: {error}");
+        }
+
+        bundle.replay().await?.map_err(|e| anyhow!("Client program failed: {e}"))?;
+        info!(target: "replay", "Client program completed successfully");
+        Ok(())
+    }
+}
+
+/// Records the preimages served to, and the hints sent by, a client program, streaming them into a
+/// replay bundle.
+///
+/// Preimages are appended to the bundle when they are first served, rather than being held in
+/// memory. The bundle is written to a temporary file next to its destination, which is only moved
+/// into place once the recording is [finished](Self::finish), and is removed otherwise.
+#[derive(Debug)]
+pub struct ReplayRecorder {
+    /// The bundle that preimages are streamed into, until the recording is finished or fails.
+    bundle: Mutex<Option<BundleWriter>>,
+    /// The hints sent by the client program, in order.
+    hints: HintLog,
+    /// The tracing output of the client program, if it runs in the host process.
+    client_log: ClientLog,
+}
+
+impl ReplayRecorder {
+    /// Creates a [ReplayRecorder] that writes its bundle to the given path.
+    pub fn new(path: &Path) -> Result<Self> {
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let file = NamedTempFile::new_in(directory)
+            .map_err(|e| anyhow!("Failed to create replay bundle in {directory:?}: {e}"))?;
+
+        Ok(Self {
+            bundle: Mutex::new(Some(BundleWriter {
+                path: path.to_path_buf(),
+                builder: tar::Builder::new(BufWriter::new(file)),
+                recorded: HashSet::new(),
+            })),
+            hints: HintLog::default(),
+            client_log: ClientLog::default(),
+        })
+    }
+
+    /// Returns the [HintLog] that hints sent by the client program should be appended to.
+    pub fn hint_log(&self) -> HintLog {
+        self.hints.clone()
+    }
+
+    /// Returns the [ClientLog] that the tracing output of a client program running in the host
+    /// process should be captured into.
+    pub fn client_log(&self) -> ClientLog {
+        self.client_log.clone()
+    }
+
+    /// Records a preimage served to the client program, appending it to the bundle if it was not
+    /// served before. If the preimage cannot be written, the bundle is discarded.
+    pub fn record_preimage(&self, key: B256, value: &[u8]) {
+        let mut bundle = self.bundle.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(writer) = bundle.as_mut() else {
+            return;
+        };
+        if !writer.recorded.insert(key) {
+            return;
+        }
+
+        if let Err(e) = append_entry(&mut writer.builder, &format!("{PREIMAGES_DIR}{key}"), value) {
+            warn!(target: "replay", "Failed to record preimage {key}, discarding replay bundle: {e}");
+            *bundle = None;
+        }
+    }
+
+    /// Finishes the recording, appending the manifest, the recorded hints and the output of the
+    /// client program, if any, to the bundle, and moving it to its destination. Returns the path
+    /// of the bundle.
+    pub fn finish(&self, manifest: &ReplayManifest, client_log: Option<&[u8]>) -> Result<PathBuf> {
+        let BundleWriter { path, mut builder, .. } = self
+            .bundle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .ok_or_else(|| anyhow!("Replay bundle was already written or discarded"))?;
+
+        append_entry(&mut builder, MANIFEST_PATH, &serde_json::to_vec_pretty(manifest)?)?;
+        let hints = self.hints.lock().expect("hint log poisoned");
+        append_entry(
+            &mut builder,
+            HINTS_PATH,
+            hints.iter().map(|hint| format!("{hint}\n")).collect::<String>().as_bytes(),
+        )?;
+        if let Some(client_log) = client_log {
+            append_entry(&mut builder, CLIENT_LOG_PATH, client_log)?;
+        }
+
+        let file = builder
+            .into_inner()?
+            .into_inner()
+            .map_err(|e| anyhow!("Failed to flush replay bundle: {}", e.error()))?;
+        file.persist(&path)
+            .map_err(|e| anyhow!("Failed to write replay bundle to {path:?}: {}", e.error))?;
+        Ok(path)
+    }
+}
+
+/// A replay bundle that is being recorded.
+struct BundleWriter {
+    /// The destination of the bundle.
+    path: PathBuf,
+    /// The tar archive of the bundle, written to a temporary file.
+    builder: tar::Builder<BufWriter<NamedTempFile>>,
+    /// The keys of the preimages appended to the bundle.
+    recorded: HashSet<B256>,
+}
+
+impl fmt::Debug for BundleWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BundleWriter")
+            .field("path", &self.path)
+            .field("recorded", &self.recorded.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The tracing output of a client program running in the host process, captured for a replay
+/// bundle.
+#[derive(Debug, Clone, Default)]
+pub struct ClientLog(Arc<Mutex<Vec<u8>>>);
+
+impl ClientLog {
+    /// Returns a [Dispatch] that logs events up to the given level to stdout, and captures them
+    /// into the [ClientLog].
+    pub fn dispatch(&self, level: LevelFilter) -> Dispatch {
+        let log = self.clone();
+        Dispatch::new(
+            tracing_subscriber::registry().with(level).with(tracing_subscriber::fmt::layer()).with(
+                tracing_subscriber::fmt::layer().with_ansi(false).with_writer(move || log.clone()),
+            ),
+        )
+    }
+
+    /// Returns the captured output, if any.
+    pub fn contents(&self) -> Option<Vec<u8>> {
+        let log = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        (!log.is_empty()).then(|| log.clone())
+    }
+}
+
+impl Write for ClientLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A [KeyValueStore] that records every preimage read from the inner store in a [ReplayRecorder].
+#[derive(Debug)]
+pub struct RecordingKeyValueStore<S> {
+    /// The inner store.
+    store: S,
+    /// The recorder that read preimages are recorded in.
+    recorder: Arc<ReplayRecorder>,
+}
+
+impl<S> RecordingKeyValueStore<S>
+where
+    S: KeyValueStore,
+{
+    /// Create a new [RecordingKeyValueStore], recording the preimages read from `store`.
+    pub const fn new(store: S, recorder: Arc<ReplayRecorder>) -> Self {
+        Self { store, recorder }
+    }
+}
+
+impl<S> KeyValueStore for RecordingKeyValueStore<S>
+where
+    S: KeyValueStore,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let value = self.store.get(key)?;
+        self.recorder.record_preimage(key, &value);
+        Some(value)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.store.set(key, value)
+    }
+}
+
+/// The manifest of a [ReplayBundle].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayManifest {
+    /// The version of the bundle format.
+    pub version: u8,
+    /// The configuration of the host that recorded the bundle, without the addresses of its
+    /// endpoints.
+    pub host: SingleChainHost,
+    /// The error returned by the client program, if it is known to have failed.
+    pub error: Option<String>,
+}
+
+impl ReplayManifest {
+    /// Creates a [ReplayManifest] for the given host configuration. The addresses of the host's
+    /// endpoints are stripped, as they are not needed to replay the bundle, and often carry API
+    /// keys.
+    pub fn new(host: SingleChainHost, error: Option<String>) -> Self {
+        let host = SingleChainHost {
+            l1_node_address: None,
+            l2_node_address: None,
+            l1_beacon_address: None,
+            l1_blob_archiver_address: None,
+            ..host
+        };
+        Self { version: REPLAY_BUNDLE_VERSION, host, error }
+    }
+}
+
+/// A self-contained replay bundle, holding everything needed to re-run the client program offline.
+///
+/// Bundles are tar archives, laid out as:
+/// - `manifest.json`: the [ReplayManifest].
+/// - `hints.log`: the hints sent by the client program, one per line, in order.
+/// - `client.log`: the output of the client program, if it was captured.
+/// - `preimages/<key>`: every preimage served to the client program, including its local boot
+///   inputs.
+#[derive(Debug, Clone)]
+pub struct ReplayBundle {
+    /// The manifest of the bundle.
+    pub manifest: ReplayManifest,
+    /// The preimages served to the client program.
+    pub preimages: BTreeMap<B256, Vec<u8>>,
+    /// The hints sent by the client program, in order.
+    pub hints: Vec<String>,
+    /// The output of the client program, if it was captured.
+    pub client_log: Option<Vec<u8>>,
+}
+
+impl ReplayBundle {
+    /// Re-runs the client program natively, serving it the bundled preimages from an offline
+    /// preimage server. Returns the result of the client program.
+    pub async fn replay(self) -> Result<Result<(), String>> {
+        let host = SingleChainHost {
+            l1_node_address: None,
+            l2_node_address: None,
+            l1_beacon_address: None,
+            l1_blob_archiver_address: None,
+            data_dir: None,
+            preimage_cache: None,
+            native: true,
+            server: false,
+            replay_bundle: None,
+            ..self.manifest.host
+        };
+        let kv_store: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore {
+            store: self.preimages.into_iter().collect(),
+        }));
+
+        Ok(if host.intermediate_claims.is_some() {
+            host.run_native(kv_store, kona_client::single::derive_intermediate_claims)
+                .await?
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        } else {
+            host.run_native(kv_store, kona_client::single::run).await?.map_err(|e| e.to_string())
+        })
+    }
+
+    /// Writes the [ReplayBundle] to a tar file at the given path.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file = File::create(path).map_err(|e| anyhow!("Failed to create {path:?}: {e}"))?;
+        self.write_to(BufWriter::new(file))?.flush()?;
+        Ok(())
+    }
+
+    /// Reads a [ReplayBundle] from the tar file at the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| anyhow!("Failed to open {path:?}: {e}"))?;
+        Self::read_from(BufReader::new(file))
+    }
+
+    /// Writes the [ReplayBundle] as a tar archive to the given writer, returning the writer.
+    ///
+    /// Entries are written in a fixed order with zeroed timestamps, so that the same bundle is
+    /// always written to the same bytes.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<W> {
+        let mut builder = tar::Builder::new(writer);
+
+        append_entry(&mut builder, MANIFEST_PATH, &serde_json::to_vec_pretty(&self.manifest)?)?;
+        append_entry(
+            &mut builder,
+            HINTS_PATH,
+            self.hints.iter().map(|hint| format!("{hint}\n")).collect::<String>().as_bytes(),
+        )?;
+        if let Some(client_log) = &self.client_log {
+            append_entry(&mut builder, CLIENT_LOG_PATH, client_log)?;
+        }
+        for (key, value) in &self.preimages {
+            append_entry(&mut builder, &format!("{PREIMAGES_DIR}{key}"), value)?;
+        }
+
+        Ok(builder.into_inner()?)
+    }
+
+    /// Reads a [ReplayBundle] from a tar archive.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self> {
+        let mut manifest = None;
+        let mut preimages = BTreeMap::new();
+        let mut hints = Vec::new();
+        let mut client_log = None;
+
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_string_lossy().into_owned();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+
+            match path.as_str() {
+                MANIFEST_PATH => manifest = Some(serde_json::from_slice::<ReplayManifest>(&data)?),
+                HINTS_PATH => {
+                    hints = String::from_utf8(data)?.lines().map(ToString::to_string).collect()
+                }
+                CLIENT_LOG_PATH => client_log = Some(data),
+                _ => {
+                    let key = path
+                        .strip_prefix(PREIMAGES_DIR)
+                        .and_then(|key| B256::from_str(key).ok())
+                        .ok_or_else(|| anyhow!("Unexpected entry in replay bundle: {path}"))?;
+                    preimages.insert(key, data);
+                }
+            }
+        }
+
+        let manifest = manifest.ok_or_else(|| anyhow!("Replay bundle is missing its manifest"))?;
+        ensure!(
+            manifest.version == REPLAY_BUNDLE_VERSION,
+            "Unsupported replay bundle version {}, expected {REPLAY_BUNDLE_VERSION}",
+            manifest.version
+        );
+
+        Ok(Self { manifest, preimages, hints, client_log })
+    }
+}
+
+/// Appends a file to a replay bundle's tar archive, with a zeroed timestamp.
+fn append_entry<W: Write>(builder: &mut tar::Builder<W>, path: &str, data: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0);
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_recording_kv_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("replay.tar");
+        let recorder = Arc::new(ReplayRecorder::new(&path).unwrap());
+        let mut kv_store =
+            RecordingKeyValueStore::new(MemoryKeyValueStore::new(), recorder.clone());
+        kv_store.set(B256::repeat_byte(0x01), vec![0x01]).unwrap();
+        kv_store.set(B256::repeat_byte(0x02), vec![0x02]).unwrap();
+
+        // Only preimages that were read are recorded, once each.
+        assert_eq!(kv_store.get(B256::repeat_byte(0x01)).unwrap(), [0x01]);
+        assert_eq!(kv_store.get(B256::repeat_byte(0x01)).unwrap(), [0x01]);
+        assert!(kv_store.get(B256::repeat_byte(0x03)).is_none());
+        recorder.hint_log().lock().unwrap().push("l1-block-header 0x01".to_string());
+
+        // The bundle is only moved into place once the recording is finished.
+        assert!(!path.exists());
+        let host = SingleChainHost {
+            l1_node_address: Some("https://l1.example/secret-key".to_string()),
+            l2_node_address: Some("https://l2.example/secret-key".to_string()),
+            l1_beacon_address: Some("https://beacon.example/secret-key".to_string()),
+            l1_blob_archiver_address: Some("https://archiver.example/secret-key".to_string()),
+            claimed_l2_block_number: 10,
+            ..Default::default()
+        };
+        let manifest = ReplayManifest::new(host, Some("Invalid claim".to_string()));
+        assert_eq!(recorder.finish(&manifest, Some(b"client output")).unwrap(), path);
+        assert!(recorder.finish(&manifest, None).is_err());
+
+        let bundle = ReplayBundle::read(&path).unwrap();
+        assert_eq!(bundle.preimages, BTreeMap::from([(B256::repeat_byte(0x01), vec![0x01])]));
+        assert_eq!(bundle.hints, ["l1-block-header 0x01"]);
+        assert_eq!(bundle.client_log.as_deref(), Some(b"client output".as_slice()));
+        assert_eq!(bundle.manifest.error, manifest.error);
+
+        // The addresses of the host's endpoints are not written to the bundle.
+        let host = bundle.manifest.host;
+        assert_eq!(host.claimed_l2_block_number, 10);
+        assert!(host.l1_node_address.is_none());
+        assert!(host.l2_node_address.is_none());
+        assert!(host.l1_beacon_address.is_none());
+        assert!(host.l1_blob_archiver_address.is_none());
+        assert!(!String::from_utf8_lossy(&std::fs::read(&path).unwrap()).contains("secret-key"));
+    }
+
+    #[test]
+    fn test_client_log_capture() {
+        let log = ClientLog::default();
+        assert!(log.contents().is_none());
+
+        tracing::dispatcher::with_default(&log.dispatch(LevelFilter::INFO), || {
+            tracing::error!(target: "client", "Failed to validate L2 block");
+            tracing::debug!(target: "client", "Filtered out");
+        });
+
+        let contents = String::from_utf8(log.contents().unwrap()).unwrap();
+        assert!(contents.contains("Failed to validate L2 block"));
+        assert!(!contents.contains("Filtered out"));
+    }
+
+    #[test]
+    fn test_replay_bundle_roundtrip() {
+        let bundle = ReplayBundle {
+            manifest: ReplayManifest {
+                version: REPLAY_BUNDLE_VERSION,
+                host: SingleChainHost { claimed_l2_block_number: 10, ..Default::default() },
+                error: Some("Invalid claim".to_string()),
+            },
+            preimages: BTreeMap::from([
+                (B256::repeat_byte(0x01), vec![0x01; 32]),
+                (B256::repeat_byte(0x02), vec![]),
+            ]),
+            hints: vec!["l1-block-header 0x01".to_string(), "l2-payload-witness 0x02".to_string()],
+            client_log: Some(b"client output".to_vec()),
+        };
+
+        let encoded = bundle.write_to(Vec::new()).unwrap();
+        assert_eq!(encoded, bundle.write_to(Vec::new()).unwrap());
+
+        let decoded = ReplayBundle::read_from(encoded.as_slice()).unwrap();
+        assert_eq!(decoded.manifest.host.claimed_l2_block_number, 10);
+        assert_eq!(decoded.manifest.error, bundle.manifest.error);
+        assert_eq!(decoded.preimages, bundle.preimages);
+        assert_eq!(decoded.hints, bundle.hints);
+        assert_eq!(decoded.client_log, bundle.client_log);
+    }
+}
@@ -1,7 +1,9 @@
 //! Contains the node CLI.
 
 use crate::{
-    commands::{BootstoreCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand},
+    commands::{
+        BootstoreCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand, UpgradesCommand,
+    },
     flags::{GlobalArgs, init_unified_metrics},
     version,
 };
@@ -28,6 +30,9 @@ pub enum Commands {
     Bootstore(BootstoreCommand),
     /// Get info about op chain.
     Info(InfoCommand),
+    /// Inspects and verifies the network upgrade transactions of the hardforks.
+    #[command(alias = "u", alias = "upgrade")]
+    Upgrades(UpgradesCommand),
 }
 
 /// The node CLI.
@@ -59,6 +64,7 @@ impl Cli {
             Commands::Registry(ref registry) => registry.init_logs(&self.global)?,
             Commands::Bootstore(ref bootstore) => bootstore.init_logs(&self.global)?,
             Commands::Info(ref info) => info.init_logs(&self.global)?,
+            Commands::Upgrades(ref upgrades) => upgrades.init_logs(&self.global)?,
         }
 
         // Initialize unified metrics
@@ -73,13 +79,19 @@ impl Cli {
         }
 
         // Run the subcommand.
-        match self.subcommand {
+        let result = match self.subcommand {
             Commands::Node(node) => Self::run_until_ctrl_c(node.run(&self.global)),
             Commands::Net(net) => Self::run_until_ctrl_c(net.run(&self.global)),
             Commands::Registry(registry) => registry.run(&self.global),
             Commands::Bootstore(bootstore) => bootstore.run(&self.global),
             Commands::Info(info) => info.run(&self.global),
-        }
+            Commands::Upgrades(upgrades) => Self::run_until_ctrl_c(upgrades.run(&self.global)),
+        };
+
+        // Flush the OTLP exporters, if any, before exiting.
+        kona_cli::shutdown_otlp();
+
+        result
     }
 
     /// Run until ctrl-c is pressed.
@@ -123,6 +135,8 @@ mod tests {
     #[case::bootstore_subcommand_long(Commands::Bootstore(Default::default()), "boot")]
     #[case::bootstore_subcommand_long2(Commands::Bootstore(Default::default()), "store")]
     #[case::info_subcommand(Commands::Info(Default::default()), "info")]
+    #[case::upgrades_subcommand_long(Commands::Upgrades(Default::default()), "upgrades")]
+    #[case::upgrades_subcommand_short(Commands::Upgrades(Default::default()), "u")]
     fn test_parse_cli(#[case] subcommand: Commands, #[case] subcommand_alias: &str) {
         let args = vec!["kona-node", subcommand_alias, "--help"];
         let cli = Cli::parse_from(args);
@@ -3,12 +3,13 @@
 use crate::flags::GlobalArgs;
 use clap::Parser;
 use kona_cli::LogConfig;
-use kona_peers::{BootStore, BootStoreFile};
+use kona_peers::{BootStore, BootStoreFile, PeerDb, PeerDbFile};
 use std::path::PathBuf;
 
 /// The `bootstore` Subcommand
 ///
-/// The `bootstore` subcommand can be used to interact with local bootstores.
+/// The `bootstore` subcommand can be used to interact with local bootstores and peer
+/// databases.
 ///
 /// # Usage
 ///
@@ -25,6 +26,12 @@ pub struct BootstoreCommand {
     /// The directory to store the bootstore.
     #[arg(long = "p2p.bootstore", env = "KONA_NODE_P2P_BOOTSTORE")]
     pub bootstore: Option<PathBuf>,
+    /// The path to the persistent peer database.
+    #[arg(long = "p2p.peerdb", env = "KONA_NODE_P2P_PEERDB")]
+    pub peer_db: Option<PathBuf>,
+    /// Prints the reputation of every peer in the peer database.
+    #[arg(long = "peers")]
+    pub peers: bool,
 }
 
 impl BootstoreCommand {
@@ -67,7 +74,46 @@ impl BootstoreCommand {
         println!("Path: {}", self.bootstore.clone().unwrap_or_default().display());
         println!("Peer Count: {}", bootstore.peers.len());
         println!("Valid peers: {}", bootstore.valid_peers_with_chain_id(chain_id).len());
+        self.peer_db_info(chain_id)?;
         println!("--------------------------");
         Ok(())
     }
+
+    /// Prints information for the peer database with the given chain ID.
+    pub fn peer_db_info(&self, chain_id: u64) -> anyhow::Result<()> {
+        let file: PeerDbFile =
+            self.peer_db.clone().map_or(PeerDbFile::Default { chain_id }, PeerDbFile::Custom);
+        let path: PathBuf = file.try_into()?;
+        if !path.exists() {
+            println!("Peer DB: not found at {}", path.display());
+            return Ok(());
+        }
+        let db = PeerDb::open_read_only(&path)?;
+        println!("Peer DB Path: {}", path.display());
+        let banned = db.banned_peers();
+        println!("Peer DB Peer Count: {}", db.len());
+        println!("Peer DB Banned Peers: {}", banned.len());
+        for (peer_id, remaining) in &banned {
+            println!("  {peer_id} (ban expires in {}s)", remaining.as_secs());
+        }
+        if self.peers {
+            let mut peers = db.peers.iter().collect::<Vec<_>>();
+            peers.sort_by(|(_, a), (_, b)| b.last_seen.cmp(&a.last_seen));
+            for (peer_id, record) in peers {
+                println!(
+                    "  {peer_id} | score: {} | successes: {} | failures: {} | last seen: {} at {} | topics: {}",
+                    record.last_score().map_or_else(|| "-".to_string(), |s| format!("{s:.2}")),
+                    record.successes,
+                    record.failures,
+                    record.last_seen,
+                    record
+                        .last_seen_addr
+                        .as_ref()
+                        .map_or_else(|| "-".to_string(), |a| a.to_string()),
+                    record.topics.len(),
+                );
+            }
+        }
+        Ok(())
+    }
 }
@@ -285,6 +285,10 @@ impl NodeCommand {
             trust_rpc: self.l1_rpc_args.l1_trust_rpc,
             beacon: self.l1_rpc_args.l1_beacon.clone(),
             rpc_url: self.l1_rpc_args.l1_eth_rpc.clone(),
+            beacon_fallbacks: self.l1_rpc_args.l1_beacon_fallbacks.clone(),
+            rpc_fallbacks: self.l1_rpc_args.l1_eth_rpc_fallbacks.clone(),
+            blob_archiver: self.l1_rpc_args.l1_blob_archiver.clone(),
+            blob_dir: self.l1_rpc_args.l1_blob_dir.clone(),
             slot_duration_override: self.l1_rpc_args.l1_slot_duration_override,
         };
 
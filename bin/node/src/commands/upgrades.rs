@@ -0,0 +1,138 @@
+//! Upgrades Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_provider::{Provider, RootProvider};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_hardforks::{
+    NetworkUpgrade, UpgradeIntent, UpgradeTxInfo, UpgradeTxStatus, verify_upgrade_txs,
+};
+use kona_registry::ROLLUP_CONFIGS;
+use op_alloy_network::Optimism;
+use tracing::info;
+use url::Url;
+
+/// The `upgrades` Subcommand
+///
+/// The `upgrades` subcommand prints the network upgrade transactions of each hardfork along with
+/// their decoded intent. If an L2 RPC is provided, the upgrade transactions are checked against
+/// the activation block of the L2 chain.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node upgrades --fork fjord --l2-rpc http://localhost:8545
+/// ```
+#[derive(Parser, Default, PartialEq, Debug, Clone)]
+#[command(about = "Inspects and verifies the network upgrade transactions of the hardforks.")]
+pub struct UpgradesCommand {
+    /// The network upgrade to inspect. If not set, all network upgrades are inspected.
+    #[arg(long = "fork")]
+    pub fork: Option<NetworkUpgrade>,
+    /// The L2 execution RPC to verify the upgrade transactions against.
+    #[arg(long = "l2-rpc", env = "KONA_NODE_L2_RPC")]
+    pub l2_rpc: Option<Url>,
+}
+
+impl UpgradesCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Inspects the network upgrade transactions, verifying them against the L2 chain if an L2
+    /// RPC is provided.
+    pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        info!(target: "upgrades", "Running upgrades command");
+
+        let upgrades = self.fork.map_or_else(|| NetworkUpgrade::ALL.to_vec(), |fork| vec![fork]);
+        let Some(l2_rpc) = self.l2_rpc else {
+            for upgrade in upgrades {
+                println!("{upgrade}:");
+                upgrade.inspect().iter().for_each(print_upgrade_tx);
+                println!("-------------");
+            }
+            return Ok(());
+        };
+
+        let rollup_config = ROLLUP_CONFIGS.get(&args.l2_chain_id.id()).ok_or_else(|| {
+            anyhow::anyhow!("No rollup config found for chain {}", args.l2_chain_id)
+        })?;
+        let provider = RootProvider::<Optimism>::new_http(l2_rpc);
+
+        let mut failures = 0;
+        for upgrade in upgrades {
+            let Some(number) = upgrade.activation_block(rollup_config) else {
+                println!("{upgrade}: not activated after genesis, skipping");
+                println!("-------------");
+                continue;
+            };
+            println!("{upgrade} (activation block {number}):");
+
+            let Some(block) = provider.get_block_by_number(number.into()).full().await? else {
+                println!("  activation block {number} not found, skipping");
+                println!("-------------");
+                continue;
+            };
+            let block_deposits = block
+                .transactions
+                .as_transactions()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|tx| tx.inner.inner.inner().as_deposit().map(|d| d.inner().clone()))
+                .collect::<Vec<_>>();
+
+            let expected = upgrade.deposits();
+            let statuses = verify_upgrade_txs(&expected, &block_deposits);
+            for (tx, status) in upgrade.inspect().iter().zip(statuses) {
+                print_upgrade_tx(tx);
+                let mut ok = matches!(status, UpgradeTxStatus::Included(_));
+                println!("    status: {status:?}");
+
+                if ok && let UpgradeIntent::Deploy { contract, .. } = tx.intent {
+                    let deployed = provider
+                        .get_transaction_receipt(tx.tx_hash)
+                        .await?
+                        .and_then(|receipt| receipt.inner.contract_address);
+                    ok = deployed == Some(contract);
+                    if !ok {
+                        println!("    deployed contract: {deployed:?}, expected: {contract}");
+                    }
+                }
+                failures += usize::from(!ok);
+            }
+            println!("-------------");
+        }
+
+        if failures > 0 {
+            anyhow::bail!("{failures} upgrade transaction(s) failed verification");
+        }
+        Ok(())
+    }
+}
+
+/// Prints a decoded upgrade transaction.
+fn print_upgrade_tx(tx: &UpgradeTxInfo) {
+    println!("  tx {}", tx.tx_hash);
+    println!("    source hash: {}", tx.source_hash);
+    println!("    from: {}", tx.from);
+    println!("    gas limit: {}", tx.gas_limit);
+    match tx.intent {
+        UpgradeIntent::Deploy { deployer, contract, bytecode_hash } => {
+            println!("    deploy: {contract} (deployer {deployer}, bytecode hash {bytecode_hash})");
+        }
+        UpgradeIntent::UpgradeProxy { proxy, implementation } => {
+            println!("    upgrade proxy: {proxy} -> {implementation}");
+        }
+        UpgradeIntent::Call { target, selector } => match selector {
+            Some(selector) => {
+                println!(
+                    "    call: {target} (selector 0x{})",
+                    alloy_primitives::hex::encode(selector)
+                )
+            }
+            None => println!("    call: {target}"),
+        },
+    }
+}
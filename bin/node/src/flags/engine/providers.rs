@@ -52,6 +52,18 @@ pub struct L1ClientArgs {
     /// URL of the L1 execution client RPC API.
     #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
     pub l1_eth_rpc: Url,
+    /// URLs of fallback L1 execution client RPC APIs, comma separated.
+    ///
+    /// Derivation data is fetched from the healthiest of the primary and fallback RPCs, failing
+    /// over to the others on errors. If the L1 RPC is not trusted, blocks fetched by number are
+    /// cross-checked between two RPCs.
+    #[arg(
+        long,
+        visible_alias = "l1.fallbacks",
+        env = "KONA_NODE_L1_ETH_RPC_FALLBACKS",
+        value_delimiter = ','
+    )]
+    pub l1_eth_rpc_fallbacks: Vec<Url>,
     /// Whether to trust the L1 RPC.
     /// If false, block hash verification is performed for all retrieved blocks.
     #[arg(
@@ -64,6 +76,29 @@ pub struct L1ClientArgs {
     /// URL of the L1 beacon API.
     #[arg(long, visible_alias = "l1.beacon", env = "KONA_NODE_L1_BEACON")]
     pub l1_beacon: Url,
+    /// URLs of fallback L1 beacon APIs, comma separated.
+    ///
+    /// Blobs are fetched from the healthiest of the primary and fallback beacon APIs, failing
+    /// over to the others on errors or missing blobs.
+    #[arg(
+        long,
+        visible_alias = "l1.beacon-fallbacks",
+        env = "KONA_NODE_L1_BEACON_FALLBACKS",
+        value_delimiter = ','
+    )]
+    pub l1_beacon_fallbacks: Vec<Url>,
+    /// URL of a blob archiver API.
+    ///
+    /// Blobs that are no longer served by the L1 beacon APIs, past their retention window, are
+    /// fetched from the blob archiver.
+    #[arg(long, visible_alias = "l1.blob-archiver", env = "KONA_NODE_L1_BLOB_ARCHIVER")]
+    pub l1_blob_archiver: Option<Url>,
+    /// Path to a local blob directory.
+    ///
+    /// Blobs that are no longer served by the L1 beacon APIs or the blob archiver are read from
+    /// the directory, and blobs fetched from the blob archiver are cached in it.
+    #[arg(long, visible_alias = "l1.blob-dir", env = "KONA_NODE_L1_BLOB_DIR")]
+    pub l1_blob_dir: Option<PathBuf>,
     /// Duration in seconds of an L1 slot.
     ///
     /// This is an optional argument that can be used to use a fixed slot duration for l1 blocks
@@ -81,8 +116,12 @@ impl Default for L1ClientArgs {
     fn default() -> Self {
         Self {
             l1_eth_rpc: Url::parse("http://localhost:8545").unwrap(),
+            l1_eth_rpc_fallbacks: Vec::new(),
             l1_trust_rpc: DEFAULT_L1_TRUST_RPC,
             l1_beacon: Url::parse("http://localhost:5052").unwrap(),
+            l1_beacon_fallbacks: Vec::new(),
+            l1_blob_archiver: None,
+            l1_blob_dir: None,
             l1_slot_duration_override: None,
         }
     }
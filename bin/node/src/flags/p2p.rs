@@ -16,7 +16,7 @@ use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
 use kona_gossip::GaterConfig;
 use kona_node_service::NetworkConfig;
-use kona_peers::{BootNode, BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BootNode, BootStoreFile, PeerDbFile, PeerMonitoring, PeerScoreLevel};
 use kona_providers_alloy::AlloyChainProvider;
 use libp2p::identity::Keypair;
 use std::{
@@ -158,6 +158,13 @@ pub struct P2PArgs {
     /// Disables the bootstore.
     #[arg(long = "p2p.no-bootstore", env = "KONA_NODE_P2P_NO_BOOTSTORE")]
     pub disable_bootstore: bool,
+    /// The path to the persistent peer database, which records peer scores, bans, addresses,
+    /// topics and connection outcomes across restarts.
+    #[arg(long = "p2p.peerdb", env = "KONA_NODE_P2P_PEERDB")]
+    pub peer_db: Option<PathBuf>,
+    /// Disables the persistent peer database.
+    #[arg(long = "p2p.no-peerdb", env = "KONA_NODE_P2P_NO_PEERDB")]
+    pub disable_peer_db: bool,
     /// Peer Redialing threshold is the maximum amount of times to attempt to redial a peer that
     /// disconnects. By default, peers are *not* redialed. If set to 0, the peer will be
     /// redialed indefinitely.
@@ -174,6 +181,15 @@ pub struct P2PArgs {
     #[arg(long = "p2p.bootnodes", value_delimiter = ',', env = "KONA_NODE_P2P_BOOTNODES")]
     pub bootnodes: Vec<String>,
 
+    /// An optional list of trusted sentry peer multiaddrs, each including the `/p2p/<peer id>`
+    /// component.
+    ///
+    /// If set, the node runs in sentry mode: it only connects and publishes to the sentry peers,
+    /// denies connections from any other peer, and disables discovery so that its address is
+    /// never advertised.
+    #[arg(long = "p2p.sentry-peers", value_delimiter = ',', env = "KONA_NODE_P2P_SENTRY_PEERS")]
+    pub sentry_peers: Vec<libp2p::Multiaddr>,
+
     /// Optionally enable topic scoring.
     ///
     /// Topic scoring is a mechanism to score peers based on their behavior in the gossip network.
@@ -396,6 +412,15 @@ impl P2PArgs {
             ))
         };
 
+        let peer_db = if self.disable_peer_db {
+            None
+        } else {
+            Some(self.peer_db.map_or(
+                PeerDbFile::Default { chain_id: args.l2_chain_id.into() },
+                PeerDbFile::Custom,
+            ))
+        };
+
         let bootnodes = self
             .bootnodes
             .iter()
@@ -416,10 +441,12 @@ impl P2PArgs {
             scoring: self.scoring,
             monitor_peers,
             bootstore,
+            peer_db,
             topic_scoring: self.topic_scoring,
             gater_config: GaterConfig {
                 peer_redialing: self.peer_redial,
                 dial_period: Duration::from_secs(60 * self.redial_period),
+                sentry_peers: self.sentry_peers,
             },
             bootnodes,
             rollup_config: config.clone(),
@@ -520,6 +547,18 @@ mod tests {
         assert!(args.p2p.no_discovery);
     }
 
+    #[test]
+    fn test_p2p_args_sentry_peers() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.sentry-peers",
+            "/ip4/10.0.0.1/tcp/9222/p2p/16Uiu2HAmUSo81N6iNQNKZCiqDAg5Mcmh9gwvPgKmKj1HH6qCR4Kq,/ip4/10.0.0.2/tcp/9222/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp",
+        ]);
+        assert_eq!(args.p2p.sentry_peers.len(), 2);
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.p2p.sentry_peers.is_empty());
+    }
+
     #[test]
     fn test_p2p_args_priv_path() {
         let args = MockCommand::parse_from(["test", "--p2p.priv.path", "test.txt"]);
@@ -4,8 +4,8 @@
 //!
 //! [op-node]: https://github.com/ethereum-optimism/optimism/blob/develop/op-node/flags/flags.go#L233-L265
 
-use clap::Parser;
-use kona_node_service::SequencerConfig;
+use clap::{Parser, ValueEnum};
+use kona_node_service::{OriginSelectionStrategy, SequencerConfig};
 use std::{num::ParseIntError, time::Duration};
 use url::Url;
 
@@ -35,6 +35,53 @@ pub struct SequencerArgs {
     #[arg(long = "sequencer.l1-confs", default_value = "4", env = "KONA_NODE_SEQUENCER_L1_CONFS")]
     pub l1_confs: u64,
 
+    /// The strategy used by the sequencer to select the next L1 origin.
+    #[arg(
+        long = "sequencer.origin-strategy",
+        value_enum,
+        default_value_t = OriginStrategyKind::Default,
+        env = "KONA_NODE_SEQUENCER_ORIGIN_STRATEGY"
+    )]
+    pub origin_strategy: OriginStrategyKind,
+
+    /// Minimum number of confirmations for an L1 block to be selected as an L1 origin.
+    /// Only used by the `confirmations` origin strategy.
+    #[arg(
+        long = "sequencer.origin-confirmations",
+        default_value = "4",
+        env = "KONA_NODE_SEQUENCER_ORIGIN_CONFIRMATIONS"
+    )]
+    pub origin_confirmations: u64,
+
+    /// Maximum number of L1 blocks the L1 origin may lag behind the L1 head before the
+    /// confirmation delay is bypassed. Only used by the `max-lag` origin strategy.
+    #[arg(
+        long = "sequencer.origin-max-lag",
+        default_value = "16",
+        env = "KONA_NODE_SEQUENCER_ORIGIN_MAX_LAG"
+    )]
+    pub origin_max_lag: u64,
+
+    /// Maximum age of the L1 head, in seconds, before the L1 provider is considered stale.
+    /// Only used by the `stale-backoff` origin strategy.
+    #[arg(
+        long = "sequencer.origin-stale-threshold",
+        default_value = "60",
+        env = "KONA_NODE_SEQUENCER_ORIGIN_STALE_THRESHOLD",
+        value_parser = |arg: &str| -> Result<Duration, ParseIntError> {Ok(Duration::from_secs(arg.parse()?))}
+    )]
+    pub origin_stale_threshold: Duration,
+
+    /// Duration, in seconds, to back off from advancing the L1 origin after stale L1 data is
+    /// observed. Only used by the `stale-backoff` origin strategy.
+    #[arg(
+        long = "sequencer.origin-stale-backoff",
+        default_value = "12",
+        env = "KONA_NODE_SEQUENCER_ORIGIN_STALE_BACKOFF",
+        value_parser = |arg: &str| -> Result<Duration, ParseIntError> {Ok(Duration::from_secs(arg.parse()?))}
+    )]
+    pub origin_stale_backoff: Duration,
+
     /// Forces the sequencer to strictly prepare the next L1 origin and create empty L2 blocks
     #[arg(
         long = "sequencer.recover",
@@ -57,6 +104,20 @@ pub struct SequencerArgs {
     pub conductor_rpc_timeout: Duration,
 }
 
+/// The kind of [`OriginSelectionStrategy`] used by the sequencer.
+#[derive(ValueEnum, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OriginStrategyKind {
+    /// Select the next L1 origin as soon as it is past the confirmation delay.
+    #[default]
+    Default,
+    /// Only select L1 origins with a minimum number of confirmations.
+    Confirmations,
+    /// Bypass the confirmation delay when the L1 origin lags too far behind the L1 head.
+    MaxLag,
+    /// Back off from advancing the L1 origin when the L1 provider serves stale data.
+    StaleBackoff,
+}
+
 impl Default for SequencerArgs {
     fn default() -> Self {
         // Construct default values using the clap parser.
@@ -73,6 +134,64 @@ impl SequencerArgs {
             sequencer_recovery_mode: self.recover,
             conductor_rpc_url: self.conductor_rpc.clone(),
             l1_conf_delay: self.l1_confs,
+            origin_selection_strategy: self.origin_selection_strategy(),
+        }
+    }
+
+    /// Returns the [`OriginSelectionStrategy`] configured by the [`SequencerArgs`].
+    pub const fn origin_selection_strategy(&self) -> OriginSelectionStrategy {
+        match self.origin_strategy {
+            OriginStrategyKind::Default => OriginSelectionStrategy::Default,
+            OriginStrategyKind::Confirmations => {
+                OriginSelectionStrategy::Confirmations { depth: self.origin_confirmations }
+            }
+            OriginStrategyKind::MaxLag => {
+                OriginSelectionStrategy::MaxLag { max_lag: self.origin_max_lag }
+            }
+            OriginStrategyKind::StaleBackoff => OriginSelectionStrategy::StaleBackoff {
+                max_head_age: self.origin_stale_threshold.as_secs(),
+                backoff: self.origin_stale_backoff.as_secs(),
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_strategy_default() {
+        let args = SequencerArgs::default();
+        assert_eq!(args.origin_selection_strategy(), OriginSelectionStrategy::Default);
+    }
+
+    #[test]
+    fn test_origin_strategy_from_flags() {
+        let args = SequencerArgs::parse_from([
+            "test",
+            "--sequencer.origin-strategy",
+            "stale-backoff",
+            "--sequencer.origin-stale-threshold",
+            "30",
+            "--sequencer.origin-stale-backoff",
+            "6",
+        ]);
+        assert_eq!(
+            args.origin_selection_strategy(),
+            OriginSelectionStrategy::StaleBackoff { max_head_age: 30, backoff: 6 }
+        );
+
+        let args = SequencerArgs::parse_from([
+            "test",
+            "--sequencer.origin-strategy",
+            "confirmations",
+            "--sequencer.origin-confirmations",
+            "10",
+        ]);
+        assert_eq!(
+            args.origin_selection_strategy(),
+            OriginSelectionStrategy::Confirmations { depth: 10 }
+        );
+    }
+}
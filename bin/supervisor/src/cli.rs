@@ -33,7 +33,7 @@ impl Cli {
 
         self.init_logs(&self.global)?;
 
-        Self::run_until_ctrl_c(async move {
+        let result = Self::run_until_ctrl_c(async move {
             let config = self.supervisor.init_config().await?;
             let mut service = Service::new(config);
 
@@ -51,7 +51,12 @@ impl Cli {
             service.shutdown().await?; // Call shutdown on the service instance itself
             info!(target: "supervisor", "Supervisor service shut down gracefully.");
             Ok(())
-        })
+        });
+
+        // Flush the OTLP exporters, if any, before exiting.
+        kona_cli::shutdown_otlp();
+
+        result
     }
 
     /// Run until ctrl-c is pressed.
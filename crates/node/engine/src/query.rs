@@ -37,6 +37,13 @@ pub enum EngineQueries {
         /// Response channel for (block_info, output_root, engine_state).
         sender: Sender<(L2BlockInfo, OutputRoot, EngineState)>,
     },
+    /// Request the [`L2BlockInfo`] of a specific block.
+    BlockInfo {
+        /// The block number or tag to retrieve the block info for.
+        block: BlockNumberOrTag,
+        /// Response channel for the block info.
+        sender: Sender<L2BlockInfo>,
+    },
     /// Subscribe to engine state updates via a watch channel receiver.
     StateReceiver(Sender<tokio::sync::watch::Receiver<EngineState>>),
     /// Development API: Subscribe to task queue length updates.
@@ -125,6 +132,13 @@ impl EngineQueries {
                     .send((output_block_info, output_response_v0, state))
                     .map_err(|_| EngineQueriesError::OutputChannelClosed)
             }
+            Self::BlockInfo { block, sender } => {
+                let block_info = client
+                    .l2_block_info_by_label(block)
+                    .await?
+                    .ok_or(EngineQueriesError::NoL2BlockFound(block))?;
+                sender.send(block_info).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
             Self::StateReceiver(subscription) => subscription
                 .send(state_recv.clone())
                 .map_err(|_| EngineQueriesError::OutputChannelClosed),
@@ -5,16 +5,16 @@ use crate::{
     state::EngineSyncStateUpdate, task_queue::build_and_seal,
 };
 use async_trait::async_trait;
-use derive_more::Constructor;
 use kona_genesis::RollupConfig;
 use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
 use std::{sync::Arc, time::Instant};
+use tracing::Span;
 
 /// The [`ConsolidateTask`] attempts to consolidate the engine state
 /// using the specified payload attributes and the oldest unsafe head.
 ///
 /// If consolidation fails, payload attributes processing is attempted using `build_and_seal`.
-#[derive(Debug, Clone, Constructor)]
+#[derive(Debug, Clone)]
 pub struct ConsolidateTask<EngineClient_: EngineClient> {
     /// The engine client.
     pub client: Arc<EngineClient_>,
@@ -24,9 +24,27 @@ pub struct ConsolidateTask<EngineClient_: EngineClient> {
     pub attributes: OpAttributesWithParent,
     /// Whether or not the payload was derived, or created by the sequencer.
     pub is_attributes_derived: bool,
+    /// The span that the attributes were derived in, if known. The task's span links to it.
+    pub derivation_span: Option<Span>,
 }
 
 impl<EngineClient_: EngineClient> ConsolidateTask<EngineClient_> {
+    /// Creates a new [`ConsolidateTask`].
+    pub const fn new(
+        client: Arc<EngineClient_>,
+        cfg: Arc<RollupConfig>,
+        attributes: OpAttributesWithParent,
+        is_attributes_derived: bool,
+    ) -> Self {
+        Self { client, cfg, attributes, is_attributes_derived, derivation_span: None }
+    }
+
+    /// Sets the span that the attributes were derived in.
+    pub fn with_derivation_span(mut self, span: Span) -> Self {
+        self.derivation_span = Some(span);
+        self
+    }
+
     /// This is used when the [`ConsolidateTask`] fails to consolidate the engine state.
     async fn execute_build_and_seal_tasks(
         &self,
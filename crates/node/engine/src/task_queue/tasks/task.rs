@@ -10,9 +10,11 @@ use crate::{
 };
 use async_trait::async_trait;
 use derive_more::Display;
+use kona_protocol::OpAttributesWithParent;
 use std::cmp::Ordering;
 use thiserror::Error;
 use tokio::task::yield_now;
+use tracing::{Instrument, Span};
 
 /// The severity of an engine task error.
 ///
@@ -122,6 +124,86 @@ impl<EngineClient_: EngineClient> EngineTask<EngineClient_> {
         Ok(())
     }
 
+    /// Executes the task, retrying it until it succeeds or a non-temporary error occurs.
+    async fn execute_with_retries(&self, state: &mut EngineState) -> Result<(), EngineTaskErrors> {
+        // Retry the task until it succeeds or a critical error occurs.
+        while let Err(e) = self.execute_inner(state).await {
+            let severity = e.severity();
+
+            kona_macros::inc!(
+                counter,
+                crate::Metrics::ENGINE_TASK_FAILURE,
+                self.task_metrics_label() => severity.to_string()
+            );
+
+            match severity {
+                EngineTaskErrorSeverity::Temporary => {
+                    trace!(target: "engine", "{e}");
+
+                    // Yield the task to allow other tasks to execute to avoid starvation.
+                    yield_now().await;
+
+                    continue;
+                }
+                EngineTaskErrorSeverity::Critical => {
+                    error!(target: "engine", "{e}");
+                    return Err(e);
+                }
+                EngineTaskErrorSeverity::Reset => {
+                    warn!(target: "engine", "Engine requested derivation reset");
+                    return Err(e);
+                }
+                EngineTaskErrorSeverity::Flush => {
+                    warn!(target: "engine", "Engine requested derivation flush");
+                    return Err(e);
+                }
+            }
+        }
+
+        kona_macros::inc!(counter, crate::Metrics::ENGINE_TASK_SUCCESS, self.task_metrics_label());
+
+        Ok(())
+    }
+
+    /// Returns the attributes the task operates on, if any.
+    fn attributes(&self) -> Option<&OpAttributesWithParent> {
+        match self {
+            Self::Build(task) => Some(&task.attributes),
+            Self::Seal(task) => Some(&task.attributes),
+            Self::Consolidate(task) => Some(&task.attributes),
+            Self::Insert(_) | Self::Finalize(_) => None,
+        }
+    }
+
+    /// Returns the tracing span for the task.
+    ///
+    /// Tasks that operate on payload attributes record the attributes' parent, timestamp and the
+    /// L1 block they were derived from. If the attributes were derived in a known span, the task's
+    /// span follows from it, which OpenTelemetry exporters record as a span link.
+    fn span(&self) -> Span {
+        let span = info_span!(
+            target: "engine",
+            "engine_task",
+            kind = self.task_metrics_label(),
+            attributes.parent = tracing::field::Empty,
+            attributes.timestamp = tracing::field::Empty,
+            attributes.l1_origin = tracing::field::Empty,
+            derived_from = tracing::field::Empty,
+        );
+        if let Some(attributes) = self.attributes() {
+            span.record("attributes.parent", attributes.parent.block_info.number);
+            span.record("attributes.timestamp", attributes.attributes.payload_attributes.timestamp);
+            span.record("attributes.l1_origin", attributes.parent.l1_origin.number);
+            span.record("derived_from", attributes.derived_from.map(|b| b.number));
+        }
+        if let Self::Consolidate(task) = self &&
+            let Some(derivation_span) = &task.derivation_span
+        {
+            span.follows_from(derivation_span);
+        }
+        span
+    }
+
     const fn task_metrics_label(&self) -> &'static str {
         match self {
             Self::Insert(_) => crate::Metrics::INSERT_TASK_LABEL,
@@ -201,42 +283,77 @@ impl<EngineClient_: EngineClient> EngineTaskExt for EngineTask<EngineClient_> {
     type Error = EngineTaskErrors;
 
     async fn execute(&self, state: &mut EngineState) -> Result<(), Self::Error> {
-        // Retry the task until it succeeds or a critical error occurs.
-        while let Err(e) = self.execute_inner(state).await {
-            let severity = e.severity();
+        let span = self.span();
+        self.execute_with_retries(state).instrument(span).await
+    }
+}
 
-            kona_macros::inc!(
-                counter,
-                crate::Metrics::ENGINE_TASK_FAILURE,
-                self.task_metrics_label() => severity.to_string()
-            );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ConsolidateTask,
+        test_utils::{TestAttributesBuilder, test_engine_client_builder},
+    };
+    use kona_genesis::RollupConfig;
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+    };
+    use tracing::{
+        Event, Id, Metadata, Subscriber,
+        span::{Attributes, Record},
+    };
 
-            match severity {
-                EngineTaskErrorSeverity::Temporary => {
-                    trace!(target: "engine", "{e}");
+    /// A [`Subscriber`] that records the links between spans.
+    #[derive(Debug, Clone, Default)]
+    struct LinkRecorder {
+        next_id: Arc<AtomicU64>,
+        links: Arc<Mutex<Vec<(u64, u64)>>>,
+    }
 
-                    // Yield the task to allow other tasks to execute to avoid starvation.
-                    yield_now().await;
+    impl Subscriber for LinkRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
 
-                    continue;
-                }
-                EngineTaskErrorSeverity::Critical => {
-                    error!(target: "engine", "{e}");
-                    return Err(e);
-                }
-                EngineTaskErrorSeverity::Reset => {
-                    warn!(target: "engine", "Engine requested derivation reset");
-                    return Err(e);
-                }
-                EngineTaskErrorSeverity::Flush => {
-                    warn!(target: "engine", "Engine requested derivation flush");
-                    return Err(e);
-                }
-            }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(self.next_id.fetch_add(1, AtomicOrdering::Relaxed) + 1)
         }
 
-        kona_macros::inc!(counter, crate::Metrics::ENGINE_TASK_SUCCESS, self.task_metrics_label());
+        fn record(&self, _: &Id, _: &Record<'_>) {}
 
-        Ok(())
+        fn record_follows_from(&self, span: &Id, follows: &Id) {
+            self.links.lock().unwrap().push((span.into_u64(), follows.into_u64()));
+        }
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_consolidate_task_span_links_derivation_span() {
+        let recorder = LinkRecorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let derivation_span = info_span!("derive_attributes");
+            let task = EngineTask::Consolidate(Box::new(
+                ConsolidateTask::new(
+                    Arc::new(test_engine_client_builder().build()),
+                    Arc::new(RollupConfig::default()),
+                    TestAttributesBuilder::new().build(),
+                    true,
+                )
+                .with_derivation_span(derivation_span.clone()),
+            ));
+
+            let span = task.span();
+            assert_eq!(
+                *recorder.links.lock().unwrap(),
+                vec![(span.id().unwrap().into_u64(), derivation_span.id().unwrap().into_u64())]
+            );
+        });
     }
 }
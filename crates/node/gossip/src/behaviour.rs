@@ -2,8 +2,10 @@
 
 use derive_more::Debug;
 use libp2p::{
+    PeerId,
+    allow_block_list::{self, AllowedPeers},
     gossipsub::{Config, IdentTopic, MessageAuthenticity},
-    swarm::NetworkBehaviour,
+    swarm::{NetworkBehaviour, behaviour::toggle::Toggle},
 };
 
 use crate::{Event, Handler};
@@ -38,6 +40,10 @@ pub struct Behaviour {
     /// See `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
     #[debug(skip)]
     pub sync_req_resp: libp2p_stream::Behaviour,
+    /// Denies connections to and from peers outside of the sentry set before they are
+    /// established. Only enabled in sentry mode.
+    #[debug(skip)]
+    pub sentry: Toggle<allow_block_list::Behaviour<AllowedPeers>>,
 }
 
 impl Behaviour {
@@ -84,7 +90,20 @@ impl Behaviour {
             tracing::info!(target: "gossip", "-> {}", topic);
         }
 
-        Ok(Self { identify, ping, gossipsub, sync_req_resp })
+        Ok(Self { identify, ping, gossipsub, sync_req_resp, sentry: Toggle::from(None) })
+    }
+
+    /// Enables sentry mode, only allowing connections to and from the given sentry peers.
+    ///
+    /// Connections with any other peer are denied by the swarm before they are established, so
+    /// unknown peers never complete a dial or an inbound connection.
+    pub fn with_sentry_peers(mut self, peers: impl IntoIterator<Item = PeerId>) -> Self {
+        let mut sentry = allow_block_list::Behaviour::<AllowedPeers>::default();
+        for peer in peers {
+            sentry.allow_peer(peer);
+        }
+        self.sentry = Toggle::from(Some(sentry));
+        self
     }
 }
 
@@ -129,4 +148,14 @@ mod tests {
         topics.sort();
         assert_eq!(topics, op_mainnet_topics());
     }
+
+    #[test]
+    fn test_behaviour_with_sentry_peers() {
+        let key = libp2p::identity::Keypair::generate_secp256k1();
+        let behaviour = Behaviour::new(key.public(), config::default_config(), &[]).unwrap();
+        assert!(!behaviour.sentry.is_enabled());
+
+        let behaviour = behaviour.with_sentry_peers([PeerId::random()]);
+        assert!(behaviour.sentry.is_enabled());
+    }
 }
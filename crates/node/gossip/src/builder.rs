@@ -2,7 +2,7 @@
 
 use alloy_primitives::Address;
 use kona_genesis::RollupConfig;
-use kona_peers::{PeerMonitoring, PeerScoreLevel};
+use kona_peers::{PeerDb, PeerDbFile, PeerMonitoring, PeerScoreLevel};
 use libp2p::{
     Multiaddr, StreamProtocol, SwarmBuilder, gossipsub::Config, identity::Keypair,
     noise::Config as NoiseConfig, tcp::Config as TcpConfig, yamux::Config as YamuxConfig,
@@ -10,7 +10,9 @@ use libp2p::{
 use std::time::Duration;
 use tokio::sync::watch::{self};
 
-use crate::{Behaviour, BlockHandler, GaterConfig, GossipDriver, GossipDriverBuilderError};
+use crate::{
+    Behaviour, BlockHandler, ConnectionGate, GaterConfig, GossipDriver, GossipDriverBuilderError,
+};
 
 /// A builder for the [`GossipDriver`].
 #[derive(Debug)]
@@ -32,6 +34,8 @@ pub struct GossipDriverBuilder {
     /// If set, the gossip layer will monitor peer scores and ban peers that are below a given
     /// threshold.
     peer_monitoring: Option<PeerMonitoring>,
+    /// The persistent peer database file. If unset, peer reputation is not persisted.
+    peer_db: Option<PeerDbFile>,
     /// The configuration for the connection gater.
     gater_config: Option<GaterConfig>,
     /// Topic scoring. Disabled by default.
@@ -54,6 +58,7 @@ impl GossipDriverBuilder {
             scoring: None,
             config: None,
             peer_monitoring: None,
+            peer_db: None,
             gater_config: None,
             rollup_config,
             topic_scoring: false,
@@ -92,6 +97,12 @@ impl GossipDriverBuilder {
         self
     }
 
+    /// Sets the [`PeerDbFile`] used to persist peer reputation across restarts.
+    pub fn with_peer_db(mut self, peer_db: Option<PeerDbFile>) -> Self {
+        self.peer_db = peer_db;
+        self
+    }
+
     /// Sets the unsafe block signer [`Address`].
     pub const fn with_unsafe_block_signer_receiver(mut self, signer: Address) -> Self {
         self.signer = signer;
@@ -164,6 +175,16 @@ impl GossipDriverBuilder {
         );
         let mut behaviour = Behaviour::new(keypair.public(), config, &[Box::new(handler.clone())])?;
 
+        let gater_config = self.gater_config.take().unwrap_or_default();
+        let mut gate = crate::ConnectionGater::new(gater_config);
+
+        // In sentry mode, connections with peers outside of the sentry set are denied by the
+        // swarm before they are established.
+        if gate.is_sentry_mode() {
+            info!(target: "gossip", peers = gate.sentry_peers.len(), "Sentry mode enabled");
+            behaviour = behaviour.with_sentry_peers(gate.sentry_peers.iter().copied());
+        }
+
         // If peer scoring is configured, set it on the behaviour.
         match self.scoring {
             None => info!(target: "scoring", "Peer scoring not enabled"),
@@ -213,9 +234,24 @@ impl GossipDriverBuilder {
             .with_swarm_config(|c| c.with_idle_connection_timeout(timeout))
             .build();
 
-        let gater_config = self.gater_config.take().unwrap_or_default();
-        let gate = crate::ConnectionGater::new(gater_config);
+        // Load the peer database, and block peers whose ban has not yet expired.
+        let peer_db = self.peer_db.take().map(|file| {
+            TryInto::<PeerDb>::try_into(file).unwrap_or_else(|e| {
+                warn!(target: "gossip", "Failed to open peer database, using an in-memory one: {:?}", e);
+                PeerDb::default()
+            })
+        });
+        if let Some(db) = &peer_db {
+            let banned = db.banned_peers();
+            info!(target: "gossip", peers = db.len(), banned = banned.len(), "Loaded peer database");
+            for (peer_id, _) in banned {
+                gate.block_peer(&peer_id);
+            }
+        }
+
+        let mut driver = GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate);
+        driver.peer_db = peer_db;
 
-        Ok((GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate), signer_tx))
+        Ok((driver, signer_tx))
     }
 }
@@ -5,7 +5,7 @@ use derive_more::Debug;
 use discv5::Enr;
 use futures::{AsyncReadExt, AsyncWriteExt, stream::StreamExt};
 use kona_genesis::RollupConfig;
-use kona_peers::{EnrValidation, PeerMonitoring, enr_to_multiaddr};
+use kona_peers::{EnrValidation, PeerDb, PeerMonitoring, enr_to_multiaddr};
 use libp2p::{
     Multiaddr, PeerId, Swarm, TransportError,
     gossipsub::{IdentTopic, MessageId},
@@ -55,6 +55,9 @@ pub struct GossipDriver<G: ConnectionGate> {
     /// If set, the gossip layer will monitor peer scores and ban peers that are below a given
     /// threshold.
     pub peer_monitoring: Option<PeerMonitoring>,
+    /// If set, peer reputation (scores, bans, addresses, topics and connection outcomes) is
+    /// recorded in the [`PeerDb`] and persisted across restarts.
+    pub peer_db: Option<PeerDb>,
     /// Tracks connection start time for peers
     pub peer_connection_start: HashMap<PeerId, Instant>,
     /// The connection gate.
@@ -92,6 +95,7 @@ where
             handler,
             peerstore: Default::default(),
             peer_monitoring: None,
+            peer_db: None,
             peer_connection_start: Default::default(),
             sync_handler,
             sync_protocol: Some(sync_protocol),
@@ -120,6 +124,19 @@ where
         let Some(payload) = payload else {
             return Ok(None);
         };
+
+        // In sentry mode, the connection gate only allows connections to the sentry peers, so
+        // the payload is only ever published to them. Fail early if none of them are connected.
+        let sentries = self.connection_gate.list_sentry_peers();
+        if !sentries.is_empty() &&
+            !sentries
+                .iter()
+                .filter_map(ConnectionGater::peer_id_from_addr)
+                .any(|peer_id| self.swarm.is_connected(&peer_id))
+        {
+            return Err(PublishError::NoSentryPeers);
+        }
+
         let topic = selector(&self.handler);
         let topic_hash = topic.hash();
         let data = self.handler.encode(topic, payload)?;
@@ -199,6 +216,9 @@ where
 
                         self.addr = address.clone();
 
+                        // Connect to the sentry peers, if any are configured.
+                        self.dial_sentries();
+
                         return Ok(address);
                     }
                 }
@@ -230,6 +250,77 @@ where
         self.swarm.connected_peers().count()
     }
 
+    /// Dials all sentry peers that are not currently connected.
+    ///
+    /// Sentry peers are registered as explicit gossipsub peers, such that they always receive
+    /// published payloads regardless of the mesh state. This is a no-op if the connection gate
+    /// is not in sentry mode.
+    pub fn dial_sentries(&mut self) {
+        for addr in self.connection_gate.list_sentry_peers() {
+            let Some(peer_id) = ConnectionGater::peer_id_from_addr(&addr) else {
+                continue;
+            };
+            self.swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
+            if !self.swarm.is_connected(&peer_id) {
+                self.dial_multiaddr(addr);
+            }
+        }
+    }
+
+    /// Records the scores of the connected peers in the [`PeerDb`], lifts expired bans and syncs
+    /// the database to disk. This is a no-op if no [`PeerDb`] is configured.
+    pub fn sync_peer_db(&mut self) {
+        let Some(db) = self.peer_db.as_mut() else {
+            return;
+        };
+
+        let gossipsub = &self.swarm.behaviour().gossipsub;
+        for peer_id in self.swarm.connected_peers() {
+            if let Some(score) = gossipsub.peer_score(peer_id) {
+                db.record_score(*peer_id, score);
+            }
+        }
+
+        for peer_id in db.prune_expired_bans() {
+            debug!(target: "gossip", ?peer_id, "Peer ban expired, unblocking peer");
+            self.connection_gate.unblock_peer(&peer_id);
+        }
+
+        if let Err(e) = db.sync() {
+            warn!(target: "gossip", "Failed to sync peer database: {:?}", e);
+        }
+    }
+
+    /// Blocks the peer in the connection gate and blacklists it from gossip.
+    ///
+    /// The ban is recorded in the [`PeerDb`] for the given duration and synced to disk right away,
+    /// so that the peer is blocked again when the node restarts.
+    pub fn block_peer(&mut self, peer_id: &PeerId, duration: Duration) {
+        self.connection_gate.block_peer(peer_id);
+        self.swarm.behaviour_mut().gossipsub.blacklist_peer(peer_id);
+
+        if let Some(db) = self.peer_db.as_mut() {
+            db.ban(*peer_id, duration);
+            if let Err(e) = db.sync() {
+                warn!(target: "gossip", "Failed to sync peer database: {:?}", e);
+            }
+        }
+    }
+
+    /// Unblocks the peer in the connection gate, removes it from the gossip blacklist and lifts
+    /// its ban in the [`PeerDb`].
+    pub fn unblock_peer(&mut self, peer_id: &PeerId) {
+        self.connection_gate.unblock_peer(peer_id);
+        self.swarm.behaviour_mut().gossipsub.remove_blacklisted_peer(peer_id);
+
+        if let Some(db) = self.peer_db.as_mut() {
+            db.unban(peer_id);
+            if let Err(e) = db.sync() {
+                warn!(target: "gossip", "Failed to sync peer database: {:?}", e);
+            }
+        }
+    }
+
     /// Dials the given [`Enr`].
     pub fn dial(&mut self, enr: Enr) {
         let validation = EnrValidation::validate(&enr, self.handler.rollup_config.l2_chain_id.id());
@@ -371,10 +462,16 @@ where
             }
             libp2p::gossipsub::Event::Subscribed { peer_id, topic } => {
                 trace!(target: "gossip", "Peer: {:?} subscribed to topic: {:?}", peer_id, topic);
+                if let Some(db) = self.peer_db.as_mut() {
+                    db.record_topic(peer_id, topic.to_string());
+                }
                 kona_macros::inc!(gauge, crate::Metrics::GOSSIP_EVENT, "type" => "subscribed", "topic" => topic.to_string());
             }
             libp2p::gossipsub::Event::Unsubscribed { peer_id, topic } => {
                 trace!(target: "gossip", "Peer: {:?} unsubscribed from topic: {:?}", peer_id, topic);
+                if let Some(db) = self.peer_db.as_mut() {
+                    db.remove_topic(&peer_id, topic.as_str());
+                }
                 kona_macros::inc!(gauge, crate::Metrics::GOSSIP_EVENT, "type" => "unsubscribed", "topic" => topic.to_string());
             }
             libp2p::gossipsub::Event::SlowPeer { peer_id, .. } => {
@@ -395,7 +492,22 @@ where
             SwarmEvent::Behaviour(behavior_event) => {
                 return self.handle_gossip_event(behavior_event)
             }
-            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+            SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
+                // Reject connections from peers that are not allowed by the connection gate. In
+                // sentry mode, the swarm already denies unknown peers before the connection is
+                // established, so this only guards against peers that slip through.
+                if !self.connection_gate.can_accept(&peer_id) {
+                    debug!(target: "gossip", ?peer_id, ?endpoint, "Rejecting connection from peer not allowed by the connection gate");
+                    kona_macros::inc!(
+                        gauge,
+                        crate::Metrics::GOSSIPSUB_CONNECTION,
+                        "type" => "rejected",
+                        "peer" => peer_id.to_string(),
+                    );
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return None;
+                }
+
                 let peer_count = self.swarm.connected_peers().count();
                 info!(target: "gossip", "Connection established: {:?} | Peer Count: {}", peer_id, peer_count);
                 kona_macros::inc!(
@@ -407,12 +519,20 @@ where
                 kona_macros::set!(gauge, crate::Metrics::GOSSIP_PEER_COUNT, peer_count as f64);
 
                 self.peer_connection_start.insert(peer_id, Instant::now());
+
+                if let Some(db) = self.peer_db.as_mut() {
+                    db.record_seen(peer_id, endpoint.get_remote_address().clone());
+                    db.record_success(peer_id);
+                }
             }
             SwarmEvent::OutgoingConnectionError { peer_id: _peer_id, error, .. } => {
                 debug!(target: "gossip", "Outgoing connection error: {:?}", error);
                 // Remove the peer from current_dials so it can be dialed again
                 if let Some(peer_id) = _peer_id {
                     self.connection_gate.remove_dial(&peer_id);
+                    if let Some(db) = self.peer_db.as_mut() {
+                        db.record_failure(peer_id);
+                    }
                 }
                 kona_macros::inc!(
                     gauge,
@@ -490,3 +610,45 @@ where
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_peers::PeerDbFile;
+    use std::path::Path;
+
+    fn driver_with_peer_db(path: &Path) -> GossipDriver<ConnectionGater> {
+        let (driver, _) = GossipDriver::<ConnectionGater>::builder(
+            RollupConfig::default(),
+            Address::ZERO,
+            "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+            Keypair::generate_secp256k1(),
+        )
+        .with_peer_db(Some(PeerDbFile::Custom(path.to_path_buf())))
+        .build()
+        .unwrap();
+        driver
+    }
+
+    #[tokio::test]
+    async fn test_blocked_peer_is_blocked_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peerdb.json");
+        let peer = Keypair::generate_secp256k1().public().to_peer_id();
+
+        let mut driver = driver_with_peer_db(&path);
+        driver.block_peer(&peer, Duration::from_secs(3600));
+        assert!(driver.connection_gate.list_blocked_peers().contains(&peer));
+        drop(driver);
+
+        let mut driver = driver_with_peer_db(&path);
+        assert!(driver.peer_db.as_ref().unwrap().is_banned(&peer));
+        assert_eq!(driver.connection_gate.list_blocked_peers(), vec![peer]);
+
+        driver.unblock_peer(&peer);
+        drop(driver);
+
+        let driver = driver_with_peer_db(&path);
+        assert!(driver.connection_gate.list_blocked_peers().is_empty());
+    }
+}
@@ -25,6 +25,12 @@ pub enum PublishError {
     /// into the binary format expected by the network protocol.
     #[error("Failed to encode payload: {0}")]
     EncodeError(#[from] HandlerEncodeError),
+
+    /// The node is in sentry mode, but none of its sentry peers are connected.
+    ///
+    /// In sentry mode, payloads are only ever published to the sentry peers.
+    #[error("No sentry peers connected")]
+    NoSentryPeers,
 }
 
 /// Error encountered when encoding payloads in the block handler.
@@ -113,4 +119,10 @@ pub enum DialError {
         /// The IP address that belongs to a blocked subnet range
         ip: IpAddr,
     },
+    /// The peer is not one of the configured sentry peers.
+    #[error("Peer is not a sentry peer: {peer_id}")]
+    NotSentryPeer {
+        /// The PeerId of the peer that is outside of the sentry set
+        peer_id: PeerId,
+    },
 }
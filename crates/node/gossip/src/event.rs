@@ -1,5 +1,6 @@
 //! Event Handling Module.
 
+use core::convert::Infallible;
 use libp2p::{gossipsub, identify, ping};
 
 /// High-level events emitted by the gossip networking system.
@@ -65,6 +66,13 @@ impl From<()> for Event {
     }
 }
 
+impl From<Infallible> for Event {
+    /// The sentry allow list never emits events.
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -15,6 +15,24 @@ pub trait ConnectionGate {
     /// Returns Ok(()) if the peer can be dialed, or Err(DialError) with the reason why not.
     fn can_dial(&mut self, peer_id: &Multiaddr) -> Result<(), DialError>;
 
+    /// Checks if an established connection with the given peer may be kept.
+    ///
+    /// This is used to reject inbound connections from peers that are not allowed by the
+    /// gate's topology, such as peers outside of the sentry set.
+    ///
+    /// By default, all peers are accepted.
+    fn can_accept(&self, _peer_id: &PeerId) -> bool {
+        true
+    }
+
+    /// Lists the addresses of the sentry peers.
+    ///
+    /// If non-empty, the node only connects to and publishes to these peers.
+    /// By default, there are no sentry peers.
+    fn list_sentry_peers(&self) -> Vec<Multiaddr> {
+        Vec::new()
+    }
+
     /// Returns the [`Connectedness`] for a given peer id.
     fn connectedness(&self, peer_id: &PeerId) -> Connectedness;
 
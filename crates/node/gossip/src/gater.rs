@@ -48,11 +48,22 @@ pub struct GaterConfig {
     /// period. The period resets after this duration has elapsed since the
     /// last dial attempt. Default is 1 hour.
     pub dial_period: Duration,
+
+    /// The trusted sentry peers of the node.
+    ///
+    /// If non-empty, the gater operates in sentry mode: only the sentry peers may be dialed, and
+    /// connections from any other peer are rejected. Each [`Multiaddr`] must contain a `/p2p`
+    /// component with the peer's [`PeerId`]. Sentry peers are protected from disconnection.
+    pub sentry_peers: Vec<Multiaddr>,
 }
 
 impl Default for GaterConfig {
     fn default() -> Self {
-        Self { peer_redialing: None, dial_period: Duration::from_secs(60 * 60) }
+        Self {
+            peer_redialing: None,
+            dial_period: Duration::from_secs(60 * 60),
+            sentry_peers: Vec::new(),
+        }
     }
 }
 
@@ -81,23 +92,49 @@ pub struct ConnectionGater {
     pub blocked_addrs: HashSet<IpAddr>,
     /// A set of blocked subnets that cannot be connected to.
     pub blocked_subnets: HashSet<IpNet>,
+    /// The set of sentry peer ids. If non-empty, only these peers may be connected to.
+    pub sentry_peers: HashSet<PeerId>,
 }
 
 impl ConnectionGater {
     /// Creates a new instance of the `ConnectionGater`.
     pub fn new(config: GaterConfig) -> Self {
+        let sentry_peers = config
+            .sentry_peers
+            .iter()
+            .filter_map(|addr| {
+                let peer_id = Self::peer_id_from_addr(addr);
+                if peer_id.is_none() {
+                    warn!(target: "p2p", peer=?addr, "Sentry peer address is missing a PeerId, ignoring");
+                }
+                peer_id
+            })
+            .collect::<HashSet<_>>();
+
         Self {
             config,
             current_dials: HashSet::new(),
             dialed_peers: HashMap::new(),
             connectedness: HashMap::new(),
-            protected_peers: HashSet::new(),
+            // Sentry peers are always protected from disconnection.
+            protected_peers: sentry_peers.clone(),
             blocked_peers: HashSet::new(),
             blocked_addrs: HashSet::new(),
             blocked_subnets: HashSet::new(),
+            sentry_peers,
         }
     }
 
+    /// Returns if the gater operates in sentry mode.
+    pub fn is_sentry_mode(&self) -> bool {
+        !self.sentry_peers.is_empty()
+    }
+
+    /// Returns if the given [`PeerId`] is one of the configured sentry peers.
+    pub fn is_sentry_peer(&self, peer_id: &PeerId) -> bool {
+        self.sentry_peers.contains(peer_id)
+    }
+
     /// Returns if the given [`Multiaddr`] has been dialed the maximum number of times.
     pub fn dial_threshold_reached(&self, addr: &Multiaddr) -> bool {
         // If the peer has not been dialed yet, the threshold is not reached.
@@ -222,6 +259,13 @@ impl ConnectionGate for ConnectionGater {
             return Err(DialError::AlreadyDialing { peer_id });
         }
 
+        // In sentry mode, only the sentry peers may be dialed.
+        if self.is_sentry_mode() && !self.is_sentry_peer(&peer_id) {
+            debug!(target: "gossip", peer=?addr, "Peer is not a sentry peer, not dialing");
+            kona_macros::inc!(gauge, crate::Metrics::DIAL_PEER_ERROR, "type" => "not_sentry_peer", "peer" => peer_id.to_string());
+            return Err(DialError::NotSentryPeer { peer_id });
+        }
+
         // If the peer is protected, do not apply thresholds.
         let protected = self.protected_peers.contains(&peer_id);
 
@@ -276,6 +320,15 @@ impl ConnectionGate for ConnectionGater {
         Ok(())
     }
 
+    fn can_accept(&self, peer_id: &PeerId) -> bool {
+        // In sentry mode, connections from peers outside of the sentry set are rejected.
+        !self.is_sentry_mode() || self.is_sentry_peer(peer_id)
+    }
+
+    fn list_sentry_peers(&self) -> Vec<Multiaddr> {
+        self.config.sentry_peers.clone()
+    }
+
     fn connectedness(&self, peer_id: &PeerId) -> Connectedness {
         self.connectedness.get(peer_id).cloned().unwrap_or(Connectedness::NotConnected)
     }
@@ -389,6 +442,7 @@ fn test_check_ip_in_blocked_subnets_ipv4() {
     let mut gater = ConnectionGater::new(GaterConfig {
         peer_redialing: None,
         dial_period: Duration::from_secs(60 * 60),
+        sentry_peers: Vec::new(),
     });
     gater.blocked_subnets.insert("192.168.1.0/24".parse::<IpNet>().unwrap());
     gater.blocked_subnets.insert("10.0.0.0/8".parse::<IpNet>().unwrap());
@@ -555,3 +609,44 @@ fn test_dns_multiaddr_blocked_by_subnet() {
     let result = gater.can_dial(&dns_localhost);
     assert!(matches!(result, Err(DialError::SubnetBlocked { .. })));
 }
+
+#[test]
+fn test_sentry_mode_gating() {
+    use crate::{ConnectionGate, DialError};
+    use std::str::FromStr;
+
+    let sentry = Multiaddr::from_str(
+        "/ip4/127.0.0.1/tcp/9003/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp",
+    )
+    .unwrap();
+    let unknown = Multiaddr::from_str(
+        "/ip4/127.0.0.2/tcp/9003/p2p/16Uiu2HAmUSo81N6iNQNKZCiqDAg5Mcmh9gwvPgKmKj1HH6qCR4Kq",
+    )
+    .unwrap();
+    let sentry_id = ConnectionGater::peer_id_from_addr(&sentry).unwrap();
+    let unknown_id = ConnectionGater::peer_id_from_addr(&unknown).unwrap();
+
+    // Without sentry peers, all peers are accepted.
+    let mut gater = ConnectionGater::new(GaterConfig::default());
+    assert!(!gater.is_sentry_mode());
+    assert!(gater.can_accept(&unknown_id));
+    assert!(gater.can_dial(&unknown).is_ok());
+
+    let mut gater = ConnectionGater::new(GaterConfig {
+        sentry_peers: vec![sentry.clone()],
+        ..Default::default()
+    });
+    assert!(gater.is_sentry_mode());
+    assert_eq!(gater.list_sentry_peers(), vec![sentry.clone()]);
+
+    // Sentry peers can be dialed and are protected from disconnection.
+    assert!(gater.can_dial(&sentry).is_ok());
+    assert!(gater.can_accept(&sentry_id));
+    assert!(!gater.can_disconnect(&sentry));
+
+    // Unknown peers can neither be dialed nor accepted.
+    let result = gater.can_dial(&unknown);
+    assert!(matches!(result, Err(DialError::NotSentryPeer { .. })));
+    assert!(!gater.can_accept(&unknown_id));
+    assert!(gater.can_disconnect(&unknown));
+}
@@ -163,6 +163,7 @@ impl Metrics {
         kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "outgoing_error", 0);
         kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "incoming_error", 0);
         kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "closed", 0);
+        kona_macros::set!(gauge, Self::GOSSIPSUB_CONNECTION, "type", "rejected", 0);
 
         // Gossipsub Events
         kona_macros::set!(gauge, Self::GOSSIPSUB_EVENT, "type", "subscribed", 0);
@@ -1,6 +1,6 @@
 //! Contains the p2p RPC request type.
 
-use std::{net::IpAddr, num::TryFromIntError, sync::Arc};
+use std::{net::IpAddr, num::TryFromIntError, sync::Arc, time::Duration};
 
 use crate::{GossipDriver, GossipScores};
 use alloy_primitives::map::{HashMap, HashSet};
@@ -20,6 +20,10 @@ use super::{
 };
 use crate::ConnectionGate;
 
+/// The ban recorded in the peer database for peers blocked through the RPC. Such blocks only end
+/// when the peer is unblocked, so the ban is long enough to outlive any node run.
+const RPC_BLOCK_DURATION: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
 /// A p2p RPC Request.
 #[derive(Debug)]
 pub enum P2pRpcRequest {
@@ -154,13 +158,11 @@ impl P2pRpcRequest {
     }
 
     fn block_peer<G: ConnectionGate>(id: PeerId, gossip: &mut GossipDriver<G>) {
-        gossip.connection_gate.block_peer(&id);
-        gossip.swarm.behaviour_mut().gossipsub.blacklist_peer(&id);
+        gossip.block_peer(&id, RPC_BLOCK_DURATION);
     }
 
     fn unblock_peer<G: ConnectionGate>(id: PeerId, gossip: &mut GossipDriver<G>) {
-        gossip.connection_gate.unblock_peer(&id);
-        gossip.swarm.behaviour_mut().gossipsub.remove_blacklisted_peer(&id);
+        gossip.unblock_peer(&id);
     }
 
     fn list_blocked_peers<G: ConnectionGate>(s: Sender<Vec<PeerId>>, gossip: &GossipDriver<G>) {
@@ -0,0 +1,416 @@
+//! Persistent Peer Database
+
+use libp2p::{Multiaddr, PeerId};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    fs::File,
+    io::{BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The maximum number of peers that can be stored in the peer database.
+const MAX_PEERS: usize = 4096;
+
+/// The maximum number of score samples kept per peer.
+const MAX_SCORE_HISTORY: usize = 32;
+
+/// Returns the current unix timestamp, in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+/// A single gossip score observation for a peer.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScoreSample {
+    /// The unix timestamp (in seconds) at which the score was observed.
+    pub timestamp: u64,
+    /// The observed gossip score.
+    pub score: f64,
+}
+
+/// The reputation of a single peer, as persisted in the [`PeerDb`].
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PeerRecord {
+    /// The most recent gossip scores of the peer, oldest first.
+    pub scores: VecDeque<ScoreSample>,
+    /// The unix timestamp (in seconds) at which the peer's ban expires, if it is banned.
+    pub ban_expiry: Option<u64>,
+    /// The last address the peer was seen at.
+    pub last_seen_addr: Option<Multiaddr>,
+    /// The unix timestamp (in seconds) at which the peer was last seen.
+    pub last_seen: u64,
+    /// The gossip topics the peer is known to be subscribed to.
+    pub topics: BTreeSet<String>,
+    /// The number of successful connections to the peer.
+    pub successes: u64,
+    /// The number of failed connection attempts to the peer.
+    pub failures: u64,
+}
+
+impl PeerRecord {
+    /// Returns the most recently observed score of the peer.
+    pub fn last_score(&self) -> Option<f64> {
+        self.scores.back().map(|s| s.score)
+    }
+
+    /// Returns true if the peer is banned at the given unix timestamp.
+    pub fn is_banned_at(&self, now: u64) -> bool {
+        self.ban_expiry.is_some_and(|expiry| expiry > now)
+    }
+
+    /// Returns the remaining ban duration at the given unix timestamp, if the peer is banned.
+    pub fn remaining_ban_at(&self, now: u64) -> Option<Duration> {
+        self.ban_expiry
+            .filter(|expiry| *expiry > now)
+            .map(|expiry| Duration::from_secs(expiry - now))
+    }
+}
+
+/// The peer database file policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerDbFile {
+    /// Default path for the peer database, ie `~/.kona/<chain_id>/peerdb.json`.
+    Default {
+        /// The l2 chain ID.
+        chain_id: u64,
+    },
+    /// A custom peer database path is used. This must be a valid path to a file.
+    Custom(PathBuf),
+}
+
+impl TryInto<PathBuf> for PeerDbFile {
+    type Error = std::io::Error;
+
+    fn try_into(self) -> Result<PathBuf, std::io::Error> {
+        match self {
+            Self::Default { chain_id } => {
+                let mut path = dirs::home_dir()
+                    .ok_or(std::io::Error::other("Failed to get home directory"))?;
+                path.push(".kona");
+                path.push(chain_id.to_string());
+                path.push("peerdb.json");
+                Ok(path)
+            }
+            Self::Custom(path) => Ok(path),
+        }
+    }
+}
+
+impl TryInto<File> for PeerDbFile {
+    type Error = std::io::Error;
+
+    /// Returns a pointer to the peer database file, creating it (and its parent directories) if
+    /// it does not exist.
+    fn try_into(self) -> Result<File, std::io::Error> {
+        let path = TryInto::<PathBuf>::try_into(self)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        File::options().read(true).write(true).create(true).truncate(false).open(path)
+    }
+}
+
+impl TryInto<PeerDb> for PeerDbFile {
+    type Error = std::io::Error;
+
+    fn try_into(self) -> Result<PeerDb, std::io::Error> {
+        let file = TryInto::<File>::try_into(self)?;
+        Ok(file.into())
+    }
+}
+
+/// On-disk storage for peer reputation.
+///
+/// The [`PeerDb`] is a JSON file, keyed by [`PeerId`], that holds the [`PeerRecord`] of every
+/// peer the node has interacted with. It allows score history, bans, last-seen addresses,
+/// supported topics and connection outcomes to survive node restarts.
+///
+/// When the number of peers exceeds `MAX_PEERS`, the least recently seen peers that are not
+/// banned are evicted.
+#[derive(Debug, Default)]
+pub struct PeerDb {
+    /// The file for the [`PeerDb`].
+    pub file: Option<File>,
+    /// The [`PeerRecord`]s, keyed by [`PeerId`].
+    pub peers: HashMap<PeerId, PeerRecord>,
+}
+
+impl From<File> for PeerDb {
+    fn from(file: File) -> Self {
+        let peers = peers_from_file(&file);
+        Self { file: Some(file), peers }
+    }
+}
+
+fn peers_from_file(file: &File) -> HashMap<PeerId, PeerRecord> {
+    debug!(target: "peerdb", "Reading peer database from disk: {:?}", file);
+    if file.metadata().map(|m| m.len() == 0).unwrap_or(false) {
+        return HashMap::new();
+    }
+    let reader = BufReader::new(file);
+    match serde_json::from_reader::<_, BTreeMap<String, serde_json::Value>>(reader) {
+        Ok(entries) => entries
+            .into_iter()
+            .filter_map(|(peer_id, record)| {
+                let peer_id = peer_id
+                    .parse::<PeerId>()
+                    .inspect_err(
+                        |e| warn!(target: "peerdb", "Invalid peer id in peer database: {:?}", e),
+                    )
+                    .ok()?;
+                let record = serde_json::from_value::<PeerRecord>(record)
+                    .inspect_err(
+                        |e| warn!(target: "peerdb", "Failed to deserialize peer record: {:?}", e),
+                    )
+                    .ok()?;
+                Some((peer_id, record))
+            })
+            .collect(),
+        Err(e) => {
+            warn!(target: "peerdb", "Failed to read peer database from disk: {:?}", e);
+            HashMap::new()
+        }
+    }
+}
+
+impl PeerDb {
+    /// Opens the peer database at the given path for reading only.
+    ///
+    /// Unlike the [`PeerDbFile`] conversion, this neither creates the file nor keeps it open for
+    /// writing, so [`Self::sync`] is a no-op on the returned database.
+    pub fn open_read_only(path: &Path) -> Result<Self, std::io::Error> {
+        let file = File::open(path)?;
+        Ok(Self { file: None, peers: peers_from_file(&file) })
+    }
+
+    /// Returns the [`PeerRecord`] for the given peer, if any.
+    pub fn get(&self, peer_id: &PeerId) -> Option<&PeerRecord> {
+        self.peers.get(peer_id)
+    }
+
+    /// Returns the number of peers in the in-memory database.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Returns if the in-memory database is empty.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Records a gossip score observation for the given peer.
+    pub fn record_score(&mut self, peer_id: PeerId, score: f64) {
+        let record = self.entry(peer_id);
+        record.scores.push_back(ScoreSample { timestamp: unix_now(), score });
+        if record.scores.len() > MAX_SCORE_HISTORY {
+            record.scores.pop_front();
+        }
+    }
+
+    /// Records that the peer was seen at the given address.
+    pub fn record_seen(&mut self, peer_id: PeerId, addr: Multiaddr) {
+        let record = self.entry(peer_id);
+        record.last_seen_addr = Some(addr);
+        record.last_seen = unix_now();
+    }
+
+    /// Records that the peer is subscribed to the given gossip topic.
+    pub fn record_topic(&mut self, peer_id: PeerId, topic: impl Into<String>) {
+        self.entry(peer_id).topics.insert(topic.into());
+    }
+
+    /// Records that the peer unsubscribed from the given gossip topic.
+    pub fn remove_topic(&mut self, peer_id: &PeerId, topic: &str) {
+        if let Some(record) = self.peers.get_mut(peer_id) {
+            record.topics.remove(topic);
+        }
+    }
+
+    /// Records a successful connection to the peer.
+    pub fn record_success(&mut self, peer_id: PeerId) {
+        self.entry(peer_id).successes += 1;
+    }
+
+    /// Records a failed connection attempt to the peer.
+    pub fn record_failure(&mut self, peer_id: PeerId) {
+        self.entry(peer_id).failures += 1;
+    }
+
+    /// Bans the peer for the given duration, starting now.
+    pub fn ban(&mut self, peer_id: PeerId, duration: Duration) {
+        let expiry = unix_now().saturating_add(duration.as_secs());
+        self.entry(peer_id).ban_expiry = Some(expiry);
+    }
+
+    /// Lifts the ban on the given peer, if any.
+    pub fn unban(&mut self, peer_id: &PeerId) {
+        if let Some(record) = self.peers.get_mut(peer_id) {
+            record.ban_expiry = None;
+        }
+    }
+
+    /// Returns true if the peer is currently banned.
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.peers.get(peer_id).is_some_and(|r| r.is_banned_at(unix_now()))
+    }
+
+    /// Returns the currently banned peers along with their remaining ban duration.
+    pub fn banned_peers(&self) -> Vec<(PeerId, Duration)> {
+        let now = unix_now();
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, record)| record.remaining_ban_at(now).map(|d| (*peer_id, d)))
+            .collect()
+    }
+
+    /// Clears bans that have expired, returning the peers whose ban was lifted.
+    pub fn prune_expired_bans(&mut self) -> Vec<PeerId> {
+        let now = unix_now();
+        self.peers
+            .iter_mut()
+            .filter(|(_, record)| record.ban_expiry.is_some_and(|expiry| expiry <= now))
+            .map(|(peer_id, record)| {
+                record.ban_expiry = None;
+                *peer_id
+            })
+            .collect()
+    }
+
+    /// Syncs the [`PeerDb`] with the contents on disk.
+    pub fn sync(&mut self) -> Result<(), std::io::Error> {
+        if let Some(file) = &mut self.file {
+            let entries = self
+                .peers
+                .iter()
+                .map(|(peer_id, record)| (peer_id.to_string(), record))
+                .collect::<BTreeMap<_, _>>();
+
+            // Reset file pointer AND truncate
+            file.seek(SeekFrom::Start(0))?;
+            file.set_len(0)?;
+
+            serde_json::to_writer(file, &entries)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the [`PeerRecord`] for the given peer, inserting a default record if necessary.
+    fn entry(&mut self, peer_id: PeerId) -> &mut PeerRecord {
+        if !self.peers.contains_key(&peer_id) && self.peers.len() >= MAX_PEERS {
+            self.evict();
+        }
+        self.peers.entry(peer_id).or_default()
+    }
+
+    /// Evicts the least recently seen peer that is not currently banned.
+    fn evict(&mut self) {
+        let now = unix_now();
+        let oldest = self
+            .peers
+            .iter()
+            .filter(|(_, record)| !record.is_banned_at(now))
+            .min_by_key(|(_, record)| record.last_seen)
+            .map(|(peer_id, _)| *peer_id);
+        if let Some(peer_id) = oldest {
+            debug!(target: "peerdb", "Peer database exceeded maximum peers, removing {}", peer_id);
+            self.peers.remove(&peer_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_peer() -> PeerId {
+        libp2p::identity::Keypair::generate_secp256k1().public().to_peer_id()
+    }
+
+    #[test]
+    fn test_peer_db_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peerdb.json");
+        let peer = random_peer();
+        let addr: Multiaddr = "/ip4/127.0.0.1/tcp/9222".parse().unwrap();
+
+        let mut db: PeerDb = PeerDbFile::Custom(path.clone()).try_into().unwrap();
+        assert!(db.is_empty());
+        db.record_score(peer, -12.5);
+        db.record_seen(peer, addr.clone());
+        db.record_topic(peer, "/optimism/10/2/blocks");
+        db.record_success(peer);
+        db.record_failure(peer);
+        db.record_failure(peer);
+        db.ban(peer, Duration::from_secs(3600));
+        db.sync().unwrap();
+        drop(db);
+
+        let db: PeerDb = PeerDbFile::Custom(path).try_into().unwrap();
+        let record = db.get(&peer).unwrap();
+        assert_eq!(record.last_score(), Some(-12.5));
+        assert_eq!(record.last_seen_addr, Some(addr));
+        assert!(record.topics.contains("/optimism/10/2/blocks"));
+        assert_eq!(record.successes, 1);
+        assert_eq!(record.failures, 2);
+        assert!(db.is_banned(&peer));
+        assert_eq!(db.banned_peers().len(), 1);
+    }
+
+    #[test]
+    fn test_peer_db_open_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peerdb.json");
+        assert!(PeerDb::open_read_only(&path).is_err());
+        assert!(!path.exists());
+
+        let peer = random_peer();
+        let mut db: PeerDb = PeerDbFile::Custom(path.clone()).try_into().unwrap();
+        db.ban(peer, Duration::from_secs(3600));
+        db.sync().unwrap();
+        drop(db);
+
+        let mut db = PeerDb::open_read_only(&path).unwrap();
+        assert!(db.is_banned(&peer));
+        db.unban(&peer);
+        db.sync().unwrap();
+        assert!(PeerDb::open_read_only(&path).unwrap().is_banned(&peer));
+    }
+
+    #[test]
+    fn test_peer_db_expired_bans() {
+        let mut db = PeerDb::default();
+        let peer = random_peer();
+        db.ban(peer, Duration::ZERO);
+        assert!(!db.is_banned(&peer));
+        assert!(db.banned_peers().is_empty());
+        assert_eq!(db.prune_expired_bans(), vec![peer]);
+        assert_eq!(db.get(&peer).unwrap().ban_expiry, None);
+    }
+
+    #[test]
+    fn test_peer_db_score_history_bounded() {
+        let mut db = PeerDb::default();
+        let peer = random_peer();
+        for i in 0..(MAX_SCORE_HISTORY + 8) {
+            db.record_score(peer, i as f64);
+        }
+        let record = db.get(&peer).unwrap();
+        assert_eq!(record.scores.len(), MAX_SCORE_HISTORY);
+        assert_eq!(record.last_score(), Some((MAX_SCORE_HISTORY + 7) as f64));
+    }
+
+    #[test]
+    fn test_peer_db_ignores_invalid_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peerdb.json");
+        let peer = random_peer();
+        let contents = format!(r#"{{"not-a-peer-id": {{}}, "{peer}": {{"successes": 3}}}}"#);
+        std::fs::write(&path, contents).unwrap();
+
+        let db: PeerDb = PeerDbFile::Custom(path).try_into().unwrap();
+        assert_eq!(db.len(), 1);
+        assert_eq!(db.get(&peer).unwrap().successes, 3);
+    }
+}
@@ -21,6 +21,9 @@ pub use nodes::{BootNodes, OP_RAW_BOOTNODES, OP_RAW_TESTNET_BOOTNODES};
 mod store;
 pub use store::{BootStore, BootStoreFile};
 
+mod db;
+pub use db::{PeerDb, PeerDbFile, PeerRecord, ScoreSample};
+
 mod score;
 pub use score::PeerScoreLevel;
 
@@ -12,7 +12,7 @@ use jsonrpsee::{
     core::{RpcResult, SubscriptionResult},
     proc_macros::rpc,
 };
-use kona_genesis::RollupConfig;
+use kona_genesis::{RollupConfig, SystemConfig};
 use kona_gossip::{PeerCount, PeerDump, PeerInfo, PeerStats};
 use kona_protocol::SyncStatus;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
@@ -43,6 +43,22 @@ pub trait RollupNodeApi {
         block_number: BlockNumberOrTag,
     ) -> RpcResult<SafeHeadResponse>;
 
+    /// Gets the system config in effect at an L1 block, including the config updates emitted in
+    /// that block.
+    #[method(name = "systemConfigAtL1Block")]
+    async fn op_system_config_at_l1_block(
+        &self,
+        block_number: BlockNumberOrTag,
+    ) -> RpcResult<SystemConfig>;
+
+    /// Gets the system config of an L2 block, which is the system config in effect at the L1
+    /// origin of the block.
+    #[method(name = "systemConfigAtL2Block")]
+    async fn op_system_config_at_l2_block(
+        &self,
+        block_number: BlockNumberOrTag,
+    ) -> RpcResult<SystemConfig>;
+
     /// Get the synchronization status.
     #[method(name = "syncStatus")]
     async fn op_sync_status(&self) -> RpcResult<SyncStatus>;
@@ -1,4 +1,5 @@
-use kona_genesis::RollupConfig;
+use alloy_eips::BlockNumberOrTag;
+use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::BlockInfo;
 use tokio::sync::oneshot::Sender;
 
@@ -40,4 +41,14 @@ pub enum L1WatcherQueries {
     Config(Sender<RollupConfig>),
     /// Get a complete view of the L1 state.
     L1State(Sender<L1State>),
+    /// Get the system config in effect at an L1 block, reconstructed from the config update
+    /// logs emitted since genesis.
+    ///
+    /// Responds with `None` if the system config could not be reconstructed.
+    SystemConfigAtL1Block {
+        /// The L1 block number or tag to retrieve the system config for.
+        block: BlockNumberOrTag,
+        /// Response channel for the system config.
+        sender: Sender<Option<SystemConfig>>,
+    },
 }
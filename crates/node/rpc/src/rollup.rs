@@ -9,7 +9,7 @@ use jsonrpsee::{
     types::{ErrorCode, ErrorObject},
 };
 use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
-use kona_genesis::RollupConfig;
+use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::SyncStatus;
 
 use crate::{
@@ -59,6 +59,20 @@ impl RollupRpc {
             finalized_l2: l2_sync_status.sync_state.finalized_head(),
         }
     }
+
+    /// Queries the L1 watcher for the system config in effect at the given L1 block.
+    async fn system_config_at_l1_block(&self, block: BlockNumberOrTag) -> RpcResult<SystemConfig> {
+        let (system_config_send, system_config_recv) = tokio::sync::oneshot::channel();
+        self.l1_watcher_sender
+            .send(L1WatcherQueries::SystemConfigAtL1Block { block, sender: system_config_send })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        system_config_recv
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?
+            .ok_or_else(|| ErrorObject::from(ErrorCode::InternalError))
+    }
 }
 
 #[async_trait]
@@ -103,6 +117,33 @@ impl RollupNodeApiServer for RollupRpc {
         return Err(ErrorObject::from(ErrorCode::MethodNotFound));
     }
 
+    async fn op_system_config_at_l1_block(
+        &self,
+        block_num: BlockNumberOrTag,
+    ) -> RpcResult<SystemConfig> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_systemConfigAtL1Block");
+
+        self.system_config_at_l1_block(block_num).await
+    }
+
+    async fn op_system_config_at_l2_block(
+        &self,
+        block_num: BlockNumberOrTag,
+    ) -> RpcResult<SystemConfig> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_systemConfigAtL2Block");
+
+        let (block_info_send, block_info_recv) = tokio::sync::oneshot::channel();
+        self.engine_sender
+            .send(EngineQueries::BlockInfo { block: block_num, sender: block_info_send })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        let block_info =
+            block_info_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        // The system config of an L2 block is the one in effect at its L1 origin.
+        self.system_config_at_l1_block(block_info.l1_origin.number.into()).await
+    }
+
     async fn op_sync_status(&self) -> RpcResult<SyncStatus> {
         kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_syncStatus");
 
@@ -150,3 +191,76 @@ impl RollupNodeApiServer for RollupRpc {
         return Ok(RPC_VERSION.to_string());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::Address;
+    use kona_protocol::L2BlockInfo;
+    use tokio::sync::mpsc;
+
+    fn rollup_rpc() -> (RollupRpc, mpsc::Receiver<EngineQueries>, mpsc::Receiver<L1WatcherQueries>)
+    {
+        let (engine_sender, engine_queries) = mpsc::channel(1);
+        let (l1_watcher_sender, l1_watcher_queries) = mpsc::channel(1);
+        (RollupRpc::new(engine_sender, l1_watcher_sender), engine_queries, l1_watcher_queries)
+    }
+
+    /// Answers the next system config query with the given config, asserting the queried block.
+    fn answer_system_config(
+        mut l1_watcher_queries: mpsc::Receiver<L1WatcherQueries>,
+        expected: BlockNumberOrTag,
+        system_config: Option<SystemConfig>,
+    ) {
+        tokio::spawn(async move {
+            let Some(L1WatcherQueries::SystemConfigAtL1Block { block, sender }) =
+                l1_watcher_queries.recv().await
+            else {
+                panic!("expected a system config query");
+            };
+            assert_eq!(block, expected);
+            sender.send(system_config).unwrap();
+        });
+    }
+
+    #[tokio::test]
+    async fn test_system_config_at_l1_block() {
+        let (rpc, _engine_queries, l1_watcher_queries) = rollup_rpc();
+        let system_config =
+            SystemConfig { batcher_address: Address::repeat_byte(0xaa), ..Default::default() };
+        answer_system_config(l1_watcher_queries, BlockNumberOrTag::Finalized, Some(system_config));
+
+        let result = rpc.op_system_config_at_l1_block(BlockNumberOrTag::Finalized).await.unwrap();
+        assert_eq!(result, system_config);
+    }
+
+    #[tokio::test]
+    async fn test_system_config_at_l1_block_unavailable() {
+        let (rpc, _engine_queries, l1_watcher_queries) = rollup_rpc();
+        answer_system_config(l1_watcher_queries, BlockNumberOrTag::Pending, None);
+
+        let err = rpc.op_system_config_at_l1_block(BlockNumberOrTag::Pending).await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InternalError.code());
+    }
+
+    #[tokio::test]
+    async fn test_system_config_at_l2_block_uses_l1_origin() {
+        let (rpc, mut engine_queries, l1_watcher_queries) = rollup_rpc();
+        let system_config =
+            SystemConfig { batcher_address: Address::repeat_byte(0xbb), ..Default::default() };
+        tokio::spawn(async move {
+            let Some(EngineQueries::BlockInfo { block, sender }) = engine_queries.recv().await
+            else {
+                panic!("expected a block info query");
+            };
+            assert_eq!(block, BlockNumberOrTag::Number(100));
+            let l1_origin = BlockNumHash { number: 42, ..Default::default() };
+            sender.send(L2BlockInfo { l1_origin, ..Default::default() }).unwrap();
+        });
+        answer_system_config(l1_watcher_queries, BlockNumberOrTag::Number(42), Some(system_config));
+
+        let result = rpc.op_system_config_at_l2_block(BlockNumberOrTag::Number(100)).await.unwrap();
+        assert_eq!(result, system_config);
+    }
+}
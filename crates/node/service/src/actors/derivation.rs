@@ -15,8 +15,8 @@ use kona_derive::{
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use kona_providers_alloy::{
-    AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider,
-    OnlinePipeline,
+    AlloyL2ChainProvider, BlobArchiverClient, BlobDirectory, FallbackBeaconClient,
+    FallbackBlobProvider, FallbackChainProvider, OnlineBlobProvider, OnlinePipeline,
 };
 use op_alloy_network::Optimism;
 use thiserror::Error;
@@ -25,6 +25,7 @@ use tokio::{
     sync::{mpsc, oneshot, watch},
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use tracing::{Instrument, Span};
 
 /// The [NodeActor] for the derivation sub-routine.
 ///
@@ -99,12 +100,16 @@ pub trait PipelineBuilder: Send + Sync + 'static {
 /// The configuration necessary to build the derivation actor.
 #[derive(Debug)]
 pub struct DerivationBuilder {
-    /// The L1 provider.
-    pub l1_provider: RootProvider,
+    /// The L1 providers, in order of preference.
+    pub l1_providers: Vec<RootProvider>,
     /// Whether to trust the L1 RPC.
     pub l1_trust_rpc: bool,
     /// The L1 beacon client.
-    pub l1_beacon: OnlineBeaconClient,
+    pub l1_beacon: FallbackBeaconClient,
+    /// The blob archiver to fetch blobs pruned by the L1 beacon nodes from, if any.
+    pub l1_blob_archiver: Option<BlobArchiverClient>,
+    /// The local blob directory to fetch blobs pruned by the L1 beacon nodes from, if any.
+    pub l1_blob_directory: Option<BlobDirectory>,
     /// The L2 provider.
     pub l2_provider: RootProvider<Optimism>,
     /// Whether to trust the L2 RPC.
@@ -123,8 +128,8 @@ impl PipelineBuilder for DerivationBuilder {
 
     async fn build(self) -> DerivationState<OnlinePipeline> {
         // Create the caching L1/L2 EL providers for derivation.
-        let l1_derivation_provider = AlloyChainProvider::new_with_trust(
-            self.l1_provider.clone(),
+        let l1_derivation_provider = FallbackChainProvider::new(
+            self.l1_providers.clone(),
             DERIVATION_PROVIDER_CACHE_SIZE,
             self.l1_trust_rpc,
         );
@@ -135,18 +140,23 @@ impl PipelineBuilder for DerivationBuilder {
             self.l2_trust_rpc,
         );
 
+        let blob_provider =
+            FallbackBlobProvider::new(OnlineBlobProvider::init(self.l1_beacon.clone()).await)
+                .with_archiver(self.l1_blob_archiver.clone())
+                .with_directory(self.l1_blob_directory.clone());
+
         let pipeline = match self.interop_mode {
             InteropMode::Polled => OnlinePipeline::new_polled(
                 self.rollup_config.clone(),
                 self.l1_config.clone(),
-                OnlineBlobProvider::init(self.l1_beacon.clone()).await,
+                blob_provider,
                 l1_derivation_provider,
                 l2_derivation_provider,
             ),
             InteropMode::Indexed => OnlinePipeline::new_indexed(
                 self.rollup_config.clone(),
                 self.l1_config.clone(),
-                OnlineBlobProvider::init(self.l1_beacon.clone()).await,
+                blob_provider,
                 l1_derivation_provider,
                 l2_derivation_provider,
             ),
@@ -174,13 +184,23 @@ pub struct DerivationInboundChannels {
     pub derivation_signal_tx: mpsc::Sender<Signal>,
 }
 
+/// Payload attributes produced by the derivation actor.
+#[derive(Debug, Clone)]
+pub struct DerivedAttributes {
+    /// The derived [`OpAttributesWithParent`].
+    pub attributes: OpAttributesWithParent,
+    /// The span that the attributes were derived in. Engine tasks that process the attributes
+    /// link to this span.
+    pub span: Span,
+}
+
 /// The communication context used by the derivation actor.
 #[derive(Debug)]
 pub struct DerivationContext {
     /// The cancellation token, shared between all tasks.
     pub cancellation: CancellationToken,
-    /// Sends the derived [`OpAttributesWithParent`]s produced by the actor.
-    pub derived_attributes_tx: mpsc::Sender<OpAttributesWithParent>,
+    /// Sends the [`DerivedAttributes`] produced by the actor.
+    pub derived_attributes_tx: mpsc::Sender<DerivedAttributes>,
     /// The reset request sender, used to handle [`PipelineErrorKind::Reset`] events and forward
     /// them to the engine.
     pub reset_request_tx: mpsc::Sender<ResetRequest>,
@@ -333,7 +353,7 @@ where
         msg: InboundDerivationMessage,
         engine_l2_safe_head: &mut watch::Receiver<L2BlockInfo>,
         el_sync_complete_rx: &oneshot::Receiver<()>,
-        derived_attributes_tx: &mpsc::Sender<OpAttributesWithParent>,
+        derived_attributes_tx: &mpsc::Sender<DerivedAttributes>,
         reset_request_tx: &mpsc::Sender<ResetRequest>,
     ) -> Result<(), DerivationError> {
         // Only attempt derivation once the engine finishes syncing.
@@ -370,19 +390,29 @@ where
         }
 
         // Advance the pipeline as much as possible, new data may be available or there still may be
-        // payloads in the attributes queue.
-        let payload_attrs =
-            match self.produce_next_attributes(engine_l2_safe_head, reset_request_tx).await {
-                Ok(attrs) => attrs,
-                Err(DerivationError::Yield) => {
-                    // Yield until more data is available.
-                    self.derivation_idle = true;
-                    return Ok(());
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            };
+        // payloads in the attributes queue. The pipeline steps are recorded within a span, which
+        // the engine task that processes the attributes links to.
+        let span = info_span!(
+            target: "derivation",
+            "derive_attributes",
+            l2_safe_head = engine_safe_head.block_info.number,
+            derived_from = tracing::field::Empty,
+        );
+        let payload_attrs = match self
+            .produce_next_attributes(engine_l2_safe_head, reset_request_tx)
+            .instrument(span.clone())
+            .await
+        {
+            Ok(attrs) => attrs,
+            Err(DerivationError::Yield) => {
+                // Yield until more data is available.
+                self.derivation_idle = true;
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
 
         // Mark derivation as busy.
         self.derivation_idle = false;
@@ -391,8 +421,9 @@ where
         engine_l2_safe_head.borrow_and_update();
 
         // Send payload attributes out for processing.
+        span.record("derived_from", payload_attrs.derived_from.map(|b| b.number));
         derived_attributes_tx
-            .send(payload_attrs)
+            .send(DerivedAttributes { attributes: payload_attrs, span })
             .await
             .map_err(|e| DerivationError::Sender(Box::new(e)))?;
 
@@ -1,7 +1,7 @@
 //! The [`EngineActor`].
 
 use super::{BlockEngineResult, EngineError, L2Finalizer};
-use crate::{BlockEngineError, NodeActor, NodeMode, actors::CancellableContext};
+use crate::{BlockEngineError, DerivedAttributes, NodeActor, NodeMode, actors::CancellableContext};
 use alloy_provider::RootProvider;
 use alloy_rpc_types_engine::{JwtSecret, PayloadId};
 use async_trait::async_trait;
@@ -65,8 +65,8 @@ pub struct SealRequest {
 /// interactions based off of the [`Ord`] implementation of [`EngineTask`].
 #[derive(Debug)]
 pub struct EngineActor {
-    /// A channel to receive [`OpAttributesWithParent`] from the derivation actor.
-    attributes_rx: mpsc::Receiver<OpAttributesWithParent>,
+    /// A channel to receive [`DerivedAttributes`] from the derivation actor.
+    attributes_rx: mpsc::Receiver<DerivedAttributes>,
     /// The [`EngineConfig`] used to build the actor.
     builder: EngineConfig,
     /// A channel to receive build requests.
@@ -106,8 +106,8 @@ pub struct EngineActor {
 /// The outbound data for the [`EngineActor`].
 #[derive(Debug)]
 pub struct EngineInboundData {
-    /// A channel to send [`OpAttributesWithParent`] to the engine actor.
-    pub attributes_tx: mpsc::Sender<OpAttributesWithParent>,
+    /// A channel to send [`DerivedAttributes`] to the engine actor.
+    pub attributes_tx: mpsc::Sender<DerivedAttributes>,
     /// A channel to use to send [`BuildRequest`] payloads to the engine actor.
     ///
     /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
@@ -689,19 +689,22 @@ impl NodeActor for EngineActor {
                     state.engine.enqueue(task);
                 }
                 attributes = self.attributes_rx.recv() => {
-                    let Some(attributes) = attributes else {
+                    let Some(DerivedAttributes { attributes, span }) = attributes else {
                         error!(target: "engine", "Attributes receiver closed unexpectedly");
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
                     };
                     self.finalizer.enqueue_for_finalization(&attributes);
 
-                    let task = EngineTask::Consolidate(Box::new(ConsolidateTask::new(
-                        state.client.clone(),
-                        state.rollup.clone(),
-                        attributes,
-                        true,
-                    )));
+                    let task = EngineTask::Consolidate(Box::new(
+                        ConsolidateTask::new(
+                            state.client.clone(),
+                            state.rollup.clone(),
+                            attributes,
+                            true,
+                        )
+                        .with_derivation_span(span),
+                    ));
                     state.engine.enqueue(task);
                 }
                 msg = self.finalizer.new_finalized_block() => {
@@ -5,13 +5,16 @@ use crate::{
     NodeActor,
     actors::{CancellableContext, l1_watcher::error::L1WatcherActorError},
 };
-use alloy_eips::BlockId;
+use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::Address;
 use alloy_provider::Provider;
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
-use kona_genesis::{RollupConfig, SystemConfigLog, SystemConfigUpdate, UnsafeBlockSignerUpdate};
+use kona_genesis::{
+    RollupConfig, SystemConfig, SystemConfigLog, SystemConfigUpdate, UnsafeBlockSignerUpdate,
+};
 use kona_protocol::BlockInfo;
+use kona_providers_alloy::AlloySystemConfigProvider;
 use kona_rpc::{L1State, L1WatcherQueries};
 use std::sync::Arc;
 use tokio::{
@@ -35,6 +38,9 @@ where
     rollup_config: Arc<RollupConfig>,
     /// The L1 provider.
     l1_provider: L1P,
+    /// The provider reconstructing the historical system config from L1 logs. Its clones, used by
+    /// the tasks answering system config queries, share the replayed history.
+    system_config_provider: AlloySystemConfigProvider,
     /// The inbound queries to the L1 watcher.
     inbound_queries: mpsc::Receiver<L1WatcherQueries>,
     /// The latest L1 head block.
@@ -57,9 +63,10 @@ where
 {
     /// Instantiate a new [`L1WatcherActor`].
     #[allow(clippy::too_many_arguments)]
-    pub const fn new(
+    pub fn new(
         rollup_config: Arc<RollupConfig>,
         l1_provider: L1P,
+        system_config_provider: AlloySystemConfigProvider,
         l1_query_rx: mpsc::Receiver<L1WatcherQueries>,
         l1_head_updates_tx: watch::Sender<Option<BlockInfo>>,
         finalized_l1_block_tx: watch::Sender<Option<BlockInfo>>,
@@ -71,6 +78,7 @@ where
         Self {
             rollup_config,
             l1_provider,
+            system_config_provider,
             inbound_queries: l1_query_rx,
             latest_head: l1_head_updates_tx,
             latest_finalized: finalized_l1_block_tx,
@@ -189,6 +197,17 @@ where
                                 warn!(target: "l1_watcher", error = ?e, "Failed to send L1 state to the query sender");
                             }
                         }
+                        L1WatcherQueries::SystemConfigAtL1Block { block, sender } => {
+                            // Reconstructing the system config may replay a large range of L1
+                            // logs, so it is done off the main loop.
+                            let provider = self.system_config_provider.clone();
+                            tokio::spawn(async move {
+                                let system_config = system_config_at_l1_block(&provider, block).await;
+                                if let Err(e) = sender.send(system_config) {
+                                    warn!(target: "l1_watcher", error = ?e, "Failed to send system config to the query sender");
+                                }
+                            });
+                        }
                     }
                 },
                 None => {
@@ -201,6 +220,36 @@ where
     }
 }
 
+/// Reconstructs the system config in effect at the given L1 block, returning `None` if it could
+/// not be reconstructed. Blocks past the L1 head, such as the pending block, are rejected.
+async fn system_config_at_l1_block(
+    provider: &AlloySystemConfigProvider,
+    block: BlockNumberOrTag,
+) -> Option<SystemConfig> {
+    let number = match block {
+        BlockNumberOrTag::Number(number) => number,
+        tag => match provider.inner.get_block_by_number(tag).await {
+            Ok(Some(block)) => block.header.number,
+            Ok(None) => {
+                warn!(target: "l1_watcher", %tag, "L1 block not found for system config query");
+                return None;
+            }
+            Err(e) => {
+                warn!(target: "l1_watcher", error = ?e, %tag, "Failed to query l1 provider for block");
+                return None;
+            }
+        },
+    };
+
+    provider
+        .system_config_at_l1_block(number)
+        .await
+        .inspect_err(|e| {
+            warn!(target: "l1_watcher", error = ?e, number, "Failed to reconstruct system config");
+        })
+        .ok()
+}
+
 impl<BS, L1P> CancellableContext for L1WatcherActor<BS, L1P>
 where
     BS: Stream<Item = BlockInfo> + Unpin + Send + 'static,
@@ -18,7 +18,8 @@ pub use rpc::{RpcActor, RpcActorError, RpcContext};
 mod derivation;
 pub use derivation::{
     DerivationActor, DerivationBuilder, DerivationContext, DerivationError,
-    DerivationInboundChannels, DerivationState, InboundDerivationMessage, PipelineBuilder,
+    DerivationInboundChannels, DerivationState, DerivedAttributes, InboundDerivationMessage,
+    PipelineBuilder,
 };
 
 mod l1_watcher;
@@ -34,8 +35,9 @@ pub use network::{
 mod sequencer;
 pub use sequencer::{
     Conductor, ConductorClient, ConductorError, DelayedL1OriginSelectorProvider, L1OriginSelector,
-    L1OriginSelectorError, L1OriginSelectorProvider, OriginSelector, QueuedSequencerAdminAPIClient,
-    SequencerActor, SequencerActorError, SequencerAdminQuery, SequencerConfig,
+    L1OriginSelectorError, L1OriginSelectorProvider, OriginSelectionStrategy, OriginSelector,
+    QueuedSequencerAdminAPIClient, SequencerActor, SequencerActorError, SequencerAdminQuery,
+    SequencerConfig,
 };
 
 #[cfg(test)]
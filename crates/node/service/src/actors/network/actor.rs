@@ -73,6 +73,12 @@ pub struct NetworkInboundData {
 }
 
 impl NetworkActor {
+    /// The interval at which disconnected sentry peers are redialed.
+    const SENTRY_REDIAL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(10);
+
+    /// The interval at which the peer database is synced to disk.
+    const PEER_DB_SYNC_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(60);
+
     /// Constructs a new [`NetworkActor`] given the [`NetworkBuilder`]
     pub fn new(driver: NetworkBuilder) -> (NetworkInboundData, Self) {
         let (signer_tx, signer_rx) = mpsc::channel(16);
@@ -151,6 +157,12 @@ impl NodeActor for NetworkActor {
         // New unsafe block channel.
         let (unsafe_block_tx, mut unsafe_block_rx) = tokio::sync::mpsc::unbounded_channel();
 
+        // In sentry mode, periodically redial the sentry peers that are disconnected.
+        let mut sentry_redial = tokio::time::interval(Self::SENTRY_REDIAL_INTERVAL);
+
+        // Periodically persist peer reputation to disk.
+        let mut peer_db_sync = tokio::time::interval(Self::PEER_DB_SYNC_INTERVAL);
+
         loop {
             select! {
                 _ = cancellation.cancelled() => {
@@ -158,6 +170,7 @@ impl NodeActor for NetworkActor {
                         target: "network",
                         "Received shutdown signal. Exiting network task."
                     );
+                    handler.gossip.sync_peer_db();
                     return Ok(());
                 }
                 block = unsafe_block_rx.recv() => {
@@ -227,7 +240,7 @@ impl NodeActor for NetworkActor {
                         }
                     }
                 },
-                enr = handler.enr_receiver.recv() => {
+                enr = handler.enr_receiver.recv(), if !handler.gossip.connection_gate.is_sentry_mode() => {
                     let Some(enr) = enr else {
                         error!(target: "node::p2p", "The enr receiver channel has closed");
                         return Err(NetworkActorError::ChannelClosed);
@@ -237,6 +250,12 @@ impl NodeActor for NetworkActor {
                 _ = handler.peer_score_inspector.tick(), if handler.gossip.peer_monitoring.as_ref().is_some() => {
                     handler.handle_peer_monitoring().await;
                 },
+                _ = sentry_redial.tick(), if handler.gossip.connection_gate.is_sentry_mode() => {
+                    handler.gossip.dial_sentries();
+                },
+                _ = peer_db_sync.tick(), if handler.gossip.peer_db.is_some() => {
+                    handler.gossip.sync_peer_db();
+                },
                 Some(NetworkAdminQuery::PostUnsafePayload { payload }) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => {
                     debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
                     if unsafe_block_tx.send(payload).is_err() {
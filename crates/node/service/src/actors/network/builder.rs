@@ -5,7 +5,7 @@ use discv5::Config as Discv5Config;
 use kona_disc::{Discv5Builder, LocalNode};
 use kona_genesis::RollupConfig;
 use kona_gossip::{GaterConfig, GossipDriverBuilder};
-use kona_peers::{BootNodes, BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BootNodes, BootStoreFile, PeerDbFile, PeerMonitoring, PeerScoreLevel};
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair};
 use std::time::Duration;
@@ -45,6 +45,7 @@ impl From<NetworkConfig> for NetworkBuilder {
         .with_enr_update(config.enr_update)
         .with_discovery_randomize(config.discovery_randomize)
         .with_bootstore(config.bootstore)
+        .with_peer_db(config.peer_db)
         .with_bootnodes(config.bootnodes)
         .with_discovery_interval(config.discovery_interval)
         .with_gossip_config(config.gossip_config)
@@ -103,6 +104,11 @@ impl NetworkBuilder {
         Self { discovery: self.discovery.with_bootstore_file(bootstore), ..self }
     }
 
+    /// Sets the persistent peer database path for the [`GossipDriverBuilder`].
+    pub fn with_peer_db(self, peer_db: Option<PeerDbFile>) -> Self {
+        Self { gossip: self.gossip.with_peer_db(peer_db), ..self }
+    }
+
     /// Sets the interval at which to randomize discovery peers.
     pub fn with_discovery_randomize(self, randomize: Option<Duration>) -> Self {
         Self { discovery: self.discovery.with_discovery_randomize(randomize), ..self }
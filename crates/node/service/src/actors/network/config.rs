@@ -4,7 +4,7 @@ use alloy_primitives::Address;
 use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
 use kona_gossip::GaterConfig;
-use kona_peers::{BootNodes, BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BootNodes, BootStoreFile, PeerDbFile, PeerMonitoring, PeerScoreLevel};
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair};
 use tokio::time::Duration;
@@ -39,6 +39,8 @@ pub struct NetworkConfig {
     pub monitor_peers: Option<PeerMonitoring>,
     /// An optional path to the bootstore.
     pub bootstore: Option<BootStoreFile>,
+    /// An optional path to the persistent peer database.
+    pub peer_db: Option<PeerDbFile>,
     /// The configuration for the connection gater.
     pub gater_config: GaterConfig,
     /// An optional list of bootnode ENRs to start the node with.
@@ -89,6 +91,7 @@ impl NetworkConfig {
             keypair: Keypair::generate_secp256k1(),
             bootnodes: Default::default(),
             bootstore: Default::default(),
+            peer_db: Default::default(),
             gater_config: Default::default(),
             gossip_config: Default::default(),
             scoring: Default::default(),
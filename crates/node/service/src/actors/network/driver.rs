@@ -1,13 +1,17 @@
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 use alloy_primitives::Address;
 use discv5::multiaddr::Protocol;
 use futures::future::OptionFuture;
-use kona_disc::Discv5Driver;
+use kona_disc::{Discv5Driver, Discv5Handler, HandlerRequest};
 use kona_gossip::{ConnectionGater, GossipDriver, PEER_SCORE_INSPECT_FREQUENCY};
 use kona_sources::{BlockSigner, BlockSignerStartError};
 use libp2p::{Multiaddr, TransportError};
-use tokio::sync::watch;
+use tokio::sync::{mpsc, watch};
 
 use crate::actors::network::handler::NetworkHandler;
 
@@ -75,8 +79,35 @@ impl NetworkDriver {
             self.discovery.disc.update_local_enr_socket(SocketAddr::new(ip_address, port), true);
         }
 
-        // Start the discovery service.
-        let (handler, enr_receiver) = self.discovery.start();
+        // In sentry mode, discovery is disabled entirely, so that the node's ENR and address are
+        // never advertised. Discovery requests fail and no ENRs are forwarded to the swarm.
+        let sentry_mode = self.gossip.connection_gate.is_sentry_mode();
+        let (handler, enr_receiver) = if sentry_mode {
+            info!(target: "network", "Sentry mode enabled, discovery is disabled");
+            let (sender, _) = mpsc::channel(1);
+            let (_, enr_receiver) = mpsc::channel(1);
+            (Discv5Handler::new(self.discovery.chain_id, sender), enr_receiver)
+        } else {
+            self.discovery.start()
+        };
+
+        // Ban the peers that were still banned when the node was last stopped from the discovery
+        // service, using their last-seen address.
+        if let Some(db) = self.gossip.peer_db.as_ref().filter(|_| !sentry_mode) {
+            for (peer_id, ban_duration) in db.banned_peers() {
+                let Some(addr) = db.get(&peer_id).and_then(|r| r.last_seen_addr.clone()) else {
+                    continue;
+                };
+                let addr = addr.clone().with_p2p(peer_id).unwrap_or(addr);
+                let request = HandlerRequest::BanAddrs {
+                    addrs_to_ban: Arc::new(HashSet::from([addr])),
+                    ban_duration,
+                };
+                if let Err(e) = handler.sender.send(request).await {
+                    warn!(target: "network", err = ?e, "Failed to restore peer ban in the discovery service");
+                }
+            }
+        }
 
         // We are checking the peer scores every [`PEER_SCORE_INSPECT_FREQUENCY`] seconds.
         let peer_score_inspector = tokio::time::interval(*PEER_SCORE_INSPECT_FREQUENCY);
@@ -80,6 +80,9 @@ impl NetworkHandler {
 
                         if let Some(info) = self.gossip.peerstore.remove(&peer_to_remove){
                             use kona_gossip::ConnectionGate;
+                            if let Some(db) = self.gossip.peer_db.as_mut() {
+                                db.ban(peer_to_remove, ban_peers.ban_duration);
+                            }
                             self.gossip.connection_gate.remove_dial(&peer_to_remove);
                             let score = self.gossip.swarm.behaviour().gossipsub.peer_score(&peer_to_remove).unwrap_or_default();
                             kona_macros::inc!(gauge, kona_gossip::Metrics::BANNED_PEERS, "peer_id" => peer_to_remove.to_string(), "score" => score.to_string());
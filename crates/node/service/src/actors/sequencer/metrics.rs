@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use kona_protocol::BlockInfo;
+
 use crate::{
     BlockBuildingClient, Conductor, OriginSelector, SequencerActor, UnsafePayloadGossipClient,
 };
@@ -68,3 +70,31 @@ pub(super) fn update_seal_duration_metrics(duration: Duration) {
     // Log the block building seal task duration, if metrics are enabled.
     kona_macros::set!(gauge, crate::Metrics::SEQUENCER_BLOCK_BUILDING_SEAL_TASK_DURATION, duration);
 }
+
+#[inline]
+pub(super) fn update_l1_origin_lag_metrics(
+    strategy: &'static str,
+    l1_origin: &BlockInfo,
+    l1_head: Option<BlockInfo>,
+) {
+    // no-op if disabled. The lag can only be computed once the L1 head is known.
+    #[cfg(feature = "metrics")]
+    if let Some(l1_head) = l1_head {
+        let lag_blocks = l1_head.number.saturating_sub(l1_origin.number);
+        let lag_secs = l1_head.timestamp.saturating_sub(l1_origin.timestamp);
+        kona_macros::set!(
+            gauge,
+            crate::Metrics::SEQUENCER_L1_ORIGIN_LAG_BLOCKS,
+            "strategy",
+            strategy,
+            lag_blocks as f64
+        );
+        kona_macros::set!(
+            gauge,
+            crate::Metrics::SEQUENCER_L1_ORIGIN_LAG_SECONDS,
+            "strategy",
+            strategy,
+            lag_secs as f64
+        );
+    }
+}
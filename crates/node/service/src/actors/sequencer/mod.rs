@@ -6,7 +6,7 @@ pub use config::SequencerConfig;
 mod origin_selector;
 pub use origin_selector::{
     DelayedL1OriginSelectorProvider, L1OriginSelector, L1OriginSelectorError,
-    L1OriginSelectorProvider, OriginSelector,
+    L1OriginSelectorProvider, OriginSelectionStrategy, OriginSelector,
 };
 
 mod actor;
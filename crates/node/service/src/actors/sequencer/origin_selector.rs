@@ -1,5 +1,6 @@
 //! The [`L1OriginSelector`].
 
+use super::metrics::update_l1_origin_lag_metrics;
 use alloy_primitives::B256;
 use alloy_provider::{Provider, RootProvider};
 use alloy_transport::{RpcError, TransportErrorKind};
@@ -31,6 +32,55 @@ pub trait OriginSelector: Debug + Send + Sync {
     ) -> Result<BlockInfo, L1OriginSelectorError>;
 }
 
+/// The strategy used by the [`L1OriginSelector`] to decide when the next L1 origin may be adopted.
+///
+/// Regardless of the strategy, the next L1 origin is only ever adopted once the next L2 block's
+/// timestamp has caught up with it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OriginSelectionStrategy {
+    /// Adopt the next L1 origin as soon as the [`L1OriginSelectorProvider`] serves it. The
+    /// provider's confirmation delay is applied on a best-effort basis.
+    #[default]
+    Default,
+    /// Only adopt L1 origins that have at least `depth` confirmations relative to the observed L1
+    /// head. If the L1 head is not known, the origin is not advanced.
+    Confirmations {
+        /// The minimum number of confirmations for an L1 block to be adopted as an origin.
+        depth: u64,
+    },
+    /// Cap how far the L1 origin may lag behind the L1 head. Once the current origin is more than
+    /// `max_lag` blocks behind the L1 head, the provider's confirmation delay is bypassed so that
+    /// the origin can catch up.
+    MaxLag {
+        /// The maximum number of blocks that the L1 origin may lag behind the L1 head.
+        max_lag: u64,
+    },
+    /// Back off from advancing the L1 origin while the L1 provider serves stale data.
+    ///
+    /// The provider is considered stale if the L1 head is more than `max_head_age` seconds older
+    /// than the next L2 block, if the next L1 block does not extend the current origin, or if the
+    /// provider returns an error. While backing off, the current origin is kept until either
+    /// `backoff` seconds of L2 time have passed or the sequencer drift is exceeded.
+    StaleBackoff {
+        /// The maximum age of the L1 head, in seconds, relative to the next L2 block's timestamp.
+        max_head_age: u64,
+        /// The duration of the back off, in seconds of L2 time.
+        backoff: u64,
+    },
+}
+
+impl OriginSelectionStrategy {
+    /// Returns the metric label for the strategy.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Confirmations { .. } => "confirmations",
+            Self::MaxLag { .. } => "max_lag",
+            Self::StaleBackoff { .. } => "stale_backoff",
+        }
+    }
+}
+
 /// The [`L1OriginSelector`] is responsible for selecting the L1 origin block based on the
 /// current L2 unsafe head's sequence epoch.
 #[derive(Debug)]
@@ -39,10 +89,14 @@ pub struct L1OriginSelector<P: L1OriginSelectorProvider> {
     cfg: Arc<RollupConfig>,
     /// The [`L1OriginSelectorProvider`].
     l1: P,
+    /// The [`OriginSelectionStrategy`].
+    strategy: OriginSelectionStrategy,
     /// The current L1 origin.
     current: Option<BlockInfo>,
     /// The next L1 origin.
     next: Option<BlockInfo>,
+    /// The L2 timestamp until which the selector backs off from fetching the next L1 origin.
+    backoff_until: Option<u64>,
 }
 
 #[async_trait]
@@ -61,6 +115,10 @@ impl<P: L1OriginSelectorProvider + Send + Sync> OriginSelector for L1OriginSelec
     ) -> Result<BlockInfo, L1OriginSelectorError> {
         self.select_origins(&unsafe_head, is_recovery_mode).await?;
 
+        if let Some(current) = self.current {
+            update_l1_origin_lag_metrics(self.strategy.label(), &current, self.l1.l1_head());
+        }
+
         // Start building on the next L1 origin block if the next L2 block's timestamp is
         // greater than or equal to the next L1 origin's timestamp.
         if let Some(next) = self.next {
@@ -108,7 +166,25 @@ impl<P: L1OriginSelectorProvider + Send + Sync> OriginSelector for L1OriginSelec
 impl<P: L1OriginSelectorProvider> L1OriginSelector<P> {
     /// Creates a new [`L1OriginSelector`].
     pub const fn new(cfg: Arc<RollupConfig>, l1: P) -> Self {
-        Self { cfg, l1, current: None, next: None }
+        Self {
+            cfg,
+            l1,
+            strategy: OriginSelectionStrategy::Default,
+            current: None,
+            next: None,
+            backoff_until: None,
+        }
+    }
+
+    /// Sets the [`OriginSelectionStrategy`] of the [`L1OriginSelector`].
+    pub const fn with_strategy(mut self, strategy: OriginSelectionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the [`OriginSelectionStrategy`] of the [`L1OriginSelector`].
+    pub const fn strategy(&self) -> OriginSelectionStrategy {
+        self.strategy
     }
 
     /// Returns the current L1 origin.
@@ -147,27 +223,129 @@ impl<P: L1OriginSelectorProvider> L1OriginSelector<P> {
             self.next = None;
         }
 
-        self.try_fetch_next_origin().await
+        self.try_fetch_next_origin(unsafe_head.block_info.timestamp + self.cfg.block_time).await
     }
 
-    /// Attempts to fetch the next L1 origin block.
-    async fn try_fetch_next_origin(&mut self) -> Result<(), L1OriginSelectorError> {
+    /// Attempts to fetch the next L1 origin block, subject to the [`OriginSelectionStrategy`].
+    async fn try_fetch_next_origin(
+        &mut self,
+        next_l2_time: u64,
+    ) -> Result<(), L1OriginSelectorError> {
         // If there is no next L1 origin set, attempt to find it. If it's not yet available, leave
         // it unset.
-        if let Some(current) = self.current.as_ref() {
-            // If the next L1 origin is already set, do nothing.
-            if self.next.is_some() {
-                return Ok(());
-            }
+        let Some(current) = self.current else {
+            return Ok(());
+        };
+
+        // If the next L1 origin is already set, do nothing.
+        if self.next.is_some() {
+            return Ok(());
+        }
 
-            // If the next L1 origin is a logical extension of the current L1 chain, set it.
-            //
-            // Ignore the eventuality that the block is not found, as the next L1 origin fetch is
-            // performed on a best-effort basis.
-            let next = self.l1.get_block_by_number(current.number + 1).await?;
-            if next.map(|n| n.parent_hash == current.hash).unwrap_or(false) {
-                self.next = next;
+        let l1_head = self.l1.l1_head();
+        let next = match self.strategy {
+            OriginSelectionStrategy::Default => {
+                self.l1.get_block_by_number(current.number + 1).await?
             }
+            OriginSelectionStrategy::Confirmations { depth } => {
+                // Only look up the next origin once it is buried under enough L1 blocks, unless
+                // the sequencer drift forces the origin to advance.
+                if l1_head.is_some_and(|head| current.number + 1 + depth <= head.number) {
+                    self.l1.get_block_by_number(current.number + 1).await?
+                } else {
+                    let max_seq_drift = self.cfg.max_sequencer_drift(current.timestamp);
+                    if next_l2_time.saturating_sub(current.timestamp) <= max_seq_drift {
+                        return Ok(());
+                    }
+                    debug!(
+                        target: "l1_origin_selector",
+                        current_origin = current.number,
+                        depth,
+                        "Sequencer drift exceeded, advancing to an unconfirmed L1 origin"
+                    );
+                    kona_macros::inc!(
+                        counter,
+                        crate::Metrics::SEQUENCER_L1_ORIGIN_FORCED_ADVANCE,
+                        "strategy" => self.strategy.label()
+                    );
+                    self.l1.get_unconfirmed_block_by_number(current.number + 1).await?
+                }
+            }
+            OriginSelectionStrategy::MaxLag { max_lag } => {
+                let lag = l1_head.map(|head| head.number.saturating_sub(current.number));
+                if lag.is_some_and(|lag| lag > max_lag) {
+                    debug!(
+                        target: "l1_origin_selector",
+                        current_origin = current.number,
+                        lag,
+                        max_lag,
+                        "L1 origin lags too far behind the L1 head, bypassing confirmation delay"
+                    );
+                    kona_macros::inc!(
+                        counter,
+                        crate::Metrics::SEQUENCER_L1_ORIGIN_FORCED_ADVANCE,
+                        "strategy" => self.strategy.label()
+                    );
+                    self.l1.get_unconfirmed_block_by_number(current.number + 1).await?
+                } else {
+                    self.l1.get_block_by_number(current.number + 1).await?
+                }
+            }
+            OriginSelectionStrategy::StaleBackoff { max_head_age, backoff } => {
+                let max_seq_drift = self.cfg.max_sequencer_drift(current.timestamp);
+                let past_seq_drift = next_l2_time.saturating_sub(current.timestamp) > max_seq_drift;
+
+                // Keep the current origin while backing off, unless the sequencer drift forces
+                // the origin to advance.
+                if !past_seq_drift && self.backoff_until.is_some_and(|until| next_l2_time < until) {
+                    return Ok(());
+                }
+                self.backoff_until = None;
+
+                // A stale L1 head is only respected while the sequencer drift has not been
+                // exceeded.
+                let stale_head = !past_seq_drift &&
+                    l1_head.is_some_and(|head| head.timestamp + max_head_age < next_l2_time);
+                let (next, stale) = if stale_head {
+                    (None, true)
+                } else {
+                    match self.l1.get_block_by_number(current.number + 1).await {
+                        // A next block that does not extend the current origin indicates that the
+                        // provider's view of the L1 chain is outdated.
+                        Ok(next) => (next, next.is_some_and(|n| n.parent_hash != current.hash)),
+                        Err(err) if !past_seq_drift => {
+                            warn!(target: "l1_origin_selector", ?err, "Failed to fetch the next L1 origin");
+                            (None, true)
+                        }
+                        Err(err) => return Err(err),
+                    }
+                };
+
+                if stale && !past_seq_drift {
+                    warn!(
+                        target: "l1_origin_selector",
+                        current_origin = current.number,
+                        stale_head,
+                        backoff,
+                        "L1 provider is serving stale data, backing off from advancing the L1 origin"
+                    );
+                    kona_macros::inc!(
+                        counter,
+                        crate::Metrics::SEQUENCER_L1_ORIGIN_BACKOFF,
+                        "strategy" => self.strategy.label()
+                    );
+                    self.backoff_until = Some(next_l2_time + backoff);
+                }
+                next
+            }
+        };
+
+        // If the next L1 origin is a logical extension of the current L1 chain, set it.
+        //
+        // Ignore the eventuality that the block is not found, as the next L1 origin fetch is
+        // performed on a best-effort basis.
+        if next.is_some_and(|n| n.parent_hash == current.hash) {
+            self.next = next;
         }
 
         Ok(())
@@ -201,6 +379,20 @@ pub trait L1OriginSelectorProvider: Debug + Sync {
         &self,
         number: u64,
     ) -> Result<Option<BlockInfo>, L1OriginSelectorError>;
+
+    /// Returns a [`BlockInfo`] by its number, bypassing any confirmation delay applied by the
+    /// provider.
+    async fn get_unconfirmed_block_by_number(
+        &self,
+        number: u64,
+    ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
+        self.get_block_by_number(number).await
+    }
+
+    /// Returns the latest L1 head observed by the provider, if it is known.
+    fn l1_head(&self) -> Option<BlockInfo> {
+        None
+    }
 }
 
 /// A wrapper around the [`RootProvider`] that delays the view of the L1 chain by a configurable
@@ -256,6 +448,17 @@ impl L1OriginSelectorProvider for DelayedL1OriginSelectorProvider {
             Ok(None)
         }
     }
+
+    async fn get_unconfirmed_block_by_number(
+        &self,
+        number: u64,
+    ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
+        Ok(Provider::get_block_by_number(&self.inner, number.into()).await?.map(Into::into))
+    }
+
+    fn l1_head(&self) -> Option<BlockInfo> {
+        *self.l1_head.borrow()
+    }
 }
 
 #[cfg(test)]
@@ -269,6 +472,8 @@ mod test {
     #[derive(Default, Debug, Clone)]
     struct MockOriginSelectorProvider {
         blocks: HashSet<BlockInfo>,
+        l1_head: Option<BlockInfo>,
+        confirmation_depth: u64,
     }
 
     impl MockOriginSelectorProvider {
@@ -276,6 +481,16 @@ mod test {
         pub(crate) fn with_block(&mut self, block: BlockInfo) {
             self.blocks.insert(block);
         }
+
+        /// Sets the L1 head observed by the [`MockOriginSelectorProvider`].
+        pub(crate) const fn with_l1_head(&mut self, l1_head: BlockInfo) {
+            self.l1_head = Some(l1_head);
+        }
+
+        /// Sets the confirmation depth applied by the [`MockOriginSelectorProvider`].
+        pub(crate) const fn with_confirmation_depth(&mut self, confirmation_depth: u64) {
+            self.confirmation_depth = confirmation_depth;
+        }
     }
 
     #[async_trait]
@@ -291,8 +506,181 @@ mod test {
             &self,
             number: u64,
         ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
+            if self.l1_head.is_some_and(|head| number + self.confirmation_depth > head.number) {
+                return Ok(None);
+            }
             Ok(self.blocks.iter().find(|b| b.number == number).copied())
         }
+
+        async fn get_unconfirmed_block_by_number(
+            &self,
+            number: u64,
+        ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
+            Ok(self.blocks.iter().find(|b| b.number == number).copied())
+        }
+
+        fn l1_head(&self) -> Option<BlockInfo> {
+            self.l1_head
+        }
+    }
+
+    /// Creates a [`MockOriginSelectorProvider`] with a chain of `num_blocks` L1 blocks, spaced
+    /// `slot_time` seconds apart, and the last block as the L1 head.
+    fn mock_l1_chain(num_blocks: u64, slot_time: u64) -> MockOriginSelectorProvider {
+        let mut provider = MockOriginSelectorProvider::default();
+        for i in 0..num_blocks {
+            provider.with_block(BlockInfo {
+                parent_hash: B256::with_last_byte(i.saturating_sub(1) as u8),
+                hash: B256::with_last_byte(i as u8),
+                number: i,
+                timestamp: i * slot_time,
+            });
+        }
+        provider.with_l1_head(BlockInfo {
+            parent_hash: B256::with_last_byte(num_blocks.saturating_sub(2) as u8),
+            hash: B256::with_last_byte((num_blocks - 1) as u8),
+            number: num_blocks - 1,
+            timestamp: (num_blocks - 1) * slot_time,
+        });
+        provider
+    }
+
+    /// Creates an [`L2BlockInfo`] at the given timestamp, with the genesis L1 block as its origin.
+    fn unsafe_head_at(timestamp: u64) -> L2BlockInfo {
+        L2BlockInfo {
+            block_info: BlockInfo { timestamp, ..Default::default() },
+            l1_origin: NumHash { number: 0, hash: B256::ZERO },
+            seq_num: 0,
+        }
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case::not_confirmed(10, 0)]
+    #[case::confirmed(4, 1)]
+    async fn test_next_l1_origin_confirmations_strategy(
+        #[case] depth: u64,
+        #[case] expected_origin: u64,
+    ) {
+        let cfg = Arc::new(RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            ..Default::default()
+        });
+
+        // The L1 head is at block 5.
+        let provider = mock_l1_chain(6, 12);
+        let mut selector = L1OriginSelector::new(cfg, provider)
+            .with_strategy(OriginSelectionStrategy::Confirmations { depth });
+
+        let next = selector.next_l1_origin(unsafe_head_at(12), false).await.unwrap();
+        assert_eq!(next.number, expected_origin);
+    }
+
+    #[tokio::test]
+    async fn test_next_l1_origin_confirmations_strategy_unknown_head() {
+        let cfg = Arc::new(RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            ..Default::default()
+        });
+
+        let mut provider = mock_l1_chain(6, 12);
+        provider.l1_head = None;
+        let mut selector = L1OriginSelector::new(cfg, provider)
+            .with_strategy(OriginSelectionStrategy::Confirmations { depth: 0 });
+
+        // Without a known L1 head, the origin may not be advanced.
+        let next = selector.next_l1_origin(unsafe_head_at(12), false).await.unwrap();
+        assert_eq!(next.number, 0);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case::within_seq_drift(12, 0)]
+    #[case::past_seq_drift(600, 1)]
+    async fn test_next_l1_origin_confirmations_strategy_past_seq_drift(
+        #[case] unsafe_head_time: u64,
+        #[case] expected_origin: u64,
+    ) {
+        let cfg = Arc::new(RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            ..Default::default()
+        });
+
+        // The L1 head is at block 5, and the provider only serves confirmed blocks, so the next
+        // origin is never buried deep enough.
+        let mut provider = mock_l1_chain(6, 12);
+        provider.with_confirmation_depth(10);
+        let mut selector = L1OriginSelector::new(cfg, provider)
+            .with_strategy(OriginSelectionStrategy::Confirmations { depth: 10 });
+
+        // Once the next L2 block would exceed the sequencer drift, the unconfirmed next origin is
+        // used.
+        let next = selector.next_l1_origin(unsafe_head_at(unsafe_head_time), false).await.unwrap();
+        assert_eq!(next.number, expected_origin);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case::within_max_lag(30, 0)]
+    #[case::past_max_lag(5, 1)]
+    async fn test_next_l1_origin_max_lag_strategy(
+        #[case] max_lag: u64,
+        #[case] expected_origin: u64,
+    ) {
+        let cfg = Arc::new(RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            ..Default::default()
+        });
+
+        // The L1 head is at block 20, and the provider only serves blocks with at least 25
+        // confirmations.
+        let mut provider = mock_l1_chain(21, 12);
+        provider.with_confirmation_depth(25);
+        let mut selector = L1OriginSelector::new(cfg, provider)
+            .with_strategy(OriginSelectionStrategy::MaxLag { max_lag });
+
+        let next = selector.next_l1_origin(unsafe_head_at(12), false).await.unwrap();
+        assert_eq!(next.number, expected_origin);
+    }
+
+    #[tokio::test]
+    async fn test_next_l1_origin_stale_backoff_strategy() {
+        let cfg = Arc::new(RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            ..Default::default()
+        });
+
+        // The L1 head is at block 1, with a timestamp of 12.
+        let provider = mock_l1_chain(2, 12);
+        let mut selector = L1OriginSelector::new(cfg, provider)
+            .with_strategy(OriginSelectionStrategy::StaleBackoff { max_head_age: 30, backoff: 24 });
+
+        // The L1 head is more than 30 seconds older than the next L2 block, so the selector backs
+        // off and keeps the current origin.
+        let next = selector.next_l1_origin(unsafe_head_at(48), false).await.unwrap();
+        assert_eq!(next.number, 0);
+        assert_eq!(selector.backoff_until, Some(74));
+
+        // Once the L1 head is fresh again, the selector keeps backing off until the back off has
+        // elapsed.
+        selector.l1.with_l1_head(BlockInfo {
+            parent_hash: B256::with_last_byte(0),
+            hash: B256::with_last_byte(1),
+            number: 1,
+            timestamp: 60,
+        });
+        let next = selector.next_l1_origin(unsafe_head_at(60), false).await.unwrap();
+        assert_eq!(next.number, 0);
+        assert!(selector.next().is_none());
+
+        let next = selector.next_l1_origin(unsafe_head_at(72), false).await.unwrap();
+        assert_eq!(next.number, 1);
+        assert!(selector.backoff_until.is_none());
     }
 
     #[tokio::test]
@@ -19,16 +19,16 @@ pub use actors::{
     BlockBuildingClient, BlockEngineError, BlockEngineResult, BlockStream, BuildRequest,
     CancellableContext, Conductor, ConductorClient, ConductorError,
     DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder, DerivationContext,
-    DerivationError, DerivationInboundChannels, DerivationState, EngineActor, EngineConfig,
-    EngineContext, EngineError, EngineInboundData, InboundDerivationMessage, L1OriginSelector,
-    L1OriginSelectorError, L1OriginSelectorProvider, L1WatcherActor, L1WatcherActorError,
-    L2Finalizer, NetworkActor, NetworkActorError, NetworkBuilder, NetworkBuilderError,
-    NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError, NetworkHandler,
-    NetworkInboundData, NodeActor, OriginSelector, PipelineBuilder, QueuedBlockBuildingClient,
-    QueuedSequencerAdminAPIClient, QueuedUnsafePayloadGossipClient, ResetRequest, RpcActor,
-    RpcActorError, RpcContext, SealRequest, SequencerActor, SequencerActorError,
-    SequencerAdminQuery, SequencerConfig, UnsafePayloadGossipClient,
-    UnsafePayloadGossipClientError,
+    DerivationError, DerivationInboundChannels, DerivationState, DerivedAttributes, EngineActor,
+    EngineConfig, EngineContext, EngineError, EngineInboundData, InboundDerivationMessage,
+    L1OriginSelector, L1OriginSelectorError, L1OriginSelectorProvider, L1WatcherActor,
+    L1WatcherActorError, L2Finalizer, NetworkActor, NetworkActorError, NetworkBuilder,
+    NetworkBuilderError, NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError,
+    NetworkHandler, NetworkInboundData, NodeActor, OriginSelectionStrategy, OriginSelector,
+    PipelineBuilder, QueuedBlockBuildingClient, QueuedSequencerAdminAPIClient,
+    QueuedUnsafePayloadGossipClient, ResetRequest, RpcActor, RpcActorError, RpcContext,
+    SealRequest, SequencerActor, SequencerActorError, SequencerAdminQuery, SequencerConfig,
+    UnsafePayloadGossipClient, UnsafePayloadGossipClientError,
 };
 
 mod metrics;
@@ -33,6 +33,21 @@ impl Metrics {
     pub const SEQUENCER_CONDUCTOR_COMMITMENT_DURATION: &str =
         "kona_node_sequencer_conductor_commitment_duration";
 
+    /// Gauge for the number of L1 blocks the sequencer's L1 origin lags behind the L1 head.
+    pub const SEQUENCER_L1_ORIGIN_LAG_BLOCKS: &str = "kona_node_sequencer_l1_origin_lag_blocks";
+
+    /// Gauge for the number of seconds the sequencer's L1 origin lags behind the L1 head.
+    pub const SEQUENCER_L1_ORIGIN_LAG_SECONDS: &str = "kona_node_sequencer_l1_origin_lag_seconds";
+
+    /// Counter for the number of times the sequencer bypassed the confirmation delay to catch up
+    /// with the L1 head.
+    pub const SEQUENCER_L1_ORIGIN_FORCED_ADVANCE: &str =
+        "kona_node_sequencer_l1_origin_forced_advance";
+
+    /// Counter for the number of times the sequencer backed off from advancing its L1 origin due
+    /// to stale L1 data.
+    pub const SEQUENCER_L1_ORIGIN_BACKOFF: &str = "kona_node_sequencer_l1_origin_backoff";
+
     /// Initializes metrics for the node service.
     ///
     /// This does two things:
@@ -85,6 +100,32 @@ impl Metrics {
             Self::SEQUENCER_CONDUCTOR_COMMITMENT_DURATION,
             "Duration of the sequencer conductor commitment"
         );
+
+        // Sequencer L1 origin lag
+        metrics::describe_gauge!(
+            Self::SEQUENCER_L1_ORIGIN_LAG_BLOCKS,
+            metrics::Unit::Count,
+            "Number of L1 blocks the sequencer's L1 origin lags behind the L1 head"
+        );
+        metrics::describe_gauge!(
+            Self::SEQUENCER_L1_ORIGIN_LAG_SECONDS,
+            metrics::Unit::Seconds,
+            "Number of seconds the sequencer's L1 origin lags behind the L1 head"
+        );
+
+        // Sequencer L1 origin forced advancements
+        metrics::describe_counter!(
+            Self::SEQUENCER_L1_ORIGIN_FORCED_ADVANCE,
+            metrics::Unit::Count,
+            "Number of times the sequencer bypassed the L1 confirmation delay"
+        );
+
+        // Sequencer L1 origin back offs
+        metrics::describe_counter!(
+            Self::SEQUENCER_L1_ORIGIN_BACKOFF,
+            metrics::Unit::Count,
+            "Number of times the sequencer backed off from advancing its L1 origin"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -12,12 +12,12 @@ use alloy_transport_http::{
 };
 use http_body_util::Full;
 use op_alloy_network::Optimism;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tower::ServiceBuilder;
 use url::Url;
 
 use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_providers_alloy::OnlineBeaconClient;
+use kona_providers_alloy::{BlobArchiverClient, BlobDirectory, FallbackBeaconClient};
 use kona_rpc::RpcBuilder;
 
 /// The [`L1ConfigBuilder`] is used to construct a [`L1Config`].
@@ -31,6 +31,16 @@ pub struct L1ConfigBuilder {
     pub beacon: Url,
     /// The L1 RPC URL.
     pub rpc_url: Url,
+    /// The fallback L1 beacon APIs, tried in order when the primary beacon API fails.
+    pub beacon_fallbacks: Vec<Url>,
+    /// The fallback L1 RPC URLs, tried in order when the primary L1 RPC fails to serve
+    /// derivation data.
+    pub rpc_fallbacks: Vec<Url>,
+    /// The blob archiver API, used to fetch blobs pruned by the beacon APIs.
+    pub blob_archiver: Option<Url>,
+    /// The local blob directory, used to fetch blobs pruned by the beacon APIs. Blobs fetched
+    /// from the blob archiver are cached in the directory.
+    pub blob_dir: Option<PathBuf>,
     /// The duration in seconds of an L1 slot. This can be used to hardcode a fixed slot
     /// duration if the l1-beacon's slot configuration is not available.
     pub slot_duration_override: Option<u64>,
@@ -107,7 +117,11 @@ impl RollupNodeBuilder {
     /// - The P2P config is not set.
     /// - The rollup boost args are not set.
     pub fn build(self) -> RollupNode {
-        let mut l1_beacon = OnlineBeaconClient::new_http(self.l1_config_builder.beacon.to_string());
+        let beacons = core::iter::once(&self.l1_config_builder.beacon)
+            .chain(&self.l1_config_builder.beacon_fallbacks)
+            .map(ToString::to_string)
+            .collect();
+        let mut l1_beacon = FallbackBeaconClient::new_http(beacons);
         if let Some(l1_slot_duration) = self.l1_config_builder.slot_duration_override {
             l1_beacon = l1_beacon.with_l1_slot_duration_override(l1_slot_duration);
         }
@@ -117,6 +131,23 @@ impl RollupNodeBuilder {
             trust_rpc: self.l1_config_builder.trust_rpc,
             beacon_client: l1_beacon,
             engine_provider: RootProvider::new_http(self.l1_config_builder.rpc_url.clone()),
+            fallback_providers: self
+                .l1_config_builder
+                .rpc_fallbacks
+                .iter()
+                .cloned()
+                .map(RootProvider::new_http)
+                .collect(),
+            blob_archiver: self
+                .l1_config_builder
+                .blob_archiver
+                .as_ref()
+                .map(|url| BlobArchiverClient::new_http(url.to_string())),
+            blob_directory: self
+                .l1_config_builder
+                .blob_dir
+                .clone()
+                .map(|dir| BlobDirectory::new(dir).with_write_through(true)),
         };
 
         let jwt_secret = self.engine_config.l2_jwt_secret;
@@ -14,7 +14,10 @@ use alloy_eips::BlockNumberOrTag;
 use alloy_provider::RootProvider;
 use kona_derive::StatefulAttributesBuilder;
 use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient};
+use kona_providers_alloy::{
+    AlloyL2ChainProvider, AlloySystemConfigProvider, BlobArchiverClient, BlobDirectory,
+    FallbackBeaconClient, FallbackChainProvider,
+};
 use kona_rpc::RpcBuilder;
 use op_alloy_network::Optimism;
 use std::{ops::Not as _, sync::Arc, time::Duration};
@@ -32,10 +35,25 @@ pub struct L1Config {
     pub chain_config: Arc<L1ChainConfig>,
     /// Whether to trust the L1 RPC.
     pub trust_rpc: bool,
-    /// The L1 beacon client.
-    pub beacon_client: OnlineBeaconClient,
+    /// The L1 beacon client, failing over between the configured beacon nodes.
+    pub beacon_client: FallbackBeaconClient,
     /// The L1 engine provider.
     pub engine_provider: RootProvider,
+    /// The fallback L1 providers, used alongside the engine provider to fetch derivation data.
+    pub fallback_providers: Vec<RootProvider>,
+    /// The blob archiver to fetch blobs pruned by the beacon nodes from, if any.
+    pub blob_archiver: Option<BlobArchiverClient>,
+    /// The local blob directory to fetch blobs pruned by the beacon nodes from, if any.
+    pub blob_directory: Option<BlobDirectory>,
+}
+
+impl L1Config {
+    /// Returns the L1 providers used to fetch derivation data, in order of preference.
+    pub fn derivation_providers(&self) -> Vec<RootProvider> {
+        core::iter::once(self.engine_provider.clone())
+            .chain(self.fallback_providers.iter().cloned())
+            .collect()
+    }
 }
 
 /// The standard implementation of the [RollupNode] service, using the governance approved OP Stack
@@ -71,9 +89,11 @@ impl RollupNode {
     /// Returns a derivation builder for the node.
     fn derivation_builder(&self) -> DerivationBuilder {
         DerivationBuilder {
-            l1_provider: self.l1_config.engine_provider.clone(),
+            l1_providers: self.l1_config.derivation_providers(),
             l1_trust_rpc: self.l1_config.trust_rpc,
             l1_beacon: self.l1_config.beacon_client.clone(),
+            l1_blob_archiver: self.l1_config.blob_archiver.clone(),
+            l1_blob_directory: self.l1_config.blob_directory.clone(),
             l2_provider: self.l2_provider.clone(),
             l2_trust_rpc: self.l2_trust_rpc,
             rollup_config: self.config.clone(),
@@ -100,9 +120,9 @@ impl RollupNode {
     /// Returns the sequencer builder for the node.
     fn create_attributes_builder(
         &self,
-    ) -> StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider> {
-        let l1_derivation_provider = AlloyChainProvider::new_with_trust(
-            self.l1_config.engine_provider.clone(),
+    ) -> StatefulAttributesBuilder<FallbackChainProvider, AlloyL2ChainProvider> {
+        let l1_derivation_provider = FallbackChainProvider::new(
+            self.l1_config.derivation_providers(),
             DERIVATION_PROVIDER_CACHE_SIZE,
             self.l1_config.trust_rpc,
         );
@@ -193,7 +213,8 @@ impl RollupNode {
         );
 
         let delayed_origin_selector =
-            L1OriginSelector::new(self.config.clone(), delayed_l1_provider);
+            L1OriginSelector::new(self.config.clone(), delayed_l1_provider)
+                .with_strategy(self.sequencer_config.origin_selection_strategy);
 
         // Conditionally add conductor if configured
         let conductor =
@@ -219,6 +240,10 @@ impl RollupNode {
         let l1_watcher = L1WatcherActor::new(
             self.config.clone(),
             self.l1_config.engine_provider.clone(),
+            AlloySystemConfigProvider::new(
+                self.l1_config.engine_provider.clone(),
+                self.config.clone(),
+            ),
             l1_query_rx,
             l1_head_updates_tx.clone(),
             finalized_l1_block_tx.clone(),
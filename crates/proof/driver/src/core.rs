@@ -1,15 +1,20 @@
 //! The driver of the kona derivation pipeline.
 
-use crate::{DriverError, DriverPipeline, DriverResult, Executor, PipelineCursor, TipCursor};
+use crate::{
+    ClaimProvider, DriverError, DriverPipeline, DriverResult, ExecutionJob, Executor,
+    ParallelExecutor, PipelineCursor, TipCursor,
+};
 use alloc::{sync::Arc, vec::Vec};
 use alloy_consensus::BlockBody;
 use alloy_primitives::{B256, Bytes};
 use alloy_rlp::Decodable;
 use core::fmt::Debug;
-use kona_derive::{Pipeline, PipelineError, PipelineErrorKind, Signal, SignalReceiver};
+use kona_derive::{
+    Pipeline, PipelineError, PipelineErrorKind, ResetSignal, Signal, SignalReceiver,
+};
 use kona_executor::BlockBuildingOutcome;
 use kona_genesis::RollupConfig;
-use kona_protocol::L2BlockInfo;
+use kona_protocol::{BlockInfo, L2BlockInfo};
 use op_alloy_consensus::{OpBlock, OpTxEnvelope, OpTxType};
 use spin::RwLock;
 
@@ -330,4 +335,479 @@ where
             self.safe_head_artifacts = Some((outcome, attributes.transactions.unwrap_or_default()));
         }
     }
+
+    /// Advances the derivation pipeline to the target block number, executing blocks
+    /// speculatively in parallel.
+    ///
+    /// Instead of executing every block before deriving the next one, the payload attributes for
+    /// up to `window` blocks are derived up front. The intermediate blocks are taken from the
+    /// `claims` provider, so that each block's claimed parent is known before any of them are
+    /// executed, and the whole window is handed to the `parallel` executor at once.
+    ///
+    /// The results are then verified as a chain: a block is only accepted if it was built on a
+    /// verified parent and its header and output root match the claim. On the first mismatch,
+    /// the cursor is left at the last verified block, the pipeline is reset to it, and the
+    /// diverging block is derived and executed sequentially through
+    /// [`Self::advance_to_target`], which also takes care of the Holocene deposit-only retry.
+    /// Speculation then resumes from the next block.
+    ///
+    /// Since claims are never trusted, errors returned by the `claims` provider or the `parallel`
+    /// executor are not fatal; they only end speculation early.
+    ///
+    /// # Arguments
+    /// * `cfg` - The rollup configuration containing chain parameters and activation heights
+    /// * `target` - The target block number
+    /// * `window` - The maximum number of blocks to execute speculatively at once
+    /// * `claims` - The provider of the claimed intermediate blocks
+    /// * `parallel` - The executor for the speculative execution jobs
+    ///
+    /// # Returns
+    /// * `Ok((l2_safe_head, output_root))` - Tuple containing the final [`L2BlockInfo`] and output
+    ///   root hash when target is reached or derivation completes
+    /// * `Err(DriverError)` - The same errors as [`Self::advance_to_target`]
+    ///
+    /// # Usage Pattern
+    /// ```rust,ignore
+    /// let (safe_head, output_root) = driver
+    ///     .advance_to_target_parallel(&rollup_config, 100, 16, &mut claims, &mut executor)
+    ///     .await?;
+    /// ```
+    pub async fn advance_to_target_parallel<C, X>(
+        &mut self,
+        cfg: &RollupConfig,
+        target: u64,
+        window: usize,
+        claims: &mut C,
+        parallel: &mut X,
+    ) -> DriverResult<(L2BlockInfo, B256), E::Error>
+    where
+        C: ClaimProvider + Send,
+        X: ParallelExecutor + Send,
+    {
+        let window = window.max(1);
+        loop {
+            let tip = self.cursor.read().tip().clone();
+            if tip.l2_safe_head.block_info.number >= target {
+                info!(target: "client", "Derivation complete, reached L2 safe head.");
+                return Ok((tip.l2_safe_head, tip.l2_safe_head_output_root));
+            }
+
+            // Derive the payload attributes for the window, trusting the claimed blocks as the
+            // parents of subsequent blocks. The cursor is advanced to the claimed blocks while
+            // deriving, so that the pipeline's L2 chain provider can serve them, and restored
+            // from the checkpoint before verification.
+            let checkpoint = self.cursor.read().clone();
+            let mut speculated = Vec::with_capacity(window);
+            let mut jobs = Vec::with_capacity(window);
+            let (mut safe_head, mut parent_header) =
+                (tip.l2_safe_head, tip.l2_safe_head_header.clone());
+            while jobs.len() < window && safe_head.block_info.number < target {
+                // The claim is fetched before the attributes are taken from the pipeline, so that
+                // ending speculation never drops derived attributes.
+                let number = safe_head.block_info.number + 1;
+                let claim = match claims.claimed_block(number).await {
+                    Ok(claim) if claim.header.number == number => claim,
+                    Ok(claim) => {
+                        warn!(target: "client", number, claimed = claim.header.number, "Claimed block has the wrong number; ending speculation");
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(target: "client", number, "Failed to fetch claimed block; ending speculation: {e}");
+                        break;
+                    }
+                };
+
+                let attributes = match self.pipeline.produce_payload(safe_head).await {
+                    Ok(attrs) => attrs.take_inner(),
+                    Err(PipelineErrorKind::Critical(PipelineError::EndOfSource)) => break,
+                    Err(e) => {
+                        *self.cursor.write() = checkpoint;
+                        error!(target: "client", "Failed to produce payload: {:?}", e);
+                        return Err(DriverError::Pipeline(e));
+                    }
+                };
+
+                let transactions = attributes.transactions.clone().unwrap_or_default();
+                let block = OpBlock {
+                    header: claim.header.inner().clone(),
+                    body: BlockBody {
+                        transactions: transactions
+                            .iter()
+                            .map(|tx| {
+                                OpTxEnvelope::decode(&mut tx.as_ref()).map_err(DriverError::Rlp)
+                            })
+                            .collect::<DriverResult<Vec<OpTxEnvelope>, E::Error>>()?,
+                        ommers: Vec::new(),
+                        withdrawals: None,
+                    },
+                };
+                let origin = self.pipeline.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+                let l2_info = L2BlockInfo::from_block_and_genesis(
+                    &block,
+                    &self.pipeline.rollup_config().genesis,
+                )?;
+                self.cursor.write().advance(
+                    origin,
+                    TipCursor::new(l2_info, claim.header.clone(), claim.output_root),
+                );
+
+                jobs.push(ExecutionJob { parent_header, attributes });
+                parent_header = claim.header.clone();
+                safe_head = l2_info;
+                speculated.push((origin, l2_info, claim, transactions));
+            }
+            *self.cursor.write() = checkpoint;
+
+            // Nothing could be speculated on, either because the data source is exhausted or
+            // because no claim is available. Finish sequentially.
+            if jobs.is_empty() {
+                return self.advance_to_target(cfg, Some(target)).await;
+            }
+
+            // Execute the window and verify the results as a chain.
+            info!(target: "client", from = tip.l2_safe_head.block_info.number + 1, blocks = jobs.len(), "Executing blocks speculatively");
+            let results = parallel.execute_jobs(jobs).await;
+            let mut diverged = speculated.len() != results.len();
+            for ((origin, l2_info, claim, transactions), result) in
+                speculated.into_iter().zip(results)
+            {
+                match result {
+                    Ok(result)
+                        if result.outcome.header.hash() == claim.header.hash() &&
+                            result.output_root == claim.output_root =>
+                    {
+                        self.cursor.write().advance(
+                            origin,
+                            TipCursor::new(
+                                l2_info,
+                                result.outcome.header.clone(),
+                                result.output_root,
+                            ),
+                        );
+                        self.safe_head_artifacts = Some((result.outcome, transactions));
+                    }
+                    Ok(result) => {
+                        warn!(
+                            target: "client",
+                            number = l2_info.block_info.number,
+                            hash = ?result.outcome.header.hash(),
+                            claimed_hash = ?claim.header.hash(),
+                            output_root = ?result.output_root,
+                            claimed_output_root = ?claim.output_root,
+                            "Speculatively executed block does not match its claim"
+                        );
+                        diverged = true;
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(target: "client", number = l2_info.block_info.number, "Failed to execute block speculatively: {e}");
+                        diverged = true;
+                        break;
+                    }
+                }
+            }
+
+            if diverged {
+                // The pipeline has derived past the last verified block. Reset it to the
+                // verified tip, and derive and execute the diverging block sequentially.
+                self.reset_to_verified_tip().await?;
+                let next = self.cursor.read().l2_safe_head().block_info.number + 1;
+                self.advance_to_target(cfg, Some(next.min(target))).await?;
+            }
+        }
+    }
+
+    /// Resets the derivation pipeline to the tip of the cursor, walking the L1 origin back by
+    /// the channel timeout so that channels which were opened before the tip's L1 origin are
+    /// re-read.
+    async fn reset_to_verified_tip(&mut self) -> DriverResult<(), E::Error> {
+        let (l2_safe_head, l1_origin) = {
+            let cursor = self.cursor.read();
+            let l2_safe_head = *cursor.l2_safe_head();
+            let channel_start =
+                l2_safe_head.l1_origin.number.saturating_sub(cursor.channel_timeout);
+            (l2_safe_head, Self::cached_origin_at_or_before(&cursor, channel_start))
+        };
+        let system_config =
+            self.pipeline.system_config_by_number(l2_safe_head.block_info.number).await?;
+        self.pipeline
+            .signal(
+                ResetSignal { l2_safe_head, l1_origin, system_config: Some(system_config) }
+                    .signal(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the latest cached L1 origin at or before the given block number, or the oldest
+    /// cached origin if there is none.
+    fn cached_origin_at_or_before(cursor: &PipelineCursor, number: u64) -> BlockInfo {
+        cursor
+            .tips
+            .range(..=number)
+            .next_back()
+            .or_else(|| cursor.tips.first_key_value())
+            .and_then(|(origin, _)| cursor.origin_infos.get(origin).copied())
+            .unwrap_or_else(|| cursor.origin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClaimedBlock, ExecutionResult};
+    use alloc::boxed::Box;
+    use alloy_consensus::{Header, Sealable, Sealed};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_evm::block::BlockExecutionResult;
+    use alloy_primitives::keccak256;
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use async_trait::async_trait;
+    use kona_derive::{OriginProvider, StepResult};
+    use kona_genesis::SystemConfig;
+    use kona_protocol::{L1BlockInfoBedrock, OpAttributesWithParent};
+    use op_alloy_consensus::TxDeposit;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+    use std::collections::HashMap;
+
+    /// The last block the mock pipeline can derive.
+    const LAST_BLOCK: u64 = 12;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("mock error")]
+    struct MockError;
+
+    /// Returns the payload attributes of the given block, starting with an L1 info deposit.
+    fn attributes(number: u64) -> OpPayloadAttributes {
+        let l1_info = L1BlockInfoBedrock { sequence_number: number, ..Default::default() };
+        let deposit = TxDeposit { input: l1_info.encode_calldata(), ..Default::default() };
+        OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: number * 2,
+                prev_randao: B256::with_last_byte(number as u8),
+                suggested_fee_recipient: Default::default(),
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            transactions: Some(vec![
+                OpTxEnvelope::Deposit(deposit.seal_slow()).encoded_2718().into(),
+            ]),
+            no_tx_pool: None,
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+            min_base_fee: None,
+        }
+    }
+
+    /// Deterministically "executes" the attributes on top of the parent, returning the outcome and
+    /// its output root.
+    fn build(
+        parent: &Sealed<Header>,
+        attributes: &OpPayloadAttributes,
+    ) -> (BlockBuildingOutcome, B256) {
+        let header = Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            timestamp: attributes.payload_attributes.timestamp,
+            mix_hash: attributes.payload_attributes.prev_randao,
+            gas_limit: attributes.gas_limit.unwrap_or_default(),
+            ..Default::default()
+        }
+        .seal_slow();
+        let output_root = keccak256(header.hash());
+        ((header, BlockExecutionResult::default()).into(), output_root)
+    }
+
+    fn genesis_header() -> Sealed<Header> {
+        Header::default().seal_slow()
+    }
+
+    /// A pipeline deriving the blocks up to [`LAST_BLOCK`] in order, like the real pipeline: the
+    /// next block only depends on its internal state, which is moved by resets.
+    #[derive(Debug, Default)]
+    struct MockPipeline {
+        config: RollupConfig,
+        next: u64,
+        prepared: Option<OpAttributesWithParent>,
+    }
+
+    impl Iterator for MockPipeline {
+        type Item = OpAttributesWithParent;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.prepared.take()
+        }
+    }
+
+    impl OriginProvider for MockPipeline {
+        fn origin(&self) -> Option<BlockInfo> {
+            Some(BlockInfo::default())
+        }
+    }
+
+    #[async_trait]
+    impl Pipeline for MockPipeline {
+        fn peek(&self) -> Option<&OpAttributesWithParent> {
+            self.prepared.as_ref()
+        }
+
+        async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+            if self.next > LAST_BLOCK {
+                return StepResult::StepFailed(PipelineError::EndOfSource.crit());
+            }
+            self.prepared =
+                Some(OpAttributesWithParent::new(attributes(self.next), cursor, None, false));
+            self.next += 1;
+            StepResult::PreparedAttributes
+        }
+
+        fn rollup_config(&self) -> &RollupConfig {
+            &self.config
+        }
+
+        async fn system_config_by_number(
+            &mut self,
+            _: u64,
+        ) -> Result<SystemConfig, PipelineErrorKind> {
+            Ok(SystemConfig::default())
+        }
+    }
+
+    #[async_trait]
+    impl SignalReceiver for MockPipeline {
+        async fn signal(&mut self, signal: Signal) -> kona_derive::PipelineResult<()> {
+            if let Signal::Reset(reset) = signal {
+                self.next = reset.l2_safe_head.block_info.number + 1;
+                self.prepared = None;
+            }
+            Ok(())
+        }
+    }
+
+    impl DriverPipeline<Self> for MockPipeline {
+        fn flush(&mut self) {}
+    }
+
+    #[derive(Debug)]
+    struct MockExecutor {
+        safe_head: Sealed<Header>,
+        output_root: B256,
+    }
+
+    #[async_trait]
+    impl Executor for MockExecutor {
+        type Error = MockError;
+
+        async fn wait_until_ready(&mut self) {}
+
+        fn update_safe_head(&mut self, header: Sealed<Header>) {
+            self.safe_head = header;
+        }
+
+        async fn execute_payload(
+            &mut self,
+            attributes: OpPayloadAttributes,
+        ) -> Result<BlockBuildingOutcome, Self::Error> {
+            let (outcome, output_root) = build(&self.safe_head, &attributes);
+            self.output_root = output_root;
+            Ok(outcome)
+        }
+
+        fn compute_output_root(&mut self) -> Result<B256, Self::Error> {
+            Ok(self.output_root)
+        }
+    }
+
+    /// Claims the canonical blocks, except for the block at `faulty`, whose lookup either fails
+    /// or returns a block with the wrong number.
+    #[derive(Debug)]
+    struct MockClaims {
+        blocks: HashMap<u64, ClaimedBlock>,
+        faulty: u64,
+        wrong_number: bool,
+    }
+
+    impl MockClaims {
+        fn new(faulty: u64, wrong_number: bool) -> Self {
+            let mut blocks = HashMap::new();
+            let mut parent = genesis_header();
+            for number in 1..=LAST_BLOCK {
+                let (outcome, output_root) = build(&parent, &attributes(number));
+                parent = outcome.header.clone();
+                blocks.insert(number, ClaimedBlock { header: outcome.header, output_root });
+            }
+            Self { blocks, faulty, wrong_number }
+        }
+    }
+
+    #[async_trait]
+    impl ClaimProvider for MockClaims {
+        type Error = MockError;
+
+        async fn claimed_block(&mut self, number: u64) -> Result<ClaimedBlock, Self::Error> {
+            match number {
+                n if n == self.faulty && self.wrong_number => Ok(self.blocks[&(n + 1)].clone()),
+                n if n == self.faulty => Err(MockError),
+                n => self.blocks.get(&n).cloned().ok_or(MockError),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockParallelExecutor;
+
+    #[async_trait]
+    impl ParallelExecutor for MockParallelExecutor {
+        type Error = MockError;
+
+        async fn execute_jobs(
+            &mut self,
+            jobs: Vec<ExecutionJob>,
+        ) -> Vec<Result<ExecutionResult, Self::Error>> {
+            jobs.iter()
+                .map(|job| {
+                    let (outcome, output_root) = build(&job.parent_header, &job.attributes);
+                    Ok(ExecutionResult { outcome, output_root })
+                })
+                .collect()
+        }
+    }
+
+    fn driver() -> Driver<MockExecutor, MockPipeline, MockPipeline> {
+        let genesis = genesis_header();
+        let safe_head = L2BlockInfo {
+            block_info: BlockInfo { hash: genesis.hash(), ..Default::default() },
+            l1_origin: Default::default(),
+            seq_num: 0,
+        };
+        let mut cursor = PipelineCursor::new(10, BlockInfo::default());
+        cursor
+            .advance(BlockInfo::default(), TipCursor::new(safe_head, genesis.clone(), B256::ZERO));
+        let executor = MockExecutor { safe_head: genesis, output_root: B256::ZERO };
+        let pipeline = MockPipeline { next: 1, ..Default::default() };
+        Driver::new(Arc::new(RwLock::new(cursor)), executor, pipeline)
+    }
+
+    async fn assert_matches_sequential(claims: &mut MockClaims) {
+        let cfg = RollupConfig::default();
+        let target = 8;
+        let expected = driver().advance_to_target(&cfg, Some(target)).await.unwrap();
+        assert_eq!(expected.0.block_info.number, target);
+
+        let actual = driver()
+            .advance_to_target_parallel(&cfg, target, 4, claims, &mut MockParallelExecutor)
+            .await
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_claim_error_matches_sequential() {
+        assert_matches_sequential(&mut MockClaims::new(3, false)).await;
+    }
+
+    #[tokio::test]
+    async fn test_parallel_claim_wrong_number_matches_sequential() {
+        assert_matches_sequential(&mut MockClaims::new(6, true)).await;
+    }
 }
@@ -21,6 +21,9 @@ pub use pipeline::DriverPipeline;
 mod executor;
 pub use executor::Executor;
 
+mod parallel;
+pub use parallel::{ClaimProvider, ClaimedBlock, ExecutionJob, ExecutionResult, ParallelExecutor};
+
 mod core;
 pub use core::Driver;
 
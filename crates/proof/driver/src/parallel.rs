@@ -0,0 +1,70 @@
+//! Abstractions for speculative, parallel block execution in the driver.
+//!
+//! In the parallel mode, the driver derives the payload attributes for a window of blocks up
+//! front, trusting the intermediate blocks claimed by a [`ClaimProvider`] (typically the L2
+//! execution layer) as the parents of subsequent blocks. Because every block then has a known
+//! parent, the window can be handed to a [`ParallelExecutor`] and executed concurrently. The
+//! driver finally verifies the results as a chain, falling back to sequential derivation from
+//! the first block whose execution does not match its claim.
+
+use alloc::{boxed::Box, vec::Vec};
+use alloy_consensus::{Header, Sealed};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use core::error::Error;
+use kona_executor::BlockBuildingOutcome;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+/// An intermediate L2 block claimed by an untrusted source, used as the parent of the next
+/// speculatively executed block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimedBlock {
+    /// The claimed header of the block.
+    pub header: Sealed<Header>,
+    /// The claimed output root of the block.
+    pub output_root: B256,
+}
+
+/// A block to execute on top of a (claimed) parent header.
+#[derive(Debug, Clone)]
+pub struct ExecutionJob {
+    /// The header of the parent block to build on.
+    pub parent_header: Sealed<Header>,
+    /// The payload attributes derived for the block.
+    pub attributes: OpPayloadAttributes,
+}
+
+/// The result of executing an [`ExecutionJob`].
+#[derive(Debug, Clone)]
+pub struct ExecutionResult {
+    /// The outcome of building the block.
+    pub outcome: BlockBuildingOutcome,
+    /// The output root computed after executing the block.
+    pub output_root: B256,
+}
+
+/// Provides the claimed intermediate blocks that speculative execution builds on.
+#[async_trait]
+pub trait ClaimProvider {
+    /// The error type for the [`ClaimProvider`].
+    type Error: Error;
+
+    /// Returns the claimed block with the given number.
+    async fn claimed_block(&mut self, number: u64) -> Result<ClaimedBlock, Self::Error>;
+}
+
+/// Executes a batch of independent [`ExecutionJob`]s, possibly concurrently.
+///
+/// Unlike the [`Executor`](crate::Executor), which builds one block at a time on top of its safe
+/// head, every job carries its own parent header, so no job depends on the result of another.
+#[async_trait]
+pub trait ParallelExecutor {
+    /// The error type for the [`ParallelExecutor`].
+    type Error: Error;
+
+    /// Executes the given jobs, returning one result per job, in the order of the jobs.
+    async fn execute_jobs(
+        &mut self,
+        jobs: Vec<ExecutionJob>,
+    ) -> Vec<Result<ExecutionResult, Self::Error>>;
+}
@@ -4,17 +4,21 @@
 //! for OP Stack L2 chains that operates in a stateless manner, pulling required state
 //! data from a [TrieDB] during execution rather than maintaining full state.
 
-use crate::{ExecutorError, ExecutorResult, TrieDB, TrieDBError, TrieDBProvider};
+use crate::{
+    BlockInspector, ExecutionTracer, ExecutorError, ExecutorResult, TrieDB, TrieDBError,
+    TrieDBProvider, trace::TracerInspector,
+};
 use alloc::{string::ToString, vec::Vec};
 use alloy_consensus::{Header, Sealed, crypto::RecoveryError};
 use alloy_evm::{
-    EvmFactory, FromRecoveredTx, FromTxWithEncoded,
+    Evm as _, EvmFactory, FromRecoveredTx, FromTxWithEncoded,
     block::{BlockExecutionResult, BlockExecutor, BlockExecutorFactory},
 };
 use alloy_op_evm::{
     OpBlockExecutionCtx, OpBlockExecutorFactory,
     block::{OpAlloyReceiptBuilder, OpTxEnv},
 };
+use alloy_primitives::keccak256;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
@@ -23,7 +27,8 @@ use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use op_revm::OpSpecId;
 use revm::{
     context::BlockEnv,
-    database::{State, states::bundle_state::BundleRetention},
+    database::{BundleState, State, states::bundle_state::BundleRetention},
+    inspector::NoOpInspector,
 };
 
 /// Stateless OP Stack L2 block builder that derives state from trie proofs during execution.
@@ -97,6 +102,11 @@ where
     /// understand OP-specific transaction types, system calls, and state
     /// management required for proper L2 block execution.
     pub(crate) factory: OpBlockExecutorFactory<OpAlloyReceiptBuilder, RollupConfig, Evm>,
+    /// Whether to retain the [`BundleState`] of each built block in its [`BlockBuildingOutcome`].
+    ///
+    /// The bundle holds every account and storage slot touched by the block, along with their
+    /// original values, so it is only retained on request.
+    pub(crate) retain_bundle: bool,
 }
 
 impl<'a, P, H, Evm> StatelessL2Builder<'a, P, H, Evm>
@@ -145,7 +155,14 @@ where
             config.clone(),
             evm_factory,
         );
-        Self { config, trie_db, factory }
+        Self { config, trie_db, factory, retain_bundle: false }
+    }
+
+    /// Sets whether the [`BundleState`] of the state changes made by each built block is retained
+    /// in its [`BlockBuildingOutcome`]. Disabled by default.
+    pub fn with_bundle_retention(mut self, retain_bundle: bool) -> Self {
+        self.retain_bundle = retain_bundle;
+        self
     }
 
     /// Builds and executes a new L2 block using the provided payload attributes.
@@ -213,6 +230,37 @@ where
         &mut self,
         attrs: OpPayloadAttributes,
     ) -> ExecutorResult<BlockBuildingOutcome> {
+        self.build_block_with_inspector(attrs, NoOpInspector {})
+    }
+
+    /// Builds and executes a new L2 block using the provided payload attributes, like
+    /// [`Self::build_block`], collecting the traces of every transaction in the block into the
+    /// given [`ExecutionTracer`], if any.
+    ///
+    /// Tracing does not affect the outcome of the block; the system calls made before and after
+    /// the block's transactions are not traced.
+    pub fn build_block_with_tracer(
+        &mut self,
+        attrs: OpPayloadAttributes,
+        tracer: Option<&mut ExecutionTracer>,
+    ) -> ExecutorResult<BlockBuildingOutcome> {
+        self.build_block_with_inspector(attrs, TracerInspector(tracer))
+    }
+
+    /// Builds and executes a new L2 block using the provided payload attributes, like
+    /// [`Self::build_block`], with the given [`BlockInspector`] attached to the block's EVM.
+    ///
+    /// The EVM is created through the factory's [`EvmFactory::create_evm_with_inspector`] hook,
+    /// so callers can inspect execution on any EVM, including the FPVM EVM. Inspection does not
+    /// affect the outcome of the block.
+    pub fn build_block_with_inspector<I>(
+        &mut self,
+        attrs: OpPayloadAttributes,
+        inspector: I,
+    ) -> ExecutorResult<BlockBuildingOutcome>
+    where
+        I: for<'s, 'db> BlockInspector<Evm::Context<&'s mut State<&'db mut TrieDB<P, H>>>>,
+    {
         // Step 1. Set up the execution environment.
         let (base_fee_params, min_base_fee) = Self::active_base_fee_params(
             self.config,
@@ -253,21 +301,31 @@ where
             .with_bundle_update()
             .without_state_clear()
             .build();
-        let evm = self.factory.evm_factory().create_evm(&mut state, evm_env);
         let ctx = OpBlockExecutionCtx {
             parent_hash,
             parent_beacon_block_root: attrs.payload_attributes.parent_beacon_block_root,
             // This field is unused for individual block building jobs.
             extra_data: Default::default(),
         };
-        let executor = self.factory.create_executor(evm, ctx);
 
         // Step 3. Execute the block containing the transactions within the payload attributes.
         let transactions = attrs
             .recovered_transactions_with_encoded()
             .collect::<Result<Vec<_>, RecoveryError>>()
             .map_err(ExecutorError::Recovery)?;
-        let ex_result = executor.execute_block(transactions.iter())?;
+        let inspecting = inspector.is_enabled();
+        let mut evm =
+            self.factory.evm_factory().create_evm_with_inspector(&mut state, evm_env, inspector);
+        evm.set_inspector_enabled(inspecting);
+        let mut executor = self.factory.create_executor(evm, ctx);
+
+        executor.apply_pre_execution_changes()?;
+        for tx in &transactions {
+            executor.evm_mut().inspector_mut().start_transaction(|| keccak256(tx.encoded_bytes()));
+            let gas_used = executor.execute_transaction(tx)?;
+            executor.evm_mut().inspector_mut().finish_transaction(gas_used);
+        }
+        let ex_result = executor.apply_post_execution_changes()?;
 
         info!(
             target: "block_builder",
@@ -279,7 +337,7 @@ where
         // Step 4. Merge state transitions and seal the block.
         state.merge_transitions(BundleRetention::Reverts);
         let bundle = state.take_bundle();
-        let header = self.seal_block(&attrs, parent_hash, &block_env, &ex_result, bundle)?;
+        let header = self.seal_block(&attrs, parent_hash, &block_env, &ex_result, &bundle)?;
 
         info!(
             target: "block_builder",
@@ -293,34 +351,82 @@ where
 
         // Update the parent block hash in the state database, preparing for the next block.
         self.trie_db.set_parent_block_header(header.clone());
-        Ok((header, ex_result).into())
+        Ok(BlockBuildingOutcome {
+            header,
+            execution_result: ex_result,
+            bundle: self.retain_bundle.then_some(bundle),
+        })
     }
 }
 
-/// The outcome of a block building operation, returning the sealed block [`Header`] and the
-/// [`BlockExecutionResult`].
+/// The outcome of a block building operation, returning the sealed block [`Header`], the
+/// [`BlockExecutionResult`] and, if retained, the [`BundleState`] of the state changes made by the
+/// block.
 #[derive(Debug, Clone)]
 pub struct BlockBuildingOutcome {
     /// The block header.
     pub header: Sealed<Header>,
     /// The block execution result.
     pub execution_result: BlockExecutionResult<OpReceiptEnvelope>,
+    /// The state changes made by the block, if the builder was configured to retain them with
+    /// [`StatelessL2Builder::with_bundle_retention`].
+    pub bundle: Option<BundleState>,
 }
 
 impl From<(Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>)> for BlockBuildingOutcome {
     fn from(
         (header, execution_result): (Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>),
     ) -> Self {
-        Self { header, execution_result }
+        Self { header, execution_result, bundle: None }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::test_utils::run_test_fixture;
+    use crate::{
+        BlockInspector, StatelessL2Builder, TraceConfig, TrieDB,
+        test_utils::{
+            CountingTrieDBProvider, open_test_fixture, pack_test_fixtures, run_test_corpus,
+            run_test_fixture, run_traced_test_fixture,
+        },
+    };
+    use alloy_op_evm::OpEvmFactory;
+    use alloy_primitives::{B256, Sealable};
+    use kona_mpt::NoopTrieHinter;
+    use kona_protocol::Predeploys;
+    use revm::{Inspector, interpreter::Interpreter};
     use rstest::rstest;
     use std::path::PathBuf;
 
+    /// Counts the transactions and executed opcodes of a block.
+    #[derive(Debug, Default)]
+    struct Counts {
+        started: usize,
+        finished: usize,
+        gas_used: u64,
+        steps: usize,
+    }
+
+    /// A caller-provided [`BlockInspector`] recording into [`Counts`].
+    struct CountingInspector<'a>(&'a mut Counts);
+
+    impl<CTX> Inspector<CTX> for CountingInspector<'_> {
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut CTX) {
+            self.0.steps += 1;
+        }
+    }
+
+    impl<CTX> BlockInspector<CTX> for CountingInspector<'_> {
+        fn start_transaction(&mut self, _tx_hash: impl FnOnce() -> B256) {
+            self.0.started += 1;
+        }
+
+        fn finish_transaction(&mut self, gas_used: u64) {
+            self.0.finished += 1;
+            self.0.gas_used += gas_used;
+        }
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_statelessly_execute_block(
@@ -330,4 +436,115 @@ mod test {
     ) {
         run_test_fixture(path).await;
     }
+
+    #[tokio::test]
+    async fn test_statelessly_execute_block_traced() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/block-26207960.tar.gz");
+        let config = TraceConfig { calls: true, struct_logs: true, stack: false };
+        let traces = run_traced_test_fixture(path, config).await;
+
+        assert!(!traces.is_empty());
+        for trace in &traces {
+            assert!(trace.call.is_some(), "Missing top-level call frame");
+            assert!(trace.struct_logs.iter().all(|log| log.depth >= 1 && log.stack.is_none()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_statelessly_execute_block_with_inspector() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/block-26207960.tar.gz");
+        let (_fixture_dir, fixture, provider) = open_test_fixture(path).await;
+        let tx_count = fixture.executing_payload.transactions.as_ref().map_or(0, Vec::len);
+
+        let mut counts = Counts::default();
+        let outcome = StatelessL2Builder::new(
+            &fixture.rollup_config,
+            OpEvmFactory::default(),
+            provider,
+            NoopTrieHinter,
+            fixture.parent_header.seal_slow(),
+        )
+        .build_block_with_inspector(
+            fixture.executing_payload.clone(),
+            CountingInspector(&mut counts),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+        assert_eq!(counts.started, tx_count);
+        assert_eq!(counts.finished, tx_count);
+        assert_eq!(counts.gas_used, outcome.execution_result.gas_used);
+        assert!(counts.steps > 0);
+    }
+
+    #[tokio::test]
+    async fn test_statelessly_execute_block_retains_bundle() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/block-26207960.tar.gz");
+        let (_fixture_dir, fixture, provider) = open_test_fixture(path).await;
+        let provider = CountingTrieDBProvider::new(provider);
+        let parent_header = fixture.parent_header.seal_slow();
+
+        let build = |retain_bundle| {
+            StatelessL2Builder::new(
+                &fixture.rollup_config,
+                OpEvmFactory::default(),
+                provider.clone(),
+                NoopTrieHinter,
+                parent_header.clone(),
+            )
+            .with_bundle_retention(retain_bundle)
+            .build_block(fixture.executing_payload.clone())
+            .unwrap()
+        };
+
+        let outcome = build(false);
+        assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+        assert!(outcome.bundle.is_none());
+
+        let outcome = build(true);
+        assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+        let bundle = outcome.bundle.expect("Missing retained bundle");
+
+        // The L1 info deposit updates the L1 block attributes in storage.
+        let l1_block = bundle.state.get(&Predeploys::L1_BLOCK_INFO).expect("Missing L1Block");
+        assert!(!l1_block.storage.is_empty());
+
+        // Applying the retained bundle to the parent state yields the block's state root.
+        let mut trie_db = TrieDB::new(parent_header, provider, NoopTrieHinter);
+        assert_eq!(trie_db.state_root(&bundle).unwrap(), outcome.header.state_root);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_statelessly_execute_corpus() {
+        let testdata = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let fixtures = (26207960..=26207963)
+            .map(|n| testdata.join(format!("block-{n}.tar.gz")))
+            .collect::<Vec<_>>();
+
+        let dir = tempfile::tempdir().unwrap();
+        let corpus_path = dir.path().join("corpus-26207960-26207963.tar.gz");
+        pack_test_fixtures(&fixtures, &corpus_path).await;
+
+        let report = run_test_corpus(corpus_path).await;
+        assert_eq!(report.blocks.len(), 4, "{report}");
+        assert_eq!(report.mismatches().count(), 0, "{report}");
+        assert!(report.blocks.iter().all(|b| b.fetches.trie_nodes > 0), "{report}");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_statelessly_execute_checked_in_corpus() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/corpus/corpus-26207960-26207962.tar.gz");
+        let report = run_test_corpus(path).await;
+
+        let numbers = report.blocks.iter().map(|b| b.number).collect::<Vec<_>>();
+        assert_eq!(numbers, [26207960, 26207961, 26207962], "{report}");
+        assert_eq!(report.mismatches().count(), 0, "{report}");
+        assert!(report.blocks.iter().all(|b| b.error.is_none()), "{report}");
+
+        let gas_used = report.blocks.iter().map(|b| b.gas_used).collect::<Vec<_>>();
+        assert_eq!(gas_used, [953_624, 5_516_493, 11_822_253], "{report}");
+        assert_eq!(report.total_gas_used(), 18_292_370);
+        assert!(report.blocks.iter().all(|b| b.fetches.trie_nodes > 0), "{report}");
+    }
 }
@@ -18,6 +18,11 @@ pub use db::{NoopTrieDBProvider, TrieDB, TrieDBProvider};
 mod builder;
 pub use builder::{BlockBuildingOutcome, StatelessL2Builder, compute_receipts_root};
 
+mod trace;
+pub use trace::{
+    BlockInspector, CallFrame, CallKind, ExecutionTracer, StructLog, TraceConfig, TransactionTrace,
+};
+
 mod errors;
 pub use errors::{
     Eip1559ValidationError, ExecutorError, ExecutorResult, TrieDBError, TrieDBResult,
@@ -0,0 +1,521 @@
+//! A regression corpus format for the [`StatelessL2Builder`], packing many consecutive blocks
+//! into a single fixture.
+//!
+//! A corpus is a tarball containing a `corpus.json` file ([`ExecutorTestCorpus`]) and a single
+//! `kv` store holding every trie node, bytecode and header preimage needed to execute all of the
+//! blocks. Preimages are keyed by their hash, so nodes shared between blocks are stored once.
+
+use super::{DiskTrieNodeProvider, ExecutorTestFixture, ExecutorTestFixtureCreator};
+use crate::{StatelessL2Builder, TrieDBProvider};
+use alloy_consensus::Header;
+use alloy_op_evm::OpEvmFactory;
+use alloy_primitives::{B256, Bytes, Sealable};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_client::RpcClient;
+use alloy_transport_http::{Client, Http};
+use core::fmt;
+use kona_genesis::RollupConfig;
+use kona_mpt::{NoopTrieHinter, TrieNode, TrieProvider};
+use kona_registry::ROLLUP_CONFIGS;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use rocksdb::{DB, IteratorMode, Options};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tempfile::TempDir;
+use tokio::{fs, sync::Mutex};
+
+/// The test corpus format for the [`StatelessL2Builder`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecutorTestCorpus {
+    /// The rollup configuration for the executing chain.
+    pub rollup_config: RollupConfig,
+    /// The header of the parent of the first block in the corpus.
+    pub parent_header: Header,
+    /// The consecutive blocks in the corpus, in execution order.
+    pub blocks: Vec<ExecutorCorpusBlock>,
+}
+
+/// A single block within an [`ExecutorTestCorpus`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutorCorpusBlock {
+    /// The block number.
+    pub number: u64,
+    /// The executing payload attributes.
+    pub executing_payload: OpPayloadAttributes,
+    /// The expected block hash.
+    pub expected_block_hash: B256,
+}
+
+/// Executes the [ExecutorTestCorpus] stored at the passed `corpus_path`, returning a
+/// [`CorpusReport`] with the outcome of every block.
+pub async fn run_test_corpus(corpus_path: PathBuf) -> CorpusReport {
+    let (_corpus_dir, corpus, provider) = open_test_corpus(&corpus_path).await;
+    execute_corpus(&corpus, provider)
+}
+
+/// Untars the [ExecutorTestCorpus] stored at the passed `corpus_path`, returning the temporary
+/// directory it was extracted to, the corpus, and a provider over its key-value store.
+///
+/// The temporary directory must be kept alive for as long as the provider is used.
+pub async fn open_test_corpus(
+    corpus_path: &Path,
+) -> (TempDir, ExecutorTestCorpus, DiskTrieNodeProvider) {
+    let corpus_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    untar(corpus_path, corpus_dir.path()).await;
+
+    let kv_store = open_kv_store(&corpus_dir.path().join("kv"));
+    let corpus: ExecutorTestCorpus =
+        serde_json::from_slice(&fs::read(corpus_dir.path().join("corpus.json")).await.unwrap())
+            .expect("Failed to deserialize corpus");
+
+    (corpus_dir, corpus, DiskTrieNodeProvider::new(kv_store))
+}
+
+/// Executes every block in the [`ExecutorTestCorpus`] against the given provider.
+///
+/// Each block is built on top of the header produced for the previous block. If a block's hash
+/// does not match, execution resumes from the expected header when the provider has it, so that
+/// a single mismatch does not mask the rest of the corpus.
+pub fn execute_corpus<P>(corpus: &ExecutorTestCorpus, provider: P) -> CorpusReport
+where
+    P: TrieDBProvider + fmt::Debug,
+{
+    let provider = CountingTrieDBProvider::new(provider);
+    let mut parent_header = corpus.parent_header.clone().seal_slow();
+    let mut report = CorpusReport::default();
+
+    for block in &corpus.blocks {
+        let mut executor = StatelessL2Builder::new(
+            &corpus.rollup_config,
+            OpEvmFactory::default(),
+            provider.clone(),
+            NoopTrieHinter,
+            parent_header.clone(),
+        );
+
+        let start = Instant::now();
+        let outcome = executor.build_block(block.executing_payload.clone());
+        let elapsed = start.elapsed();
+        let fetches = provider.take_counts();
+
+        let mut block_report = CorpusBlockReport {
+            number: block.number,
+            expected_block_hash: block.expected_block_hash,
+            produced_block_hash: None,
+            error: None,
+            gas_used: 0,
+            elapsed,
+            fetches,
+        };
+        let next_parent = match outcome {
+            Ok(outcome) => {
+                block_report.produced_block_hash = Some(outcome.header.seal());
+                block_report.gas_used = outcome.execution_result.gas_used;
+                (outcome.header.seal() == block.expected_block_hash).then_some(outcome.header)
+            }
+            Err(e) => {
+                block_report.error = Some(e.to_string());
+                None
+            }
+        };
+        report.blocks.push(block_report);
+
+        parent_header = match next_parent {
+            Some(header) => header,
+            None => match provider.inner.header_by_hash(block.expected_block_hash) {
+                Ok(header) => header.seal_slow(),
+                Err(_) => break,
+            },
+        };
+    }
+
+    report
+}
+
+/// Packs consecutive single-block [`ExecutorTestFixture`]s into a corpus tarball at `output`.
+///
+/// The fixtures must form a chain, i.e. each fixture's parent header must be the block produced
+/// by the previous fixture. The key-value stores of the fixtures are merged, deduplicating the
+/// preimages they share.
+pub async fn pack_test_fixtures(fixtures: &[PathBuf], output: &Path) {
+    let work_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let corpus_dir = work_dir.path().join("corpus");
+    let corpus_kv = open_kv_store(&corpus_dir.join("kv"));
+
+    let mut corpus: Option<ExecutorTestCorpus> = None;
+    for (i, fixture_path) in fixtures.iter().enumerate() {
+        let fixture_dir = work_dir.path().join(format!("fixture-{i}"));
+        fs::create_dir_all(&fixture_dir).await.expect("Failed to create fixture directory");
+        untar(fixture_path, &fixture_dir).await;
+
+        let fixture: ExecutorTestFixture =
+            serde_json::from_slice(&fs::read(fixture_dir.join("fixture.json")).await.unwrap())
+                .expect("Failed to deserialize fixture");
+
+        // Merge the fixture's preimages into the corpus store.
+        let fixture_kv = open_kv_store(&fixture_dir.join("kv"));
+        for entry in fixture_kv.iterator(IteratorMode::Start) {
+            let (key, value) = entry.expect("Failed to read fixture key-value store");
+            corpus_kv.put(key, value).expect("Failed to write corpus key-value store");
+        }
+
+        // Store the parent header, so that execution can resume from it after a mismatch.
+        let parent_header = fixture.parent_header.clone().seal_slow();
+        corpus_kv
+            .put(parent_header.seal(), alloy_rlp::encode(parent_header.inner()))
+            .expect("Failed to write corpus key-value store");
+
+        let block = ExecutorCorpusBlock {
+            number: fixture.parent_header.number + 1,
+            executing_payload: fixture.executing_payload,
+            expected_block_hash: fixture.expected_block_hash,
+        };
+        match corpus.as_mut() {
+            Some(corpus) => {
+                let previous = corpus.blocks.last().expect("Corpus has at least one block");
+                assert_eq!(
+                    parent_header.seal(),
+                    previous.expected_block_hash,
+                    "Fixture {fixture_path:?} does not build on the previous fixture"
+                );
+                corpus.blocks.push(block);
+            }
+            None => {
+                corpus = Some(ExecutorTestCorpus {
+                    rollup_config: fixture.rollup_config,
+                    parent_header: fixture.parent_header,
+                    blocks: vec![block],
+                })
+            }
+        }
+    }
+    drop(corpus_kv);
+
+    let corpus = corpus.expect("No fixtures to pack");
+    fs::write(corpus_dir.join("corpus.json"), serde_json::to_vec(&corpus).unwrap())
+        .await
+        .expect("Failed to write corpus");
+    tar_directory(&corpus_dir, output).await;
+}
+
+/// A corpus creator for the [`StatelessL2Builder`], recording a range of consecutive blocks from
+/// an archival L2 execution layer.
+#[derive(Debug)]
+pub struct ExecutorCorpusCreator {
+    /// The RPC provider for the L2 execution layer.
+    pub provider: RootProvider,
+    /// The first block number to record.
+    pub start_block: u64,
+    /// The last block number to record, inclusive.
+    pub end_block: u64,
+    /// The key value store for the corpus.
+    pub kv_store: Arc<Mutex<DB>>,
+    /// The data directory for the corpus.
+    pub data_dir: PathBuf,
+}
+
+impl ExecutorCorpusCreator {
+    /// Creates a new [`ExecutorCorpusCreator`] with the given parameters.
+    pub fn new(
+        provider_url: &str,
+        start_block: u64,
+        end_block: u64,
+        base_corpus_directory: PathBuf,
+    ) -> Self {
+        let base = base_corpus_directory.join(format!("corpus-{start_block}-{end_block}"));
+
+        let url = provider_url.parse().expect("Invalid provider URL");
+        let http = Http::<Client>::new(url);
+        let provider = RootProvider::new(RpcClient::new(http, false));
+        let db = open_kv_store(&base.join("kv"));
+
+        Self {
+            provider,
+            start_block,
+            end_block,
+            kv_store: Arc::new(Mutex::new(db)),
+            data_dir: base,
+        }
+    }
+
+    /// Create a static test corpus with the configuration provided.
+    pub async fn create_static_corpus(self) {
+        let chain_id = self.provider.get_chain_id().await.expect("Failed to get chain ID");
+        let rollup_config = ROLLUP_CONFIGS.get(&chain_id).expect("Rollup config not found");
+
+        let mut parent_header = None;
+        let mut blocks = Vec::with_capacity((self.end_block - self.start_block + 1) as usize);
+        for number in self.start_block..=self.end_block {
+            let fetcher = ExecutorTestFixtureCreator {
+                provider: self.provider.clone(),
+                block_number: number,
+                kv_store: self.kv_store.clone(),
+                data_dir: self.data_dir.clone(),
+            };
+            let (executing_header, parent, payload_attrs) =
+                fetcher.fetch_executing_payload(rollup_config).await;
+            parent_header.get_or_insert_with(|| parent.inner().clone());
+
+            // Execute the block to record its witness in the key-value store.
+            let mut executor = StatelessL2Builder::new(
+                rollup_config,
+                OpEvmFactory::default(),
+                fetcher,
+                NoopTrieHinter,
+                parent,
+            );
+            let outcome =
+                executor.build_block(payload_attrs.clone()).expect("Failed to execute block");
+            assert_eq!(
+                outcome.header.inner(),
+                &executing_header,
+                "Produced header does not match the expected header"
+            );
+
+            // Store the block's header, so that execution can resume from it after a mismatch.
+            let expected_block_hash = executing_header.hash_slow();
+            self.kv_store
+                .lock()
+                .await
+                .put(expected_block_hash, alloy_rlp::encode(&executing_header))
+                .expect("Failed to write corpus key-value store");
+
+            blocks.push(ExecutorCorpusBlock {
+                number,
+                executing_payload: payload_attrs,
+                expected_block_hash,
+            });
+        }
+
+        let corpus = ExecutorTestCorpus {
+            rollup_config: rollup_config.clone(),
+            parent_header: parent_header.expect("Corpus has at least one block"),
+            blocks,
+        };
+        fs::write(self.data_dir.join("corpus.json"), serde_json::to_vec(&corpus).unwrap())
+            .await
+            .unwrap();
+        drop(self.kv_store);
+
+        tar_directory(&self.data_dir, &self.data_dir.with_extension("tar.gz")).await;
+        fs::remove_dir_all(&self.data_dir).await.expect("Failed to remove temporary directory");
+    }
+}
+
+/// The number of preimages fetched from a [`TrieDBProvider`], by kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchCounts {
+    /// The number of trie nodes fetched.
+    pub trie_nodes: u64,
+    /// The number of bytecodes fetched.
+    pub bytecodes: u64,
+    /// The number of headers fetched.
+    pub headers: u64,
+}
+
+/// A [`TrieDBProvider`] that counts the preimages fetched through it.
+///
+/// Clones share the same inner provider and counters.
+#[derive(Debug)]
+pub struct CountingTrieDBProvider<P> {
+    inner: Arc<P>,
+    trie_nodes: Arc<AtomicU64>,
+    bytecodes: Arc<AtomicU64>,
+    headers: Arc<AtomicU64>,
+}
+
+impl<P> Clone for CountingTrieDBProvider<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            trie_nodes: self.trie_nodes.clone(),
+            bytecodes: self.bytecodes.clone(),
+            headers: self.headers.clone(),
+        }
+    }
+}
+
+impl<P> CountingTrieDBProvider<P> {
+    /// Creates a new [`CountingTrieDBProvider`] wrapping the given provider.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            trie_nodes: Default::default(),
+            bytecodes: Default::default(),
+            headers: Default::default(),
+        }
+    }
+
+    /// Returns the fetch counts since the last call, and resets the counters.
+    pub fn take_counts(&self) -> FetchCounts {
+        FetchCounts {
+            trie_nodes: self.trie_nodes.swap(0, Ordering::Relaxed),
+            bytecodes: self.bytecodes.swap(0, Ordering::Relaxed),
+            headers: self.headers.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+impl<P: TrieProvider> TrieProvider for CountingTrieDBProvider<P> {
+    type Error = P::Error;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        self.trie_nodes.fetch_add(1, Ordering::Relaxed);
+        self.inner.trie_node_by_hash(key)
+    }
+}
+
+impl<P: TrieDBProvider> TrieDBProvider for CountingTrieDBProvider<P> {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        self.bytecodes.fetch_add(1, Ordering::Relaxed);
+        self.inner.bytecode_by_hash(code_hash)
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        self.headers.fetch_add(1, Ordering::Relaxed);
+        self.inner.header_by_hash(hash)
+    }
+}
+
+/// The outcome of executing a single block of an [`ExecutorTestCorpus`].
+#[derive(Debug, Clone)]
+pub struct CorpusBlockReport {
+    /// The block number.
+    pub number: u64,
+    /// The expected block hash.
+    pub expected_block_hash: B256,
+    /// The produced block hash, if the block was built.
+    pub produced_block_hash: Option<B256>,
+    /// The error hit while building the block, if any.
+    pub error: Option<String>,
+    /// The gas used by the block.
+    pub gas_used: u64,
+    /// The time spent building the block.
+    pub elapsed: Duration,
+    /// The preimages fetched while building the block.
+    pub fetches: FetchCounts,
+}
+
+impl CorpusBlockReport {
+    /// Returns whether the produced block hash matches the expected block hash.
+    pub fn is_match(&self) -> bool {
+        self.produced_block_hash == Some(self.expected_block_hash)
+    }
+
+    /// Returns the execution throughput of the block, in gas per second.
+    pub fn gas_per_second(&self) -> f64 {
+        self.gas_used as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// The outcome of executing an [`ExecutorTestCorpus`].
+#[derive(Debug, Clone, Default)]
+pub struct CorpusReport {
+    /// The per-block outcomes, in execution order.
+    pub blocks: Vec<CorpusBlockReport>,
+}
+
+impl CorpusReport {
+    /// Returns the blocks whose produced hash did not match the expected hash.
+    pub fn mismatches(&self) -> impl Iterator<Item = &CorpusBlockReport> {
+        self.blocks.iter().filter(|b| !b.is_match())
+    }
+
+    /// Returns the total gas used across all blocks.
+    pub fn total_gas_used(&self) -> u64 {
+        self.blocks.iter().map(|b| b.gas_used).sum()
+    }
+
+    /// Returns the total time spent building blocks.
+    pub fn total_elapsed(&self) -> Duration {
+        self.blocks.iter().map(|b| b.elapsed).sum()
+    }
+
+    /// Returns the overall execution throughput, in gas per second.
+    pub fn gas_per_second(&self) -> f64 {
+        self.total_gas_used() as f64 / self.total_elapsed().as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl fmt::Display for CorpusReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for block in &self.blocks {
+            let status = match (&block.error, block.is_match()) {
+                (Some(e), _) => format!("error: {e}"),
+                (None, true) => "ok".to_string(),
+                (None, false) => format!(
+                    "mismatch: expected {}, produced {}",
+                    block.expected_block_hash,
+                    block.produced_block_hash.unwrap_or_default()
+                ),
+            };
+            writeln!(
+                f,
+                "block {}: {status} | gas: {} | {:.0} gas/s | trie nodes: {} | bytecodes: {} | headers: {}",
+                block.number,
+                block.gas_used,
+                block.gas_per_second(),
+                block.fetches.trie_nodes,
+                block.fetches.bytecodes,
+                block.fetches.headers,
+            )?;
+        }
+        write!(
+            f,
+            "{} blocks, {} mismatches | {:.0} gas/s",
+            self.blocks.len(),
+            self.mismatches().count(),
+            self.gas_per_second()
+        )
+    }
+}
+
+/// Opens (or creates) a snappy-compressed [`rocksdb`] key-value store at the given path.
+fn open_kv_store(path: &Path) -> DB {
+    let mut options = Options::default();
+    options.set_compression_type(rocksdb::DBCompressionType::Snappy);
+    options.create_if_missing(true);
+    DB::open(&options, path).unwrap_or_else(|e| panic!("Failed to open database at {path:?}: {e}"))
+}
+
+/// Extracts the tarball at `archive` into `dir`, stripping its top-level directory.
+async fn untar(archive: &Path, dir: &Path) {
+    let output = tokio::process::Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(dir)
+        .arg("--strip-components=1")
+        .output()
+        .await
+        .expect("Failed to run tar");
+    assert!(
+        output.status.success(),
+        "Failed to untar {archive:?}: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Tars and compresses the given directory into `output`.
+async fn tar_directory(dir: &Path, output: &Path) {
+    let result = tokio::process::Command::new("tar")
+        .arg("-czf")
+        .arg(output)
+        .arg(dir.file_name().unwrap())
+        .current_dir(dir.parent().unwrap())
+        .output()
+        .await
+        .expect("Failed to run tar");
+    assert!(
+        result.status.success(),
+        "Failed to tar {dir:?}: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+}
@@ -1,9 +1,9 @@
 //! Test utilities for the executor.
 
-use crate::{StatelessL2Builder, TrieDBProvider};
+use crate::{ExecutionTracer, StatelessL2Builder, TraceConfig, TransactionTrace, TrieDBProvider};
 use alloy_consensus::Header;
 use alloy_op_evm::OpEvmFactory;
-use alloy_primitives::{B256, Bytes, Sealable};
+use alloy_primitives::{B256, Bytes, Sealable, Sealed};
 use alloy_provider::{Provider, RootProvider, network::primitives::BlockTransactions};
 use alloy_rlp::Decodable;
 use alloy_rpc_client::RpcClient;
@@ -18,10 +18,69 @@ use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, sync::Arc};
 use tokio::{fs, runtime::Handle, sync::Mutex};
 
+mod corpus;
+pub use corpus::{
+    CorpusBlockReport, CorpusReport, CountingTrieDBProvider, ExecutorCorpusBlock,
+    ExecutorCorpusCreator, ExecutorTestCorpus, FetchCounts, execute_corpus, open_test_corpus,
+    pack_test_fixtures, run_test_corpus,
+};
+
 /// Executes a [ExecutorTestFixture] stored at the passed `fixture_path` and asserts that the
 /// produced block hash matches the expected block hash.
 pub async fn run_test_fixture(fixture_path: PathBuf) {
-    // First, untar the fixture.
+    let (_fixture_dir, fixture, provider) = open_test_fixture(fixture_path).await;
+
+    let mut executor = StatelessL2Builder::new(
+        &fixture.rollup_config,
+        OpEvmFactory::default(),
+        provider,
+        NoopTrieHinter,
+        fixture.parent_header.seal_slow(),
+    );
+
+    let outcome = executor.build_block(fixture.executing_payload).unwrap();
+
+    assert_eq!(
+        outcome.header.hash(),
+        fixture.expected_block_hash,
+        "Produced header does not match the expected header"
+    );
+}
+
+/// Executes a [ExecutorTestFixture] stored at the passed `fixture_path` with tracing enabled,
+/// asserting that the produced block hash still matches the expected block hash, and returns the
+/// collected traces.
+pub async fn run_traced_test_fixture(
+    fixture_path: PathBuf,
+    config: TraceConfig,
+) -> Vec<TransactionTrace> {
+    let (_fixture_dir, fixture, provider) = open_test_fixture(fixture_path).await;
+
+    let mut executor = StatelessL2Builder::new(
+        &fixture.rollup_config,
+        OpEvmFactory::default(),
+        provider,
+        NoopTrieHinter,
+        fixture.parent_header.seal_slow(),
+    );
+
+    let mut tracer = ExecutionTracer::new(config);
+    let outcome =
+        executor.build_block_with_tracer(fixture.executing_payload, Some(&mut tracer)).unwrap();
+
+    assert_eq!(
+        outcome.header.hash(),
+        fixture.expected_block_hash,
+        "Produced header does not match the expected header"
+    );
+    tracer.take_traces()
+}
+
+/// Untars the [ExecutorTestFixture] stored at the passed `fixture_path`, returning the temporary
+/// directory it was extracted to, the fixture, and a provider over its key-value store.
+pub(crate) async fn open_test_fixture(
+    fixture_path: PathBuf,
+) -> (tempfile::TempDir, ExecutorTestFixture, DiskTrieNodeProvider) {
     let fixture_dir = tempfile::tempdir().expect("Failed to create temporary directory");
     tokio::process::Command::new("tar")
         .arg("-xvf")
@@ -43,21 +102,7 @@ pub async fn run_test_fixture(fixture_path: PathBuf) {
         serde_json::from_slice(&fs::read(fixture_dir.path().join("fixture.json")).await.unwrap())
             .expect("Failed to deserialize fixture");
 
-    let mut executor = StatelessL2Builder::new(
-        &fixture.rollup_config,
-        OpEvmFactory::default(),
-        provider,
-        NoopTrieHinter,
-        fixture.parent_header.seal_slow(),
-    );
-
-    let outcome = executor.build_block(fixture.executing_payload).unwrap();
-
-    assert_eq!(
-        outcome.header.hash(),
-        fixture.expected_block_hash,
-        "Produced header does not match the expected header"
-    );
+    (fixture_dir, fixture, provider)
 }
 
 /// The test fixture format for the [`StatelessL2Builder`].
@@ -111,6 +156,54 @@ impl ExecutorTestFixtureCreator {
         let chain_id = self.provider.get_chain_id().await.expect("Failed to get chain ID");
         let rollup_config = ROLLUP_CONFIGS.get(&chain_id).expect("Rollup config not found");
 
+        let (executing_header, parent_header, payload_attrs) =
+            self.fetch_executing_payload(rollup_config).await;
+
+        let fixture_path = self.data_dir.join("fixture.json");
+        let fixture = ExecutorTestFixture {
+            rollup_config: rollup_config.clone(),
+            parent_header: parent_header.inner().clone(),
+            executing_payload: payload_attrs.clone(),
+            expected_block_hash: executing_header.hash_slow(),
+        };
+
+        let mut executor = StatelessL2Builder::new(
+            rollup_config,
+            OpEvmFactory::default(),
+            self,
+            NoopTrieHinter,
+            parent_header,
+        );
+        let outcome = executor.build_block(payload_attrs).expect("Failed to execute block");
+
+        assert_eq!(
+            outcome.header.inner(),
+            &executing_header,
+            "Produced header does not match the expected header"
+        );
+        fs::write(fixture_path.as_path(), serde_json::to_vec(&fixture).unwrap()).await.unwrap();
+
+        // Tar the fixture.
+        let data_dir = fixture_path.parent().unwrap();
+        tokio::process::Command::new("tar")
+            .arg("-czf")
+            .arg(data_dir.with_extension("tar.gz").file_name().unwrap())
+            .arg(data_dir.file_name().unwrap())
+            .current_dir(data_dir.parent().unwrap())
+            .output()
+            .await
+            .expect("Failed to tar fixture");
+
+        // Remove the leftover directory.
+        fs::remove_dir_all(data_dir).await.expect("Failed to remove temporary directory");
+    }
+
+    /// Fetches the executing block's header, its sealed parent header, and the payload
+    /// attributes that reproduce it.
+    pub async fn fetch_executing_payload(
+        &self,
+        rollup_config: &RollupConfig,
+    ) -> (Header, Sealed<Header>, OpPayloadAttributes) {
         let executing_block = self
             .provider
             .get_block_by_number(self.block_number.into())
@@ -171,43 +264,7 @@ impl ExecutorTestFixtureCreator {
             }),
         };
 
-        let fixture_path = self.data_dir.join("fixture.json");
-        let fixture = ExecutorTestFixture {
-            rollup_config: rollup_config.clone(),
-            parent_header: parent_header.inner().clone(),
-            executing_payload: payload_attrs.clone(),
-            expected_block_hash: executing_header.hash_slow(),
-        };
-
-        let mut executor = StatelessL2Builder::new(
-            rollup_config,
-            OpEvmFactory::default(),
-            self,
-            NoopTrieHinter,
-            parent_header,
-        );
-        let outcome = executor.build_block(payload_attrs).expect("Failed to execute block");
-
-        assert_eq!(
-            outcome.header.inner(),
-            &executing_header.inner,
-            "Produced header does not match the expected header"
-        );
-        fs::write(fixture_path.as_path(), serde_json::to_vec(&fixture).unwrap()).await.unwrap();
-
-        // Tar the fixture.
-        let data_dir = fixture_path.parent().unwrap();
-        tokio::process::Command::new("tar")
-            .arg("-czf")
-            .arg(data_dir.with_extension("tar.gz").file_name().unwrap())
-            .arg(data_dir.file_name().unwrap())
-            .current_dir(data_dir.parent().unwrap())
-            .output()
-            .await
-            .expect("Failed to tar fixture");
-
-        // Remove the leftover directory.
-        fs::remove_dir_all(data_dir).await.expect("Failed to remove temporary directory");
+        (executing_header.inner, parent_header, payload_attrs)
     }
 }
 
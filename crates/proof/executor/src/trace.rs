@@ -0,0 +1,421 @@
+//! Per-transaction EVM execution traces for the [`StatelessL2Builder`].
+//!
+//! The [`ExecutionTracer`] collects a call trace and opcode-level struct logs for every
+//! transaction in a block, in the same shape as the `callTracer` and the default struct logger of
+//! `debug_traceBlock`. This makes it possible to diff kona's execution against an execution layer
+//! client when a block produces an unexpected state root.
+//!
+//! [`StatelessL2Builder`]: crate::StatelessL2Builder
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{Address, B256, Bytes, U256};
+use revm::{
+    Inspector,
+    bytecode::OpCode,
+    context::ContextTr,
+    inspector::NoOpInspector,
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme,
+        Interpreter, InterpreterResult, interpreter_types::Jumps,
+    },
+};
+
+/// Configures which traces the [`ExecutionTracer`] collects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TraceConfig {
+    /// Whether to collect the call trace of each transaction.
+    pub calls: bool,
+    /// Whether to collect a struct log for every executed opcode.
+    pub struct_logs: bool,
+    /// Whether to include the stack in the struct logs.
+    pub stack: bool,
+}
+
+/// The kind of a [`CallFrame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallKind {
+    /// A `CALL`.
+    #[cfg_attr(feature = "serde", serde(rename = "CALL"))]
+    Call,
+    /// A `CALLCODE`.
+    #[cfg_attr(feature = "serde", serde(rename = "CALLCODE"))]
+    CallCode,
+    /// A `DELEGATECALL`.
+    #[cfg_attr(feature = "serde", serde(rename = "DELEGATECALL"))]
+    DelegateCall,
+    /// A `STATICCALL`.
+    #[cfg_attr(feature = "serde", serde(rename = "STATICCALL"))]
+    StaticCall,
+    /// A `CREATE`.
+    #[cfg_attr(feature = "serde", serde(rename = "CREATE"))]
+    Create,
+    /// A `CREATE2`.
+    #[cfg_attr(feature = "serde", serde(rename = "CREATE2"))]
+    Create2,
+}
+
+impl From<CallScheme> for CallKind {
+    fn from(scheme: CallScheme) -> Self {
+        match scheme {
+            CallScheme::Call => Self::Call,
+            CallScheme::CallCode => Self::CallCode,
+            CallScheme::DelegateCall => Self::DelegateCall,
+            CallScheme::StaticCall => Self::StaticCall,
+        }
+    }
+}
+
+/// A single call frame, in the shape of the geth `callTracer` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CallFrame {
+    /// The kind of the call.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: CallKind,
+    /// The caller.
+    pub from: Address,
+    /// The callee, or the created contract. `None` if a creation failed.
+    pub to: Option<Address>,
+    /// The value transferred with the call.
+    pub value: U256,
+    /// The gas made available to the call.
+    pub gas: u64,
+    /// The gas used by the call.
+    pub gas_used: u64,
+    /// The call data, or the init code of a creation.
+    pub input: Bytes,
+    /// The returned data, or the deployed code of a creation.
+    pub output: Bytes,
+    /// The error, if the call did not succeed.
+    pub error: Option<String>,
+    /// The sub-calls made by the call.
+    pub calls: Vec<CallFrame>,
+}
+
+/// The state of the EVM before an opcode is executed, in the shape of the geth struct logger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StructLog {
+    /// The program counter.
+    pub pc: u64,
+    /// The name of the opcode.
+    pub op: String,
+    /// The remaining gas before the opcode is executed.
+    pub gas: u64,
+    /// The gas charged for the opcode.
+    pub gas_cost: u64,
+    /// The call depth, starting at 1 for the transaction's top-level call.
+    pub depth: u64,
+    /// The stack before the opcode is executed, if enabled in the [`TraceConfig`].
+    pub stack: Option<Vec<U256>>,
+}
+
+/// The traces of a single transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TransactionTrace {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The top-level call frame of the transaction, if call traces are enabled.
+    pub call: Option<CallFrame>,
+    /// The struct logs of the transaction, if enabled.
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Collects [`TransactionTrace`]s for the transactions executed by the
+/// [`StatelessL2Builder`](crate::StatelessL2Builder).
+///
+/// Events outside of a transaction, such as the system calls made before and after the block's
+/// transactions, are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTracer {
+    /// The trace configuration.
+    config: TraceConfig,
+    /// The traces of the completed transactions.
+    traces: Vec<TransactionTrace>,
+    /// The trace of the transaction currently being executed.
+    current: Option<TransactionTrace>,
+    /// The call frames that have been entered but not exited yet. Its length is the current call
+    /// depth.
+    call_stack: Vec<CallFrame>,
+    /// The remaining gas before the opcode currently being executed.
+    step_gas: u64,
+}
+
+impl ExecutionTracer {
+    /// Creates a new [`ExecutionTracer`] with the given [`TraceConfig`].
+    pub fn new(config: TraceConfig) -> Self {
+        Self { config, ..Default::default() }
+    }
+
+    /// Returns the [`TraceConfig`] of the tracer.
+    pub const fn config(&self) -> &TraceConfig {
+        &self.config
+    }
+
+    /// Returns the traces of the transactions executed so far.
+    pub fn traces(&self) -> &[TransactionTrace] {
+        &self.traces
+    }
+
+    /// Takes the traces of the transactions executed so far, leaving the tracer empty.
+    pub fn take_traces(&mut self) -> Vec<TransactionTrace> {
+        core::mem::take(&mut self.traces)
+    }
+
+    /// Begins tracing the transaction with the given hash.
+    pub fn start_transaction(&mut self, tx_hash: B256) {
+        self.call_stack.clear();
+        self.current = Some(TransactionTrace { tx_hash, ..Default::default() });
+    }
+
+    /// Finishes tracing the current transaction.
+    pub fn finish_transaction(&mut self, gas_used: u64) {
+        if let Some(mut trace) = self.current.take() {
+            trace.gas_used = gas_used;
+            self.traces.push(trace);
+        }
+        self.call_stack.clear();
+    }
+
+    /// Enters a new call frame.
+    fn enter(&mut self, frame: CallFrame) {
+        if self.current.is_some() {
+            self.call_stack.push(frame);
+        }
+    }
+
+    /// Exits the innermost call frame with the given result.
+    fn exit(&mut self, result: &InterpreterResult, created: Option<Address>) {
+        let Some(mut frame) = self.call_stack.pop() else { return };
+        frame.gas_used = result.gas.spent();
+        if matches!(frame.kind, CallKind::Create | CallKind::Create2) {
+            frame.to = created;
+        }
+        frame.output = result.output.clone();
+        if !result.result.is_ok() {
+            frame.error = Some(format!("{:?}", result.result));
+        }
+
+        match self.call_stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => {
+                if let Some(trace) = self.current.as_mut().filter(|_| self.config.calls) {
+                    trace.call = Some(frame);
+                }
+            }
+        }
+    }
+}
+
+/// An [`Inspector`] attached to the EVM of a block built by the [`StatelessL2Builder`].
+///
+/// The builder hands the inspector to [`EvmFactory::create_evm_with_inspector`], so it runs on
+/// whichever EVM the factory produces, such as the FPVM EVM with accelerated precompiles, and is
+/// additionally told where each of the block's transactions starts and ends. The system calls
+/// made before and after the block's transactions are not inspected.
+///
+/// [`StatelessL2Builder`]: crate::StatelessL2Builder
+/// [`EvmFactory::create_evm_with_inspector`]: alloy_evm::EvmFactory::create_evm_with_inspector
+pub trait BlockInspector<CTX>: Inspector<CTX> {
+    /// Returns whether the EVM should run with inspection enabled. Defaults to `true`.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Called before a transaction of the block is executed, with a function computing its hash.
+    fn start_transaction(&mut self, _tx_hash: impl FnOnce() -> B256) {}
+
+    /// Called after the current transaction was executed, with the gas it used.
+    fn finish_transaction(&mut self, _gas_used: u64) {}
+}
+
+impl<CTX> BlockInspector<CTX> for NoOpInspector {
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// The [`Inspector`] handed to the EVM, feeding an [`ExecutionTracer`], if tracing is enabled.
+///
+/// Without a tracer, every hook is a no-op, and the EVM is run with inspection disabled.
+#[derive(Debug)]
+pub(crate) struct TracerInspector<'a>(pub(crate) Option<&'a mut ExecutionTracer>);
+
+impl<CTX: ContextTr> BlockInspector<CTX> for TracerInspector<'_> {
+    fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn start_transaction(&mut self, tx_hash: impl FnOnce() -> B256) {
+        if let Some(tracer) = self.0.as_deref_mut() {
+            tracer.start_transaction(tx_hash());
+        }
+    }
+
+    fn finish_transaction(&mut self, gas_used: u64) {
+        if let Some(tracer) = self.0.as_deref_mut() {
+            tracer.finish_transaction(gas_used);
+        }
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX> for TracerInspector<'_> {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut CTX) {
+        let Some(tracer) = self.0.as_deref_mut() else { return };
+        let Some(trace) = tracer.current.as_mut() else { return };
+        if !tracer.config.struct_logs {
+            return;
+        }
+
+        tracer.step_gas = interp.gas.remaining();
+        let opcode = interp.bytecode.opcode();
+        trace.struct_logs.push(StructLog {
+            pc: interp.bytecode.pc() as u64,
+            op: OpCode::new(opcode)
+                .map_or_else(|| format!("opcode {opcode:#04x} not defined"), |op| op.to_string()),
+            gas: tracer.step_gas,
+            gas_cost: 0,
+            depth: tracer.call_stack.len().max(1) as u64,
+            stack: tracer.config.stack.then(|| interp.stack.data().clone()),
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut CTX) {
+        let Some(tracer) = self.0.as_deref_mut() else { return };
+        let Some(log) = tracer.current.as_mut().and_then(|t| t.struct_logs.last_mut()) else {
+            return;
+        };
+        log.gas_cost = tracer.step_gas.saturating_sub(interp.gas.remaining());
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let tracer = self.0.as_deref_mut()?;
+        let input = inputs.input.bytes(context);
+        tracer.enter(CallFrame {
+            kind: inputs.scheme.into(),
+            from: inputs.caller,
+            to: Some(inputs.target_address),
+            value: inputs.value.get(),
+            gas: inputs.gas_limit,
+            gas_used: 0,
+            input,
+            output: Bytes::new(),
+            error: None,
+            calls: Vec::new(),
+        });
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        if let Some(tracer) = self.0.as_deref_mut() {
+            tracer.exit(&outcome.result, None);
+        }
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        let tracer = self.0.as_deref_mut()?;
+        let kind = match inputs.scheme() {
+            CreateScheme::Create2 { .. } => CallKind::Create2,
+            _ => CallKind::Create,
+        };
+        tracer.enter(CallFrame {
+            kind,
+            from: inputs.caller(),
+            to: None,
+            value: inputs.value(),
+            gas: inputs.gas_limit(),
+            gas_used: 0,
+            input: inputs.init_code().clone(),
+            output: Bytes::new(),
+            error: None,
+            calls: Vec::new(),
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if let Some(tracer) = self.0.as_deref_mut() {
+            tracer.exit(&outcome.result, outcome.address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use revm::interpreter::{Gas, InstructionResult};
+
+    fn frame(kind: CallKind) -> CallFrame {
+        CallFrame {
+            kind,
+            from: Address::ZERO,
+            to: Some(Address::repeat_byte(1)),
+            value: U256::ZERO,
+            gas: 100,
+            gas_used: 0,
+            input: Bytes::new(),
+            output: Bytes::new(),
+            error: None,
+            calls: Vec::new(),
+        }
+    }
+
+    fn result(result: InstructionResult, spent: u64) -> InterpreterResult {
+        let mut gas = Gas::new(100);
+        assert!(gas.record_cost(spent));
+        InterpreterResult { result, output: Bytes::from_static(&[0xff]), gas }
+    }
+
+    #[test]
+    fn test_tracer_nests_call_frames() {
+        let mut tracer = ExecutionTracer::new(TraceConfig { calls: true, ..Default::default() });
+        tracer.start_transaction(B256::repeat_byte(0xaa));
+        tracer.enter(frame(CallKind::Call));
+        tracer.enter(frame(CallKind::Create2));
+        tracer.exit(&result(InstructionResult::Revert, 30), Some(Address::repeat_byte(2)));
+        tracer.exit(&result(InstructionResult::Return, 60), None);
+        tracer.finish_transaction(21_060);
+
+        let traces = tracer.take_traces();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].tx_hash, B256::repeat_byte(0xaa));
+        assert_eq!(traces[0].gas_used, 21_060);
+
+        let call = traces[0].call.as_ref().unwrap();
+        assert_eq!(call.gas_used, 60);
+        assert!(call.error.is_none());
+        assert_eq!(call.calls.len(), 1);
+        assert_eq!(call.calls[0].kind, CallKind::Create2);
+        assert_eq!(call.calls[0].to, Some(Address::repeat_byte(2)));
+        assert_eq!(call.calls[0].gas_used, 30);
+        assert_eq!(call.calls[0].error.as_deref(), Some("Revert"));
+        assert!(tracer.traces().is_empty());
+    }
+
+    #[test]
+    fn test_tracer_ignores_events_outside_transactions() {
+        let mut tracer = ExecutionTracer::new(TraceConfig { calls: true, ..Default::default() });
+        tracer.enter(frame(CallKind::Call));
+        tracer.exit(&result(InstructionResult::Stop, 10), None);
+        tracer.finish_transaction(0);
+        assert!(tracer.traces().is_empty());
+    }
+}
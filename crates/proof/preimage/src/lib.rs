@@ -33,3 +33,11 @@ pub use traits::{
 mod native_channel;
 #[cfg(feature = "std")]
 pub use native_channel::{BidirectionalChannel, NativeChannel};
+
+#[cfg(feature = "socket")]
+mod socket_channel;
+#[cfg(feature = "socket")]
+pub use socket_channel::{
+    HANDSHAKE_TIMEOUT, PENDING_SESSION_TIMEOUT, PreimageListener, PreimageSession,
+    SESSION_HINT_CHANNEL, SESSION_PREIMAGE_CHANNEL, SocketAddress, SocketChannel,
+};
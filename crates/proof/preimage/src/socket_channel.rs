@@ -0,0 +1,461 @@
+//! Stream socket implementation of the [Channel] trait, allowing the client and host to run on
+//! different machines.
+//!
+//! Each [Channel] is carried by its own TCP or Unix stream connection, framed exactly like the
+//! pipe protocol. A [PreimageSession] pairs the hint and preimage connections of a client with a
+//! short handshake, performed once when the connections are opened:
+//!
+//! 1. The client opens the hint connection and writes [SESSION_HINT_CHANNEL]. The host responds
+//!    with the 8 byte big-endian ID of the new session, chosen at random.
+//! 2. The client opens the preimage connection and writes [SESSION_PREIMAGE_CHANNEL], followed by
+//!    the session ID.
+//!
+//! Each step must complete within [HANDSHAKE_TIMEOUT], and the preimage connection must follow
+//! the hint connection within [PENDING_SESSION_TIMEOUT].
+
+use crate::{
+    Channel,
+    errors::{ChannelError, ChannelResult},
+};
+use async_trait::async_trait;
+use std::{
+    boxed::Box,
+    collections::HashMap,
+    fmt,
+    io::{self, ErrorKind},
+    net::SocketAddr,
+    path::PathBuf,
+    str::FromStr,
+    string::{String, ToString},
+    sync::{Arc, Mutex as StdMutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{Mutex, mpsc},
+    time::{sleep, timeout},
+};
+
+/// The handshake byte opening the hint connection of a [PreimageSession].
+pub const SESSION_HINT_CHANNEL: u8 = 0x00;
+
+/// The handshake byte opening the preimage connection of a [PreimageSession].
+pub const SESSION_PREIMAGE_CHANNEL: u8 = 0x01;
+
+/// The time a new connection has to complete its handshake.
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The time the preimage connection of a session has to connect after its hint connection.
+pub const PENDING_SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A boxed read half of a stream socket.
+type BoxedReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// A boxed write half of a stream socket.
+type BoxedWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// A [Channel] backed by a stream socket.
+///
+/// Clones of a [SocketChannel] share the underlying socket.
+#[derive(Clone)]
+pub struct SocketChannel {
+    /// The read half of the socket.
+    read: Arc<Mutex<BoxedReader>>,
+    /// The write half of the socket.
+    write: Arc<Mutex<BoxedWriter>>,
+}
+
+impl fmt::Debug for SocketChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketChannel").finish_non_exhaustive()
+    }
+}
+
+impl SocketChannel {
+    /// Creates a new [SocketChannel] from the read and write halves of a stream socket.
+    pub fn new<R, W>(read: R, write: W) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Self {
+            read: Arc::new(Mutex::new(Box::new(read))),
+            write: Arc::new(Mutex::new(Box::new(write))),
+        }
+    }
+
+    /// Creates a new [SocketChannel] from a TCP stream.
+    pub fn from_tcp(stream: TcpStream) -> Self {
+        // Preimage requests are small and latency bound.
+        let _ = stream.set_nodelay(true);
+        let (read, write) = stream.into_split();
+        Self::new(read, write)
+    }
+
+    /// Creates a new [SocketChannel] from a Unix stream.
+    #[cfg(unix)]
+    pub fn from_unix(stream: tokio::net::UnixStream) -> Self {
+        let (read, write) = stream.into_split();
+        Self::new(read, write)
+    }
+}
+
+#[async_trait]
+impl Channel for SocketChannel {
+    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read.lock().await.read(buf).await.map_err(|_| ChannelError::Closed)
+    }
+
+    async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read.lock().await.read_exact(buf).await.map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => ChannelError::UnexpectedEOF,
+            _ => ChannelError::Closed,
+        })
+    }
+
+    async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
+        let mut write = self.write.lock().await;
+        write.write_all(buf).await.map_err(|_| ChannelError::Closed)?;
+        write.flush().await.map_err(|_| ChannelError::Closed)?;
+        Ok(buf.len())
+    }
+}
+
+/// The address of a stream socket serving [PreimageSession]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketAddress {
+    /// A TCP socket address.
+    Tcp(SocketAddr),
+    /// The path of a Unix socket.
+    Unix(PathBuf),
+}
+
+impl fmt::Display for SocketAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+impl FromStr for SocketAddress {
+    type Err = String;
+
+    /// Parses a `tcp://<ip>:<port>` or `unix://<path>` address. Addresses without a scheme are
+    /// parsed as TCP addresses.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix://") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        let addr = s.strip_prefix("tcp://").unwrap_or(s);
+        addr.parse().map(Self::Tcp).map_err(|e| format!("Invalid socket address {s}: {e}"))
+    }
+}
+
+/// The hint and preimage [SocketChannel]s of a client connected to the host.
+#[derive(Debug, Clone)]
+pub struct PreimageSession {
+    /// The ID of the session, assigned by the host.
+    pub id: u64,
+    /// The hint channel.
+    pub hint: SocketChannel,
+    /// The preimage channel.
+    pub preimage: SocketChannel,
+}
+
+impl PreimageSession {
+    /// Connects to the host listening at the given address, opening a new session.
+    pub async fn connect(addr: &SocketAddress) -> io::Result<Self> {
+        let hint = connect(addr).await?;
+        hint.write(&[SESSION_HINT_CHANNEL]).await.map_err(channel_to_io_error)?;
+        let mut id = [0u8; 8];
+        hint.read_exact(&mut id).await.map_err(channel_to_io_error)?;
+
+        let preimage = connect(addr).await?;
+        let mut handshake = [0u8; 9];
+        handshake[0] = SESSION_PREIMAGE_CHANNEL;
+        handshake[1..].copy_from_slice(&id);
+        preimage.write(&handshake).await.map_err(channel_to_io_error)?;
+
+        Ok(Self { id: u64::from_be_bytes(id), hint, preimage })
+    }
+}
+
+/// Opens a [SocketChannel] to the given address.
+async fn connect(addr: &SocketAddress) -> io::Result<SocketChannel> {
+    match addr {
+        SocketAddress::Tcp(addr) => TcpStream::connect(addr).await.map(SocketChannel::from_tcp),
+        #[cfg(unix)]
+        SocketAddress::Unix(path) => {
+            tokio::net::UnixStream::connect(path).await.map(SocketChannel::from_unix)
+        }
+        #[cfg(not(unix))]
+        SocketAddress::Unix(_) => Err(io::Error::new(ErrorKind::Unsupported, "Unix sockets")),
+    }
+}
+
+/// Converts a [ChannelError] raised during a handshake into an [io::Error].
+fn channel_to_io_error(e: ChannelError) -> io::Error {
+    match e {
+        ChannelError::UnexpectedEOF => io::Error::new(ErrorKind::UnexpectedEof, e.to_string()),
+        ChannelError::Closed => io::Error::new(ErrorKind::BrokenPipe, e.to_string()),
+    }
+}
+
+/// The hint channels of the sessions whose preimage channel has not connected yet, keyed by
+/// session ID.
+type PendingSessions = Arc<StdMutex<HashMap<u64, SocketChannel>>>;
+
+/// A listener accepting [PreimageSession]s from clients.
+///
+/// Handshakes run in their own tasks, so a slow or idle connection does not hold up other
+/// sessions.
+#[derive(Debug)]
+pub struct PreimageListener {
+    /// The underlying socket listener.
+    inner: Listener,
+    /// The sessions waiting for their preimage channel.
+    pending: PendingSessions,
+    /// The sender of completed sessions, cloned into the handshake tasks.
+    completed_tx: mpsc::UnboundedSender<PreimageSession>,
+    /// The receiver of completed sessions.
+    completed_rx: mpsc::UnboundedReceiver<PreimageSession>,
+}
+
+/// A TCP or Unix socket listener.
+#[derive(Debug)]
+enum Listener {
+    /// A TCP listener.
+    Tcp(TcpListener),
+    /// A Unix socket listener.
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+}
+
+impl Listener {
+    /// Accepts the next connection as a [SocketChannel].
+    async fn accept(&self) -> io::Result<SocketChannel> {
+        match self {
+            Self::Tcp(listener) => Ok(SocketChannel::from_tcp(listener.accept().await?.0)),
+            #[cfg(unix)]
+            Self::Unix(listener) => Ok(SocketChannel::from_unix(listener.accept().await?.0)),
+        }
+    }
+}
+
+impl PreimageListener {
+    /// Binds a new [PreimageListener] to the given address.
+    pub async fn bind(addr: &SocketAddress) -> io::Result<Self> {
+        let inner = match addr {
+            SocketAddress::Tcp(addr) => Listener::Tcp(TcpListener::bind(addr).await?),
+            #[cfg(unix)]
+            SocketAddress::Unix(path) => Listener::Unix(tokio::net::UnixListener::bind(path)?),
+            #[cfg(not(unix))]
+            SocketAddress::Unix(_) => {
+                return Err(io::Error::new(ErrorKind::Unsupported, "Unix sockets"));
+            }
+        };
+        let (completed_tx, completed_rx) = mpsc::unbounded_channel();
+        Ok(Self { inner, pending: Default::default(), completed_tx, completed_rx })
+    }
+
+    /// Returns the local TCP address of the listener, if it listens on a TCP socket.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match &self.inner {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+
+    /// Accepts the next [PreimageSession], once both its hint and preimage channels have
+    /// connected.
+    ///
+    /// Connections that fail their handshake are dropped without failing the listener. An error
+    /// is only returned if accepting a connection fails, after which the listener may still be
+    /// used.
+    pub async fn accept(&mut self) -> io::Result<PreimageSession> {
+        loop {
+            tokio::select! {
+                Some(session) = self.completed_rx.recv() => return Ok(session),
+                channel = self.inner.accept() => {
+                    let pending = Arc::clone(&self.pending);
+                    let completed = self.completed_tx.clone();
+                    tokio::spawn(handshake(channel?, pending, completed));
+                }
+            }
+        }
+    }
+}
+
+/// Locks the [PendingSessions]. They are only modified by single map operations, so they are
+/// consistent even if a holder of the lock panicked.
+fn lock_pending(pending: &PendingSessions) -> MutexGuard<'_, HashMap<u64, SocketChannel>> {
+    pending.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Performs the handshake of a new connection, sending the session it completes, if any, to
+/// `completed`. Hint channels whose preimage channel does not follow within
+/// [PENDING_SESSION_TIMEOUT] are dropped.
+async fn handshake(
+    channel: SocketChannel,
+    pending: PendingSessions,
+    completed: mpsc::UnboundedSender<PreimageSession>,
+) {
+    let result = timeout(HANDSHAKE_TIMEOUT, async {
+        let mut kind = [0u8; 1];
+        channel.read_exact(&mut kind).await?;
+
+        match kind[0] {
+            SESSION_HINT_CHANNEL => {
+                let id = {
+                    let mut pending = lock_pending(&pending);
+                    let id = loop {
+                        let id = rand::random::<u64>();
+                        if !pending.contains_key(&id) {
+                            break id;
+                        }
+                    };
+                    pending.insert(id, channel.clone());
+                    id
+                };
+                if let Err(e) = channel.write(&id.to_be_bytes()).await {
+                    lock_pending(&pending).remove(&id);
+                    return Err(e);
+                }
+                Ok::<_, ChannelError>(Some(id))
+            }
+            SESSION_PREIMAGE_CHANNEL => {
+                let mut id = [0u8; 8];
+                channel.read_exact(&mut id).await?;
+                let id = u64::from_be_bytes(id);
+                let hint = lock_pending(&pending).remove(&id).ok_or(ChannelError::Closed)?;
+                let _ = completed.send(PreimageSession { id, hint, preimage: channel.clone() });
+                Ok(None)
+            }
+            _ => Err(ChannelError::Closed),
+        }
+    })
+    .await;
+
+    match result {
+        Ok(Ok(Some(id))) => {
+            sleep(PENDING_SESSION_TIMEOUT).await;
+            if lock_pending(&pending).remove(&id).is_some() {
+                warn!(target: "preimage_listener", "Dropping session {id}: preimage channel did not connect");
+            }
+        }
+        Ok(Ok(None)) => {}
+        Ok(Err(e)) => {
+            warn!(target: "preimage_listener", "Dropping connection after failed handshake: {e}");
+        }
+        Err(_) => {
+            warn!(target: "preimage_listener", "Dropping connection after handshake timed out");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        HintReader, HintReaderServer, HintRouter, HintWriter, HintWriterClient, OracleReader,
+        OracleServer, PreimageFetcher, PreimageKey, PreimageOracleClient, PreimageOracleServer,
+        errors::PreimageOracleResult,
+    };
+    use alloy_primitives::keccak256;
+    use std::vec::Vec;
+
+    struct TestBackend;
+
+    #[async_trait]
+    impl PreimageFetcher for TestBackend {
+        async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            Ok(key.key_value().to_be_bytes::<32>().to_vec())
+        }
+    }
+
+    #[async_trait]
+    impl HintRouter for TestBackend {
+        async fn route_hint(&self, _: String) -> PreimageOracleResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_socket_address_from_str() {
+        assert_eq!(
+            "127.0.0.1:7000".parse::<SocketAddress>().unwrap(),
+            SocketAddress::Tcp("127.0.0.1:7000".parse().unwrap())
+        );
+        assert_eq!(
+            "tcp://127.0.0.1:7000".parse::<SocketAddress>().unwrap(),
+            SocketAddress::Tcp("127.0.0.1:7000".parse().unwrap())
+        );
+        assert_eq!(
+            "unix:///tmp/kona.sock".parse::<SocketAddress>().unwrap(),
+            SocketAddress::Unix(PathBuf::from("/tmp/kona.sock"))
+        );
+        assert!("localhost".parse::<SocketAddress>().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_sessions() {
+        let mut listener = PreimageListener::bind(&"127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let addr = SocketAddress::Tcp(listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok(session) = listener.accept().await {
+                tokio::spawn(async move {
+                    let oracle = OracleServer::new(session.preimage);
+                    while oracle.next_preimage_request(&TestBackend).await.is_ok() {}
+                });
+                tokio::spawn(async move {
+                    let hints = HintReader::new(session.hint);
+                    while hints.next_hint(&TestBackend).await.is_ok() {}
+                });
+            }
+        });
+
+        let clients = (0..4u8)
+            .map(|i| {
+                let addr = addr.clone();
+                tokio::spawn(async move {
+                    let session = PreimageSession::connect(&addr).await.unwrap();
+                    let oracle = OracleReader::new(session.preimage);
+                    let hints = HintWriter::new(session.hint);
+
+                    hints.write(&format!("hint {i}")).await.unwrap();
+                    let key = PreimageKey::new_keccak256(*keccak256([i]));
+                    let preimage = oracle.get(key).await.unwrap();
+                    assert_eq!(preimage, key.key_value().to_be_bytes::<32>().to_vec());
+                    session.id
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut ids = Vec::new();
+        for client in clients {
+            ids.push(client.await.unwrap());
+        }
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_idle_connection_does_not_block_sessions() {
+        let mut listener = PreimageListener::bind(&"127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let addr = SocketAddress::Tcp(local_addr);
+
+        // A connection that never sends its handshake.
+        let _idle = TcpStream::connect(local_addr).await.unwrap();
+
+        let client = tokio::spawn(async move { PreimageSession::connect(&addr).await.unwrap() });
+        let session = timeout(HANDSHAKE_TIMEOUT / 2, listener.accept()).await.unwrap().unwrap();
+        assert_eq!(session.id, client.await.unwrap().id);
+    }
+}
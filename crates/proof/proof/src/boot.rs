@@ -2,6 +2,7 @@
 //! through the `PreimageOracle` ABI as local keys.
 
 use crate::errors::OracleProviderError;
+use alloc::vec::Vec;
 use alloy_primitives::{B256, U256};
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{PreimageKey, PreimageOracleClient};
@@ -57,6 +58,79 @@ pub const L2_ROLLUP_CONFIG_KEY: U256 = U256::from_be_slice(&[6]);
 /// given chain ID. Oracle-loaded configs require additional validation.
 pub const L1_CONFIG_KEY: U256 = U256::from_be_slice(&[7]);
 
+/// The local key identifier for the intermediate L2 output root claims.
+///
+/// This key retrieves a list of output root claims at L2 blocks between the agreed
+/// and disputed blocks, encoded as consecutive [`IntermediateClaim`]s. It is only
+/// served by hosts running the client program natively, to check every claim in a
+/// single derivation pass.
+pub const L2_INTERMEDIATE_CLAIMS_KEY: U256 = U256::from_be_slice(&[8]);
+
+/// An output root claim at an L2 block between the agreed and disputed blocks.
+///
+/// Intermediate claims are not part of the fault proof. They allow off-chain monitors
+/// to check a sequence of output roots against a single derivation of the L2 chain,
+/// rather than re-deriving from the agreed head for every claim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntermediateClaim {
+    /// The claimed L2 block number.
+    pub block_number: u64,
+    /// The claimed output root at the L2 block.
+    pub output_root: B256,
+}
+
+impl IntermediateClaim {
+    /// The length of an encoded [`IntermediateClaim`]: the block number as 8 big-endian
+    /// bytes, followed by the 32 byte output root.
+    pub const ENCODED_LEN: usize = 8 + 32;
+
+    /// Creates a new [`IntermediateClaim`].
+    pub const fn new(block_number: u64, output_root: B256) -> Self {
+        Self { block_number, output_root }
+    }
+
+    /// Encodes a list of [`IntermediateClaim`]s, as served under
+    /// [`L2_INTERMEDIATE_CLAIMS_KEY`].
+    pub fn encode_list(claims: &[Self]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(claims.len() * Self::ENCODED_LEN);
+        for claim in claims {
+            out.extend_from_slice(&claim.block_number.to_be_bytes());
+            out.extend_from_slice(claim.output_root.as_slice());
+        }
+        out
+    }
+
+    /// Decodes a list of [`IntermediateClaim`]s, as served under
+    /// [`L2_INTERMEDIATE_CLAIMS_KEY`].
+    pub fn decode_list(data: &[u8]) -> Result<Vec<Self>, OracleProviderError> {
+        data.chunks(Self::ENCODED_LEN)
+            .map(|chunk| {
+                let chunk: [u8; Self::ENCODED_LEN] =
+                    chunk.try_into().map_err(OracleProviderError::SliceConversion)?;
+                let block_number = u64::from_be_bytes(
+                    chunk[..8].try_into().map_err(OracleProviderError::SliceConversion)?,
+                );
+                Ok(Self::new(block_number, B256::from_slice(&chunk[8..])))
+            })
+            .collect()
+    }
+
+    /// Load the intermediate claims from the preimage oracle.
+    ///
+    /// The intermediate claims are not part of the [`BootInfo`], as they are only
+    /// available when the client program runs natively.
+    pub async fn load<O>(oracle: &O) -> Result<Vec<Self>, OracleProviderError>
+    where
+        O: PreimageOracleClient + Send,
+    {
+        let data = oracle
+            .get(PreimageKey::new_local(L2_INTERMEDIATE_CLAIMS_KEY.to()))
+            .await
+            .map_err(OracleProviderError::Preimage)?;
+        Self::decode_list(&data)
+    }
+}
+
 /// The boot information for the client program.
 ///
 /// [`BootInfo`] contains all the essential parameters needed to initialize the fault proof
@@ -277,3 +351,32 @@ impl BootInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intermediate_claims_roundtrip() {
+        let claims = [
+            IntermediateClaim::new(10, B256::repeat_byte(0x01)),
+            IntermediateClaim::new(20, B256::repeat_byte(0x02)),
+        ];
+        let encoded = IntermediateClaim::encode_list(&claims);
+
+        assert_eq!(encoded.len(), 2 * IntermediateClaim::ENCODED_LEN);
+        assert_eq!(IntermediateClaim::decode_list(&encoded).unwrap(), claims);
+        assert!(IntermediateClaim::decode_list(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_intermediate_claims_decode_truncated() {
+        let encoded =
+            IntermediateClaim::encode_list(&[IntermediateClaim::new(10, B256::repeat_byte(0x01))]);
+
+        assert!(matches!(
+            IntermediateClaim::decode_list(&encoded[..encoded.len() - 1]),
+            Err(OracleProviderError::SliceConversion(_))
+        ));
+    }
+}
@@ -135,3 +135,19 @@ impl From<OracleProviderError> for PipelineErrorKind {
 #[derive(Error, Debug)]
 #[error("Hint parsing error: {_0}")]
 pub struct HintParsingError(pub String);
+
+/// Error from the [`ParallelKonaExecutor`](crate::executor::ParallelKonaExecutor).
+///
+/// Each speculative execution job runs on its own blocking task. Besides the errors returned
+/// while building the block, a job can fail because its task panicked or was cancelled before it
+/// ran to completion.
+#[cfg(feature = "std")]
+#[derive(Error, Debug)]
+pub enum ParallelExecutorError {
+    /// Building the block or computing its output root failed.
+    #[error(transparent)]
+    Executor(#[from] kona_executor::ExecutorError),
+    /// The execution task panicked or was cancelled.
+    #[error("Execution task failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
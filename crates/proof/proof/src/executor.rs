@@ -1,6 +1,6 @@
 //! An executor constructor.
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::{Header, Sealed};
 use alloy_evm::{EvmFactory, FromRecoveredTx, FromTxWithEncoded, revm::context::BlockEnv};
 use alloy_op_evm::block::OpTxEnv;
@@ -8,13 +8,23 @@ use alloy_primitives::B256;
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_driver::Executor;
-use kona_executor::{BlockBuildingOutcome, StatelessL2Builder, TrieDBProvider};
+use kona_executor::{
+    BlockBuildingOutcome, ExecutionTracer, StatelessL2Builder, TraceConfig, TransactionTrace,
+    TrieDBProvider,
+};
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
 use op_alloy_consensus::OpTxEnvelope;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use op_revm::OpSpecId;
 
+#[cfg(feature = "std")]
+use crate::errors::ParallelExecutorError;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use kona_driver::{ExecutionJob, ExecutionResult, ParallelExecutor};
+
 /// An executor wrapper type.
 #[derive(Debug)]
 pub struct KonaExecutor<'a, P, H, Evm>
@@ -33,6 +43,10 @@ where
     evm_factory: Evm,
     /// The executor.
     inner: Option<StatelessL2Builder<'a, P, H, Evm>>,
+    /// The optional tracer collecting the traces of the executed transactions.
+    tracer: Option<ExecutionTracer>,
+    /// Whether to retain the state changes of each executed block in its outcome.
+    retain_bundle: bool,
 }
 
 impl<'a, P, H, Evm> KonaExecutor<'a, P, H, Evm>
@@ -49,7 +63,33 @@ where
         evm_factory: Evm,
         inner: Option<StatelessL2Builder<'a, P, H, Evm>>,
     ) -> Self {
-        Self { rollup_config, trie_provider, trie_hinter, evm_factory, inner }
+        Self {
+            rollup_config,
+            trie_provider,
+            trie_hinter,
+            evm_factory,
+            inner,
+            tracer: None,
+            retain_bundle: false,
+        }
+    }
+
+    /// Sets whether the state changes of each executed block are retained in the
+    /// [`BlockBuildingOutcome::bundle`]. Disabled by default.
+    pub fn with_bundle_retention(mut self, retain_bundle: bool) -> Self {
+        self.retain_bundle = retain_bundle;
+        self
+    }
+
+    /// Enables tracing of the executed transactions with the given [`TraceConfig`].
+    pub fn with_tracing(mut self, config: TraceConfig) -> Self {
+        self.tracer = Some(ExecutionTracer::new(config));
+        self
+    }
+
+    /// Takes the traces of the transactions executed since the last call, if tracing is enabled.
+    pub fn take_traces(&mut self) -> Vec<TransactionTrace> {
+        self.tracer.as_mut().map(|t| t.take_traces()).unwrap_or_default()
     }
 }
 
@@ -75,13 +115,16 @@ where
     /// Since the L2 block executor is stateless, on an update to the safe head,
     /// a new executor is created with the updated header.
     fn update_safe_head(&mut self, header: Sealed<Header>) {
-        self.inner = Some(StatelessL2Builder::new(
-            self.rollup_config,
-            self.evm_factory.clone(),
-            self.trie_provider.clone(),
-            self.trie_hinter.clone(),
-            header,
-        ));
+        self.inner = Some(
+            StatelessL2Builder::new(
+                self.rollup_config,
+                self.evm_factory.clone(),
+                self.trie_provider.clone(),
+                self.trie_hinter.clone(),
+                header,
+            )
+            .with_bundle_retention(self.retain_bundle),
+        );
     }
 
     /// Execute the given payload attributes.
@@ -89,17 +132,168 @@ where
         &mut self,
         attributes: OpPayloadAttributes,
     ) -> Result<BlockBuildingOutcome, Self::Error> {
-        self.inner.as_mut().map_or_else(
-            || Err(kona_executor::ExecutorError::MissingExecutor),
-            |e| e.build_block(attributes),
-        )
+        let Some(inner) = self.inner.as_mut() else {
+            return Err(kona_executor::ExecutorError::MissingExecutor);
+        };
+        let _phase = crate::profile::phase("execution");
+        inner.build_block_with_tracer(attributes, self.tracer.as_mut())
     }
 
     /// Computes the output root.
     fn compute_output_root(&mut self) -> Result<B256, Self::Error> {
+        let _phase = crate::profile::phase("output-root");
         self.inner.as_mut().map_or_else(
             || Err(kona_executor::ExecutorError::MissingExecutor),
             |e| e.compute_output_root(),
         )
     }
 }
+
+/// A [`ParallelExecutor`] that executes every job on its own blocking task, with a fresh
+/// [`StatelessL2Builder`] built on the job's parent header.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct ParallelKonaExecutor<P, H, Evm>
+where
+    P: TrieDBProvider + Send + Sync + Clone,
+    H: TrieHinter + Send + Sync + Clone,
+    Evm: EvmFactory + Send + Sync + Clone,
+{
+    /// The rollup config for the executor.
+    rollup_config: Arc<RollupConfig>,
+    /// The trie provider for the executor.
+    trie_provider: P,
+    /// The trie hinter for the executor.
+    trie_hinter: H,
+    /// The evm factory for the executor.
+    evm_factory: Evm,
+}
+
+#[cfg(feature = "std")]
+impl<P, H, Evm> ParallelKonaExecutor<P, H, Evm>
+where
+    P: TrieDBProvider + Send + Sync + Clone,
+    H: TrieHinter + Send + Sync + Clone,
+    Evm: EvmFactory + Send + Sync + Clone,
+{
+    /// Creates a new parallel executor.
+    pub const fn new(
+        rollup_config: Arc<RollupConfig>,
+        trie_provider: P,
+        trie_hinter: H,
+        evm_factory: Evm,
+    ) -> Self {
+        Self { rollup_config, trie_provider, trie_hinter, evm_factory }
+    }
+}
+
+#[cfg(feature = "std")]
+#[async_trait]
+impl<P, H, Evm> ParallelExecutor for ParallelKonaExecutor<P, H, Evm>
+where
+    P: TrieDBProvider + Debug + Send + Sync + Clone + 'static,
+    H: TrieHinter + Debug + Send + Sync + Clone + 'static,
+    Evm: EvmFactory<Spec = OpSpecId, BlockEnv = BlockEnv> + Send + Sync + Clone + 'static,
+    <Evm as EvmFactory>::Tx:
+        FromTxWithEncoded<OpTxEnvelope> + FromRecoveredTx<OpTxEnvelope> + OpTxEnv,
+{
+    type Error = ParallelExecutorError;
+
+    /// Executes the jobs concurrently on tokio's blocking thread pool.
+    async fn execute_jobs(
+        &mut self,
+        jobs: Vec<ExecutionJob>,
+    ) -> Vec<Result<ExecutionResult, Self::Error>> {
+        let tasks = jobs
+            .into_iter()
+            .map(|job| {
+                let rollup_config = self.rollup_config.clone();
+                let evm_factory = self.evm_factory.clone();
+                let trie_provider = self.trie_provider.clone();
+                let trie_hinter = self.trie_hinter.clone();
+                tokio::task::spawn_blocking(move || {
+                    let mut builder = StatelessL2Builder::new(
+                        rollup_config.as_ref(),
+                        evm_factory,
+                        trie_provider,
+                        trie_hinter,
+                        job.parent_header,
+                    );
+                    let outcome = builder.build_block(job.attributes)?;
+                    let output_root = builder.compute_output_root()?;
+                    Ok::<_, ParallelExecutorError>(ExecutionResult { outcome, output_root })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut results = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            results.push(task.await.map_err(ParallelExecutorError::from).and_then(|result| result));
+        }
+        results
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use alloy_op_evm::OpEvmFactory;
+    use alloy_primitives::Sealable;
+    use kona_executor::test_utils::{CountingTrieDBProvider, open_test_corpus};
+    use kona_mpt::NoopTrieHinter;
+    use std::path::PathBuf;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_parallel_execution_matches_sequential() {
+        let corpus_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../executor/testdata/corpus/corpus-26207960-26207962.tar.gz");
+        let (_corpus_dir, corpus, provider) = open_test_corpus(&corpus_path).await;
+        let provider = CountingTrieDBProvider::new(provider);
+        let rollup_config = Arc::new(corpus.rollup_config);
+
+        // Execute the blocks sequentially, each on top of the previous one, collecting a job per
+        // block on the same parent.
+        let mut parent_header = corpus.parent_header.seal_slow();
+        let mut jobs = Vec::with_capacity(corpus.blocks.len());
+        let mut sequential = Vec::with_capacity(corpus.blocks.len());
+        for block in &corpus.blocks {
+            jobs.push(ExecutionJob {
+                parent_header: parent_header.clone(),
+                attributes: block.executing_payload.clone(),
+            });
+
+            let mut executor = KonaExecutor::new(
+                rollup_config.as_ref(),
+                provider.clone(),
+                NoopTrieHinter,
+                OpEvmFactory::default(),
+                None,
+            );
+            executor.update_safe_head(parent_header);
+            let outcome = executor.execute_payload(block.executing_payload.clone()).await.unwrap();
+            assert_eq!(outcome.header.hash(), block.expected_block_hash);
+            sequential.push((outcome.header.hash(), executor.compute_output_root().unwrap()));
+            parent_header = outcome.header;
+        }
+
+        // Execute all of the blocks at once.
+        let mut executor = ParallelKonaExecutor::new(
+            rollup_config,
+            provider,
+            NoopTrieHinter,
+            OpEvmFactory::default(),
+        );
+        let parallel = executor
+            .execute_jobs(jobs)
+            .await
+            .into_iter()
+            .map(|result| {
+                let result = result.unwrap();
+                (result.outcome.header.hash(), result.output_root)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(parallel.len(), 3);
+        assert_eq!(parallel, sequential);
+    }
+}
@@ -165,6 +165,7 @@ where
 
     /// Attempts to progress the pipeline.
     async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+        let _phase = crate::profile::phase("derivation");
         self.pipeline.step(cursor).await
     }
 
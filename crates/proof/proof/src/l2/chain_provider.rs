@@ -174,6 +174,7 @@ impl<T: CommsClient> TrieProvider for OracleL2ChainProvider<T> {
     fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, OracleProviderError> {
         // On L2, trie node preimages are stored as keccak preimage types in the oracle. We assume
         // that a hint for these preimages has already been sent, prior to this call.
+        let _phase = crate::profile::phase("trie-nodes");
         crate::block_on(async move {
             TrieNode::decode(
                 &mut self
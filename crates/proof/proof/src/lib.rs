@@ -22,6 +22,8 @@ pub mod errors;
 
 pub mod executor;
 
+pub mod profile;
+
 mod hint;
 pub use hint::{Hint, HintType};
 
@@ -0,0 +1,333 @@
+//! Phase profiling for the client program.
+//!
+//! The client program marks the phases it spends its time in with [`phase`], which returns a
+//! [`PhaseGuard`] that attributes the time until it is dropped to the named phase. Time is
+//! measured with a clock installed by the platform through [`set_clock`], e.g. wall-clock
+//! nanoseconds when running natively, or a cheap counter inside of a fault proof VM, which need not
+//! count steps. Until a clock is installed, profiling is disabled and phase markers are no-ops that
+//! do not touch the profiler.
+//!
+//! Phases may be nested. Each phase reports both its total time and its self time, which excludes
+//! the time spent in the phases nested within it.
+//!
+//! The accumulated [`ProfileReport`] is read with [`report`], or sent to the host at the end of a
+//! run with [`send_report`], through the dedicated [`PROFILE_HINT`].
+
+use crate::{Hint, errors::OracleProviderError};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use kona_preimage::HintWriterClient;
+use spin::Mutex;
+
+/// The name of the hint carrying an encoded [`ProfileReport`] from the client to the host.
+pub const PROFILE_HINT: &str = "profile";
+
+/// A clock, returning a monotonically increasing counter.
+pub type Clock = fn() -> u64;
+
+/// Whether a clock is installed, checked before taking the [`PROFILER`] lock so that phase
+/// markers are free while profiling is disabled.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The global profiler.
+static PROFILER: Mutex<Profiler> =
+    Mutex::new(Profiler { clock: None, phases: Vec::new(), open: Vec::new() });
+
+/// The state of the global profiler.
+#[derive(Debug)]
+struct Profiler {
+    /// The installed clock and the name of its unit, if profiling is enabled.
+    clock: Option<(Clock, &'static str)>,
+    /// The accumulated statistics of each phase, in the order they were first exited.
+    phases: Vec<PhaseStats>,
+    /// The time spent in nested phases of each open phase, innermost last.
+    open: Vec<u64>,
+}
+
+/// Enables profiling, measuring phases with the given clock. `unit` names the unit of the clock,
+/// and is reported alongside the phases.
+pub fn set_clock(clock: Clock, unit: &'static str) {
+    PROFILER.lock().clock = Some((clock, unit));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Returns `true` if a clock is installed and phases are being recorded.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Enters the named phase, returning a [`PhaseGuard`] that exits it when dropped.
+///
+/// Phases may be nested, in which case the time spent in the inner phase is attributed to the
+/// total time of both, and to the self time of the inner phase only.
+pub fn phase(name: &'static str) -> PhaseGuard {
+    if !is_enabled() {
+        return PhaseGuard { name, start: None };
+    }
+
+    let mut profiler = PROFILER.lock();
+    let start = profiler.clock.map(|(clock, _)| clock());
+    if start.is_some() {
+        profiler.open.push(0);
+    }
+    PhaseGuard { name, start }
+}
+
+/// Returns the [`ProfileReport`] accumulated since the last [`reset`].
+pub fn report() -> ProfileReport {
+    let profiler = PROFILER.lock();
+    ProfileReport {
+        unit: profiler.clock.map(|(_, unit)| unit.to_string()).unwrap_or_default(),
+        phases: profiler.phases.clone(),
+    }
+}
+
+/// Clears the accumulated phase statistics.
+pub fn reset() {
+    PROFILER.lock().phases.clear();
+}
+
+/// Sends the [`ProfileReport`] to the host through the [`PROFILE_HINT`], if profiling is enabled.
+pub async fn send_report<H: HintWriterClient>(hint_writer: &H) -> Result<(), OracleProviderError> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    Hint::new(PROFILE_HINT, report().encode()).send(hint_writer).await
+}
+
+/// A guard for a phase entered with [`phase`]. The phase is exited when the guard is dropped.
+#[derive(Debug)]
+#[must_use = "the phase is exited when the guard is dropped"]
+pub struct PhaseGuard {
+    /// The name of the phase.
+    name: &'static str,
+    /// The clock reading when the phase was entered, if profiling is enabled.
+    start: Option<u64>,
+}
+
+impl Drop for PhaseGuard {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+
+        let mut profiler = PROFILER.lock();
+        let Some((clock, _)) = profiler.clock else {
+            return;
+        };
+        let elapsed = clock().saturating_sub(start);
+        let self_elapsed = elapsed.saturating_sub(profiler.open.pop().unwrap_or_default());
+        if let Some(parent) = profiler.open.last_mut() {
+            *parent += elapsed;
+        }
+
+        match profiler.phases.iter_mut().find(|phase| phase.name == self.name) {
+            Some(phase) => {
+                phase.entries += 1;
+                phase.elapsed += elapsed;
+                phase.self_elapsed += self_elapsed;
+            }
+            None => profiler.phases.push(PhaseStats {
+                name: self.name.to_string(),
+                entries: 1,
+                elapsed,
+                self_elapsed,
+            }),
+        }
+    }
+}
+
+/// The statistics of a single phase in a [`ProfileReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseStats {
+    /// The name of the phase.
+    pub name: String,
+    /// The number of times the phase was entered.
+    pub entries: u64,
+    /// The total clock time spent in the phase.
+    pub elapsed: u64,
+    /// The clock time spent in the phase, excluding the time spent in phases nested within it.
+    pub self_elapsed: u64,
+}
+
+/// A per-phase report of where the client program spent its time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileReport {
+    /// The unit of the clock that measured the phases.
+    pub unit: String,
+    /// The statistics of each phase, in the order they were first entered.
+    pub phases: Vec<PhaseStats>,
+}
+
+impl ProfileReport {
+    /// Encodes the [`ProfileReport`], as sent in the [`PROFILE_HINT`].
+    ///
+    /// The encoding is the length-prefixed unit, followed by the length-prefixed name, entry count,
+    /// elapsed time and self time of each phase. Lengths are single bytes, and counts are 8 byte
+    /// big-endian integers.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode_str(&mut out, &self.unit);
+        for phase in &self.phases {
+            encode_str(&mut out, &phase.name);
+            out.extend_from_slice(&phase.entries.to_be_bytes());
+            out.extend_from_slice(&phase.elapsed.to_be_bytes());
+            out.extend_from_slice(&phase.self_elapsed.to_be_bytes());
+        }
+        out
+    }
+
+    /// Decodes a [`ProfileReport`] encoded with [`ProfileReport::encode`]. Returns `None` if the
+    /// encoding is malformed.
+    pub fn decode(mut buf: &[u8]) -> Option<Self> {
+        let unit = decode_str(&mut buf)?;
+
+        let mut phases = Vec::new();
+        while !buf.is_empty() {
+            let name = decode_str(&mut buf)?;
+            let entries = decode_u64(&mut buf)?;
+            let elapsed = decode_u64(&mut buf)?;
+            let self_elapsed = decode_u64(&mut buf)?;
+            phases.push(PhaseStats { name, entries, elapsed, self_elapsed });
+        }
+        Some(Self { unit, phases })
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (total, self_time) = (format!("total {}", self.unit), format!("self {}", self.unit));
+        writeln!(f, "{:<24} {:>12} {:>20} {:>20}", "phase", "entries", total, self_time)?;
+        for phase in &self.phases {
+            writeln!(
+                f,
+                "{:<24} {:>12} {:>20} {:>20}",
+                phase.name, phase.entries, phase.elapsed, phase.self_elapsed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a string, prefixed with its length as a single byte. Longer strings are truncated.
+fn encode_str(out: &mut Vec<u8>, s: &str) {
+    let bytes = &s.as_bytes()[..s.len().min(u8::MAX as usize)];
+    out.push(bytes.len() as u8);
+    out.extend_from_slice(bytes);
+}
+
+/// Decodes a string encoded with [`encode_str`], advancing the buffer.
+fn decode_str(buf: &mut &[u8]) -> Option<String> {
+    let (&len, rest) = buf.split_first()?;
+    let (bytes, rest) = rest.split_at_checked(len as usize)?;
+    *buf = rest;
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Decodes a big-endian [u64], advancing the buffer.
+fn decode_u64(buf: &mut &[u8]) -> Option<u64> {
+    let (bytes, rest) = buf.split_first_chunk::<8>()?;
+    *buf = rest;
+    Some(u64::from_be_bytes(*bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn test_profile_report_roundtrip() {
+        let report = ProfileReport {
+            unit: "ns".to_string(),
+            phases: alloc::vec![
+                PhaseStats {
+                    name: "boot".to_string(),
+                    entries: 1,
+                    elapsed: 100,
+                    self_elapsed: 100
+                },
+                PhaseStats {
+                    name: "execution".to_string(),
+                    entries: 12,
+                    elapsed: 4_000,
+                    self_elapsed: 3_000
+                },
+            ],
+        };
+
+        assert_eq!(ProfileReport::decode(&report.encode()).unwrap(), report);
+        assert_eq!(
+            ProfileReport::decode(&ProfileReport::default().encode()).unwrap(),
+            Default::default()
+        );
+    }
+
+    #[test]
+    fn test_profile_report_decode_truncated() {
+        let report = ProfileReport {
+            unit: "ns".to_string(),
+            phases: alloc::vec![PhaseStats {
+                name: "boot".to_string(),
+                entries: 1,
+                elapsed: 1,
+                self_elapsed: 1
+            }],
+        };
+        let encoded = report.encode();
+
+        for len in [0, 2, encoded.len() - 1] {
+            assert!(ProfileReport::decode(&encoded[..len]).is_none());
+        }
+    }
+
+    #[test]
+    fn test_phases() {
+        static TICKS: AtomicU64 = AtomicU64::new(0);
+        fn tick() -> u64 {
+            TICKS.fetch_add(1, Ordering::Relaxed)
+        }
+
+        // Phases are not recorded until a clock is installed.
+        drop(phase("boot"));
+        assert!(!is_enabled());
+        assert!(report().phases.is_empty());
+
+        set_clock(tick, "ticks");
+        {
+            let _outer = phase("execution");
+            drop(phase("precompiles"));
+            drop(phase("precompiles"));
+        }
+
+        let report = report();
+        assert_eq!(report.unit, "ticks");
+        assert_eq!(
+            report.phases,
+            [
+                PhaseStats {
+                    name: "precompiles".to_string(),
+                    entries: 2,
+                    elapsed: 2,
+                    self_elapsed: 2
+                },
+                PhaseStats {
+                    name: "execution".to_string(),
+                    entries: 1,
+                    elapsed: 5,
+                    self_elapsed: 3
+                },
+            ]
+        );
+
+        reset();
+        assert!(super::report().phases.is_empty());
+    }
+}
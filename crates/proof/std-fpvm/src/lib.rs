@@ -19,6 +19,8 @@ pub mod tracing;
 
 pub mod malloc;
 
+pub mod profile;
+
 mod traits;
 pub use traits::BasicKernelInterface;
 
@@ -24,6 +24,30 @@ pub(crate) enum SyscallNumber {
     /// Similar behavior as Linux/MIPS for mapping memory on the host machine. Only accepts 2
     /// arguments for cannon.
     Mmap = 5009,
+    /// Reads the clock with the given ID. The monotonic clock advances with the VM's step counter.
+    ClockGetTime = 5222,
+}
+
+/// The `CLOCK_MONOTONIC` clock ID.
+const CLOCK_MONOTONIC: usize = 1;
+
+/// The frequency of the VM's monotonic clock, which advances by one tick per step.
+const CLOCK_HZ: u64 = 10_000_000;
+
+impl Mips64IO {
+    /// Returns the number of steps executed by the VM, read from its monotonic clock.
+    pub(crate) fn steps() -> u64 {
+        // `struct timespec { tv_sec: i64, tv_nsec: i64 }`
+        let mut timespec = [0u64; 2];
+        unsafe {
+            let _ = syscall::syscall2(
+                SyscallNumber::ClockGetTime as usize,
+                CLOCK_MONOTONIC,
+                timespec.as_mut_ptr() as usize,
+            );
+        }
+        timespec[0] * CLOCK_HZ + timespec[1] / (1_000_000_000 / CLOCK_HZ)
+    }
 }
 
 impl BasicKernelInterface for Mips64IO {
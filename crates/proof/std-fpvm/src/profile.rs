@@ -0,0 +1,48 @@
+//! Clocks for profiling the client program on each target.
+//!
+//! The clock is installed into the client's profiler, and its readings are attributed to the
+//! phases of the program. Inside of a fault proof VM, reading the clock must be cheap, as it is
+//! executed alongside the program being measured.
+
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(target_arch = "mips64")] {
+        /// The unit of the [clock].
+        pub const CLOCK_UNIT: &str = "steps";
+
+        /// Returns the number of steps executed by the VM.
+        ///
+        /// The monotonic clock of the multi-threaded Cannon VM is derived from its step counter, so
+        /// reading it costs a single syscall.
+        pub fn clock() -> u64 {
+            crate::mips64::io::Mips64IO::steps()
+        }
+    } else if #[cfg(target_arch = "riscv64")] {
+        /// The unit of the [clock].
+        pub const CLOCK_UNIT: &str = "syscalls";
+
+        /// Returns the number of read and write syscalls issued to the kernel since the clock was
+        /// first read.
+        ///
+        /// The `riscv64` kernel does not expose its step counter, so the number of syscalls, which
+        /// each transfer hint or preimage data, is counted instead. Syscalls are not steps: phases
+        /// that only compute, such as execution, barely advance this clock. Syscalls are only
+        /// counted once the clock has been read.
+        pub fn clock() -> u64 {
+            use core::sync::atomic::Ordering;
+
+            crate::riscv64::io::COUNT_SYSCALLS.store(true, Ordering::Relaxed);
+            crate::riscv64::io::SYSCALLS.load(Ordering::Relaxed)
+        }
+    } else {
+        /// The unit of the [clock].
+        pub const CLOCK_UNIT: &str = "ns";
+
+        /// Returns the wall-clock time elapsed since the clock was first read, in nanoseconds.
+        pub fn clock() -> u64 {
+            static EPOCH: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+            EPOCH.get_or_init(std::time::Instant::now).elapsed().as_nanos() as u64
+        }
+    }
+}
@@ -1,4 +1,20 @@
 use crate::{BasicKernelInterface, FileDescriptor, errors::IOResult, riscv64::syscall};
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// The number of read and write syscalls issued to the kernel, while [COUNT_SYSCALLS] is set.
+pub(crate) static SYSCALLS: AtomicU64 = AtomicU64::new(0);
+
+/// Whether read and write syscalls are counted in [SYSCALLS]. Counting is enabled by the profiling
+/// clock, so that syscalls are not counted unless the client program is profiled.
+pub(crate) static COUNT_SYSCALLS: AtomicBool = AtomicBool::new(false);
+
+/// Counts a read or write syscall in [SYSCALLS], if counting is enabled.
+#[inline]
+fn count_syscall() {
+    if COUNT_SYSCALLS.load(Ordering::Relaxed) {
+        SYSCALLS.fetch_add(1, Ordering::Relaxed);
+    }
+}
 
 /// Concrete implementation of the [`KernelIO`] trait for the `riscv64` target architecture.
 #[derive(Debug)]
@@ -26,6 +42,7 @@ pub(crate) enum SyscallNumber {
 
 impl BasicKernelInterface for RiscV64IO {
     fn write(fd: FileDescriptor, buf: &[u8]) -> IOResult<usize> {
+        count_syscall();
         unsafe {
             crate::linux::from_ret(syscall::syscall3(
                 SyscallNumber::Write as usize,
@@ -37,6 +54,7 @@ impl BasicKernelInterface for RiscV64IO {
     }
 
     fn read(fd: FileDescriptor, buf: &mut [u8]) -> IOResult<usize> {
+        count_syscall();
         unsafe {
             crate::linux::from_ret(syscall::syscall3(
                 SyscallNumber::Read as usize,
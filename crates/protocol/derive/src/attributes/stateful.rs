@@ -5,7 +5,7 @@ use crate::{
     PipelineError, PipelineErrorKind, PipelineResult,
 };
 use alloc::{boxed::Box, fmt::Debug, string::ToString, sync::Arc, vec, vec::Vec};
-use alloy_consensus::{Eip658Value, Receipt};
+use alloy_consensus::Receipt;
 use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
 use alloy_primitives::{Address, B256, Bytes};
 use alloy_rlp::Encodable;
@@ -13,9 +13,7 @@ use alloy_rpc_types_engine::PayloadAttributes;
 use async_trait::async_trait;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_hardforks::{Hardfork, Hardforks};
-use kona_protocol::{
-    DEPOSIT_EVENT_ABI_HASH, L1BlockInfoTx, L2BlockInfo, Predeploys, decode_deposit,
-};
+use kona_protocol::{L1BlockInfoTx, L2BlockInfo, Predeploys, UserDeposit};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
 /// A stateful implementation of the [`AttributesBuilder`].
@@ -228,32 +226,14 @@ where
 ///
 /// Successful deposits must be emitted by the deposit contract and have the correct event
 /// signature. So the receipt address must equal the specified deposit contract and the first topic
-/// must be the [`DEPOSIT_EVENT_ABI_HASH`].
+/// must be the [`DEPOSIT_EVENT_ABI_HASH`](kona_protocol::DEPOSIT_EVENT_ABI_HASH).
 async fn derive_deposits(
     block_hash: B256,
     receipts: &[Receipt],
     deposit_contract: Address,
 ) -> Result<Vec<Bytes>, PipelineEncodingError> {
-    let mut global_index = 0;
-    let mut res = Vec::new();
-    for r in receipts.iter() {
-        if Eip658Value::Eip658(false) == r.status {
-            continue;
-        }
-        for l in r.logs.iter() {
-            let curr_index = global_index;
-            global_index += 1;
-            if l.data.topics().first().is_none_or(|i| *i != DEPOSIT_EVENT_ABI_HASH) {
-                continue;
-            }
-            if l.address != deposit_contract {
-                continue;
-            }
-            let decoded = decode_deposit(block_hash, curr_index, l)?;
-            res.push(decoded);
-        }
-    }
-    Ok(res)
+    let deposits = kona_protocol::derive_deposits(block_hash, receipts, deposit_contract)?;
+    Ok(deposits.iter().map(UserDeposit::encoded).collect())
 }
 
 #[cfg(test)]
@@ -264,10 +244,10 @@ mod tests {
         test_utils::{TestChainProvider, TestSystemConfigL2Fetcher},
     };
     use alloc::vec;
-    use alloy_consensus::Header;
+    use alloy_consensus::{Eip658Value, Header};
     use alloy_primitives::{B256, Log, LogData, U64, U256, address};
     use kona_genesis::{HardForkConfig, SystemConfig};
-    use kona_protocol::{BlockInfo, DepositError};
+    use kona_protocol::{BlockInfo, DEPOSIT_EVENT_ABI_HASH, DepositError};
     use kona_registry::L1Config;
 
     fn generate_valid_log() -> Log {
@@ -10,6 +10,7 @@ use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use tracing::Instrument;
 
 /// The derivation pipeline is responsible for deriving L2 inputs from L1 data.
 #[derive(Debug)]
@@ -132,46 +133,13 @@ where
     }
 }
 
-#[async_trait]
-impl<S, P> Pipeline for DerivationPipeline<S, P>
+impl<S, P> DerivationPipeline<S, P>
 where
     S: NextAttributes + SignalReceiver + OriginProvider + OriginAdvancer + Debug + Send + Sync,
     P: L2ChainProvider + Send + Sync + Debug,
 {
-    /// Peeks at the next prepared [`OpAttributesWithParent`] from the pipeline.
-    fn peek(&self) -> Option<&OpAttributesWithParent> {
-        self.prepared.front()
-    }
-
-    /// Returns the rollup config.
-    fn rollup_config(&self) -> &RollupConfig {
-        &self.rollup_config
-    }
-
-    /// Returns the [`SystemConfig`] by L2 number.
-    async fn system_config_by_number(
-        &mut self,
-        number: u64,
-    ) -> Result<SystemConfig, PipelineErrorKind> {
-        self.l2_chain_provider
-            .system_config_by_number(number, self.rollup_config.clone())
-            .await
-            .map_err(Into::into)
-    }
-
-    /// Attempts to progress the pipeline.
-    ///
-    /// ## Returns
-    ///
-    /// A [PipelineError::Eof] is returned if the pipeline is blocked by waiting for new L1 data.
-    /// Any other error is critical and the derivation pipeline should be reset.
-    /// An error is expected when the underlying source closes.
-    ///
-    /// When [DerivationPipeline::step] returns [Ok(())], it should be called again, to continue the
-    /// derivation process.
-    ///
-    /// [`PipelineError`]: crate::errors::PipelineError
-    async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+    /// Attempts to progress the pipeline by a single step. See [`Pipeline::step`].
+    async fn step_inner(&mut self, cursor: L2BlockInfo) -> StepResult {
         kona_macros::inc!(gauge, crate::metrics::Metrics::PIPELINE_STEPS);
         kona_macros::set!(
             gauge,
@@ -181,6 +149,9 @@ where
         match self.attributes.next_attributes(cursor).await {
             Ok(a) => {
                 trace!(target: "pipeline", "Prepared L2 attributes: {:?}", a);
+                let span = tracing::Span::current();
+                span.record("attributes.timestamp", a.attributes.payload_attributes.timestamp);
+                span.record("derived_from", a.derived_from.map(|b| b.number));
                 kona_macros::inc!(
                     gauge,
                     crate::metrics::Metrics::PIPELINE_PAYLOAD_ATTRIBUTES_BUFFER
@@ -224,6 +195,58 @@ where
     }
 }
 
+#[async_trait]
+impl<S, P> Pipeline for DerivationPipeline<S, P>
+where
+    S: NextAttributes + SignalReceiver + OriginProvider + OriginAdvancer + Debug + Send + Sync,
+    P: L2ChainProvider + Send + Sync + Debug,
+{
+    /// Peeks at the next prepared [`OpAttributesWithParent`] from the pipeline.
+    fn peek(&self) -> Option<&OpAttributesWithParent> {
+        self.prepared.front()
+    }
+
+    /// Returns the rollup config.
+    fn rollup_config(&self) -> &RollupConfig {
+        &self.rollup_config
+    }
+
+    /// Returns the [`SystemConfig`] by L2 number.
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+    ) -> Result<SystemConfig, PipelineErrorKind> {
+        self.l2_chain_provider
+            .system_config_by_number(number, self.rollup_config.clone())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Attempts to progress the pipeline.
+    ///
+    /// ## Returns
+    ///
+    /// A [PipelineError::Eof] is returned if the pipeline is blocked by waiting for new L1 data.
+    /// Any other error is critical and the derivation pipeline should be reset.
+    /// An error is expected when the underlying source closes.
+    ///
+    /// When [DerivationPipeline::step] returns [Ok(())], it should be called again, to continue the
+    /// derivation process.
+    ///
+    /// [`PipelineError`]: crate::errors::PipelineError
+    async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+        let span = info_span!(
+            target: "pipeline",
+            "derivation_step",
+            l2_cursor = cursor.block_info.number,
+            l1_origin = self.origin().map(|o| o.number),
+            attributes.timestamp = tracing::field::Empty,
+            derived_from = tracing::field::Empty,
+        );
+        self.step_inner(cursor).instrument(span).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
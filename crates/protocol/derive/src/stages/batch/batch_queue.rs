@@ -132,6 +132,15 @@ where
             let batch = &self.batches[i];
             let validity =
                 batch.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+            debug!(
+                target: "batch_queue",
+                %validity,
+                timestamp = batch.batch.timestamp(),
+                is_span = matches!(batch.batch, Batch::Span(_)),
+                parent = parent.block_info.number,
+                inclusion_block = batch.inclusion_block.number,
+                "Checked batch"
+            );
             match validity {
                 BatchValidity::Future => {
                     // Drop Future batches post-holocene.
@@ -244,6 +253,15 @@ where
         // If we drop the batch, validation logs the drop reason with WARN level.
         let validity =
             data.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+        debug!(
+            target: "batch_queue",
+            %validity,
+            timestamp = data.batch.timestamp(),
+            is_span = matches!(data.batch, Batch::Span(_)),
+            parent = parent.block_info.number,
+            inclusion_block = origin.number,
+            "Checked batch"
+        );
         // Post-Holocene, future batches are dropped due to prevent gaps.
         let drop = validity.is_drop() ||
             (self.cfg.is_holocene_active(origin.timestamp) && validity.is_future());
@@ -156,6 +156,15 @@ where
                             &mut self.fetcher,
                         )
                         .await;
+                    debug!(
+                        target: "batch_stream",
+                        %validity,
+                        timestamp = b.starting_timestamp(),
+                        is_span = true,
+                        parent = parent.block_info.number,
+                        inclusion_block = batch_with_inclusion.inclusion_block.number,
+                        "Checked batch"
+                    );
                     kona_macros::record!(
                         histogram,
                         crate::metrics::Metrics::PIPELINE_CHECK_BATCH_PREFIX,
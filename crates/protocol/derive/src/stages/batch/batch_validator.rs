@@ -248,12 +248,22 @@ where
         next_batch.parent_hash = parent.block_info.hash;
 
         // Check the validity of the single batch before forwarding it.
-        match next_batch.check_batch(
+        let validity = next_batch.check_batch(
             self.cfg.as_ref(),
             self.l1_blocks.as_ref(),
             parent,
             &stage_origin,
-        ) {
+        );
+        debug!(
+            target: "batch_validator",
+            %validity,
+            timestamp = next_batch.timestamp,
+            is_span = false,
+            parent = parent.block_info.number,
+            inclusion_block = stage_origin.number,
+            "Checked batch"
+        );
+        match validity {
             BatchValidity::Accept => {
                 info!(target: "batch_validator", "Found next batch (epoch #{})", next_batch.epoch_num);
                 Ok(next_batch)
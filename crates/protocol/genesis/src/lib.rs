@@ -43,11 +43,11 @@ pub use updates::{
 
 mod system;
 pub use system::{
-    BatcherUpdateError, CONFIG_UPDATE_EVENT_VERSION_0, CONFIG_UPDATE_TOPIC,
+    BatcherUpdateError, CONFIG_UPDATE_EVENT_VERSION_0, CONFIG_UPDATE_TOPIC, ConfigUpdateLog,
     DaFootprintGasScalarUpdateError, EIP1559UpdateError, GasConfigUpdateError, GasLimitUpdateError,
     LogProcessingError, MinBaseFeeUpdateError, OperatorFeeUpdateError, SystemConfig,
-    SystemConfigLog, SystemConfigUpdate, SystemConfigUpdateError, SystemConfigUpdateKind,
-    UnsafeBlockSignerUpdateError,
+    SystemConfigHistory, SystemConfigHistoryError, SystemConfigLog, SystemConfigUpdate,
+    SystemConfigUpdateError, SystemConfigUpdateKind, UnsafeBlockSignerUpdateError,
 };
 
 mod chain;
@@ -255,3 +255,26 @@ pub enum OperatorFeeUpdateError {
     #[error("Failed to decode operator fee parameter update log: constant")]
     ConstantDecodingError,
 }
+
+/// An error for replaying config update logs into a [crate::SystemConfigHistory].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum SystemConfigHistoryError {
+    /// A log is not within the L1 block range being replayed.
+    #[error(
+        "Config update log in L1 block {block} is outside of the replayed range ({start}, {end}]"
+    )]
+    LogOutOfRange {
+        /// The L1 block of the log.
+        block: u64,
+        /// The last L1 block replayed before the range, exclusive.
+        start: u64,
+        /// The last L1 block of the range, inclusive.
+        end: u64,
+    },
+    /// The end of the replayed range is before the last replayed L1 block.
+    #[error("Cannot replay to L1 block {0}, already synced past it")]
+    AlreadySynced(u64),
+    /// A config update log could not be applied.
+    #[error("Failed to apply config update: {0}")]
+    Update(#[from] SystemConfigUpdateError),
+}
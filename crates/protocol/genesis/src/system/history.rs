@@ -0,0 +1,236 @@
+//! Contains the [`SystemConfigHistory`] type.
+
+use crate::{
+    CONFIG_UPDATE_TOPIC, RollupConfig, SystemConfig, SystemConfigHistoryError, SystemConfigLog,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_primitives::Log;
+
+/// A log emitted by the system config contract in an L1 block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigUpdateLog {
+    /// The number of the L1 block that emitted the log.
+    pub block_number: u64,
+    /// The timestamp of the L1 block that emitted the log.
+    pub block_timestamp: u64,
+    /// The log.
+    pub log: Log,
+}
+
+/// The history of the [`SystemConfig`], reconstructed by replaying the config update logs emitted
+/// by the system config contract since the L1 genesis block.
+///
+/// The history only stores the [`SystemConfig`] at the L1 blocks that changed it, so looking up
+/// the config in effect at any L1 block up to [`SystemConfigHistory::synced_to`] does not require
+/// replaying logs again.
+///
+/// The config in effect at an L1 block includes the updates emitted in that block. It is the
+/// system config of the L2 blocks in the epoch with that L1 origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemConfigHistory {
+    /// The L1 genesis block number.
+    genesis: u64,
+    /// The last L1 block that has been replayed.
+    synced_to: u64,
+    /// The [`SystemConfig`] after each L1 block that changed it, keyed by L1 block number.
+    updates: BTreeMap<u64, SystemConfig>,
+}
+
+impl SystemConfigHistory {
+    /// Creates a new [`SystemConfigHistory`] starting at the genesis of the given rollup config.
+    pub fn new(rollup_config: &RollupConfig) -> Self {
+        let genesis = rollup_config.genesis.l1.number;
+        let config = rollup_config.genesis.system_config.unwrap_or_default();
+        Self { genesis, synced_to: genesis, updates: BTreeMap::from([(genesis, config)]) }
+    }
+
+    /// Returns the last L1 block that has been replayed.
+    pub const fn synced_to(&self) -> u64 {
+        self.synced_to
+    }
+
+    /// Returns the [`SystemConfig`] in effect at the last replayed L1 block.
+    pub fn latest(&self) -> SystemConfig {
+        // The genesis config is always present.
+        self.updates.last_key_value().map(|(_, config)| *config).unwrap_or_default()
+    }
+
+    /// Returns the [`SystemConfig`] in effect at the given L1 block.
+    ///
+    /// Returns `None` if the block is before the L1 genesis block, or after the last replayed
+    /// L1 block.
+    pub fn config_at(&self, l1_block: u64) -> Option<SystemConfig> {
+        if l1_block < self.genesis || l1_block > self.synced_to {
+            return None;
+        }
+        self.updates.range(..=l1_block).next_back().map(|(_, config)| *config)
+    }
+
+    /// Returns an iterator over the L1 blocks that changed the [`SystemConfig`], along with the
+    /// config after each of them, starting with the genesis config.
+    pub fn updates(&self) -> impl Iterator<Item = (u64, &SystemConfig)> {
+        self.updates.iter().map(|(block, config)| (*block, config))
+    }
+
+    /// Replays the config update logs emitted in the L1 blocks after
+    /// [`SystemConfigHistory::synced_to`], up to and including `end`.
+    ///
+    /// Logs that were not emitted by the system config contract, or that are not config updates,
+    /// are skipped. The history is left untouched if any log fails to apply.
+    ///
+    /// # Arguments
+    /// * `rollup_config` - The rollup config of the L2 chain.
+    /// * `end` - The last L1 block of the replayed range.
+    /// * `logs` - The logs emitted in the replayed range, in log order.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of L1 blocks in the range that changed the config.
+    /// * `Err(SystemConfigHistoryError)` - A log is out of order or could not be applied.
+    pub fn replay(
+        &mut self,
+        rollup_config: &RollupConfig,
+        end: u64,
+        logs: impl IntoIterator<Item = ConfigUpdateLog>,
+    ) -> Result<usize, SystemConfigHistoryError> {
+        if end < self.synced_to {
+            return Err(SystemConfigHistoryError::AlreadySynced(end));
+        }
+
+        let mut config = self.latest();
+        let mut last_block = self.synced_to + 1;
+        let mut updates: Vec<(u64, SystemConfig)> = Vec::new();
+        for ConfigUpdateLog { block_number, block_timestamp, log } in logs {
+            if block_number < last_block || block_number > end {
+                return Err(SystemConfigHistoryError::LogOutOfRange {
+                    block: block_number,
+                    start: self.synced_to,
+                    end,
+                });
+            }
+            last_block = block_number;
+
+            let topics = log.topics();
+            if log.address != rollup_config.l1_system_config_address ||
+                topics.first() != Some(&CONFIG_UPDATE_TOPIC)
+            {
+                continue;
+            }
+
+            let ecotone_active = rollup_config.is_ecotone_active(block_timestamp);
+            let update = SystemConfigLog::new(log, ecotone_active).build()?;
+            let previous = config;
+            update.apply(&mut config);
+            if config == previous {
+                continue;
+            }
+            match updates.last_mut() {
+                Some((block, last)) if *block == block_number => *last = config,
+                _ => updates.push((block_number, config)),
+            }
+        }
+
+        let changed = updates.len();
+        self.updates.extend(updates);
+        self.synced_to = end;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CONFIG_UPDATE_EVENT_VERSION_0, ChainGenesis};
+    use alloc::vec;
+    use alloy_primitives::{Address, B256, LogData, address, hex};
+
+    const SYSTEM_CONFIG: Address = address!("1111111111111111111111111111111111111111");
+
+    fn batcher_log(address: Address, batcher: Address) -> Log {
+        let mut data = hex!("00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000020").to_vec();
+        data.extend_from_slice(B256::left_padding_from(batcher.as_slice()).as_slice());
+        Log {
+            address,
+            data: LogData::new_unchecked(
+                vec![CONFIG_UPDATE_TOPIC, CONFIG_UPDATE_EVENT_VERSION_0, B256::ZERO],
+                data.into(),
+            ),
+        }
+    }
+
+    fn rollup_config() -> RollupConfig {
+        let mut config = RollupConfig {
+            l1_system_config_address: SYSTEM_CONFIG,
+            genesis: ChainGenesis {
+                system_config: Some(SystemConfig { gas_limit: 30_000_000, ..Default::default() }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        config.genesis.l1.number = 10;
+        config
+    }
+
+    #[test]
+    fn test_system_config_history_replay() {
+        let rollup_config = rollup_config();
+        let mut history = SystemConfigHistory::new(&rollup_config);
+        let genesis = history.latest();
+        assert_eq!(history.config_at(10), Some(genesis));
+
+        let first = address!("000000000000000000000000000000000000bEEF");
+        let second = address!("000000000000000000000000000000000000cafe");
+        let logs = vec![
+            ConfigUpdateLog {
+                block_number: 12,
+                block_timestamp: 0,
+                log: batcher_log(Address::ZERO, second),
+            },
+            ConfigUpdateLog {
+                block_number: 13,
+                block_timestamp: 0,
+                log: batcher_log(SYSTEM_CONFIG, first),
+            },
+            ConfigUpdateLog {
+                block_number: 13,
+                block_timestamp: 0,
+                log: batcher_log(SYSTEM_CONFIG, second),
+            },
+            ConfigUpdateLog {
+                block_number: 15,
+                block_timestamp: 0,
+                log: batcher_log(SYSTEM_CONFIG, first),
+            },
+        ];
+        assert_eq!(history.replay(&rollup_config, 20, logs), Ok(2));
+        assert_eq!(history.synced_to(), 20);
+
+        assert_eq!(history.config_at(9), None);
+        assert_eq!(history.config_at(12), Some(genesis));
+        assert_eq!(history.config_at(13).unwrap().batcher_address, second);
+        assert_eq!(history.config_at(14).unwrap().batcher_address, second);
+        assert_eq!(history.config_at(20).unwrap().batcher_address, first);
+        assert_eq!(history.config_at(20).unwrap().gas_limit, 30_000_000);
+        assert_eq!(history.config_at(21), None);
+        assert_eq!(history.updates().count(), 3);
+    }
+
+    #[test]
+    fn test_system_config_history_out_of_range() {
+        let rollup_config = rollup_config();
+        let mut history = SystemConfigHistory::new(&rollup_config);
+        let log = batcher_log(SYSTEM_CONFIG, Address::ZERO);
+
+        let logs = vec![ConfigUpdateLog { block_number: 10, block_timestamp: 0, log: log.clone() }];
+        assert_eq!(
+            history.replay(&rollup_config, 20, logs),
+            Err(SystemConfigHistoryError::LogOutOfRange { block: 10, start: 10, end: 20 })
+        );
+        assert_eq!(history.synced_to(), 10);
+
+        history.replay(&rollup_config, 20, vec![]).unwrap();
+        assert_eq!(
+            history.replay(&rollup_config, 19, vec![]),
+            Err(SystemConfigHistoryError::AlreadySynced(19))
+        );
+    }
+}
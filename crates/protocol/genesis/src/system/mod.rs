@@ -12,6 +12,9 @@ pub const CONFIG_UPDATE_EVENT_VERSION_0: B256 = B256::ZERO;
 mod config;
 pub use config::SystemConfig;
 
+mod history;
+pub use history::{ConfigUpdateLog, SystemConfigHistory};
+
 mod log;
 pub use log::SystemConfigLog;
 
@@ -25,5 +28,5 @@ mod errors;
 pub use errors::{
     BatcherUpdateError, DaFootprintGasScalarUpdateError, EIP1559UpdateError, GasConfigUpdateError,
     GasLimitUpdateError, LogProcessingError, MinBaseFeeUpdateError, OperatorFeeUpdateError,
-    SystemConfigUpdateError, UnsafeBlockSignerUpdateError,
+    SystemConfigHistoryError, SystemConfigUpdateError, UnsafeBlockSignerUpdateError,
 };
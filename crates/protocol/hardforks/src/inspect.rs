@@ -0,0 +1,311 @@
+//! Inspection and verification of network upgrade transactions.
+
+use crate::{Ecotone, Fjord, Interop, Isthmus, Jovian, utils::UPGRADE_TO_FUNC_BYTES_4};
+use alloc::vec::Vec;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B256, TxKind, keccak256};
+use kona_genesis::RollupConfig;
+use op_alloy_consensus::TxDeposit;
+
+/// A network upgrade that includes upgrade transactions in its activation block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum NetworkUpgrade {
+    /// The Ecotone network upgrade.
+    Ecotone,
+    /// The Fjord network upgrade.
+    Fjord,
+    /// The Isthmus network upgrade.
+    Isthmus,
+    /// The Jovian network upgrade.
+    Jovian,
+    /// The Interop network upgrade.
+    Interop,
+}
+
+impl NetworkUpgrade {
+    /// All network upgrades with upgrade transactions, in activation order.
+    pub const ALL: [Self; 5] =
+        [Self::Ecotone, Self::Fjord, Self::Isthmus, Self::Jovian, Self::Interop];
+
+    /// Returns the upgrade deposit transactions of the network upgrade, in block order.
+    pub fn deposits(&self) -> Vec<TxDeposit> {
+        match self {
+            Self::Ecotone => Ecotone::deposits().collect(),
+            Self::Fjord => Fjord::deposits().collect(),
+            Self::Isthmus => Isthmus::deposits().collect(),
+            Self::Jovian => Jovian::deposits().collect(),
+            Self::Interop => Interop::deposits().collect(),
+        }
+    }
+
+    /// Returns the decoded upgrade transactions of the network upgrade, in block order.
+    pub fn inspect(&self) -> Vec<UpgradeTxInfo> {
+        self.deposits().iter().map(UpgradeTxInfo::from_deposit).collect()
+    }
+
+    /// Returns the activation timestamp of the network upgrade in the given rollup config, if
+    /// it is scheduled.
+    pub const fn activation_time(&self, config: &RollupConfig) -> Option<u64> {
+        match self {
+            Self::Ecotone => config.hardforks.ecotone_time,
+            Self::Fjord => config.hardforks.fjord_time,
+            Self::Isthmus => config.hardforks.isthmus_time,
+            Self::Jovian => config.hardforks.jovian_time,
+            Self::Interop => config.hardforks.interop_time,
+        }
+    }
+
+    /// Returns the number of the L2 block that activates the network upgrade, which carries the
+    /// upgrade transactions.
+    ///
+    /// Returns `None` if the upgrade is not scheduled, or if it is active at genesis, in which
+    /// case no upgrade transactions are included.
+    pub fn activation_block(&self, config: &RollupConfig) -> Option<u64> {
+        let time = self.activation_time(config)?;
+        if time <= config.genesis.l2_time || config.block_time == 0 {
+            return None;
+        }
+        let blocks = (time - config.genesis.l2_time).div_ceil(config.block_time);
+        Some(config.genesis.l2.number + blocks)
+    }
+}
+
+impl core::str::FromStr for NetworkUpgrade {
+    type Err = UnknownNetworkUpgrade;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|upgrade| alloc::format!("{upgrade}").eq_ignore_ascii_case(s))
+            .ok_or(UnknownNetworkUpgrade)
+    }
+}
+
+/// An error parsing a [`NetworkUpgrade`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown network upgrade")]
+pub struct UnknownNetworkUpgrade;
+
+/// The decoded intent of an upgrade transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeIntent {
+    /// Deploys a contract.
+    Deploy {
+        /// The account deploying the contract.
+        deployer: Address,
+        /// The address of the deployed contract, assuming the deployer's nonce is 0.
+        contract: Address,
+        /// The keccak256 hash of the contract's creation bytecode.
+        bytecode_hash: B256,
+    },
+    /// Points a proxy to a new implementation through `upgradeTo(address)`.
+    UpgradeProxy {
+        /// The proxy being upgraded.
+        proxy: Address,
+        /// The new implementation of the proxy.
+        implementation: Address,
+    },
+    /// Calls a contract.
+    Call {
+        /// The contract being called.
+        target: Address,
+        /// The 4-byte function selector of the call, if the calldata contains one.
+        selector: Option<[u8; 4]>,
+    },
+}
+
+/// A decoded network upgrade transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpgradeTxInfo {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The source hash of the deposit.
+    pub source_hash: B256,
+    /// The sender of the deposit.
+    pub from: Address,
+    /// The gas limit of the deposit.
+    pub gas_limit: u64,
+    /// The decoded intent of the deposit.
+    pub intent: UpgradeIntent,
+}
+
+impl UpgradeTxInfo {
+    /// Decodes the intent of an upgrade deposit transaction.
+    pub fn from_deposit(tx: &TxDeposit) -> Self {
+        let intent = match tx.to {
+            TxKind::Create => UpgradeIntent::Deploy {
+                deployer: tx.from,
+                contract: tx.from.create(0),
+                bytecode_hash: keccak256(&tx.input),
+            },
+            TxKind::Call(to)
+                if tx.input.len() == 36 && tx.input[..4] == UPGRADE_TO_FUNC_BYTES_4 =>
+            {
+                UpgradeIntent::UpgradeProxy {
+                    proxy: to,
+                    implementation: Address::from_slice(&tx.input[16..]),
+                }
+            }
+            TxKind::Call(to) => UpgradeIntent::Call {
+                target: to,
+                selector: tx.input.get(..4).map(|s| s.try_into().expect("slice is 4 bytes")),
+            },
+        };
+        Self {
+            tx_hash: keccak256(tx.encoded_2718()),
+            source_hash: tx.source_hash,
+            from: tx.from,
+            gas_limit: tx.gas_limit,
+            intent,
+        }
+    }
+}
+
+/// The outcome of checking an expected upgrade transaction against a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeTxStatus {
+    /// The transaction is included in the block, at the given index among the block's deposits.
+    Included(usize),
+    /// A deposit with the same source hash is included at the given index among the block's
+    /// deposits, but its encoding differs from the expected transaction.
+    Mismatch(usize),
+    /// The transaction is included at the given index among the block's deposits, but not
+    /// after the previous upgrade transaction.
+    OutOfOrder(usize),
+    /// No deposit with the transaction's source hash is included in the block.
+    Missing,
+}
+
+/// Checks the expected upgrade transactions against the deposit transactions of the
+/// activation block.
+///
+/// # Arguments
+/// * `expected` - The expected upgrade transactions, in block order.
+/// * `block_deposits` - The deposit transactions of the activation block, in block order.
+///
+/// # Returns
+/// The status of each expected upgrade transaction, in the order of `expected`.
+pub fn verify_upgrade_txs(
+    expected: &[TxDeposit],
+    block_deposits: &[TxDeposit],
+) -> Vec<UpgradeTxStatus> {
+    let mut last_index = None;
+    expected
+        .iter()
+        .map(|tx| {
+            let Some(index) =
+                block_deposits.iter().position(|deposit| deposit.source_hash == tx.source_hash)
+            else {
+                return UpgradeTxStatus::Missing;
+            };
+            if block_deposits[index].encoded_2718() != tx.encoded_2718() {
+                return UpgradeTxStatus::Mismatch(index);
+            }
+            if last_index.is_some_and(|last| index != last + 1) {
+                last_index = Some(index);
+                return UpgradeTxStatus::OutOfOrder(index);
+            }
+            last_index = Some(index);
+            UpgradeTxStatus::Included(index)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use kona_genesis::{ChainGenesis, HardForkConfig};
+    use kona_protocol::Predeploys;
+
+    #[test]
+    fn test_network_upgrade_from_str() {
+        for upgrade in NetworkUpgrade::ALL {
+            let name = alloc::format!("{upgrade}").to_lowercase();
+            assert_eq!(name.parse::<NetworkUpgrade>(), Ok(upgrade));
+        }
+        assert_eq!("granite".parse::<NetworkUpgrade>(), Err(UnknownNetworkUpgrade));
+    }
+
+    #[test]
+    fn test_activation_block() {
+        let mut config = RollupConfig {
+            block_time: 2,
+            genesis: ChainGenesis { l2_time: 100, ..Default::default() },
+            hardforks: HardForkConfig {
+                ecotone_time: Some(100),
+                fjord_time: Some(103),
+                isthmus_time: Some(110),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        config.genesis.l2.number = 1_000;
+
+        assert_eq!(NetworkUpgrade::Ecotone.activation_block(&config), None);
+        assert_eq!(NetworkUpgrade::Fjord.activation_block(&config), Some(1_002));
+        assert_eq!(NetworkUpgrade::Isthmus.activation_block(&config), Some(1_005));
+        assert_eq!(NetworkUpgrade::Jovian.activation_block(&config), None);
+    }
+
+    #[test]
+    fn test_inspect_ecotone() {
+        let txs = NetworkUpgrade::Ecotone.inspect();
+        assert_eq!(txs.len(), 6);
+        assert_eq!(txs[0].source_hash, Ecotone::deploy_l1_block_source());
+        assert_eq!(
+            txs[0].intent,
+            UpgradeIntent::Deploy {
+                deployer: Ecotone::L1_BLOCK_DEPLOYER,
+                contract: Ecotone::NEW_L1_BLOCK,
+                bytecode_hash: keccak256(Ecotone::l1_block_deployment_bytecode()),
+            }
+        );
+        assert_eq!(
+            txs[2].intent,
+            UpgradeIntent::UpgradeProxy {
+                proxy: Predeploys::L1_BLOCK_INFO,
+                implementation: Ecotone::NEW_L1_BLOCK,
+            }
+        );
+        assert_eq!(
+            txs[4].intent,
+            UpgradeIntent::Call {
+                target: Predeploys::GAS_PRICE_ORACLE,
+                selector: Some(Ecotone::ENABLE_ECOTONE_INPUT),
+            }
+        );
+        assert!(matches!(
+            txs[1].intent,
+            UpgradeIntent::Deploy { contract, .. } if contract == Ecotone::GAS_PRICE_ORACLE
+        ));
+    }
+
+    #[test]
+    fn test_verify_upgrade_txs() {
+        let expected = NetworkUpgrade::Fjord.deposits();
+        let l1_info = TxDeposit { source_hash: B256::repeat_byte(1), ..Default::default() };
+
+        let mut block = vec![l1_info.clone()];
+        block.extend(expected.iter().cloned());
+        assert_eq!(
+            verify_upgrade_txs(&expected, &block),
+            vec![
+                UpgradeTxStatus::Included(1),
+                UpgradeTxStatus::Included(2),
+                UpgradeTxStatus::Included(3),
+            ]
+        );
+
+        let mut block = vec![l1_info, expected[1].clone(), expected[0].clone()];
+        block[1].gas_limit += 1;
+        assert_eq!(
+            verify_upgrade_txs(&expected, &block),
+            vec![
+                UpgradeTxStatus::Included(2),
+                UpgradeTxStatus::Mismatch(1),
+                UpgradeTxStatus::Missing,
+            ]
+        );
+    }
+}
@@ -30,6 +30,12 @@ pub use interop::Interop;
 mod jovian;
 pub use jovian::Jovian;
 
+mod inspect;
+pub use inspect::{
+    NetworkUpgrade, UnknownNetworkUpgrade, UpgradeIntent, UpgradeTxInfo, UpgradeTxStatus,
+    verify_upgrade_txs,
+};
+
 mod utils;
 pub(crate) use utils::upgrade_to_calldata;
 
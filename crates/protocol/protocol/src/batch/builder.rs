@@ -0,0 +1,473 @@
+//! Module containing the [`SpanBatchBuilder`], a streaming builder for [`SpanBatch`]es.
+
+use crate::{L1BlockInfoTx, SingleBatch, SpanBatch, SpanBatchError, SpanBatchTransactions};
+use alloy_primitives::B256;
+use op_alloy_consensus::OpBlock;
+
+/// The length of the parent check and L1 origin check in the span batch prefix.
+const PREFIX_CHECKS_LEN: usize = 40;
+
+/// The length of a transaction signature in a span batch, excluding the y-parity bit.
+const TX_SIG_LEN: usize = 64;
+
+/// The length of a transaction `to` address in a span batch.
+const TX_TO_LEN: usize = 20;
+
+/// Streaming builder for a [`SpanBatch`].
+///
+/// L2 blocks are appended one at a time, either directly as an [`OpBlock`] along with its
+/// [`L1BlockInfoTx`], or as a [`SingleBatch`]. The builder keeps track of the size of the span
+/// batch's encoding as blocks are appended, without re-encoding the span, so that a batcher can
+/// fill a channel up to a target size.
+///
+/// The size tracked is the length of the uncompressed [`Batch::Span`](crate::Batch::Span)
+/// encoding, including the batch type byte. It is an upper bound of the span's contribution to a
+/// compressed channel, not its compressed size. When a maximum encoded length is configured, a
+/// block that would push the encoding over it is rejected, leaving the builder untouched, and the
+/// span should be flushed with [`SpanBatchBuilder::take`]. The first block of a span is always
+/// accepted, as a block cannot be split across span batches.
+#[derive(Debug, Clone)]
+pub struct SpanBatchBuilder {
+    /// The span batch being built.
+    span: SpanBatch,
+    /// The size of the span batch's encoding.
+    size: EncodedSize,
+    /// The maximum length of the span batch's uncompressed encoding, if any.
+    max_encoded_len: Option<usize>,
+    /// The hash of the last block appended through [`SpanBatchBuilder::add_block`], used to
+    /// ensure that the appended blocks form a chain.
+    last_block_hash: Option<B256>,
+}
+
+impl SpanBatchBuilder {
+    /// Creates a new, empty [`SpanBatchBuilder`] for the chain with the given genesis timestamp
+    /// and chain ID.
+    pub fn new(genesis_timestamp: u64, chain_id: u64) -> Self {
+        Self {
+            span: SpanBatch { genesis_timestamp, chain_id, ..Default::default() },
+            size: EncodedSize::default(),
+            max_encoded_len: None,
+            last_block_hash: None,
+        }
+    }
+
+    /// Sets the maximum length of the span batch's uncompressed encoding, in bytes.
+    ///
+    /// The limit applies to the encoding before channel compression, so it should be derived
+    /// from the channel's target size and the expected compression ratio.
+    pub const fn with_max_encoded_len(mut self, max_encoded_len: usize) -> Self {
+        self.max_encoded_len = Some(max_encoded_len);
+        self
+    }
+
+    /// Returns the span batch built so far.
+    pub const fn span_batch(&self) -> &SpanBatch {
+        &self.span
+    }
+
+    /// Returns `true` if no blocks have been appended to the span batch.
+    pub fn is_empty(&self) -> bool {
+        self.span.batches.is_empty()
+    }
+
+    /// Returns the number of blocks in the span batch.
+    pub fn block_count(&self) -> usize {
+        self.span.batches.len()
+    }
+
+    /// Returns the length of the span batch's encoding, including the batch type byte, or `0` if
+    /// the span batch is empty.
+    pub const fn encoded_len(&self) -> usize {
+        self.size.total()
+    }
+
+    /// Appends an L2 block to the span batch.
+    ///
+    /// # Arguments
+    /// * `block` - The L2 block to append. Its deposit transactions are omitted from the batch.
+    /// * `l1_info` - The [`L1BlockInfoTx`] decoded from the block's first transaction.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The block was appended.
+    /// * `Ok(false)` - Appending the block would exceed the maximum encoded length. The block was
+    ///   not appended.
+    /// * `Err(SpanBatchError)` - The block does not extend the span or its transactions could not
+    ///   be added.
+    pub fn add_block(
+        &mut self,
+        block: &OpBlock,
+        l1_info: &L1BlockInfoTx,
+    ) -> Result<bool, SpanBatchError> {
+        if let Some(last_block_hash) = self.last_block_hash &&
+            block.header.parent_hash != last_block_hash
+        {
+            return Err(SpanBatchError::NonContiguousBlock);
+        }
+
+        let batch = SingleBatch::from_block(block, l1_info);
+        if !self.push(&batch, l1_info.sequence_number())? {
+            return Ok(false);
+        }
+        self.last_block_hash = Some(block.header.hash_slow());
+        Ok(true)
+    }
+
+    /// Appends a [`SingleBatch`] to the span batch.
+    ///
+    /// # Arguments
+    /// * `batch` - The batch to append.
+    /// * `seq_num` - The sequence number of the batch's block within its epoch.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The batch was appended.
+    /// * `Ok(false)` - Appending the batch would exceed the maximum encoded length. The batch was
+    ///   not appended.
+    /// * `Err(SpanBatchError)` - The batch is not ordered after the span or its transactions could
+    ///   not be added.
+    pub fn add_singular_batch(
+        &mut self,
+        batch: &SingleBatch,
+        seq_num: u64,
+    ) -> Result<bool, SpanBatchError> {
+        let appended = self.push(batch, seq_num)?;
+        if appended {
+            // The hash of the batch's block is unknown, so the chain can no longer be checked.
+            self.last_block_hash = None;
+        }
+        Ok(appended)
+    }
+
+    /// Takes the span batch built so far, resetting the builder for the next span. Blocks
+    /// appended afterwards must still extend the last block of the taken span.
+    pub fn take(&mut self) -> SpanBatch {
+        let next = SpanBatch {
+            genesis_timestamp: self.span.genesis_timestamp,
+            chain_id: self.span.chain_id,
+            ..Default::default()
+        };
+        self.size = EncodedSize::default();
+        core::mem::replace(&mut self.span, next)
+    }
+
+    /// Appends a [`SingleBatch`] to the span batch if it fits within the maximum encoded length.
+    ///
+    /// The batch's transactions are converted once, measured, and then appended to the span as
+    /// they are.
+    fn push(&mut self, batch: &SingleBatch, seq_num: u64) -> Result<bool, SpanBatchError> {
+        if let Some(last) = self.span.batches.last() &&
+            last.timestamp >= batch.timestamp
+        {
+            return Err(SpanBatchError::UnorderedBatch);
+        }
+
+        let mut txs = SpanBatchTransactions::default();
+        txs.add_txs(batch.transactions.clone(), self.span.chain_id)?;
+
+        let mut size = self.size;
+        size.push(
+            batch.timestamp.saturating_sub(self.span.genesis_timestamp),
+            batch.epoch_num,
+            &txs,
+        );
+        if let Some(max_encoded_len) = self.max_encoded_len &&
+            !self.is_empty() &&
+            size.total() > max_encoded_len
+        {
+            return Ok(false);
+        }
+
+        self.span.append_singular_batch_with_txs(batch.clone(), seq_num, txs);
+        self.size = size;
+        Ok(true)
+    }
+}
+
+/// The components of a span batch's encoding that grow as blocks are appended.
+#[derive(Debug, Default, Clone, Copy)]
+struct EncodedSize {
+    /// The timestamp of the first block, relative to genesis.
+    rel_timestamp: u64,
+    /// The L1 origin number of the last block.
+    l1_origin_num: u64,
+    /// The number of blocks.
+    block_count: usize,
+    /// The length of the varint-encoded transaction counts of the blocks.
+    block_tx_counts_len: usize,
+    /// The number of transactions.
+    tx_count: usize,
+    /// The number of legacy transactions.
+    legacy_tx_count: usize,
+    /// The length of the per-transaction signatures, `to` addresses, data, nonces and gas limits.
+    txs_len: usize,
+}
+
+impl EncodedSize {
+    /// Accounts for a block with the given transactions.
+    fn push(&mut self, rel_timestamp: u64, l1_origin_num: u64, txs: &SpanBatchTransactions) {
+        if self.block_count == 0 {
+            self.rel_timestamp = rel_timestamp;
+        }
+        self.l1_origin_num = l1_origin_num;
+        self.block_count += 1;
+        self.block_tx_counts_len += varint_len(txs.total_block_tx_count);
+        self.tx_count += txs.total_block_tx_count as usize;
+        self.legacy_tx_count += txs.legacy_tx_count as usize;
+        self.txs_len += txs.tx_sigs.len() * TX_SIG_LEN +
+            txs.tx_tos.len() * TX_TO_LEN +
+            txs.tx_data.iter().map(|data| data.len()).sum::<usize>() +
+            txs.tx_nonces.iter().map(|nonce| varint_len(*nonce)).sum::<usize>() +
+            txs.tx_gases.iter().map(|gas| varint_len(*gas)).sum::<usize>();
+    }
+
+    /// Returns the total length of the encoding, including the batch type byte.
+    const fn total(&self) -> usize {
+        if self.block_count == 0 {
+            return 0;
+        }
+
+        // Batch type
+        1 +
+            // Prefix
+            varint_len(self.rel_timestamp) +
+            varint_len(self.l1_origin_num) +
+            PREFIX_CHECKS_LEN +
+            // Payload
+            varint_len(self.block_count as u64) +
+            bitlist_len(self.block_count) +
+            self.block_tx_counts_len +
+            // Transactions: contract creation bits, y-parity bits and protected bits
+            2 * bitlist_len(self.tx_count) +
+            bitlist_len(self.legacy_tx_count) +
+            self.txs_len
+    }
+}
+
+/// Returns the length of the unsigned varint encoding of `value`.
+const fn varint_len(value: u64) -> usize {
+    let bits = 64 - value.leading_zeros() as usize;
+    if bits == 0 { 1 } else { bits.div_ceil(7) }
+}
+
+/// Returns the length of a span batch bitlist of `bit_length` bits.
+const fn bitlist_len(bit_length: usize) -> usize {
+    bit_length.div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Batch, BlockInfo, L1BlockInfoBedrock, RawSpanBatch};
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::{BlockBody, Header, Signed, TxEip1559, TxEnvelope, TxLegacy};
+    use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+    use alloy_primitives::{Bytes, Sealed, Signature, TxKind, address};
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+
+    const CHAIN_ID: u64 = 10;
+
+    fn tx(nonce: u64, create: bool) -> Bytes {
+        let to = if create {
+            TxKind::Create
+        } else {
+            TxKind::Call(address!("0123456789012345678901234567890123456789"))
+        };
+        let tx = if nonce % 2 == 0 {
+            TxEnvelope::Eip1559(Signed::new_unchecked(
+                TxEip1559 {
+                    chain_id: CHAIN_ID,
+                    nonce,
+                    to,
+                    gas_limit: 21_000,
+                    ..Default::default()
+                },
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        } else {
+            TxEnvelope::Legacy(Signed::new_unchecked(
+                TxLegacy {
+                    chain_id: Some(CHAIN_ID),
+                    nonce,
+                    to,
+                    gas_limit: 100_000,
+                    ..Default::default()
+                },
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        };
+        tx.encoded_2718().into()
+    }
+
+    fn batches(count: u64) -> Vec<SingleBatch> {
+        (0..count)
+            .map(|i| SingleBatch {
+                parent_hash: B256::repeat_byte(0xff),
+                epoch_num: 100 + i / 3,
+                epoch_hash: B256::repeat_byte((i / 3) as u8),
+                timestamp: 1_000 + 2 * i,
+                transactions: (0..i).map(|n| tx(n, n % 5 == 4)).collect(),
+            })
+            .collect()
+    }
+
+    fn encode(span: SpanBatch) -> Vec<u8> {
+        let mut buf = Vec::new();
+        Batch::Span(span).encode(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_encoded_len_matches_encoding() {
+        let mut builder = SpanBatchBuilder::new(0, CHAIN_ID);
+        assert_eq!(builder.encoded_len(), 0);
+
+        for batch in batches(20) {
+            assert!(builder.add_singular_batch(&batch, 0).unwrap());
+            assert_eq!(builder.encoded_len(), encode(builder.span_batch().clone()).len());
+        }
+    }
+
+    #[test]
+    fn test_max_encoded_len() {
+        let mut builder = SpanBatchBuilder::new(0, CHAIN_ID).with_max_encoded_len(1_000);
+        let mut spans = Vec::new();
+        for batch in batches(20) {
+            if !builder.add_singular_batch(&batch, 0).unwrap() {
+                spans.push(builder.take());
+                assert!(builder.add_singular_batch(&batch, 0).unwrap());
+            }
+        }
+        spans.push(builder.take());
+        assert!(builder.is_empty());
+
+        assert!(spans.len() > 1);
+        assert_eq!(spans.iter().map(|span| span.batches.len()).sum::<usize>(), 20);
+        for span in spans.into_iter().filter(|span| span.batches.len() > 1) {
+            assert!(encode(span).len() <= 1_000);
+        }
+    }
+
+    #[test]
+    fn test_unordered_batch() {
+        let mut builder = SpanBatchBuilder::new(0, CHAIN_ID);
+        let batches = batches(2);
+        builder.add_singular_batch(&batches[1], 0).unwrap();
+
+        let err = builder.add_singular_batch(&batches[0], 0).unwrap_err();
+        assert_eq!(err, SpanBatchError::UnorderedBatch);
+        assert_eq!(builder.block_count(), 1);
+    }
+
+    #[test]
+    fn test_singular_batches_roundtrip() {
+        let batches = batches(10);
+        let span = SpanBatch::from_singular_batches(&batches, 0, 0, CHAIN_ID).unwrap();
+        let encoded = encode(span);
+
+        let Batch::Span(decoded) = Batch::decode(
+            &mut encoded.as_slice(),
+            &kona_genesis::RollupConfig {
+                block_time: 2,
+                l2_chain_id: CHAIN_ID.into(),
+                ..Default::default()
+            },
+        )
+        .unwrap() else {
+            panic!("expected a span batch");
+        };
+
+        let l1_origins = batches
+            .iter()
+            .map(|b| BlockInfo { number: b.epoch_num, hash: b.epoch_hash, ..Default::default() })
+            .collect::<Vec<_>>();
+        let mut singles = decoded.to_singular_batches(&l1_origins).unwrap();
+        assert_eq!(singles.len(), batches.len());
+        singles[0].parent_hash = batches[0].parent_hash;
+        for (single, batch) in singles.iter().zip(&batches) {
+            assert_eq!(single.epoch_num, batch.epoch_num);
+            assert_eq!(single.epoch_hash, batch.epoch_hash);
+            assert_eq!(single.timestamp, batch.timestamp);
+            assert_eq!(single.transactions, batch.transactions);
+        }
+
+        let rebuilt = SpanBatch::from_singular_batches(&singles, 0, 0, CHAIN_ID).unwrap();
+        assert_eq!(encode(rebuilt), encoded);
+    }
+
+    #[test]
+    fn test_raw_span_batch_roundtrip() {
+        // Load in the raw span batch from the `op-node` derivation pipeline implementation.
+        let raw_span_batch_hex = include_bytes!("./testdata/raw_batch.hex");
+        let mut raw = RawSpanBatch::decode(&mut raw_span_batch_hex.as_slice()).unwrap();
+        let span = raw.derive(2, 0, CHAIN_ID).unwrap();
+
+        let l1_origin_hash = B256::right_padding_from(span.l1_origin_check.as_slice());
+        let l1_origins = span
+            .batches
+            .iter()
+            .map(|b| BlockInfo { number: b.epoch_num, hash: l1_origin_hash, ..Default::default() })
+            .collect::<Vec<_>>();
+        let mut singles = span.to_singular_batches(&l1_origins).unwrap();
+        singles[0].parent_hash = B256::right_padding_from(span.parent_check.as_slice());
+
+        let first_seq_num = if raw.payload.origin_bits.get_bit(0) == Some(1) { 0 } else { 1 };
+        let rebuilt = SpanBatch::from_singular_batches(&singles, first_seq_num, 0, CHAIN_ID)
+            .unwrap()
+            .to_raw_span_batch()
+            .unwrap();
+
+        let mut encoding_buf = Vec::new();
+        rebuilt.encode(&mut encoding_buf).unwrap();
+        assert_eq!(encoding_buf, raw_span_batch_hex);
+    }
+
+    #[test]
+    fn test_add_block() {
+        let l1_info = |sequence_number| {
+            L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+                number: 100,
+                block_hash: B256::repeat_byte(1),
+                sequence_number,
+                ..Default::default()
+            })
+        };
+        let block = |parent_hash, timestamp| OpBlock {
+            header: Header { parent_hash, timestamp, ..Default::default() },
+            body: BlockBody {
+                transactions: vec![
+                    OpTxEnvelope::Deposit(Sealed::new(TxDeposit::default())),
+                    OpTxEnvelope::decode_2718(&mut tx(0, false).as_ref()).unwrap(),
+                ],
+                ommers: vec![],
+                withdrawals: None,
+            },
+        };
+
+        let mut builder = SpanBatchBuilder::new(0, CHAIN_ID);
+        let first = block(B256::repeat_byte(0xff), 2);
+        assert!(builder.add_block(&first, &l1_info(0)).unwrap());
+
+        let err = builder.add_block(&block(B256::ZERO, 4), &l1_info(1)).unwrap_err();
+        assert_eq!(err, SpanBatchError::NonContiguousBlock);
+
+        assert!(builder.add_block(&block(first.header.hash_slow(), 4), &l1_info(1)).unwrap());
+        let span = builder.take();
+        assert!(span.check_parent_hash(B256::repeat_byte(0xff)));
+        assert!(span.check_origin_hash(B256::repeat_byte(1)));
+        assert_eq!(span.block_tx_counts, vec![1, 1]);
+        assert_eq!(span.batches[0].transactions, vec![tx(0, false)]);
+    }
+
+    #[test]
+    fn test_varint_len() {
+        let mut buf = [0u8; 10];
+        for value in [0, 1, 127, 128, 16_383, 16_384, u32::MAX as u64, u64::MAX] {
+            assert_eq!(varint_len(value), unsigned_varint::encode::u64(value, &mut buf).len());
+        }
+        assert_eq!(
+            vec![bitlist_len(0), bitlist_len(1), bitlist_len(8), bitlist_len(9)],
+            vec![0, 1, 1, 2]
+        );
+    }
+}
@@ -18,6 +18,12 @@ pub enum SpanBatchError {
     /// Missing L1 origin
     #[error("Missing L1 origin")]
     MissingL1Origin,
+    /// The batch is not ordered after the last batch in the span
+    #[error("Batch is not ordered after the last batch in the span")]
+    UnorderedBatch,
+    /// The block does not build on the last block in the span
+    #[error("Block does not build on the last block in the span")]
+    NonContiguousBlock,
     /// Decoding errors
     #[error("Span batch decoding error: {0}")]
     Decoding(#[from] SpanDecodingError),
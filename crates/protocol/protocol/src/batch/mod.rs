@@ -24,6 +24,7 @@
 //! # Key Components
 //!
 //! - **Batch Types**: [`SingleBatch`], [`SpanBatch`] for different batch formats
+//! - **Batch Building**: [`SpanBatchBuilder`] for building span batches from L2 blocks
 //! - **Batch Reading**: [`BatchReader`] for decoding batch data from channels
 //! - **Validation**: [`BatchValidationProvider`] for batch validity checking
 //! - **Transaction Data**: Specialized transaction formats for span batches
@@ -62,6 +63,9 @@ pub use bits::SpanBatchBits;
 mod span;
 pub use span::SpanBatch;
 
+mod builder;
+pub use builder::SpanBatchBuilder;
+
 mod transactions;
 pub use transactions::SpanBatchTransactions;
 
@@ -1,13 +1,13 @@
 //! This module contains the [`SingleBatch`] type.
 
-use crate::{BatchValidity, BlockInfo, L2BlockInfo};
+use crate::{BatchValidity, BlockInfo, L1BlockInfoTx, L2BlockInfo};
 use alloc::vec::Vec;
-use alloy_eips::BlockNumHash;
+use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
 use alloy_primitives::{BlockHash, Bytes};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
 use kona_genesis::RollupConfig;
-use op_alloy_consensus::OpTxType;
-use tracing::warn;
+use op_alloy_consensus::{OpBlock, OpTxType};
+use tracing::{debug, warn};
 
 /// Represents a single batch: a single encoded L2 block
 #[derive(Debug, Default, RlpDecodable, RlpEncodable, Clone, PartialEq, Eq)]
@@ -26,6 +26,26 @@ pub struct SingleBatch {
 }
 
 impl SingleBatch {
+    /// Creates a [`SingleBatch`] from an L2 [`OpBlock`] and the [`L1BlockInfoTx`] decoded from
+    /// its first transaction. Deposit transactions are derived from L1, and are therefore omitted
+    /// from the batch.
+    pub fn from_block(block: &OpBlock, l1_info: &L1BlockInfoTx) -> Self {
+        let epoch = l1_info.id();
+        Self {
+            parent_hash: block.header.parent_hash,
+            epoch_num: epoch.number,
+            epoch_hash: epoch.hash,
+            timestamp: block.header.timestamp,
+            transactions: block
+                .body
+                .transactions
+                .iter()
+                .filter(|tx| !tx.is_deposit())
+                .map(|tx| tx.encoded_2718().into())
+                .collect(),
+        }
+    }
+
     /// Returns the [`BlockNumHash`] of the batch.
     pub const fn epoch(&self) -> BlockNumHash {
         BlockNumHash { number: self.epoch_num, hash: self.epoch_hash }
@@ -41,6 +61,12 @@ impl SingleBatch {
         let next_timestamp = l2_safe_head.block_info.timestamp + cfg.block_time;
         if self.timestamp > next_timestamp {
             if cfg.is_holocene_active(inclusion_block.timestamp) {
+                debug!(
+                    target: "single_batch",
+                    "dropping future batch with time {}, expected {}",
+                    self.timestamp,
+                    next_timestamp
+                );
                 return BatchValidity::Drop;
             }
             return BatchValidity::Future;
@@ -49,6 +75,12 @@ impl SingleBatch {
             if cfg.is_holocene_active(inclusion_block.timestamp) {
                 return BatchValidity::Past;
             }
+            debug!(
+                target: "single_batch",
+                "dropping batch with old timestamp {}, min timestamp {}",
+                self.timestamp,
+                next_timestamp
+            );
             return BatchValidity::Drop;
         }
         BatchValidity::Accept
@@ -82,17 +114,29 @@ impl SingleBatch {
         // Dependent on the above timestamp check.
         // If the timestamp is correct, then it must build on top of the safe head.
         if self.parent_hash != l2_safe_head.block_info.hash {
+            debug!(
+                target: "single_batch",
+                "ignoring batch with mismatching parent hash {}, current safe head {}",
+                self.parent_hash,
+                l2_safe_head.block_info.hash
+            );
             return BatchValidity::Drop;
         }
 
         // Filter out batches that were included too late.
         if self.epoch_num + cfg.seq_window_size < inclusion_block.number {
+            debug!(target: "single_batch", "batch was included too late, sequence window expired");
             return BatchValidity::Drop;
         }
 
         // Check the L1 origin of the batch
         let mut batch_origin = epoch;
         if self.epoch_num < epoch.number {
+            debug!(
+                target: "single_batch",
+                "dropped batch, epoch is too old, minimum: {:?}",
+                epoch.id()
+            );
             return BatchValidity::Drop;
         } else if self.epoch_num == epoch.number {
             // Batch is sticking to the current epoch, continue.
@@ -107,15 +151,31 @@ impl SingleBatch {
             }
             batch_origin = l1_blocks[1];
         } else {
+            debug!(
+                target: "single_batch",
+                "batch is for future epoch too far ahead, while it has the next timestamp, so it must be invalid"
+            );
             return BatchValidity::Drop;
         }
 
         // Validate the batch epoch hash
         if self.epoch_hash != batch_origin.hash {
+            debug!(
+                target: "single_batch",
+                "batch is for different L1 chain, epoch hash does not match, expected: {:?}",
+                batch_origin.id()
+            );
             return BatchValidity::Drop;
         }
 
         if self.timestamp < batch_origin.timestamp {
+            debug!(
+                target: "single_batch",
+                "batch timestamp is less than L1 origin timestamp, l2_timestamp: {}, l1_timestamp: {}, origin: {:?}",
+                self.timestamp,
+                batch_origin.timestamp,
+                batch_origin.id()
+            );
             return BatchValidity::Drop;
         }
 
@@ -124,6 +184,7 @@ impl SingleBatch {
         let max = if let Some(max) = batch_origin.timestamp.checked_add(max_drift) {
             max
         } else {
+            debug!(target: "single_batch", "L1 origin timestamp overflows max sequencer drift");
             return BatchValidity::Drop;
         };
 
@@ -132,6 +193,11 @@ impl SingleBatch {
             // If the sequencer is ignoring the time drift rule, then drop the batch and force an
             // empty batch instead, as the sequencer is not allowed to include anything
             // past this point without moving to the next epoch.
+            debug!(
+                target: "single_batch",
+                "batch exceeded sequencer time drift, sequencer must adopt new L1 origin to include transactions again, max_time: {}",
+                max
+            );
             return BatchValidity::Drop;
         }
         if self.timestamp > max && no_txs {
@@ -146,6 +212,10 @@ impl SingleBatch {
                 let next_origin = l1_blocks[1];
                 // Check if the next L1 Origin could have been adopted
                 if self.timestamp >= next_origin.timestamp {
+                    debug!(
+                        target: "single_batch",
+                        "batch exceeded sequencer time drift without adopting next origin, and next L1 origin would have been valid"
+                    );
                     return BatchValidity::Drop;
                 }
             }
@@ -164,17 +234,28 @@ impl SingleBatch {
         }
 
         // We can do this check earlier, but it's intensive so we do it last for the sad-path.
-        for tx in self.transactions.iter() {
+        for (i, tx) in self.transactions.iter().enumerate() {
             if tx.is_empty() {
+                debug!(target: "single_batch", "transaction data must not be empty, tx_index: {}", i);
                 return BatchValidity::Drop;
             }
             if tx.as_ref().first() == Some(&(OpTxType::Deposit as u8)) {
+                debug!(
+                    target: "single_batch",
+                    "sequencers may not embed any deposits into batch data, but found tx that has one, tx_index: {}",
+                    i
+                );
                 return BatchValidity::Drop;
             }
             // If isthmus is not active yet and the transaction is a 7702, drop the batch.
             if !cfg.is_isthmus_active(self.timestamp) &&
                 tx.as_ref().first() == Some(&(OpTxType::Eip7702 as u8))
             {
+                debug!(
+                    target: "single_batch",
+                    "EIP-7702 transactions are not supported pre-isthmus. tx_index: {}",
+                    i
+                );
                 return BatchValidity::Drop;
             }
         }
@@ -636,4 +717,34 @@ mod tests {
                 .any(|s| { s.contains("Sequencer included user transactions") })
         )
     }
+
+    #[test]
+    fn test_check_batch_drop_logs_reason() {
+        let trace_store: TraceStorage = Default::default();
+        let layer = CollectingLayer::new(trace_store.clone());
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let single_batch = SingleBatch {
+            parent_hash: BlockHash::with_last_byte(1),
+            epoch_num: 0,
+            epoch_hash: BlockHash::ZERO,
+            timestamp: 1,
+            transactions: Vec::new(),
+        };
+
+        let cfg = RollupConfig { block_time: 1, ..Default::default() };
+        let l1_blocks = vec![BlockInfo::default()];
+        let l2_safe_head = L2BlockInfo::default();
+        let inclusion_block = BlockInfo::default();
+        assert_eq!(
+            single_batch.check_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
+            BatchValidity::Drop
+        );
+
+        assert!(trace_store.get_by_level(Level::WARN).is_empty());
+        let reasons = trace_store.get_by_level(Level::DEBUG);
+        assert_eq!(reasons.len(), 1);
+        assert!(reasons[0].contains("ignoring batch with mismatching parent hash"));
+    }
 }
@@ -14,8 +14,8 @@ use tracing::{info, warn};
 
 use crate::{
     BatchValidationProvider, BatchValidity, BlockInfo, L2BlockInfo, RawSpanBatch, SingleBatch,
-    SpanBatchBits, SpanBatchElement, SpanBatchError, SpanBatchPayload, SpanBatchPrefix,
-    SpanBatchTransactions,
+    SpanBatchBits, SpanBatchBuilder, SpanBatchElement, SpanBatchError, SpanBatchPayload,
+    SpanBatchPrefix, SpanBatchTransactions,
 };
 
 /// Container for the inputs required to build a span of L2 blocks in derived form.
@@ -334,12 +334,82 @@ impl SpanBatch {
         Ok(single_batches)
     }
 
+    /// Converts all [`SpanBatchElement`]s to [`SingleBatch`]es, resolving the epoch hash of each
+    /// element from the passed L1 origins.
+    ///
+    /// Unlike [`SpanBatch::get_singular_batches`], no element is skipped. The resulting
+    /// [`SingleBatch`]es do not contain a parent hash, as the span only commits to a prefix of
+    /// the first block's parent hash.
+    ///
+    /// # Errors
+    /// Returns [`SpanBatchError::MissingL1Origin`] if the L1 origin of an element is not in
+    /// `l1_origins`.
+    pub fn to_singular_batches(
+        &self,
+        l1_origins: &[BlockInfo],
+    ) -> Result<Vec<SingleBatch>, SpanBatchError> {
+        self.batches
+            .iter()
+            .map(|batch| {
+                let epoch_hash = l1_origins
+                    .iter()
+                    .find(|origin| origin.number == batch.epoch_num)
+                    .map(|origin| origin.hash)
+                    .ok_or(SpanBatchError::MissingL1Origin)?;
+                Ok(SingleBatch {
+                    epoch_num: batch.epoch_num,
+                    epoch_hash,
+                    timestamp: batch.timestamp,
+                    transactions: batch.transactions.clone(),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// Builds a [`SpanBatch`] from a sequence of [`SingleBatch`]es, ordered by timestamp.
+    ///
+    /// The parent check is taken from the first batch's parent hash, and `first_seq_num`, the
+    /// sequence number of the first batch's block within its epoch, determines whether the span
+    /// starts a new epoch. The encoding of the resulting span batch is identical to the encoding
+    /// of a span batch that decodes to the same [`SingleBatch`]es.
+    ///
+    /// # Errors
+    /// Returns [`SpanBatchError::UnorderedBatch`] if the batches are not ordered by timestamp,
+    /// or an error if the transactions of a batch could not be added to the span.
+    pub fn from_singular_batches(
+        batches: &[SingleBatch],
+        first_seq_num: u64,
+        genesis_timestamp: u64,
+        chain_id: u64,
+    ) -> Result<Self, SpanBatchError> {
+        let mut builder = SpanBatchBuilder::new(genesis_timestamp, chain_id);
+        for batch in batches {
+            builder.add_singular_batch(batch, first_seq_num)?;
+        }
+        Ok(builder.take())
+    }
+
     /// Append a [`SingleBatch`] to the [`SpanBatch`]. Updates the L1 origin check if need be.
     pub fn append_singular_batch(
         &mut self,
         singular_batch: SingleBatch,
         seq_num: u64,
     ) -> Result<(), SpanBatchError> {
+        let mut new_txs = SpanBatchTransactions::default();
+        new_txs.add_txs(singular_batch.transactions.clone(), self.chain_id)?;
+        self.append_singular_batch_with_txs(singular_batch, seq_num, new_txs);
+        Ok(())
+    }
+
+    /// Appends a [`SingleBatch`] whose transactions were already converted into
+    /// [`SpanBatchTransactions`] with [`SpanBatchTransactions::add_txs`].
+    pub(crate) fn append_singular_batch_with_txs(
+        &mut self,
+        singular_batch: SingleBatch,
+        seq_num: u64,
+        new_txs: SpanBatchTransactions,
+    ) {
         // If the new element is not ordered with respect to the last element, panic.
         if !self.batches.is_empty() && self.peek(0).timestamp > singular_batch.timestamp {
             panic!("Batch is not ordered");
@@ -365,13 +435,11 @@ impl SpanBatch {
         // Set the respective bit in the origin bits.
         self.origin_bits.set_bit(self.batches.len() - 1, epoch_bit);
 
-        let new_txs = self.peek(0).transactions.clone();
-
         // Update the block tx counts cache with the latest batch's transaction count.
-        self.block_tx_counts.push(new_txs.len() as u64);
+        self.block_tx_counts.push(new_txs.total_block_tx_count);
 
         // Add the new transactions to the transaction cache.
-        self.txs.add_txs(new_txs, self.chain_id)
+        self.txs.extend(new_txs);
     }
 
     /// Checks if the span batch is valid.
@@ -7,7 +7,7 @@ use crate::{
 };
 use alloc::vec::Vec;
 use alloy_consensus::{Transaction, TxEnvelope, TxType};
-use alloy_eips::eip2718::Encodable2718;
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use alloy_primitives::{Address, Bytes, Signature, U256, bytes};
 use alloy_rlp::{Buf, Decodable, Encodable};
 
@@ -277,13 +277,23 @@ impl SpanBatchTransactions {
     }
 
     /// Add raw transactions into the [`SpanBatchTransactions`].
+    ///
+    /// Transactions may be given in their RLP network encoding or in their EIP-2718 encoding, the
+    /// form carried by [`SingleBatch`]es and produced by [`Self::full_txs`]. The two encodings
+    /// only differ for typed transactions, whose network encoding is wrapped in an RLP string
+    /// header, so the EIP-2718 decoding is tried first.
+    ///
+    /// [`SingleBatch`]: crate::SingleBatch
     pub fn add_txs(&mut self, txs: Vec<Bytes>, chain_id: u64) -> Result<(), SpanBatchError> {
         let total_block_tx_count = txs.len() as u64;
         let offset = self.total_block_tx_count;
 
         for i in 0..total_block_tx_count {
-            let tx_enveloped = TxEnvelope::decode(&mut txs[i as usize].as_ref())
-                .map_err(|_| SpanBatchError::Decoding(SpanDecodingError::InvalidTransactionData))?;
+            let raw_tx = txs[i as usize].as_ref();
+            let tx_enveloped = TxEnvelope::decode_2718(&mut &raw_tx[..])
+                .ok()
+                .or_else(|| TxEnvelope::decode(&mut &raw_tx[..]).ok())
+                .ok_or(SpanBatchError::Decoding(SpanDecodingError::InvalidTransactionData))?;
             let span_batch_tx = SpanBatchTransactionData::try_from(&tx_enveloped)?;
 
             let tx_type = tx_enveloped.tx_type();
@@ -343,13 +353,37 @@ impl SpanBatchTransactions {
         self.total_block_tx_count += total_block_tx_count;
         Ok(())
     }
+
+    /// Appends the transactions of `other` after the transactions of `self`, with the same result
+    /// as adding them to `self` with [`Self::add_txs`].
+    pub fn extend(&mut self, other: Self) {
+        let tx_offset = self.total_block_tx_count as usize;
+        for i in 0..other.total_block_tx_count as usize {
+            let bit = other.contract_creation_bits.get_bit(i) == Some(1);
+            self.contract_creation_bits.set_bit(tx_offset + i, bit);
+        }
+        let legacy_offset = self.legacy_tx_count as usize;
+        for i in 0..other.legacy_tx_count as usize {
+            let bit = other.protected_bits.get_bit(i) == Some(1);
+            self.protected_bits.set_bit(legacy_offset + i, bit);
+        }
+
+        self.tx_sigs.extend(other.tx_sigs);
+        self.tx_nonces.extend(other.tx_nonces);
+        self.tx_gases.extend(other.tx_gases);
+        self.tx_tos.extend(other.tx_tos);
+        self.tx_data.extend(other.tx_data);
+        self.tx_types.extend(other.tx_types);
+        self.total_block_tx_count += other.total_block_tx_count;
+        self.legacy_tx_count += other.legacy_tx_count;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloc::vec;
-    use alloy_consensus::{Signed, TxEip1559, TxEip2930, TxEip7702};
+    use alloy_consensus::{Signed, TxEip1559, TxEip2930, TxEip7702, TxLegacy};
     use alloy_primitives::{Signature, TxKind, address};
 
     #[test]
@@ -418,6 +452,31 @@ mod tests {
         assert_eq!(span_batch_txs.total_block_tx_count, 1);
     }
 
+    #[test]
+    fn test_span_batch_transactions_add_eip2718_encoded_tx() {
+        // Typed transactions in singular batches are EIP-2718 encoded, which previously failed to
+        // decode as the RLP network encoding.
+        let sig = Signature::test_signature();
+        let to = address!("0123456789012345678901234567890123456789");
+        let tx = TxEnvelope::Eip1559(Signed::new_unchecked(
+            TxEip1559 { to: TxKind::Call(to), chain_id: 1, ..Default::default() },
+            sig,
+            Default::default(),
+        ));
+        let mut network_buf = vec![];
+        tx.encode(&mut network_buf);
+        let mut eip2718_buf = vec![];
+        tx.encode_2718(&mut eip2718_buf);
+        assert_ne!(network_buf, eip2718_buf);
+
+        let mut from_network = SpanBatchTransactions::default();
+        from_network.add_txs(vec![Bytes::from(network_buf)], 1).unwrap();
+        let mut from_eip2718 = SpanBatchTransactions::default();
+        from_eip2718.add_txs(vec![Bytes::from(eip2718_buf.clone())], 1).unwrap();
+        assert_eq!(from_network, from_eip2718);
+        assert_eq!(from_eip2718.full_txs(1).unwrap(), vec![eip2718_buf]);
+    }
+
     #[test]
     fn test_span_batch_transactions_add_eip7702_tx() {
         let sig = Signature::test_signature();
@@ -436,4 +495,42 @@ mod tests {
         assert_eq!(result, Ok(()));
         assert_eq!(span_batch_txs.total_block_tx_count, 1);
     }
+
+    #[test]
+    fn test_span_batch_transactions_extend() {
+        let sig = Signature::test_signature();
+        let to = address!("0123456789012345678901234567890123456789");
+        let txs = (0..9u64)
+            .map(|nonce| {
+                let kind = if nonce % 3 == 0 { TxKind::Create } else { TxKind::Call(to) };
+                let tx = if nonce % 2 == 0 {
+                    TxEnvelope::Eip1559(Signed::new_unchecked(
+                        TxEip1559 { to: kind, nonce, chain_id: 1, ..Default::default() },
+                        sig,
+                        Default::default(),
+                    ))
+                } else {
+                    let chain_id = (nonce % 4 == 1).then_some(1);
+                    TxEnvelope::Legacy(Signed::new_unchecked(
+                        TxLegacy { to: kind, nonce, chain_id, ..Default::default() },
+                        sig,
+                        Default::default(),
+                    ))
+                };
+                Bytes::from(tx.encoded_2718())
+            })
+            .collect::<Vec<_>>();
+
+        let mut expected = SpanBatchTransactions::default();
+        expected.add_txs(txs[..4].to_vec(), 1).unwrap();
+        expected.add_txs(txs[4..].to_vec(), 1).unwrap();
+
+        let mut extended = SpanBatchTransactions::default();
+        extended.add_txs(txs[..4].to_vec(), 1).unwrap();
+        let mut other = SpanBatchTransactions::default();
+        other.add_txs(txs[4..].to_vec(), 1).unwrap();
+        extended.extend(other);
+
+        assert_eq!(extended, expected);
+    }
 }
@@ -82,6 +82,17 @@ pub enum DepositError {
     GasDecode(Bytes),
 }
 
+/// Derives a deposit transaction from an EVM log event emitted by the deposit contract, and
+/// returns its EIP-2718 encoding.
+///
+/// See [`decode_deposit_tx`] for the expected format of the log.
+pub fn decode_deposit(block_hash: B256, index: usize, log: &Log) -> Result<Bytes, DepositError> {
+    let deposit_tx = decode_deposit_tx(block_hash, index, log)?;
+    let mut buffer = Vec::with_capacity(deposit_tx.eip2718_encoded_length());
+    deposit_tx.encode_2718(&mut buffer);
+    Ok(Bytes::from(buffer))
+}
+
 /// Derives a deposit transaction from an EVM log event emitted by the deposit contract.
 ///
 /// The emitted log must be in format:
@@ -93,7 +104,14 @@ pub enum DepositError {
 ///    bytes opaqueData
 /// );
 /// ```
-pub fn decode_deposit(block_hash: B256, index: usize, log: &Log) -> Result<Bytes, DepositError> {
+///
+/// The source hash of the deposit is derived from the L1 `block_hash` and the `index` of the
+/// log within the block.
+pub fn decode_deposit_tx(
+    block_hash: B256,
+    index: usize,
+    log: &Log,
+) -> Result<TxDeposit, DepositError> {
     let topics = log.data.topics();
     if topics.len() != 4 {
         return Err(DepositError::UnexpectedTopicsLen(topics.len()));
@@ -198,11 +216,7 @@ pub fn decode_deposit(block_hash: B256, index: usize, log: &Log) -> Result<Bytes
     }
 
     unmarshal_deposit_version0(&mut deposit_tx, to, opaque_data)?;
-
-    // Re-encode the deposit transaction
-    let mut buffer = Vec::with_capacity(deposit_tx.eip2718_encoded_length());
-    deposit_tx.encode_2718(&mut buffer);
-    Ok(Bytes::from(buffer))
+    Ok(deposit_tx)
 }
 
 /// Unmarshals a deposit transaction from the opaque data.
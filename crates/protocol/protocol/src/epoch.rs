@@ -0,0 +1,245 @@
+//! Derivation of the L1-derived transactions of the first L2 block of an epoch.
+//!
+//! The first L2 block of an epoch opens with the L1 info deposit transaction, followed by the user
+//! deposits emitted by the deposit contract in the epoch's L1 origin. Both are fully determined by
+//! the L1 origin's header and receipts, along with the [`SystemConfig`] of the parent L2 block,
+//! so they can be derived without running the derivation pipeline.
+
+use crate::{
+    BlockInfoError, DEPOSIT_EVENT_ABI_HASH, DepositError, L1BlockInfoTx, decode_deposit_tx,
+};
+use alloc::vec::Vec;
+use alloy_consensus::{Eip658Value, Header, Receipt};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B256, Bytes, Sealed};
+use kona_genesis::{L1ChainConfig, RollupConfig, SystemConfig, SystemConfigUpdateError};
+use op_alloy_consensus::TxDeposit;
+
+/// A user deposit, derived from a log emitted by the deposit contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDeposit {
+    /// The index of the deposit log within the L1 block.
+    pub log_index: u64,
+    /// The deposit transaction.
+    pub tx: TxDeposit,
+}
+
+impl UserDeposit {
+    /// Returns the source hash of the deposit.
+    pub const fn source_hash(&self) -> B256 {
+        self.tx.source_hash
+    }
+
+    /// Returns the EIP-2718 encoding of the deposit transaction.
+    pub fn encoded(&self) -> Bytes {
+        self.tx.encoded_2718().into()
+    }
+}
+
+/// The L1-derived transactions of the first L2 block of an epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochDeposits {
+    /// The L1 info of the epoch's L1 origin.
+    pub l1_info: L1BlockInfoTx,
+    /// The L1 info deposit transaction.
+    pub l1_info_tx: Sealed<TxDeposit>,
+    /// The user deposits emitted in the epoch's L1 origin, in log order.
+    pub deposits: Vec<UserDeposit>,
+    /// The system config after applying the updates emitted in the epoch's L1 origin.
+    pub system_config: SystemConfig,
+}
+
+impl EpochDeposits {
+    /// Returns the EIP-2718 encoded transactions that open the epoch's first L2 block: the L1
+    /// info deposit transaction followed by the user deposits.
+    ///
+    /// Network upgrade transactions, which follow the user deposits in the first block of a
+    /// hardfork, are not included.
+    pub fn transactions(&self) -> Vec<Bytes> {
+        let mut txs = Vec::with_capacity(1 + self.deposits.len());
+        txs.push(self.l1_info_tx.encoded_2718().into());
+        txs.extend(self.deposits.iter().map(UserDeposit::encoded));
+        txs
+    }
+}
+
+/// An error deriving the [`EpochDeposits`] of an L1 block.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum EpochDerivationError {
+    /// A deposit log could not be decoded.
+    #[error("Failed to decode deposit: {0}")]
+    Deposit(#[from] DepositError),
+    /// A system config update log could not be applied.
+    #[error("Failed to apply system config update: {0}")]
+    SystemConfigUpdate(#[from] SystemConfigUpdateError),
+    /// The L1 info transaction could not be constructed.
+    #[error("Failed to construct L1 info transaction: {0}")]
+    L1Info(#[from] BlockInfoError),
+}
+
+/// Derives the user deposits from the receipts of an L1 block.
+///
+/// Deposits must be emitted by the deposit contract with the [`DEPOSIT_EVENT_ABI_HASH`] event
+/// signature in a successful transaction. Logs that are not deposits are skipped.
+///
+/// # Arguments
+/// * `block_hash` - The hash of the L1 block, which seeds the deposit source hashes.
+/// * `receipts` - The receipts of the L1 block, in transaction order.
+/// * `deposit_contract` - The address of the deposit contract.
+///
+/// # Returns
+/// * `Ok(Vec<UserDeposit>)` - The user deposits, in log order.
+/// * `Err(DepositError)` - A deposit log could not be decoded.
+pub fn derive_deposits(
+    block_hash: B256,
+    receipts: &[Receipt],
+    deposit_contract: Address,
+) -> Result<Vec<UserDeposit>, DepositError> {
+    let mut global_index = 0;
+    let mut deposits = Vec::new();
+    for receipt in receipts {
+        if Eip658Value::Eip658(false) == receipt.status {
+            continue;
+        }
+        for log in &receipt.logs {
+            let log_index = global_index;
+            global_index += 1;
+            if log.data.topics().first().is_none_or(|t| *t != DEPOSIT_EVENT_ABI_HASH) ||
+                log.address != deposit_contract
+            {
+                continue;
+            }
+            let tx = decode_deposit_tx(block_hash, log_index, log)?;
+            deposits.push(UserDeposit { log_index: log_index as u64, tx });
+        }
+    }
+    Ok(deposits)
+}
+
+/// Derives the L1 info transaction and user deposits of the first L2 block of the epoch with the
+/// given L1 origin.
+///
+/// # Arguments
+/// * `rollup_config` - The rollup config of the L2 chain.
+/// * `l1_config` - The chain config of the L1 chain.
+/// * `system_config` - The system config of the L2 parent block, which is the last block of the
+///   previous epoch.
+/// * `l1_header` - The header of the epoch's L1 origin.
+/// * `receipts` - The receipts of the epoch's L1 origin, in transaction order.
+/// * `l2_block_time` - The timestamp of the epoch's first L2 block.
+///
+/// # Returns
+/// * `Ok(EpochDeposits)` - The L1-derived transactions of the epoch's first L2 block.
+/// * `Err(EpochDerivationError)` - The receipts contain an invalid deposit or system config update
+///   log, or the L1 info transaction could not be constructed.
+pub fn derive_epoch_deposits(
+    rollup_config: &RollupConfig,
+    l1_config: &L1ChainConfig,
+    system_config: &SystemConfig,
+    l1_header: &Header,
+    receipts: &[Receipt],
+    l2_block_time: u64,
+) -> Result<EpochDeposits, EpochDerivationError> {
+    let block_hash = l1_header.hash_slow();
+    let deposits = derive_deposits(block_hash, receipts, rollup_config.deposit_contract_address)?;
+
+    let mut system_config = *system_config;
+    system_config.update_with_receipts(
+        receipts,
+        rollup_config.l1_system_config_address,
+        rollup_config.is_ecotone_active(l1_header.timestamp),
+    )?;
+
+    // The first block of an epoch always has a sequence number of 0.
+    let (l1_info, l1_info_tx) = L1BlockInfoTx::try_new_with_deposit_tx(
+        rollup_config,
+        l1_config,
+        &system_config,
+        0,
+        l1_header,
+        l2_block_time,
+    )?;
+
+    Ok(EpochDeposits { l1_info, l1_info_tx, deposits, system_config })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::{Log, LogData, U64, U256, address};
+    use op_alloy_consensus::UserDepositSource;
+
+    const DEPOSIT_CONTRACT: Address = address!("1111111111111111111111111111111111111111");
+
+    fn deposit_log(address: Address, mint: u128) -> Log {
+        let mut data = vec![0u8; 192];
+        data[24..32].copy_from_slice(&U64::from(32).to_be_bytes::<8>());
+        data[56..64].copy_from_slice(&U64::from(128).to_be_bytes::<8>());
+        data[80..96].copy_from_slice(&mint.to_be_bytes());
+        data[96..128].copy_from_slice(&U256::from(100).to_be_bytes::<32>());
+        data[128..136].copy_from_slice(&21_000_u64.to_be_bytes());
+        Log {
+            address,
+            data: LogData::new_unchecked(
+                vec![DEPOSIT_EVENT_ABI_HASH, B256::ZERO, B256::ZERO, B256::ZERO],
+                data.into(),
+            ),
+        }
+    }
+
+    fn receipt(status: bool, logs: Vec<Log>) -> Receipt {
+        Receipt { status: Eip658Value::Eip658(status), logs, ..Default::default() }
+    }
+
+    #[test]
+    fn test_derive_deposits() {
+        let block_hash = B256::repeat_byte(1);
+        let other = address!("2222222222222222222222222222222222222222");
+        let receipts = vec![
+            receipt(true, vec![deposit_log(other, 1), deposit_log(DEPOSIT_CONTRACT, 2)]),
+            receipt(false, vec![deposit_log(DEPOSIT_CONTRACT, 3)]),
+            receipt(true, vec![deposit_log(DEPOSIT_CONTRACT, 4)]),
+        ];
+
+        let deposits = derive_deposits(block_hash, &receipts, DEPOSIT_CONTRACT).unwrap();
+        assert_eq!(deposits.len(), 2);
+        assert_eq!(deposits[0].log_index, 1);
+        assert_eq!(deposits[0].tx.mint, 2);
+        assert_eq!(deposits[1].log_index, 2);
+        assert_eq!(deposits[1].tx.mint, 4);
+        for deposit in &deposits {
+            let source = UserDepositSource::new(block_hash, deposit.log_index);
+            assert_eq!(deposit.source_hash(), source.source_hash());
+        }
+    }
+
+    #[test]
+    fn test_derive_epoch_deposits() {
+        let rollup_config =
+            RollupConfig { deposit_contract_address: DEPOSIT_CONTRACT, ..Default::default() };
+        let l1_header = Header { number: 10, timestamp: 100, ..Default::default() };
+        let receipts = vec![receipt(true, vec![deposit_log(DEPOSIT_CONTRACT, 7)])];
+
+        let epoch = derive_epoch_deposits(
+            &rollup_config,
+            &L1ChainConfig::default(),
+            &SystemConfig::default(),
+            &l1_header,
+            &receipts,
+            102,
+        )
+        .unwrap();
+
+        assert_eq!(epoch.l1_info.id().number, 10);
+        assert_eq!(epoch.l1_info.id().hash, l1_header.hash_slow());
+        assert_eq!(epoch.l1_info.sequence_number(), 0);
+        assert_eq!(epoch.deposits.len(), 1);
+        assert_eq!(epoch.system_config, SystemConfig::default());
+
+        let txs = epoch.transactions();
+        assert_eq!(txs.len(), 2);
+        assert_eq!(txs[0], Bytes::from(epoch.l1_info_tx.encoded_2718()));
+        assert_eq!(txs[1], epoch.deposits[0].encoded());
+    }
+}
@@ -14,10 +14,10 @@ pub use batch::{
     Batch, BatchDecodingError, BatchEncodingError, BatchReader, BatchTransaction, BatchType,
     BatchValidationProvider, BatchValidity, BatchWithInclusionBlock, DecompressionError,
     MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE, SingleBatch,
-    SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData, SpanBatchEip2930TransactionData,
-    SpanBatchEip7702TransactionData, SpanBatchElement, SpanBatchError,
-    SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix, SpanBatchTransactionData,
-    SpanBatchTransactions, SpanDecodingError,
+    SpanBatch, SpanBatchBits, SpanBatchBuilder, SpanBatchEip1559TransactionData,
+    SpanBatchEip2930TransactionData, SpanBatchEip7702TransactionData, SpanBatchElement,
+    SpanBatchError, SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix,
+    SpanBatchTransactionData, SpanBatchTransactions, SpanDecodingError,
 };
 
 mod brotli;
@@ -52,7 +52,12 @@ pub use channel::{
 mod deposits;
 pub use deposits::{
     DEPOSIT_EVENT_ABI, DEPOSIT_EVENT_ABI_HASH, DEPOSIT_EVENT_VERSION_0, DepositError,
-    decode_deposit,
+    decode_deposit, decode_deposit_tx,
+};
+
+mod epoch;
+pub use epoch::{
+    EpochDeposits, EpochDerivationError, UserDeposit, derive_deposits, derive_epoch_deposits,
 };
 
 mod info;
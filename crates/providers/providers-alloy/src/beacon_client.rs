@@ -2,8 +2,9 @@
 
 #[cfg(feature = "metrics")]
 use crate::Metrics;
-use crate::blobs::BoxedBlobWithIndex;
+use crate::blobs::{BoxedBlobWithIndex, blob_from_cells};
 use alloy_eips::eip4844::IndexedBlobHash;
+use alloy_primitives::Bytes;
 use alloy_rpc_types_beacon::sidecar::{BeaconBlobBundle, GetBlobsResponse};
 use async_trait::async_trait;
 use reqwest::Client;
@@ -21,6 +22,26 @@ const SIDECARS_METHOD_PREFIX_DEPRECATED: &str = "eth/v1/beacon/blob_sidecars";
 /// THe blobs engine api method prefix.
 const BLOBS_METHOD_PREFIX: &str = "eth/v1/beacon/blobs";
 
+/// The data column sidecars engine api method prefix.
+const DATA_COLUMN_SIDECARS_METHOD_PREFIX: &str = "eth/v1/debug/beacon/data_column_sidecars";
+
+/// A data column sidecar, reduced to the fields needed to rebuild blobs from its cells.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReducedDataColumnSidecar {
+    /// The index of the column.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub index: u64,
+    /// The cells of the column, one for each blob in the block.
+    pub column: Vec<Bytes>,
+}
+
+/// An API data column sidecars response.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct APIDataColumnSidecarsResponse {
+    /// The data column sidecars.
+    pub data: Vec<ReducedDataColumnSidecar>,
+}
+
 /// A reduced genesis data.
 #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ReducedGenesisData {
@@ -128,46 +149,104 @@ impl OnlineBeaconClient {
     ) -> Result<Vec<BoxedBlobWithIndex>, reqwest::Error> {
         let blob_indexes = blob_hashes.iter().map(|blob| blob.index).collect::<Vec<_>>();
 
-        Ok(
-            match self
-                .inner
-                .get(format!("{}/{}/{}", self.base, BLOBS_METHOD_PREFIX, slot))
-                .send()
-                .await
-            {
-                Ok(response) if response.status().is_success() => {
-                    let bundle = response.json::<GetBlobsResponse>().await?;
-
-                    bundle
-                        .data
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(index, blob)| {
-                            let index = index as u64;
-                            blob_indexes
-                                .contains(&index)
-                                .then_some(BoxedBlobWithIndex { index, blob: Box::new(blob) })
-                        })
-                        .collect::<Vec<_>>()
-                }
-                // If the blobs endpoint fails, try the deprecated sidecars endpoint. CL Clients
-                // only support the blobs endpoint from Fusaka (Fulu) onwards.
-                _ => self
-                    .inner
-                    .get(format!("{}/{}/{}", self.base, SIDECARS_METHOD_PREFIX_DEPRECATED, slot))
-                    .send()
-                    .await?
-                    .json::<BeaconBlobBundle>()
-                    .await?
-                    .into_iter()
-                    .filter_map(|blob| {
-                        blob_indexes
-                            .contains(&blob.index)
-                            .then_some(BoxedBlobWithIndex { index: blob.index, blob: blob.blob })
+        // CL Clients only support the blobs endpoint from Fusaka (Fulu) onwards.
+        if let Ok(blobs) = self.blobs(slot, &blob_indexes).await {
+            return Ok(blobs);
+        }
+
+        // If the blobs endpoint fails, try the deprecated sidecars endpoint.
+        let sidecars = self.blob_sidecars(slot, &blob_indexes).await;
+        if sidecars.as_ref().is_ok_and(|blobs| blobs.len() == blob_indexes.len()) {
+            return sidecars;
+        }
+
+        // After PeerDAS, blob sidecars are no longer served. Rebuild the blobs from the cells of
+        // the data column sidecars instead, if the node serves enough of them.
+        match self.data_column_blobs(slot, &blob_indexes).await {
+            Ok(blobs) if blobs.len() == blob_indexes.len() => Ok(blobs),
+            _ => sidecars,
+        }
+    }
+
+    /// Fetches the blobs at the given indexes from the blobs endpoint.
+    async fn blobs(
+        &self,
+        slot: u64,
+        blob_indexes: &[u64],
+    ) -> Result<Vec<BoxedBlobWithIndex>, reqwest::Error> {
+        let bundle = self
+            .inner
+            .get(format!("{}/{}/{}", self.base, BLOBS_METHOD_PREFIX, slot))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<GetBlobsResponse>()
+            .await?;
+
+        Ok(bundle
+            .data
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, blob)| {
+                let index = index as u64;
+                blob_indexes
+                    .contains(&index)
+                    .then_some(BoxedBlobWithIndex { index, blob: Box::new(blob) })
+            })
+            .collect())
+    }
+
+    /// Fetches the blobs at the given indexes from the deprecated blob sidecars endpoint.
+    async fn blob_sidecars(
+        &self,
+        slot: u64,
+        blob_indexes: &[u64],
+    ) -> Result<Vec<BoxedBlobWithIndex>, reqwest::Error> {
+        Ok(self
+            .inner
+            .get(format!("{}/{}/{}", self.base, SIDECARS_METHOD_PREFIX_DEPRECATED, slot))
+            .send()
+            .await?
+            .json::<BeaconBlobBundle>()
+            .await?
+            .into_iter()
+            .filter_map(|blob| {
+                blob_indexes
+                    .contains(&blob.index)
+                    .then_some(BoxedBlobWithIndex { index: blob.index, blob: blob.blob })
+            })
+            .collect())
+    }
+
+    /// Rebuilds the blobs at the given indexes from the cells of the data column sidecars served
+    /// by the node. Blobs that cannot be rebuilt from the served columns are omitted.
+    async fn data_column_blobs(
+        &self,
+        slot: u64,
+        blob_indexes: &[u64],
+    ) -> Result<Vec<BoxedBlobWithIndex>, reqwest::Error> {
+        let columns = self
+            .inner
+            .get(format!("{}/{}/{}", self.base, DATA_COLUMN_SIDECARS_METHOD_PREFIX, slot))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<APIDataColumnSidecarsResponse>()
+            .await?
+            .data;
+
+        Ok(blob_indexes
+            .iter()
+            .filter_map(|&index| {
+                let cells = columns
+                    .iter()
+                    .filter_map(|column| {
+                        Some((column.index, column.column.get(index as usize)?.as_ref()))
                     })
-                    .collect::<Vec<_>>(),
-            },
-        )
+                    .collect();
+                Some(BoxedBlobWithIndex { index, blob: blob_from_cells(cells)? })
+            })
+            .collect())
     }
 }
 
@@ -229,3 +308,129 @@ impl BeaconClient for OnlineBeaconClient {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blobs::{BYTES_PER_CELL, CELLS_PER_BLOB};
+    use alloy_eips::eip4844::Blob;
+    use alloy_primitives::{B256, hex};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Serves the JSON body of the first route whose prefix matches the request path on a local
+    /// HTTP stub, or a 404 if none does, returning its base URL.
+    async fn serve_routes(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match routes.iter().find(|(route, _)| path.starts_with(route)) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    fn test_blob(seed: u8) -> Box<Blob> {
+        let mut blob = Box::new(Blob::ZERO);
+        blob.iter_mut().enumerate().for_each(|(i, b)| *b = (i as u8).wrapping_add(seed));
+        blob
+    }
+
+    /// Returns a data column sidecars response holding the first `columns` columns of the given
+    /// blobs.
+    fn data_columns_body(blobs: &[Box<Blob>], columns: usize) -> String {
+        let data = (0..columns)
+            .map(|i| {
+                let cells = blobs
+                    .iter()
+                    .map(|blob| {
+                        let cell = &blob[i * BYTES_PER_CELL..(i + 1) * BYTES_PER_CELL];
+                        format!(r#""0x{}""#, hex::encode(cell))
+                    })
+                    .collect::<Vec<_>>();
+                format!(r#"{{"index":"{i}","column":[{}]}}"#, cells.join(","))
+            })
+            .collect::<Vec<_>>();
+        format!(r#"{{"data":[{}]}}"#, data.join(","))
+    }
+
+    fn blob_hashes(indexes: &[u64]) -> Vec<IndexedBlobHash> {
+        indexes.iter().map(|&index| IndexedBlobHash { index, hash: B256::ZERO }).collect()
+    }
+
+    #[test]
+    fn test_data_column_sidecar_index_decimal_string() {
+        let sidecar: ReducedDataColumnSidecar =
+            serde_json::from_str(r#"{"index":"17","column":[]}"#).unwrap();
+        assert_eq!(sidecar.index, 17);
+        assert_eq!(serde_json::to_string(&sidecar).unwrap(), r#"{"index":"17","column":[]}"#);
+    }
+
+    #[tokio::test]
+    async fn test_filtered_beacon_blobs_from_blobs_endpoint() {
+        let blobs = [test_blob(0), test_blob(1)];
+        let body = format!(
+            r#"{{"execution_optimistic":false,"finalized":true,"data":["0x{}","0x{}"]}}"#,
+            hex::encode(blobs[0].as_slice()),
+            hex::encode(blobs[1].as_slice())
+        );
+        let base = serve_routes(vec![("/eth/v1/beacon/blobs/", body)]).await;
+
+        let client = OnlineBeaconClient::new_http(base);
+        let fetched = client.filtered_beacon_blobs(1, &blob_hashes(&[1])).await.unwrap();
+        assert_eq!(fetched, vec![BoxedBlobWithIndex { index: 1, blob: blobs[1].clone() }]);
+    }
+
+    #[tokio::test]
+    async fn test_filtered_beacon_blobs_from_data_columns() {
+        let blobs = [test_blob(0), test_blob(1)];
+        let base = serve_routes(vec![(
+            "/eth/v1/debug/beacon/data_column_sidecars/",
+            data_columns_body(&blobs, CELLS_PER_BLOB),
+        )])
+        .await;
+
+        // Neither the blobs nor the blob sidecars endpoints are served, so the blobs are rebuilt
+        // from the data columns.
+        let client = OnlineBeaconClient::new_http(base);
+        let fetched = client.filtered_beacon_blobs(1, &blob_hashes(&[0, 1])).await.unwrap();
+        assert_eq!(
+            fetched,
+            vec![
+                BoxedBlobWithIndex { index: 0, blob: blobs[0].clone() },
+                BoxedBlobWithIndex { index: 1, blob: blobs[1].clone() },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filtered_beacon_blobs_too_few_data_columns() {
+        let blobs = [test_blob(0)];
+        let base = serve_routes(vec![(
+            "/eth/v1/debug/beacon/data_column_sidecars/",
+            data_columns_body(&blobs, CELLS_PER_BLOB - 1),
+        )])
+        .await;
+
+        // The blob cannot be rebuilt, so the error of the blob sidecars endpoint is returned.
+        let client = OnlineBeaconClient::new_http(base);
+        assert!(client.filtered_beacon_blobs(1, &blob_hashes(&[0])).await.is_err());
+    }
+}
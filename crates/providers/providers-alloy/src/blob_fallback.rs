@@ -0,0 +1,435 @@
+//! Contains a [BlobProvider] that falls back to a blob archiver and a local blob directory for
+//! blobs that are no longer served by the beacon node.
+
+use crate::{
+    BeaconClient, OnlineBlobProvider,
+    blobs::{BoxedBlobWithIndex, sidecars_from_blobs, validate_blob_sidecars},
+};
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob, BlobTransactionSidecarItem, IndexedBlobHash};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use kona_derive::{BlobProvider, BlobProviderError};
+use kona_protocol::BlockInfo;
+use reqwest::Client;
+use std::{
+    boxed::Box,
+    format, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    string::{String, ToString},
+    vec::Vec,
+};
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+/// The blob sidecars method of the blob archiver API.
+const ARCHIVER_SIDECARS_METHOD_PREFIX: &str = "eth/v1/beacon/blob_sidecars";
+
+/// A blob sidecar served by the blob archiver API, reduced to the fields needed to rebuild the
+/// sidecar.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct ArchivedBlobSidecar {
+    /// The index of the blob in its block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    index: u64,
+    /// The blob data.
+    blob: Box<Blob>,
+}
+
+/// A blob sidecars response of the blob archiver API.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct ArchivedBlobSidecars {
+    /// The blob sidecars.
+    data: Vec<ArchivedBlobSidecar>,
+}
+
+/// A client for a [blob archiver] API, which serves the blob sidecars of every slot since the
+/// Ecotone upgrade, past the retention window of beacon nodes.
+///
+/// [blob archiver]: https://github.com/base-org/blob-archiver
+#[derive(Debug, Clone)]
+pub struct BlobArchiverClient {
+    /// The base URL of the blob archiver API.
+    pub base: String,
+    /// The inner reqwest client.
+    pub inner: Client,
+}
+
+impl BlobArchiverClient {
+    /// Creates a new [BlobArchiverClient] from the provided base URL string.
+    pub fn new_http(mut base: String) -> Self {
+        // If base ends with a slash, remove it
+        if base.ends_with("/") {
+            base.remove(base.len() - 1);
+        }
+        Self { base, inner: Client::builder().build().expect("Failed to create archiver client") }
+    }
+
+    /// Fetches the blobs with the given indexes that were confirmed in the L1 block with the
+    /// given slot. Blob data is not checked for validity.
+    pub async fn filtered_blobs(
+        &self,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, reqwest::Error> {
+        let sidecars = self
+            .inner
+            .get(format!("{}/{}/{}", self.base, ARCHIVER_SIDECARS_METHOD_PREFIX, slot))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ArchivedBlobSidecars>()
+            .await?;
+
+        Ok(sidecars
+            .data
+            .into_iter()
+            .filter(|sidecar| blob_hashes.iter().any(|hash| hash.index == sidecar.index))
+            .map(|sidecar| BoxedBlobWithIndex { index: sidecar.index, blob: sidecar.blob })
+            .collect())
+    }
+}
+
+/// A local directory of blobs, keyed by their versioned hash.
+///
+/// Each blob is stored in its own file, named after the hex encoded versioned hash of the blob
+/// and holding the raw blob bytes. The directory can be populated ahead of time, or filled by a
+/// [FallbackBlobProvider] with the blobs it fetches when write-through is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobDirectory {
+    /// The path of the directory.
+    pub path: PathBuf,
+    /// Whether blobs fetched from remote sources are written to the directory.
+    pub write_through: bool,
+}
+
+impl BlobDirectory {
+    /// Creates a new read-only [BlobDirectory] at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), write_through: false }
+    }
+
+    /// Sets whether blobs fetched from remote sources are written to the directory.
+    pub const fn with_write_through(mut self, write_through: bool) -> Self {
+        self.write_through = write_through;
+        self
+    }
+
+    /// Returns the path of the file holding the blob with the given versioned hash.
+    fn blob_path(&self, blob_hash: &IndexedBlobHash) -> PathBuf {
+        self.path.join(blob_hash.hash.to_string())
+    }
+
+    /// Reads the blobs with the given hashes from the directory. Blob data is not checked for
+    /// validity.
+    pub fn read_blobs(
+        &self,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> io::Result<Vec<BoxedBlobWithIndex>> {
+        blob_hashes
+            .iter()
+            .map(|blob_hash| {
+                let bytes = fs::read(self.blob_path(blob_hash))?;
+                let blob = Blob::try_from(bytes.as_slice()).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("expected {BYTES_PER_BLOB} bytes, got {}", bytes.len()),
+                    )
+                })?;
+                Ok(BoxedBlobWithIndex { index: blob_hash.index, blob: Box::new(blob) })
+            })
+            .collect()
+    }
+
+    /// Writes the given blob sidecars to the directory, keyed by the versioned hash of their
+    /// commitment.
+    pub fn write_sidecars(&self, sidecars: &[BlobTransactionSidecarItem]) -> io::Result<()> {
+        fs::create_dir_all(&self.path)?;
+        for sidecar in sidecars {
+            let path = self.path.join(B256::from(sidecar.to_kzg_versioned_hash()).to_string());
+            write_atomic(&path, sidecar.blob.as_slice())?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a file through a uniquely named temporary file in the same directory, so that readers
+/// never observe a partially written blob and concurrent writers do not race.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp = NamedTempFile::new_in(path.parent().unwrap_or(Path::new(".")))?;
+    tmp.write_all(contents)?;
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// A [BlobProvider] that fetches blobs from the beacon node, falling back to a blob archiver and
+/// then to a local [BlobDirectory] for blobs the beacon node no longer serves.
+///
+/// Blobs from every source are checked against their KZG commitments and versioned hashes, and
+/// the next source is tried if the check fails.
+#[derive(Debug, Clone)]
+pub struct FallbackBlobProvider<B: BeaconClient> {
+    /// The beacon node blob provider.
+    pub primary: OnlineBlobProvider<B>,
+    /// The blob archiver, if any.
+    pub archiver: Option<BlobArchiverClient>,
+    /// The local blob directory, if any.
+    pub directory: Option<BlobDirectory>,
+}
+
+impl<B: BeaconClient> FallbackBlobProvider<B> {
+    /// Creates a new [FallbackBlobProvider] without any fallback source.
+    pub const fn new(primary: OnlineBlobProvider<B>) -> Self {
+        Self { primary, archiver: None, directory: None }
+    }
+
+    /// Sets the blob archiver to fall back to.
+    pub fn with_archiver(mut self, archiver: Option<BlobArchiverClient>) -> Self {
+        self.archiver = archiver;
+        self
+    }
+
+    /// Sets the local blob directory to fall back to.
+    pub fn with_directory(mut self, directory: Option<BlobDirectory>) -> Self {
+        self.directory = directory;
+        self
+    }
+
+    /// Fetches blob sidecars for the given block reference and blob hashes, trying the beacon
+    /// node, the blob archiver and the local blob directory in order. Returns the sidecars of the
+    /// first source that serves valid blobs.
+    pub async fn fetch_filtered_blob_sidecars(
+        &self,
+        block_ref: &BlockInfo,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BlobTransactionSidecarItem>, BlobProviderError> {
+        if blob_hashes.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let mut errors = Vec::new();
+
+        match self
+            .primary
+            .fetch_filtered_blob_sidecars(block_ref, blob_hashes)
+            .await
+            .and_then(|sidecars| validate_blob_sidecars(&sidecars, blob_hashes).map(|_| sidecars))
+        {
+            Ok(sidecars) => return Ok(sidecars),
+            Err(e) => errors.push(format!("beacon: {e}")),
+        }
+
+        if let Some(archiver) = &self.archiver {
+            let slot = OnlineBlobProvider::<B>::slot(
+                self.primary.genesis_time,
+                self.primary.slot_interval,
+                block_ref.timestamp,
+            )?;
+            let result = archiver
+                .filtered_blobs(slot, blob_hashes)
+                .await
+                .map_err(|e| BlobProviderError::Backend(e.to_string()))
+                .and_then(|blobs| Self::validated_sidecars(blobs, blob_hashes));
+            match result {
+                Ok(sidecars) => {
+                    self.write_through(&sidecars);
+                    return Ok(sidecars);
+                }
+                Err(e) => errors.push(format!("archiver: {e}")),
+            }
+        }
+
+        if let Some(directory) = &self.directory {
+            let result = directory
+                .read_blobs(blob_hashes)
+                .map_err(|e| BlobProviderError::Backend(e.to_string()))
+                .and_then(|blobs| Self::validated_sidecars(blobs, blob_hashes));
+            match result {
+                Ok(sidecars) => return Ok(sidecars),
+                Err(e) => errors.push(format!("directory: {e}")),
+            }
+        }
+
+        Err(BlobProviderError::Backend(format!(
+            "Failed to fetch blobs from all sources: [{}]",
+            errors.join(", ")
+        )))
+    }
+
+    /// Rebuilds the sidecars of the given blobs, and validates them against the blob hashes.
+    fn validated_sidecars(
+        blobs: Vec<BoxedBlobWithIndex>,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BlobTransactionSidecarItem>, BlobProviderError> {
+        let sidecars = sidecars_from_blobs(blobs)
+            .map_err(|e| BlobProviderError::Backend(format!("KZG commitment error: {e}")))?;
+        validate_blob_sidecars(&sidecars, blob_hashes)?;
+        Ok(sidecars)
+    }
+
+    /// Writes the given validated sidecars to the local blob directory, if write-through is
+    /// enabled. Failing to write the blobs does not fail the request, as the blobs were fetched.
+    fn write_through(&self, sidecars: &[BlobTransactionSidecarItem]) {
+        if let Some(directory) = &self.directory &&
+            directory.write_through &&
+            let Err(e) = directory.write_sidecars(sidecars)
+        {
+            warn!(
+                target: "blob_provider",
+                error = %e,
+                path = %directory.path.display(),
+                "Failed to write blobs through to the local blob directory"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl<B> BlobProvider for FallbackBlobProvider<B>
+where
+    B: BeaconClient + Send + Sync,
+{
+    type Error = BlobProviderError;
+
+    /// Fetches blobs that were confirmed in the specified L1 block with the given indexed
+    /// hashes, falling back to the blob archiver and the local blob directory. The blobs are
+    /// validated for their index and hashes using the specified [IndexedBlobHash].
+    async fn get_and_validate_blobs(
+        &mut self,
+        block_ref: &BlockInfo,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        let sidecars = self.fetch_filtered_blob_sidecars(block_ref, blob_hashes).await?;
+        Ok(sidecars.into_iter().map(|sidecar| sidecar.blob).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{APIConfigResponse, APIGenesisResponse};
+    use alloy_primitives::hex;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// A beacon client that has pruned all blobs.
+    #[derive(Debug)]
+    struct PrunedBeaconClient;
+
+    #[async_trait]
+    impl BeaconClient for PrunedBeaconClient {
+        type Error = String;
+
+        async fn slot_interval(&self) -> Result<APIConfigResponse, Self::Error> {
+            Ok(APIConfigResponse::new(12))
+        }
+
+        async fn genesis_time(&self) -> Result<APIGenesisResponse, Self::Error> {
+            Ok(APIGenesisResponse::new(0))
+        }
+
+        async fn filtered_beacon_blobs(
+            &self,
+            _: u64,
+            _: &[IndexedBlobHash],
+        ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+            Err("blobs pruned".to_string())
+        }
+    }
+
+    /// Returns a valid blob and its indexed blob hash at index 1.
+    fn test_blob() -> (Box<Blob>, IndexedBlobHash) {
+        let mut blob = Box::new(Blob::ZERO);
+        blob[1] = 0x42;
+        let sidecar =
+            sidecars_from_blobs(vec![BoxedBlobWithIndex { index: 1, blob: blob.clone() }])
+                .unwrap()
+                .remove(0);
+        (blob, IndexedBlobHash { index: 1, hash: B256::from(sidecar.to_kzg_versioned_hash()) })
+    }
+
+    /// Serves the given JSON body to every request on a local HTTP stub, returning its base URL.
+    async fn serve_json(body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    async fn provider() -> FallbackBlobProvider<PrunedBeaconClient> {
+        FallbackBlobProvider::new(OnlineBlobProvider::init(PrunedBeaconClient).await)
+    }
+
+    #[tokio::test]
+    async fn test_fallback_to_archiver() {
+        let (blob, blob_hash) = test_blob();
+        let body = format!(
+            r#"{{"data":[{{"index":"0","blob":"0x{}"}},{{"index":"1","blob":"0x{}"}}]}}"#,
+            hex::encode(Blob::ZERO),
+            hex::encode(blob.as_slice())
+        );
+        let archiver = BlobArchiverClient::new_http(serve_json(body).await);
+
+        let dir = tempfile::tempdir().unwrap();
+        let directory = BlobDirectory::new(dir.path()).with_write_through(true);
+        let mut provider =
+            provider().await.with_archiver(Some(archiver)).with_directory(Some(directory.clone()));
+
+        let blobs = provider
+            .get_and_validate_blobs(
+                &BlockInfo { timestamp: 24, ..Default::default() },
+                &[blob_hash],
+            )
+            .await
+            .unwrap();
+        assert_eq!(blobs, vec![blob]);
+
+        // The archived blob was written through to the directory.
+        assert!(directory.read_blobs(&[blob_hash]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_to_directory() {
+        let (blob, blob_hash) = test_blob();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(blob_hash.hash.to_string()), blob.as_slice()).unwrap();
+
+        let mut provider = provider().await.with_directory(Some(BlobDirectory::new(dir.path())));
+        let blobs =
+            provider.get_and_validate_blobs(&BlockInfo::default(), &[blob_hash]).await.unwrap();
+        assert_eq!(blobs, vec![blob]);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_rejects_invalid_blobs() {
+        let (_, blob_hash) = test_blob();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(blob_hash.hash.to_string()), Blob::ZERO.as_slice()).unwrap();
+
+        let mut provider = provider().await.with_directory(Some(BlobDirectory::new(dir.path())));
+        let err = provider
+            .get_and_validate_blobs(
+                &BlockInfo::default(),
+                &[IndexedBlobHash { index: 1, hash: B256::ZERO }],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("directory"));
+
+        let err =
+            provider.get_and_validate_blobs(&BlockInfo::default(), &[blob_hash]).await.unwrap_err();
+        assert!(err.to_string().contains("KZG commitment mismatch"));
+    }
+}
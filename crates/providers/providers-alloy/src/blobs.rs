@@ -4,7 +4,7 @@ use crate::BeaconClient;
 #[cfg(feature = "metrics")]
 use crate::Metrics;
 use alloy_eips::eip4844::{
-    Blob, BlobTransactionSidecarItem, IndexedBlobHash, env_settings::EnvKzgSettings,
+    BYTES_PER_BLOB, Blob, BlobTransactionSidecarItem, IndexedBlobHash, env_settings::EnvKzgSettings,
 };
 use alloy_primitives::FixedBytes;
 use async_trait::async_trait;
@@ -12,6 +12,13 @@ use kona_derive::{BlobProvider, BlobProviderError};
 use kona_protocol::BlockInfo;
 use std::{boxed::Box, string::ToString, vec::Vec};
 
+/// The number of cells a blob is split into. PeerDAS extends each blob to twice as many cells, the
+/// first half of which hold the blob itself.
+pub(crate) const CELLS_PER_BLOB: usize = 64;
+
+/// The number of bytes in a cell.
+pub(crate) const BYTES_PER_CELL: usize = BYTES_PER_BLOB / CELLS_PER_BLOB;
+
 /// A boxed blob with index.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BoxedBlobWithIndex {
@@ -91,45 +98,6 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
         result
     }
 
-    /// Converts a vector of boxed blobs with index to a vector of blob transaction sidecar items.
-    ///
-    /// Note: for performance reasons, we need to transmute the blobs to the c_kzg::Blob type to
-    /// avoid the overhead of moving the blobs around or reallocating the memory.
-    fn sidecar_from_blobs(
-        blobs: Vec<BoxedBlobWithIndex>,
-    ) -> Result<Vec<BlobTransactionSidecarItem>, c_kzg::Error> {
-        blobs
-            .into_iter()
-            .map(|blob| {
-                let kzg_settings = EnvKzgSettings::Default;
-
-                // SAFETY: all types have the same size and alignment
-                let kzg_blob =
-                    unsafe { Box::from_raw(Box::<Blob>::into_raw(blob.blob) as *mut c_kzg::Blob) };
-
-                let commitment = kzg_settings
-                    .get()
-                    .blob_to_kzg_commitment(&kzg_blob)
-                    .map(|blob| blob.to_bytes())?;
-                let proof = kzg_settings
-                    .get()
-                    .compute_blob_kzg_proof(&kzg_blob, &commitment)
-                    .map(|proof| proof.to_bytes())?;
-
-                // SAFETY: all types have the same size and alignment
-                let alloy_blob =
-                    unsafe { Box::from_raw(Box::<c_kzg::Blob>::into_raw(kzg_blob) as *mut Blob) };
-
-                Ok(BlobTransactionSidecarItem {
-                    index: blob.index,
-                    blob: alloy_blob,
-                    kzg_commitment: FixedBytes::from(*commitment),
-                    kzg_proof: FixedBytes::from(*proof),
-                })
-            })
-            .collect()
-    }
-
     /// Fetches blob sidecars for the given block reference and blob hashes.
     /// Does not validate the blobs. Recomputes the kzg proofs associated with the blobs.
     ///
@@ -150,7 +118,7 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
         // Fetch blobs for the slot using.
         let blobs = self.fetch_filtered_blobs(slot, blob_hashes).await?;
 
-        Self::sidecar_from_blobs(blobs)
+        sidecars_from_blobs(blobs)
             .map_err(|e| BlobProviderError::Backend(format!("KZG commitment error: {e}")))
     }
 }
@@ -174,24 +142,166 @@ where
         let blobs = self.fetch_filtered_blob_sidecars(block_ref, blob_hashes).await?;
 
         // Validate the blob sidecars straight away with the num hashes.
-        let blobs = blobs
-            .into_iter()
-            .enumerate()
-            .map(|(i, sidecar)| {
-                let hash = blob_hashes
-                    .get(i)
-                    .ok_or(BlobProviderError::Backend("Missing blob hash".to_string()))?
-                    .hash
-                    .as_slice();
-
-                if sidecar.to_kzg_versioned_hash() != hash {
-                    return Err(BlobProviderError::Backend("KZG commitment mismatch".to_string()));
-                }
-
-                Ok(sidecar.blob)
+        validate_blob_sidecars(&blobs, blob_hashes)?;
+        Ok(blobs.into_iter().map(|sidecar| sidecar.blob).collect())
+    }
+}
+
+/// Converts a vector of boxed blobs with index to a vector of blob transaction sidecar items.
+///
+/// Note: for performance reasons, we need to transmute the blobs to the c_kzg::Blob type to
+/// avoid the overhead of moving the blobs around or reallocating the memory.
+pub(crate) fn sidecars_from_blobs(
+    blobs: Vec<BoxedBlobWithIndex>,
+) -> Result<Vec<BlobTransactionSidecarItem>, c_kzg::Error> {
+    blobs
+        .into_iter()
+        .map(|blob| {
+            let kzg_settings = EnvKzgSettings::Default;
+
+            // SAFETY: all types have the same size and alignment
+            let kzg_blob =
+                unsafe { Box::from_raw(Box::<Blob>::into_raw(blob.blob) as *mut c_kzg::Blob) };
+
+            let commitment =
+                kzg_settings.get().blob_to_kzg_commitment(&kzg_blob).map(|blob| blob.to_bytes())?;
+            let proof = kzg_settings
+                .get()
+                .compute_blob_kzg_proof(&kzg_blob, &commitment)
+                .map(|proof| proof.to_bytes())?;
+
+            // SAFETY: all types have the same size and alignment
+            let alloy_blob =
+                unsafe { Box::from_raw(Box::<c_kzg::Blob>::into_raw(kzg_blob) as *mut Blob) };
+
+            Ok(BlobTransactionSidecarItem {
+                index: blob.index,
+                blob: alloy_blob,
+                kzg_commitment: FixedBytes::from(*commitment),
+                kzg_proof: FixedBytes::from(*proof),
             })
-            .collect::<Result<Vec<Box<Blob>>, BlobProviderError>>()
-            .map_err(|e| BlobProviderError::Backend(e.to_string()))?;
-        Ok(blobs)
+        })
+        .collect()
+}
+
+/// Rebuilds a blob from the cells of its extended blob, given as `(cell index, cell)` pairs, e.g.
+/// as served in the data column sidecars of a block after PeerDAS.
+///
+/// If the first half of the cells is available, the blob is read from them directly. Otherwise, the
+/// blob is recovered from any half of the cells. Returns `None` if fewer than half of the cells are
+/// available, or if the cells are malformed.
+pub(crate) fn blob_from_cells(mut cells: Vec<(u64, &[u8])>) -> Option<Box<Blob>> {
+    cells.sort_unstable_by_key(|(index, _)| *index);
+    cells.dedup_by_key(|(index, _)| *index);
+    if cells.len() < CELLS_PER_BLOB || cells.iter().any(|(_, cell)| cell.len() != BYTES_PER_CELL) {
+        return None;
+    }
+
+    let mut blob = Box::new(Blob::ZERO);
+    if cells.iter().take(CELLS_PER_BLOB).enumerate().all(|(i, (index, _))| *index == i as u64) {
+        for (chunk, (_, cell)) in blob.chunks_exact_mut(BYTES_PER_CELL).zip(&cells) {
+            chunk.copy_from_slice(cell);
+        }
+        return Some(blob);
+    }
+
+    let (indices, cells): (Vec<u64>, Vec<c_kzg::Cell>) = cells
+        .into_iter()
+        .map(|(index, cell)| c_kzg::Cell::from_bytes(cell).map(|cell| (index, cell)))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?
+        .into_iter()
+        .unzip();
+    let (recovered, _) =
+        EnvKzgSettings::Default.get().recover_cells_and_kzg_proofs(&indices, &cells).ok()?;
+    for (chunk, cell) in blob.chunks_exact_mut(BYTES_PER_CELL).zip(recovered.iter()) {
+        chunk.copy_from_slice(&cell.to_bytes());
+    }
+    Some(blob)
+}
+
+/// Validates blob sidecars against the indexed blob hashes they were fetched for.
+///
+/// Each sidecar must match the blob hash at the same position, and there must be exactly one
+/// sidecar per blob hash.
+pub(crate) fn validate_blob_sidecars(
+    sidecars: &[BlobTransactionSidecarItem],
+    blob_hashes: &[IndexedBlobHash],
+) -> Result<(), BlobProviderError> {
+    if sidecars.len() != blob_hashes.len() {
+        return Err(BlobProviderError::Backend(format!(
+            "Expected {} blobs, got {}",
+            blob_hashes.len(),
+            sidecars.len()
+        )));
+    }
+
+    for (sidecar, blob_hash) in sidecars.iter().zip(blob_hashes) {
+        if sidecar.to_kzg_versioned_hash() != blob_hash.hash.as_slice() {
+            return Err(BlobProviderError::Backend("KZG commitment mismatch".to_string()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_blob() -> Box<Blob> {
+        let mut blob = Box::new(Blob::ZERO);
+        // Keep every field element below the BLS modulus.
+        for (i, element) in blob.chunks_exact_mut(32).enumerate() {
+            element[1..9].copy_from_slice(&(i as u64).to_be_bytes());
+        }
+        blob
+    }
+
+    fn extended_cells(blob: &Blob) -> Vec<[u8; BYTES_PER_CELL]> {
+        // SAFETY: all types have the same size and alignment
+        let kzg_blob = unsafe { &*(blob as *const Blob as *const c_kzg::Blob) };
+        let cells = EnvKzgSettings::Default.get().compute_cells(kzg_blob).unwrap();
+        cells.iter().map(|cell| cell.to_bytes()).collect()
+    }
+
+    #[test]
+    fn test_blob_from_first_half_of_cells() {
+        let blob = test_blob();
+        let cells = blob
+            .chunks_exact(BYTES_PER_CELL)
+            .enumerate()
+            .rev()
+            .map(|(i, cell)| (i as u64, cell))
+            .collect::<Vec<_>>();
+
+        assert_eq!(blob_from_cells(cells).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_blob_from_recovered_cells() {
+        let blob = test_blob();
+        let extended = extended_cells(&blob);
+        let cells = extended
+            .iter()
+            .enumerate()
+            .skip(CELLS_PER_BLOB / 2)
+            .take(CELLS_PER_BLOB)
+            .map(|(i, cell)| (i as u64, cell.as_slice()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(blob_from_cells(cells).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_blob_from_too_few_cells() {
+        let blob = test_blob();
+        let cells = blob
+            .chunks_exact(BYTES_PER_CELL)
+            .enumerate()
+            .skip(1)
+            .map(|(i, cell)| (i as u64, cell))
+            .collect::<Vec<_>>();
+
+        assert!(blob_from_cells(cells).is_none());
     }
 }
@@ -0,0 +1,201 @@
+//! A [BeaconClient] that spreads requests over several beacon nodes.
+
+use crate::{
+    APIConfigResponse, APIGenesisResponse, BeaconClient, OnlineBeaconClient,
+    blobs::BoxedBlobWithIndex, health::EndpointSet,
+};
+use alloy_eips::eip4844::IndexedBlobHash;
+use async_trait::async_trait;
+use std::{boxed::Box, string::String, vec::Vec};
+
+/// The [FallbackBeaconClient] is an implementation of the [BeaconClient] trait that spreads
+/// requests over several beacon nodes.
+///
+/// Each request is sent to the healthiest beacon node first, and fails over to the next one if
+/// the beacon node errors. Blob data is not checked for validity, as the blob provider verifies
+/// blobs against their versioned hashes.
+#[derive(Debug, Clone)]
+pub struct FallbackBeaconClient<B = OnlineBeaconClient> {
+    /// The beacon API clients.
+    endpoints: EndpointSet<B>,
+}
+
+impl<B> FallbackBeaconClient<B> {
+    /// Creates a new [FallbackBeaconClient] with the given beacon clients, in order of
+    /// preference.
+    ///
+    /// ## Panics
+    /// - Panics if `clients` is empty.
+    pub fn new(clients: Vec<B>) -> Self {
+        Self { endpoints: EndpointSet::new(clients) }
+    }
+
+    /// Returns the beacon API clients, along with their health.
+    pub const fn endpoints(&self) -> &EndpointSet<B> {
+        &self.endpoints
+    }
+}
+
+impl FallbackBeaconClient<OnlineBeaconClient> {
+    /// Creates a new [FallbackBeaconClient] from the provided base URL strings, in order of
+    /// preference.
+    ///
+    /// ## Panics
+    /// - Panics if `bases` is empty.
+    pub fn new_http(bases: Vec<String>) -> Self {
+        Self::new(bases.into_iter().map(OnlineBeaconClient::new_http).collect())
+    }
+
+    /// Sets the duration in seconds of an L1 slot on all beacon clients. This can be used to
+    /// override the CL slot duration if the l1-beacon's slot configuration endpoint is not
+    /// available.
+    pub fn with_l1_slot_duration_override(self, l1_slot_duration: u64) -> Self {
+        Self::new(
+            self.endpoints
+                .endpoints()
+                .iter()
+                .cloned()
+                .map(|client| client.with_l1_slot_duration_override(l1_slot_duration))
+                .collect(),
+        )
+    }
+}
+
+/// An error for the [FallbackBeaconClient], holding the last error of each beacon node that was
+/// tried, in the order they were tried.
+#[derive(Debug, thiserror::Error)]
+#[error("All beacon nodes failed: [{}]", .0.join(", "))]
+pub struct FallbackBeaconClientError(pub Vec<String>);
+
+#[async_trait]
+impl<B> BeaconClient for FallbackBeaconClient<B>
+where
+    B: BeaconClient + Send + Sync,
+{
+    type Error = FallbackBeaconClientError;
+
+    async fn slot_interval(&self) -> Result<APIConfigResponse, Self::Error> {
+        let mut errors = Vec::new();
+        for index in self.endpoints.order() {
+            match self.endpoints.get(index).slot_interval().await {
+                Ok(response) => {
+                    self.endpoints.record_success(index);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.endpoints.record_failure(index);
+                    errors.push(e.to_string());
+                }
+            }
+        }
+        Err(FallbackBeaconClientError(errors))
+    }
+
+    async fn genesis_time(&self) -> Result<APIGenesisResponse, Self::Error> {
+        let mut errors = Vec::new();
+        for index in self.endpoints.order() {
+            match self.endpoints.get(index).genesis_time().await {
+                Ok(response) => {
+                    self.endpoints.record_success(index);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.endpoints.record_failure(index);
+                    errors.push(e.to_string());
+                }
+            }
+        }
+        Err(FallbackBeaconClientError(errors))
+    }
+
+    async fn filtered_beacon_blobs(
+        &self,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+        let mut errors = Vec::new();
+        for index in self.endpoints.order() {
+            match self.endpoints.get(index).filtered_beacon_blobs(slot, blob_hashes).await {
+                // A beacon node that pruned the blobs, or missed the slot, serves fewer blobs
+                // than requested. Another beacon node may still have them.
+                Ok(blobs) if blobs.len() == blob_hashes.len() => {
+                    self.endpoints.record_success(index);
+                    return Ok(blobs);
+                }
+                Ok(blobs) => {
+                    errors.push(std::format!(
+                        "expected {} blobs, got {}",
+                        blob_hashes.len(),
+                        blobs.len()
+                    ));
+                }
+                Err(e) => {
+                    self.endpoints.record_failure(index);
+                    errors.push(e.to_string());
+                }
+            }
+        }
+        Err(FallbackBeaconClientError(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct MockBeaconClient {
+        genesis_time: Option<u64>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl BeaconClient for MockBeaconClient {
+        type Error = String;
+
+        async fn slot_interval(&self) -> Result<APIConfigResponse, Self::Error> {
+            Ok(APIConfigResponse::new(12))
+        }
+
+        async fn genesis_time(&self) -> Result<APIGenesisResponse, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.genesis_time.map(APIGenesisResponse::new).ok_or_else(|| "offline".into())
+        }
+
+        async fn filtered_beacon_blobs(
+            &self,
+            _: u64,
+            _: &[IndexedBlobHash],
+        ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_beacon_client_failover() {
+        let client = FallbackBeaconClient::new(vec![
+            MockBeaconClient::default(),
+            MockBeaconClient { genesis_time: Some(10), ..Default::default() },
+        ]);
+
+        assert_eq!(client.genesis_time().await.unwrap(), APIGenesisResponse::new(10));
+        assert_eq!(client.endpoints().health()[0].consecutive_failures, 1);
+
+        // The failed beacon node is backed off, so the next request goes to the healthy one.
+        assert_eq!(client.genesis_time().await.unwrap(), APIGenesisResponse::new(10));
+        assert_eq!(client.endpoints().get(0).calls.load(Ordering::SeqCst), 1);
+        assert_eq!(client.endpoints().get(1).calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_beacon_client_all_failed() {
+        let client = FallbackBeaconClient::new(vec![
+            MockBeaconClient::default(),
+            MockBeaconClient::default(),
+        ]);
+
+        let err = client.genesis_time().await.unwrap_err();
+        assert_eq!(err.0, vec!["offline".to_string(), "offline".to_string()]);
+    }
+}
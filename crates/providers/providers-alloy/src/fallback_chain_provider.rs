@@ -0,0 +1,588 @@
+//! A [ChainProvider] that spreads requests over several L1 execution RPCs.
+
+#[cfg(feature = "metrics")]
+use crate::Metrics;
+use crate::health::EndpointSet;
+use alloy_consensus::{
+    Header, Receipt, ReceiptEnvelope, TxEnvelope,
+    proofs::{calculate_receipt_root, calculate_transaction_root},
+};
+use alloy_eips::BlockId;
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_client::BatchRequest;
+use alloy_rpc_types_eth::{Block, TransactionReceipt};
+use alloy_transport::{RpcError, TransportErrorKind};
+use async_trait::async_trait;
+use kona_derive::{ChainProvider, PipelineError, PipelineErrorKind};
+use kona_protocol::BlockInfo;
+use lru::LruCache;
+use std::{boxed::Box, num::NonZeroUsize, vec::Vec};
+
+/// The [FallbackChainProvider] is an implementation of the [ChainProvider] trait that spreads
+/// requests over several L1 execution RPCs.
+///
+/// Each request is sent to the healthiest endpoint first, and fails over to the next one if the
+/// endpoint errors or serves invalid data. Headers and receipts of a block are fetched together in
+/// a single JSON-RPC batch.
+///
+/// If the RPCs are not trusted, headers fetched by hash are verified against the hash, and
+/// receipts and transactions are verified against the receipts and transactions roots of their
+/// header. A block fetched by number is accepted from a single endpoint if its parent is a block
+/// that was already verified, and is otherwise cross-checked against a second endpoint.
+#[derive(Debug, Clone)]
+pub struct FallbackChainProvider {
+    /// The L1 execution RPC endpoints.
+    endpoints: EndpointSet<RootProvider>,
+    /// Whether to trust the RPCs without verification.
+    pub trust_rpc: bool,
+    /// `header_by_hash` LRU cache.
+    header_by_hash_cache: LruCache<B256, Header>,
+    /// `receipts_by_hash_cache` LRU cache.
+    receipts_by_hash_cache: LruCache<B256, Vec<Receipt>>,
+    /// `block_info_and_transactions_by_hash` LRU cache.
+    block_info_and_transactions_by_hash_cache: LruCache<B256, (BlockInfo, Vec<TxEnvelope>)>,
+    /// The hashes of the blocks accepted by `block_info_by_number`, which children fetched by
+    /// number may link to.
+    linked_hashes_cache: LruCache<B256, ()>,
+}
+
+impl FallbackChainProvider {
+    /// Creates a new [FallbackChainProvider] with the given alloy providers, in order of
+    /// preference.
+    ///
+    /// ## Panics
+    /// - Panics if `providers` is empty.
+    /// - Panics if `cache_size` is zero.
+    pub fn new(providers: Vec<RootProvider>, cache_size: usize, trust_rpc: bool) -> Self {
+        Self {
+            endpoints: EndpointSet::new(providers),
+            trust_rpc,
+            header_by_hash_cache: LruCache::new(NonZeroUsize::new(cache_size).unwrap()),
+            receipts_by_hash_cache: LruCache::new(NonZeroUsize::new(cache_size).unwrap()),
+            block_info_and_transactions_by_hash_cache: LruCache::new(
+                NonZeroUsize::new(cache_size).unwrap(),
+            ),
+            linked_hashes_cache: LruCache::new(NonZeroUsize::new(cache_size).unwrap()),
+        }
+    }
+
+    /// Creates a new [FallbackChainProvider] from the provided [reqwest::Url]s, in order of
+    /// preference.
+    ///
+    /// ## Panics
+    /// - Panics if `urls` is empty.
+    /// - Panics if `cache_size` is zero.
+    pub fn new_http(urls: Vec<reqwest::Url>, cache_size: usize, trust_rpc: bool) -> Self {
+        Self::new(urls.into_iter().map(RootProvider::new_http).collect(), cache_size, trust_rpc)
+    }
+
+    /// Returns the L1 execution RPC endpoints, along with their health.
+    pub const fn endpoints(&self) -> &EndpointSet<RootProvider> {
+        &self.endpoints
+    }
+
+    /// Returns `Ok(())` if the header hashes to the expected hash, or if the RPCs are trusted.
+    fn verify_header_hash(
+        &self,
+        header: &Header,
+        expected_hash: B256,
+    ) -> Result<(), FallbackChainProviderError> {
+        if self.trust_rpc {
+            return Ok(());
+        }
+
+        let actual_hash = header.hash_slow();
+        if actual_hash != expected_hash {
+            return Err(FallbackChainProviderError::HeaderHashMismatch {
+                expected: expected_hash,
+                actual: actual_hash,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns `Ok(())` if the receipts match the receipts root of the header, or if the RPCs are
+    /// trusted.
+    fn verify_receipts_root(
+        &self,
+        header: &Header,
+        receipts: &[ReceiptEnvelope],
+    ) -> Result<(), FallbackChainProviderError> {
+        if self.trust_rpc {
+            return Ok(());
+        }
+
+        let actual_root = calculate_receipt_root(receipts);
+        if actual_root != header.receipts_root {
+            return Err(FallbackChainProviderError::ReceiptsRootMismatch {
+                expected: header.receipts_root,
+                actual: actual_root,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns `Ok(())` if the transactions match the transactions root of the header, or if the
+    /// RPCs are trusted.
+    fn verify_transactions_root(
+        &self,
+        header: &Header,
+        transactions: &[TxEnvelope],
+    ) -> Result<(), FallbackChainProviderError> {
+        if self.trust_rpc {
+            return Ok(());
+        }
+
+        let actual_root = calculate_transaction_root(transactions);
+        if actual_root != header.transactions_root {
+            return Err(FallbackChainProviderError::TransactionsRootMismatch {
+                expected: header.transactions_root,
+                actual: actual_root,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the parent of the block is a block that was already verified, either by
+    /// its hash or by linkage to its own parent.
+    fn is_linked(&self, block_info: &BlockInfo) -> bool {
+        let parent = &block_info.parent_hash;
+        self.linked_hashes_cache.contains(parent) ||
+            self.header_by_hash_cache.contains(parent) ||
+            self.block_info_and_transactions_by_hash_cache.contains(parent)
+    }
+
+    /// Fetches the header and receipts of the block with the given hash from the given provider,
+    /// in a single JSON-RPC batch.
+    async fn fetch_header_and_receipts(
+        &self,
+        provider: &RootProvider,
+        hash: B256,
+    ) -> Result<(Header, Vec<Receipt>), FallbackChainProviderError> {
+        let mut batch = BatchRequest::new(provider.client());
+        let block = batch.add_call::<_, Option<Block>>("eth_getBlockByHash", &(hash, false))?;
+        let receipts = batch
+            .add_call::<_, Option<Vec<TransactionReceipt>>>("eth_getBlockReceipts", &(hash,))?;
+        batch.send().await?;
+
+        let header = block
+            .await?
+            .ok_or(FallbackChainProviderError::BlockNotFound(hash.into()))?
+            .header
+            .into_consensus();
+        let receipts = receipts
+            .await?
+            .ok_or(FallbackChainProviderError::BlockNotFound(hash.into()))?
+            .into_iter()
+            .map(|r| r.inner.into_primitives_receipt())
+            .collect::<Vec<_>>();
+
+        self.verify_header_hash(&header, hash)?;
+        self.verify_receipts_root(&header, &receipts)?;
+
+        let receipts = receipts
+            .iter()
+            .map(|r| r.as_receipt().cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(FallbackChainProviderError::ReceiptsConversion(hash))?;
+        Ok((header, receipts))
+    }
+
+    /// Fetches the hash of the block with the given number from the given provider.
+    async fn fetch_block_info_by_number(
+        provider: &RootProvider,
+        number: u64,
+    ) -> Result<BlockInfo, FallbackChainProviderError> {
+        let header = provider
+            .get_block_by_number(number.into())
+            .await?
+            .ok_or(FallbackChainProviderError::BlockNotFound(number.into()))?
+            .header
+            .into_consensus();
+        Ok(BlockInfo {
+            hash: header.hash_slow(),
+            number,
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+        })
+    }
+
+    /// Records the outcome of a request to the endpoint at the given index.
+    ///
+    /// Missing blocks are not held against the endpoint, since it may simply lag behind the
+    /// others.
+    fn record<T>(&self, index: usize, result: &Result<T, FallbackChainProviderError>) {
+        match result {
+            Ok(_) => self.endpoints.record_success(index),
+            Err(FallbackChainProviderError::BlockNotFound(_)) => {}
+            Err(_) => self.endpoints.record_failure(index),
+        }
+    }
+}
+
+/// An error for the [FallbackChainProvider].
+#[derive(Debug, thiserror::Error)]
+pub enum FallbackChainProviderError {
+    /// Transport error
+    #[error(transparent)]
+    Transport(#[from] RpcError<TransportErrorKind>),
+    /// Block not found.
+    #[error("Block not found: {0}")]
+    BlockNotFound(BlockId),
+    /// Failed to convert RPC receipts into consensus receipts.
+    #[error("Failed to convert RPC receipts into consensus receipts: {0}")]
+    ReceiptsConversion(B256),
+    /// The header served by an endpoint does not hash to the requested hash.
+    #[error("Header hash mismatch: expected {expected}, got {actual}")]
+    HeaderHashMismatch {
+        /// The requested hash.
+        expected: B256,
+        /// The hash of the served header.
+        actual: B256,
+    },
+    /// The receipts served by an endpoint do not match the receipts root of their header.
+    #[error("Receipts root mismatch: expected {expected}, got {actual}")]
+    ReceiptsRootMismatch {
+        /// The receipts root of the header.
+        expected: B256,
+        /// The receipts root of the served receipts.
+        actual: B256,
+    },
+    /// The transactions served by an endpoint do not match the transactions root of their header.
+    #[error("Transactions root mismatch: expected {expected}, got {actual}")]
+    TransactionsRootMismatch {
+        /// The transactions root of the header.
+        expected: B256,
+        /// The transactions root of the served transactions.
+        actual: B256,
+    },
+    /// A block fetched by number does not link to a verified block, and no second endpoint
+    /// confirmed it.
+    #[error("Block {0} could not be verified against a second L1 RPC or a known parent")]
+    Unverified(u64),
+    /// Two endpoints served different blocks at the same height.
+    #[error("L1 RPCs disagree on the hash of block {number}: {first} != {second}")]
+    CrossCheckMismatch {
+        /// The block number.
+        number: u64,
+        /// The hash served by the first endpoint.
+        first: B256,
+        /// The hash served by the second endpoint.
+        second: B256,
+    },
+}
+
+impl From<FallbackChainProviderError> for PipelineErrorKind {
+    fn from(e: FallbackChainProviderError) -> Self {
+        Self::Temporary(PipelineError::Provider(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl ChainProvider for FallbackChainProvider {
+    type Error = FallbackChainProviderError;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        if let Some(header) = self.header_by_hash_cache.get(&hash) {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_HITS, "cache" => "header_by_hash");
+            return Ok(header.clone());
+        }
+
+        kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "header_by_hash");
+
+        let mut last_error = FallbackChainProviderError::BlockNotFound(hash.into());
+        for index in self.endpoints.order() {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "header_by_hash");
+
+            let result = async {
+                let header = self
+                    .endpoints
+                    .get(index)
+                    .get_block_by_hash(hash)
+                    .await?
+                    .ok_or(FallbackChainProviderError::BlockNotFound(hash.into()))?
+                    .header
+                    .into_consensus();
+                self.verify_header_hash(&header, hash)?;
+                Ok::<_, FallbackChainProviderError>(header)
+            }
+            .await;
+            self.record(index, &result);
+
+            match result {
+                Ok(header) => {
+                    self.header_by_hash_cache.put(hash, header.clone());
+                    kona_macros::inc!(gauge, Metrics::CACHE_ENTRIES, "cache" => "header_by_hash");
+                    return Ok(header);
+                }
+                Err(e) => {
+                    kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "header_by_hash");
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        let mut last_error = FallbackChainProviderError::BlockNotFound(number.into());
+        let mut candidate: Option<BlockInfo> = None;
+        let mut accepted = None;
+        for index in self.endpoints.order() {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "block_by_number");
+
+            let mut result =
+                Self::fetch_block_info_by_number(self.endpoints.get(index), number).await;
+            if let (Ok(block_info), Some(first)) = (&result, &candidate) &&
+                block_info.hash != first.hash
+            {
+                result = Err(FallbackChainProviderError::CrossCheckMismatch {
+                    number,
+                    first: first.hash,
+                    second: block_info.hash,
+                });
+            }
+
+            match result {
+                Ok(block_info) => {
+                    self.endpoints.record_success(index);
+                    // Blocks fetched by number cannot be verified against their hash, so an
+                    // untrusted answer is only accepted if it links to a verified parent, or
+                    // once a second endpoint confirms it.
+                    if self.trust_rpc ||
+                        self.endpoints.endpoints().len() == 1 ||
+                        candidate.is_some() ||
+                        self.is_linked(&block_info)
+                    {
+                        accepted = Some(block_info);
+                        break;
+                    }
+                    last_error = FallbackChainProviderError::Unverified(number);
+                    candidate = Some(block_info);
+                }
+                Err(e) => {
+                    kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "block_by_number");
+                    // A disagreement between endpoints at the tip may be a reorg rather than a
+                    // faulty endpoint, so it is reported without penalizing either of them.
+                    if matches!(e, FallbackChainProviderError::CrossCheckMismatch { .. }) {
+                        return Err(e);
+                    }
+                    if !matches!(e, FallbackChainProviderError::BlockNotFound(_)) {
+                        self.endpoints.record_failure(index);
+                    }
+                    if candidate.is_none() {
+                        last_error = e;
+                    }
+                }
+            }
+        }
+
+        let block_info = accepted.ok_or(last_error)?;
+        self.linked_hashes_cache.put(block_info.hash, ());
+        Ok(block_info)
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        if let Some(receipts) = self.receipts_by_hash_cache.get(&hash) {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_HITS, "cache" => "receipts_by_hash");
+            return Ok(receipts.clone());
+        }
+
+        kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "receipts_by_hash");
+
+        let mut last_error = FallbackChainProviderError::BlockNotFound(hash.into());
+        for index in self.endpoints.order() {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "receipts_by_hash");
+
+            let result = self.fetch_header_and_receipts(self.endpoints.get(index), hash).await;
+            self.record(index, &result);
+
+            match result {
+                Ok((header, receipts)) => {
+                    self.header_by_hash_cache.put(hash, header);
+                    self.receipts_by_hash_cache.put(hash, receipts.clone());
+                    kona_macros::inc!(gauge, Metrics::CACHE_ENTRIES, "cache" => "receipts_by_hash");
+                    return Ok(receipts);
+                }
+                Err(e) => {
+                    kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "receipts_by_hash");
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        if let Some(block_info_and_txs) = self.block_info_and_transactions_by_hash_cache.get(&hash)
+        {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_HITS, "cache" => "block_info_and_tx");
+            return Ok(block_info_and_txs.clone());
+        }
+
+        kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "block_info_and_tx");
+
+        let mut last_error = FallbackChainProviderError::BlockNotFound(hash.into());
+        for index in self.endpoints.order() {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "block_by_hash");
+
+            let result = async {
+                let block = self
+                    .endpoints
+                    .get(index)
+                    .get_block_by_hash(hash)
+                    .full()
+                    .await?
+                    .ok_or(FallbackChainProviderError::BlockNotFound(hash.into()))?
+                    .into_consensus()
+                    .map_transactions(|t| t.inner.into_inner());
+                self.verify_header_hash(&block.header, hash)?;
+                self.verify_transactions_root(&block.header, &block.body.transactions)?;
+                Ok::<_, FallbackChainProviderError>(block)
+            }
+            .await;
+            self.record(index, &result);
+
+            match result {
+                Ok(block) => {
+                    let block_info = BlockInfo {
+                        hash,
+                        number: block.header.number,
+                        parent_hash: block.header.parent_hash,
+                        timestamp: block.header.timestamp,
+                    };
+                    self.block_info_and_transactions_by_hash_cache
+                        .put(hash, (block_info, block.body.transactions.clone()));
+                    kona_macros::inc!(gauge, Metrics::CACHE_ENTRIES, "cache" => "block_info_and_tx");
+                    return Ok((block_info, block.body.transactions));
+                }
+                Err(e) => {
+                    kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "block_by_hash");
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{SignableTransaction, TxLegacy, transaction::Recovered};
+    use alloy_primitives::{Address, Signature};
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::{BlockTransactions, Transaction};
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    fn provider(asserters: &[&Asserter]) -> FallbackChainProvider {
+        let providers = asserters
+            .iter()
+            .map(|asserter| {
+                RootProvider::new(RpcClient::new(MockTransport::new((*asserter).clone()), false))
+            })
+            .collect();
+        FallbackChainProvider::new(providers, 16, false)
+    }
+
+    fn header(number: u64, parent_hash: B256) -> Header {
+        Header { number, parent_hash, timestamp: number * 12, ..Default::default() }
+    }
+
+    fn rpc_block(header: Header, transactions: Vec<TxEnvelope>) -> Block {
+        let transactions = transactions
+            .into_iter()
+            .map(|tx| Transaction {
+                inner: Recovered::new_unchecked(tx, Address::ZERO),
+                block_hash: None,
+                block_number: None,
+                effective_gas_price: None,
+                transaction_index: None,
+            })
+            .collect();
+        Block {
+            header: alloy_rpc_types_eth::Header {
+                hash: header.hash_slow(),
+                inner: header,
+                ..Default::default()
+            },
+            transactions: BlockTransactions::Full(transactions),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_info_by_number_linked_to_verified_parent() {
+        let (first, second) = (Asserter::new(), Asserter::new());
+        let mut provider = provider(&[&first, &second]);
+        let parent = header(9, B256::ZERO);
+        let child = header(10, parent.hash_slow());
+
+        first.push_success(&rpc_block(parent.clone(), vec![]));
+        provider.header_by_hash(parent.hash_slow()).await.unwrap();
+
+        // The second endpoint is not needed to accept a child of a verified block.
+        first.push_success(&rpc_block(child.clone(), vec![]));
+        let block_info = provider.block_info_by_number(10).await.unwrap();
+        assert_eq!(block_info.hash, child.hash_slow());
+    }
+
+    #[tokio::test]
+    async fn test_block_info_by_number_unlinked_needs_second_endpoint() {
+        let (first, second) = (Asserter::new(), Asserter::new());
+        let mut provider = provider(&[&first, &second]);
+        let block = header(10, B256::repeat_byte(1));
+
+        first.push_success(&rpc_block(block.clone(), vec![]));
+        second.push_failure_msg("unavailable");
+        let err = provider.block_info_by_number(10).await.unwrap_err();
+        assert!(matches!(err, FallbackChainProviderError::Unverified(10)));
+
+        first.push_success(&rpc_block(block.clone(), vec![]));
+        second.push_success(&rpc_block(block.clone(), vec![]));
+        let block_info = provider.block_info_by_number(10).await.unwrap();
+        assert_eq!(block_info.hash, block.hash_slow());
+
+        // Once accepted, the block verifies its children on its own.
+        let child = header(11, block.hash_slow());
+        first.push_success(&rpc_block(child.clone(), vec![]));
+        let block_info = provider.block_info_by_number(11).await.unwrap();
+        assert_eq!(block_info.hash, child.hash_slow());
+    }
+
+    #[tokio::test]
+    async fn test_block_info_by_number_cross_check_mismatch() {
+        let (first, second) = (Asserter::new(), Asserter::new());
+        let mut provider = provider(&[&first, &second]);
+
+        first.push_success(&rpc_block(header(10, B256::repeat_byte(1)), vec![]));
+        second.push_success(&rpc_block(header(10, B256::repeat_byte(2)), vec![]));
+        let err = provider.block_info_by_number(10).await.unwrap_err();
+        assert!(matches!(err, FallbackChainProviderError::CrossCheckMismatch { number: 10, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_block_info_and_transactions_by_hash_verifies_transactions_root() {
+        let (first, second) = (Asserter::new(), Asserter::new());
+        let mut provider = provider(&[&first, &second]);
+        let tx: TxEnvelope = TxLegacy { nonce: 1, gas_limit: 21_000, ..Default::default() }
+            .into_signed(Signature::test_signature())
+            .into();
+        let block = Header {
+            transactions_root: calculate_transaction_root(core::slice::from_ref(&tx)),
+            ..header(10, B256::ZERO)
+        };
+
+        // The first endpoint omits the transaction, so the provider fails over to the second.
+        first.push_success(&rpc_block(block.clone(), vec![]));
+        second.push_success(&rpc_block(block.clone(), vec![tx.clone()]));
+        let (block_info, transactions) =
+            provider.block_info_and_transactions_by_hash(block.hash_slow()).await.unwrap();
+        assert_eq!(block_info.number, 10);
+        assert_eq!(transactions, vec![tx]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Health scoring for sets of redundant endpoints serving the same data.
+
+use std::{
+    cmp::Reverse,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+/// The health of an endpoint, scored from the outcome of its recent requests.
+///
+/// Successful requests slowly raise the score, while failed requests sharply lower it and put the
+/// endpoint in an exponential backoff, during which it is only used if no other endpoint is
+/// available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// The score of the endpoint, between `0` and [EndpointHealth::MAX_SCORE].
+    pub score: u32,
+    /// The number of requests that failed in a row.
+    pub consecutive_failures: u32,
+    /// The instant until which the endpoint is backed off, if any.
+    pub backoff_until: Option<Instant>,
+}
+
+impl Default for EndpointHealth {
+    fn default() -> Self {
+        Self { score: Self::MAX_SCORE, consecutive_failures: 0, backoff_until: None }
+    }
+}
+
+impl EndpointHealth {
+    /// The maximum score of an endpoint.
+    pub const MAX_SCORE: u32 = 100;
+
+    /// The score gained by an endpoint for a successful request.
+    const SUCCESS_REWARD: u32 = 1;
+
+    /// The score lost by an endpoint for a failed request.
+    const FAILURE_PENALTY: u32 = 20;
+
+    /// The backoff applied after the first failed request, doubled for each consecutive failure.
+    const BASE_BACKOFF: Duration = Duration::from_millis(500);
+
+    /// The maximum backoff applied to an endpoint.
+    const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+    /// Records a successful request.
+    pub fn record_success(&mut self) {
+        self.score = (self.score + Self::SUCCESS_REWARD).min(Self::MAX_SCORE);
+        self.consecutive_failures = 0;
+        self.backoff_until = None;
+    }
+
+    /// Records a failed request at the given instant.
+    pub fn record_failure(&mut self, now: Instant) {
+        self.score = self.score.saturating_sub(Self::FAILURE_PENALTY);
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        let backoff = Self::BASE_BACKOFF
+            .saturating_mul(1 << (self.consecutive_failures - 1).min(16))
+            .min(Self::MAX_BACKOFF);
+        self.backoff_until = Some(now + backoff);
+    }
+
+    /// Returns `true` if the endpoint is not backed off at the given instant.
+    pub fn is_available(&self, now: Instant) -> bool {
+        self.backoff_until.is_none_or(|until| until <= now)
+    }
+}
+
+/// A set of redundant endpoints serving the same data, along with their [EndpointHealth].
+///
+/// Clones of an [EndpointSet] share the health of their endpoints.
+#[derive(Debug, Clone)]
+pub struct EndpointSet<T> {
+    /// The endpoints.
+    endpoints: Vec<T>,
+    /// The health of each endpoint, by index.
+    health: Arc<Mutex<Vec<EndpointHealth>>>,
+}
+
+impl<T> EndpointSet<T> {
+    /// Creates a new [EndpointSet] with the given endpoints, all starting out healthy.
+    ///
+    /// ## Panics
+    /// - Panics if `endpoints` is empty.
+    pub fn new(endpoints: Vec<T>) -> Self {
+        assert!(!endpoints.is_empty(), "endpoint set must not be empty");
+        let health = Arc::new(Mutex::new(vec![EndpointHealth::default(); endpoints.len()]));
+        Self { endpoints, health }
+    }
+
+    /// Returns the endpoints, in the order they were provided.
+    pub fn endpoints(&self) -> &[T] {
+        &self.endpoints
+    }
+
+    /// Returns the endpoint at the given index.
+    pub fn get(&self, index: usize) -> &T {
+        &self.endpoints[index]
+    }
+
+    /// Returns a snapshot of the health of each endpoint, by index.
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        self.health.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Returns the indices of the endpoints in the order they should be tried.
+    ///
+    /// Available endpoints come first, from the highest to the lowest score, followed by the
+    /// backed off endpoints, from the earliest to the latest end of their backoff. Ties are
+    /// broken by the order the endpoints were provided in.
+    pub fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut order = (0..self.endpoints.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| {
+            let health = &health[index];
+            let available = health.is_available(now);
+            (!available, health.backoff_until.filter(|_| !available), Reverse(health.score))
+        });
+        order
+    }
+
+    /// Records a successful request to the endpoint at the given index.
+    pub fn record_success(&self, index: usize) {
+        self.health.lock().unwrap_or_else(PoisonError::into_inner)[index].record_success();
+    }
+
+    /// Records a failed request to the endpoint at the given index.
+    pub fn record_failure(&self, index: usize) {
+        self.health.lock().unwrap_or_else(PoisonError::into_inner)[index]
+            .record_failure(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_health_backoff() {
+        let now = Instant::now();
+        let mut health = EndpointHealth::default();
+
+        health.record_failure(now);
+        assert_eq!(health.score, EndpointHealth::MAX_SCORE - EndpointHealth::FAILURE_PENALTY);
+        assert!(!health.is_available(now));
+        assert!(health.is_available(now + EndpointHealth::BASE_BACKOFF));
+
+        health.record_failure(now);
+        assert!(!health.is_available(now + EndpointHealth::BASE_BACKOFF));
+        assert!(health.is_available(now + EndpointHealth::BASE_BACKOFF * 2));
+
+        for _ in 0..64 {
+            health.record_failure(now);
+        }
+        assert_eq!(health.score, 0);
+        assert!(health.is_available(now + EndpointHealth::MAX_BACKOFF));
+
+        health.record_success();
+        assert_eq!(health.score, EndpointHealth::SUCCESS_REWARD);
+        assert_eq!(health.consecutive_failures, 0);
+        assert!(health.is_available(now));
+    }
+
+    #[test]
+    fn test_endpoint_set_order() {
+        let set = EndpointSet::new(vec!["a", "b", "c"]);
+        assert_eq!(set.order(), vec![0, 1, 2]);
+
+        // A backed off endpoint is tried last.
+        set.record_failure(0);
+        assert_eq!(set.order(), vec![1, 2, 0]);
+
+        // Among available endpoints, the highest score is tried first.
+        set.record_failure(1);
+        set.health.lock().unwrap()[1].backoff_until = None;
+        assert_eq!(set.order(), vec![2, 1, 0]);
+
+        // Clones share the health of their endpoints.
+        let clone = set.clone();
+        clone.record_success(0);
+        assert_eq!(set.order(), vec![2, 0, 1]);
+        assert_eq!(set.health()[0].consecutive_failures, 0);
+    }
+}
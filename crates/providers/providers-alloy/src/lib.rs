@@ -11,18 +11,33 @@ pub use metrics::Metrics;
 
 mod beacon_client;
 pub use beacon_client::{
-    APIConfigResponse, APIGenesisResponse, BeaconClient, OnlineBeaconClient, ReducedConfigData,
-    ReducedGenesisData,
+    APIConfigResponse, APIDataColumnSidecarsResponse, APIGenesisResponse, BeaconClient,
+    OnlineBeaconClient, ReducedConfigData, ReducedDataColumnSidecar, ReducedGenesisData,
 };
 
 mod blobs;
 pub use blobs::{BoxedBlobWithIndex, OnlineBlobProvider};
 
+mod blob_fallback;
+pub use blob_fallback::{BlobArchiverClient, BlobDirectory, FallbackBlobProvider};
+
+mod health;
+pub use health::{EndpointHealth, EndpointSet};
+
+mod fallback_beacon_client;
+pub use fallback_beacon_client::{FallbackBeaconClient, FallbackBeaconClientError};
+
 mod chain_provider;
 pub use chain_provider::{AlloyChainProvider, AlloyChainProviderError};
 
+mod fallback_chain_provider;
+pub use fallback_chain_provider::{FallbackChainProvider, FallbackChainProviderError};
+
 mod l2_chain_provider;
 pub use l2_chain_provider::{AlloyL2ChainProvider, AlloyL2ChainProviderError};
 
+mod system_config;
+pub use system_config::{AlloySystemConfigProvider, AlloySystemConfigProviderError};
+
 mod pipeline;
 pub use pipeline::OnlinePipeline;
@@ -1,6 +1,8 @@
 //! Contains an online derivation pipeline.
 
-use crate::{AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use crate::{
+    AlloyL2ChainProvider, FallbackBeaconClient, FallbackBlobProvider, FallbackChainProvider,
+};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
@@ -17,7 +19,7 @@ use std::sync::Arc;
 type OnlinePolledDerivationPipeline = DerivationPipeline<
     PolledAttributesQueueStage<
         OnlineDataProvider,
-        AlloyChainProvider,
+        FallbackChainProvider,
         AlloyL2ChainProvider,
         OnlineAttributesBuilder,
     >,
@@ -28,7 +30,7 @@ type OnlinePolledDerivationPipeline = DerivationPipeline<
 type OnlineManagedDerivationPipeline = DerivationPipeline<
     IndexedAttributesQueueStage<
         OnlineDataProvider,
-        AlloyChainProvider,
+        FallbackChainProvider,
         AlloyL2ChainProvider,
         OnlineAttributesBuilder,
     >,
@@ -37,11 +39,12 @@ type OnlineManagedDerivationPipeline = DerivationPipeline<
 
 /// An RPC-backed Ethereum data source.
 type OnlineDataProvider =
-    EthereumDataSource<AlloyChainProvider, OnlineBlobProvider<OnlineBeaconClient>>;
+    EthereumDataSource<FallbackChainProvider, FallbackBlobProvider<FallbackBeaconClient>>;
 
 /// An RPC-backed payload attributes builder for the `AttributesQueue` stage of the derivation
 /// pipeline.
-type OnlineAttributesBuilder = StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>;
+type OnlineAttributesBuilder =
+    StatefulAttributesBuilder<FallbackChainProvider, AlloyL2ChainProvider>;
 
 /// An online derivation pipeline.
 #[derive(Debug)]
@@ -59,8 +62,8 @@ impl OnlinePipeline {
         l1_cfg: Arc<L1ChainConfig>,
         l2_safe_head: L2BlockInfo,
         l1_origin: BlockInfo,
-        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
-        chain_provider: AlloyChainProvider,
+        blob_provider: FallbackBlobProvider<FallbackBeaconClient>,
+        chain_provider: FallbackChainProvider,
         mut l2_chain_provider: AlloyL2ChainProvider,
     ) -> PipelineResult<Self> {
         let mut pipeline = Self::new_polled(
@@ -100,8 +103,8 @@ impl OnlinePipeline {
     pub fn new_polled(
         cfg: Arc<RollupConfig>,
         l1_cfg: Arc<L1ChainConfig>,
-        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
-        chain_provider: AlloyChainProvider,
+        blob_provider: FallbackBlobProvider<FallbackBeaconClient>,
+        chain_provider: FallbackChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
     ) -> Self {
         let attributes = StatefulAttributesBuilder::new(
@@ -134,8 +137,8 @@ impl OnlinePipeline {
     pub fn new_indexed(
         cfg: Arc<RollupConfig>,
         l1_cfg: Arc<L1ChainConfig>,
-        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
-        chain_provider: AlloyChainProvider,
+        blob_provider: FallbackBlobProvider<FallbackBeaconClient>,
+        chain_provider: FallbackChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
     ) -> Self {
         let attributes = StatefulAttributesBuilder::new(
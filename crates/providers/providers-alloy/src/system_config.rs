@@ -0,0 +1,312 @@
+//! A provider that reconstructs the historical [SystemConfig] of the L2 chain from L1 logs.
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types_eth::Filter;
+use alloy_transport::{RpcError, TransportErrorKind};
+use kona_genesis::{
+    CONFIG_UPDATE_TOPIC, ConfigUpdateLog, RollupConfig, SystemConfig, SystemConfigHistory,
+    SystemConfigHistoryError,
+};
+use std::{
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    vec::Vec,
+};
+
+/// The [AlloySystemConfigProvider] reconstructs the [SystemConfig] in effect at any L1 block by
+/// replaying the config update logs emitted by the system config contract since the L1 genesis
+/// block.
+///
+/// Only finalized L1 blocks are replayed into the cached [SystemConfigHistory], so each finalized
+/// L1 block range is only fetched once and the cache never holds blocks that may reorg. Queries
+/// for L1 blocks past the finalized block replay the remaining logs on a copy of the history that
+/// is discarded afterwards. Clones of the provider share the cached history.
+#[derive(Debug, Clone)]
+pub struct AlloySystemConfigProvider {
+    /// The inner L1 Ethereum JSON-RPC provider.
+    pub inner: RootProvider,
+    /// The rollup config of the L2 chain.
+    rollup_config: Arc<RollupConfig>,
+    /// The system config history, replayed up to at most the L1 finalized block.
+    history: Arc<Mutex<SystemConfigHistory>>,
+    /// The maximum number of L1 blocks to fetch logs for in a single request.
+    log_range: u64,
+}
+
+impl AlloySystemConfigProvider {
+    /// The default maximum number of L1 blocks to fetch logs for in a single request.
+    pub const DEFAULT_LOG_RANGE: u64 = 10_000;
+
+    /// Creates a new [AlloySystemConfigProvider] with the given alloy provider.
+    pub fn new(inner: RootProvider, rollup_config: Arc<RollupConfig>) -> Self {
+        let history = Arc::new(Mutex::new(SystemConfigHistory::new(&rollup_config)));
+        Self { inner, rollup_config, history, log_range: Self::DEFAULT_LOG_RANGE }
+    }
+
+    /// Sets the maximum number of L1 blocks to fetch logs for in a single request.
+    ///
+    /// ## Panics
+    /// - Panics if `log_range` is zero.
+    pub fn with_log_range(mut self, log_range: u64) -> Self {
+        assert!(log_range > 0, "log range must be non-zero");
+        self.log_range = log_range;
+        self
+    }
+
+    /// Returns a snapshot of the cached [SystemConfigHistory].
+    pub fn history(&self) -> SystemConfigHistory {
+        self.lock_history().clone()
+    }
+
+    /// Returns the [SystemConfig] in effect at the given L1 block, replaying the config update
+    /// logs up to that block if they have not been replayed yet.
+    ///
+    /// The returned config includes the updates emitted in the given L1 block, which makes it the
+    /// system config of the L2 blocks in the epoch with that L1 origin.
+    ///
+    /// # Errors
+    /// Returns [AlloySystemConfigProviderError::AboveHead] if the block is past the L1 head.
+    pub async fn system_config_at_l1_block(
+        &self,
+        number: u64,
+    ) -> Result<SystemConfig, AlloySystemConfigProviderError> {
+        if number < self.rollup_config.genesis.l1.number {
+            return Err(AlloySystemConfigProviderError::BeforeGenesis(number));
+        }
+
+        let head = self.block_number(BlockNumberOrTag::Latest).await?;
+        if number > head {
+            return Err(AlloySystemConfigProviderError::AboveHead { number, head });
+        }
+        let finalized = self.block_number(BlockNumberOrTag::Finalized).await?;
+
+        // The cached history is never synced past the finalized block. The lock is only held
+        // between fetches, so concurrent queries may replay the same range; only the first
+        // replay is applied.
+        let cache_to = number.min(finalized);
+        loop {
+            let synced_to = self.lock_history().synced_to();
+            if synced_to >= cache_to {
+                break;
+            }
+            let end = cache_to.min(synced_to + self.log_range);
+            let logs = self.config_update_logs(synced_to + 1, end).await?;
+
+            let mut history = self.lock_history();
+            if history.synced_to() == synced_to {
+                history.replay(&self.rollup_config, end, logs)?;
+            }
+        }
+
+        let mut history = self.lock_history().clone();
+        while history.synced_to() < number {
+            let start = history.synced_to() + 1;
+            let end = number.min(history.synced_to() + self.log_range);
+            let logs = self.config_update_logs(start, end).await?;
+            history.replay(&self.rollup_config, end, logs)?;
+        }
+
+        // The history is synced past the requested block, after genesis.
+        history.config_at(number).ok_or(AlloySystemConfigProviderError::BeforeGenesis(number))
+    }
+
+    /// Locks the cached [SystemConfigHistory]. [SystemConfigHistory::replay] leaves the history
+    /// untouched on failure, so it is consistent even if a holder of the lock panicked.
+    fn lock_history(&self) -> MutexGuard<'_, SystemConfigHistory> {
+        self.history.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the number of the L1 block with the given tag.
+    async fn block_number(
+        &self,
+        tag: BlockNumberOrTag,
+    ) -> Result<u64, AlloySystemConfigProviderError> {
+        self.inner
+            .get_block_by_number(tag)
+            .await?
+            .map(|block| block.header.number)
+            .ok_or(AlloySystemConfigProviderError::TagNotFound(tag))
+    }
+
+    /// Fetches the config update logs emitted by the system config contract in the given
+    /// inclusive range of L1 blocks.
+    async fn config_update_logs(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<ConfigUpdateLog>, AlloySystemConfigProviderError> {
+        let filter = Filter::new()
+            .address(self.rollup_config.l1_system_config_address)
+            .event_signature(CONFIG_UPDATE_TOPIC)
+            .from_block(start)
+            .to_block(end);
+        let logs = self.inner.get_logs(&filter).await?;
+
+        let mut updates = Vec::with_capacity(logs.len());
+        let mut last_timestamp: Option<(u64, u64)> = None;
+        for log in logs.into_iter().filter(|log| !log.removed) {
+            let block_number =
+                log.block_number.ok_or(AlloySystemConfigProviderError::MissingBlockNumber)?;
+
+            // Not all L1 nodes include the block timestamp in logs.
+            let block_timestamp = match (log.block_timestamp, last_timestamp) {
+                (Some(timestamp), _) => timestamp,
+                (None, Some((number, timestamp))) if number == block_number => timestamp,
+                (None, _) => {
+                    self.inner
+                        .get_block_by_number(block_number.into())
+                        .await?
+                        .ok_or(AlloySystemConfigProviderError::BlockNotFound(block_number))?
+                        .header
+                        .timestamp
+                }
+            };
+            last_timestamp = Some((block_number, block_timestamp));
+
+            updates.push(ConfigUpdateLog { block_number, block_timestamp, log: log.inner });
+        }
+        Ok(updates)
+    }
+}
+
+/// An error for the [AlloySystemConfigProvider].
+#[derive(Debug, thiserror::Error)]
+pub enum AlloySystemConfigProviderError {
+    /// Transport error
+    #[error(transparent)]
+    Transport(#[from] RpcError<TransportErrorKind>),
+    /// The requested L1 block is before the L1 genesis block.
+    #[error("L1 block {0} is before the L1 genesis block")]
+    BeforeGenesis(u64),
+    /// The requested L1 block is past the L1 head.
+    #[error("L1 block {number} is past the L1 head {head}")]
+    AboveHead {
+        /// The requested L1 block.
+        number: u64,
+        /// The L1 head block.
+        head: u64,
+    },
+    /// Block not found.
+    #[error("Block not found: {0}")]
+    BlockNotFound(u64),
+    /// The L1 provider has no block with the given tag.
+    #[error("Block not found: {0}")]
+    TagNotFound(BlockNumberOrTag),
+    /// A log returned by the provider is missing its block number.
+    #[error("Log is missing its block number")]
+    MissingBlockNumber,
+    /// The config update logs could not be replayed.
+    #[error(transparent)]
+    History(#[from] SystemConfigHistoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256, LogData, hex};
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::{Block, Header, Log};
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use kona_genesis::{CONFIG_UPDATE_EVENT_VERSION_0, ChainGenesis};
+
+    const SYSTEM_CONFIG: Address = Address::repeat_byte(0x11);
+    const FIRST: Address = Address::repeat_byte(0xaa);
+    const SECOND: Address = Address::repeat_byte(0xbb);
+
+    fn provider(asserter: &Asserter) -> AlloySystemConfigProvider {
+        let rollup_config = RollupConfig {
+            l1_system_config_address: SYSTEM_CONFIG,
+            genesis: ChainGenesis {
+                system_config: Some(SystemConfig::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let client = RpcClient::new(MockTransport::new(asserter.clone()), false);
+        AlloySystemConfigProvider::new(RootProvider::new(client), Arc::new(rollup_config))
+    }
+
+    fn block(number: u64) -> Block {
+        Block {
+            header: Header {
+                inner: alloy_consensus::Header { number, ..Default::default() },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// A batcher update emitted in the given L1 block.
+    fn batcher_update(block_number: u64, batcher: Address) -> Log {
+        let mut data = hex!("00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000020").to_vec();
+        data.extend_from_slice(B256::left_padding_from(batcher.as_slice()).as_slice());
+        Log {
+            inner: alloy_primitives::Log {
+                address: SYSTEM_CONFIG,
+                data: LogData::new_unchecked(
+                    vec![CONFIG_UPDATE_TOPIC, CONFIG_UPDATE_EVENT_VERSION_0, B256::ZERO],
+                    data.into(),
+                ),
+            },
+            block_number: Some(block_number),
+            block_timestamp: Some(block_number * 12),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_system_config_at_l1_block_above_head() {
+        let asserter = Asserter::new();
+        let provider = provider(&asserter);
+        asserter.push_success(&block(10));
+
+        let err = provider.system_config_at_l1_block(11).await.unwrap_err();
+        assert!(matches!(err, AlloySystemConfigProviderError::AboveHead { number: 11, head: 10 }));
+        assert_eq!(provider.history().synced_to(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_system_config_at_l1_block_caches_finalized_only() {
+        let asserter = Asserter::new();
+        let provider = provider(&asserter);
+        asserter.push_success(&block(20));
+        asserter.push_success(&block(10));
+        asserter.push_success(&vec![batcher_update(5, FIRST)]);
+        asserter.push_success(&vec![batcher_update(15, SECOND)]);
+
+        let config = provider.system_config_at_l1_block(15).await.unwrap();
+        assert_eq!(config.batcher_address, SECOND);
+
+        // The unfinalized update is not cached.
+        let history = provider.history();
+        assert_eq!(history.synced_to(), 10);
+        assert_eq!(history.latest().batcher_address, FIRST);
+
+        // After a reorg of the unfinalized blocks, the update is gone.
+        asserter.push_success(&block(20));
+        asserter.push_success(&block(10));
+        asserter.push_success(&Vec::<Log>::new());
+        let config = provider.system_config_at_l1_block(15).await.unwrap();
+        assert_eq!(config.batcher_address, FIRST);
+    }
+
+    #[tokio::test]
+    async fn test_system_config_at_finalized_l1_block_is_cached() {
+        let asserter = Asserter::new();
+        let provider = provider(&asserter).with_log_range(4);
+        asserter.push_success(&block(20));
+        asserter.push_success(&block(10));
+        asserter.push_success(&vec![batcher_update(3, FIRST)]);
+        asserter.push_success(&Vec::<Log>::new());
+
+        let config = provider.system_config_at_l1_block(8).await.unwrap();
+        assert_eq!(config.batcher_address, FIRST);
+        assert_eq!(provider.history().synced_to(), 8);
+
+        // Finalized blocks that were replayed are not fetched again.
+        asserter.push_success(&block(20));
+        asserter.push_success(&block(10));
+        let config = provider.system_config_at_l1_block(2).await.unwrap();
+        assert_eq!(config.batcher_address, Address::ZERO);
+    }
+}
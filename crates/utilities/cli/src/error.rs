@@ -20,6 +20,10 @@ pub enum CliError {
     /// Error initializing metrics.
     #[error("Failed to initialize metrics")]
     MetricsInitialization(#[from] metrics_exporter_prometheus::BuildError),
+
+    /// Error initializing the OTLP exporters.
+    #[error(transparent)]
+    Otlp(#[from] crate::OtlpError),
 }
 
 /// Type alias for CLI results.
@@ -57,6 +57,10 @@ pub struct LogArgs {
     /// If set, new log files will be created every interval.
     #[arg(long = "logs.file.rotation", default_value = "never", env = "KONA_LOG_FILE_ROTATION")]
     pub file_rotation: LogRotation,
+    /// The OTLP/HTTP collector endpoint to export tracing spans to, e.g. `http://localhost:4318`.
+    /// If not set, spans are not exported.
+    #[arg(long = "tracing.otlp.endpoint", global = true, env = "KONA_TRACING_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
 }
 
 #[cfg(test)]
@@ -91,4 +95,14 @@ mod tests {
         let cli_v5 = TestCli::parse_from(["test_app", "-vvvvv"]);
         assert_eq!(cli_v5.global.level, 5, "Verbosity with -vvvvv should be 5.");
     }
+
+    #[test]
+    fn test_otlp_endpoint() {
+        let cli = TestCli::parse_from(["test_app"]);
+        assert_eq!(cli.global.otlp_endpoint, None);
+
+        let cli =
+            TestCli::parse_from(["test_app", "--tracing.otlp.endpoint", "http://localhost:4318"]);
+        assert_eq!(cli.global.otlp_endpoint.as_deref(), Some("http://localhost:4318"));
+    }
 }
@@ -1,15 +1,26 @@
 //! Utility module to house implementation and declaration of MetricsArgs since it's being used in
 //! multiple places, it's just being referenced from this module.
 
-use crate::{CliResult, init_prometheus_server};
-use clap::{Parser, arg};
-use std::net::IpAddr;
+use crate::{CliResult, init_otlp_metrics, init_prometheus_server, prometheus};
+use clap::{Parser, ValueEnum, arg};
+use std::{net::IpAddr, time::Duration};
 
-/// Configuration for Prometheus metrics.
+/// The exporter used to publish metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "lowercase")]
+pub enum MetricsExporter {
+    /// Serve metrics on a Prometheus scrape endpoint.
+    #[default]
+    Prometheus,
+    /// Push metrics to an OpenTelemetry collector over OTLP/HTTP.
+    Otlp,
+}
+
+/// Configuration for metrics.
 #[derive(Debug, Clone, Parser)]
 #[command(next_help_heading = "Metrics")]
 pub struct MetricsArgs {
-    /// Controls whether Prometheus metrics are enabled. Disabled by default.
+    /// Controls whether metrics are enabled. Disabled by default.
     #[arg(
         long = "metrics.enabled",
         global = true,
@@ -30,6 +41,33 @@ pub struct MetricsArgs {
         env = "KONA_METRICS_ADDR"
     )]
     pub addr: IpAddr,
+
+    /// The exporter used to publish metrics. One of: prometheus, otlp.
+    #[arg(
+        long = "metrics.exporter",
+        global = true,
+        default_value = "prometheus",
+        env = "KONA_METRICS_EXPORTER"
+    )]
+    pub exporter: MetricsExporter,
+
+    /// The OTLP/HTTP collector endpoint to push metrics to, when using the `otlp` exporter.
+    #[arg(
+        long = "metrics.otlp.endpoint",
+        global = true,
+        default_value = "http://localhost:4318",
+        env = "KONA_METRICS_OTLP_ENDPOINT"
+    )]
+    pub otlp_endpoint: String,
+
+    /// The interval, in seconds, at which metrics are pushed to the OTLP collector.
+    #[arg(
+        long = "metrics.otlp.interval",
+        global = true,
+        default_value = "10",
+        env = "KONA_METRICS_OTLP_INTERVAL"
+    )]
+    pub otlp_interval: u64,
 }
 
 impl Default for MetricsArgs {
@@ -39,12 +77,20 @@ impl Default for MetricsArgs {
 }
 
 impl MetricsArgs {
-    /// Initialize the tracing stack and Prometheus metrics recorder.
+    /// Initialize the metrics recorder for the configured [`MetricsExporter`].
     ///
     /// This function should be called at the beginning of the program.
     pub fn init_metrics(&self) -> CliResult<()> {
-        if self.enabled {
-            init_prometheus_server(self.addr, self.port)?;
+        if !self.enabled {
+            return Ok(());
+        }
+
+        match self.exporter {
+            MetricsExporter::Prometheus => init_prometheus_server(self.addr, self.port)?,
+            MetricsExporter::Otlp => {
+                init_otlp_metrics(&self.otlp_endpoint, Duration::from_secs(self.otlp_interval))?;
+                prometheus::spawn_process_collector();
+            }
         }
 
         Ok(())
@@ -74,6 +120,26 @@ mod tests {
             IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
             "Default for metrics.addr should be 0.0.0.0."
         );
+        assert_eq!(cli.metrics.exporter, MetricsExporter::Prometheus);
+        assert_eq!(cli.metrics.otlp_endpoint, "http://localhost:4318");
+        assert_eq!(cli.metrics.otlp_interval, 10);
+    }
+
+    #[test]
+    fn test_metrics_args_otlp_exporter() {
+        let cli = TestCli::parse_from([
+            "test_app",
+            "--metrics.enabled",
+            "--metrics.exporter",
+            "otlp",
+            "--metrics.otlp.endpoint",
+            "http://collector:4318",
+            "--metrics.otlp.interval",
+            "5",
+        ]);
+        assert_eq!(cli.metrics.exporter, MetricsExporter::Otlp);
+        assert_eq!(cli.metrics.otlp_endpoint, "http://collector:4318");
+        assert_eq!(cli.metrics.otlp_interval, 5);
     }
 
     #[test]
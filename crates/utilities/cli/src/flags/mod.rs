@@ -12,4 +12,4 @@ mod log;
 pub use log::LogArgs;
 
 mod metrics;
-pub use metrics::MetricsArgs;
+pub use metrics::{MetricsArgs, MetricsExporter};
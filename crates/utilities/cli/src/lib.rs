@@ -9,7 +9,7 @@ mod error;
 pub use error::{CliError, CliResult};
 
 mod flags;
-pub use flags::{GlobalArgs, LogArgs, MetricsArgs, OverrideArgs};
+pub use flags::{GlobalArgs, LogArgs, MetricsArgs, MetricsExporter, OverrideArgs};
 
 mod logs;
 pub use logs::{FileLogConfig, LogConfig, LogRotation, StdoutLogConfig};
@@ -30,4 +30,10 @@ pub use tracing::{LogFormat, init_test_tracing};
 mod prometheus;
 pub use prometheus::init_prometheus_server;
 
+mod otlp;
+pub use otlp::{
+    OtlpError, OtlpRecorder, init_otlp_metrics, otlp_meter_provider, otlp_tracer, service_name,
+    shutdown_otlp,
+};
+
 pub mod sigsegv_handler;
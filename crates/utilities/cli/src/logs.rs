@@ -51,6 +51,8 @@ pub struct LogConfig {
     pub stdout_logs: Option<StdoutLogConfig>,
     /// The configuration for file logging.
     pub file_logs: Option<FileLogConfig>,
+    /// The OTLP/HTTP collector endpoint to export tracing spans to.
+    pub otlp_endpoint: Option<String>,
 }
 
 impl Default for LogConfig {
@@ -59,6 +61,7 @@ impl Default for LogConfig {
             global_level: LevelFilter::INFO,
             stdout_logs: Some(StdoutLogConfig { format: LogFormat::Full }),
             file_logs: None,
+            otlp_endpoint: None,
         }
     }
 }
@@ -92,6 +95,6 @@ impl LogConfig {
             rotation: args.file_rotation,
         });
 
-        Self { global_level: level, stdout_logs, file_logs }
+        Self { global_level: level, stdout_logs, file_logs, otlp_endpoint: args.otlp_endpoint }
     }
 }
@@ -0,0 +1,445 @@
+//! Utilities for exporting metrics and tracing spans over OpenTelemetry OTLP.
+//!
+//! Metrics are recorded through the [`metrics`] facade, exactly like with the Prometheus exporter,
+//! and are bridged to OpenTelemetry instruments by the [`OtlpRecorder`]. Tracing spans are
+//! exported through a [`tracing_opentelemetry`] layer built from [`otlp_tracer`].
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use opentelemetry::{
+    KeyValue,
+    metrics::{Meter, MeterProvider as _},
+    trace::TracerProvider as _,
+};
+use opentelemetry_otlp::{ExporterBuildError, MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    metrics::{PeriodicReader, SdkMeterProvider},
+    trace::{SdkTracer, SdkTracerProvider},
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tracing::{info, warn};
+
+/// The OTLP/HTTP path that metrics are exported to.
+const METRICS_PATH: &str = "/v1/metrics";
+
+/// The OTLP/HTTP path that tracing spans are exported to.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// The instrumentation scope used for all kona metrics and spans.
+const SCOPE: &str = "kona";
+
+/// The globally installed meter provider, kept around to flush it on shutdown.
+static METER_PROVIDER: OnceLock<SdkMeterProvider> = OnceLock::new();
+
+/// The globally installed tracer provider, kept around to flush it on shutdown.
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// An error that can occur while setting up the OTLP exporters.
+#[derive(Debug, thiserror::Error)]
+pub enum OtlpError {
+    /// The OTLP exporter could not be built.
+    #[error("Failed to build OTLP exporter: {0}")]
+    Exporter(#[from] ExporterBuildError),
+    /// A global metrics recorder was already installed.
+    #[error("A metrics recorder is already installed")]
+    RecorderAlreadyInstalled,
+}
+
+/// Returns the service name reported to the OTLP collector.
+///
+/// This is the name of the running binary (e.g. `kona-node`, `kona-host` or `kona-supervisor`),
+/// falling back to `kona` if it cannot be determined.
+pub fn service_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| SCOPE.to_string())
+}
+
+/// Returns the [`Resource`] describing this process.
+fn resource() -> Resource {
+    Resource::builder().with_service_name(service_name()).build()
+}
+
+/// Joins the collector base endpoint with the given OTLP/HTTP signal path.
+fn signal_endpoint(endpoint: &str, path: &str) -> String {
+    format!("{}{path}", endpoint.trim_end_matches('/'))
+}
+
+/// Builds an [`SdkMeterProvider`] that periodically pushes metrics to the OTLP/HTTP collector at
+/// the given endpoint.
+pub fn otlp_meter_provider(
+    endpoint: &str,
+    interval: Duration,
+) -> Result<SdkMeterProvider, OtlpError> {
+    let exporter = MetricExporter::builder()
+        .with_http()
+        .with_endpoint(signal_endpoint(endpoint, METRICS_PATH))
+        .build()?;
+    let reader = PeriodicReader::builder(exporter).with_interval(interval).build();
+    Ok(SdkMeterProvider::builder().with_reader(reader).with_resource(resource()).build())
+}
+
+/// Installs a global [`OtlpRecorder`] that pushes metrics to the OTLP/HTTP collector at the given
+/// endpoint every `interval`.
+pub fn init_otlp_metrics(endpoint: &str, interval: Duration) -> Result<(), OtlpError> {
+    let provider = otlp_meter_provider(endpoint, interval)?;
+    let recorder = OtlpRecorder::new(provider.meter(SCOPE));
+    metrics::set_global_recorder(recorder).map_err(|_| OtlpError::RecorderAlreadyInstalled)?;
+    let _ = METER_PROVIDER.set(provider);
+
+    info!(target: "otlp", "Exporting metrics to: {}", endpoint);
+
+    Ok(())
+}
+
+/// Builds an [`SdkTracer`] that exports spans in batches to the OTLP/HTTP collector at the given
+/// endpoint, and registers its provider globally.
+pub fn otlp_tracer(endpoint: &str) -> Result<SdkTracer, OtlpError> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(signal_endpoint(endpoint, TRACES_PATH))
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource())
+        .build();
+    let tracer = provider.tracer(SCOPE);
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    let _ = TRACER_PROVIDER.set(provider);
+    Ok(tracer)
+}
+
+/// Flushes and shuts down the OTLP exporters, if any were installed.
+///
+/// This should be called before the program exits, so that buffered spans and the last metrics
+/// are not lost.
+pub fn shutdown_otlp() {
+    if let Some(provider) = TRACER_PROVIDER.get() &&
+        let Err(e) = provider.shutdown()
+    {
+        warn!(target: "otlp", "Failed to shut down the OTLP tracer provider: {e}");
+    }
+    if let Some(provider) = METER_PROVIDER.get() &&
+        let Err(e) = provider.shutdown()
+    {
+        warn!(target: "otlp", "Failed to shut down the OTLP meter provider: {e}");
+    }
+}
+
+/// Converts the labels of a metric [`Key`] into OpenTelemetry attributes.
+fn attributes(key: &Key) -> Vec<KeyValue> {
+    key.labels().map(|l| KeyValue::new(l.key().to_string(), l.value().to_string())).collect()
+}
+
+/// A [`Recorder`] that bridges the [`metrics`] facade to OpenTelemetry instruments.
+///
+/// Handles are cached per [`Key`], such that gauges keep their value across
+/// `increment`/`decrement` calls made through different handles.
+pub struct OtlpRecorder {
+    /// The meter used to create instruments.
+    meter: Meter,
+    /// The descriptions and units of the metrics, by name.
+    descriptions: Mutex<HashMap<String, (SharedString, Option<Unit>)>>,
+    /// The registered counters.
+    counters: Mutex<HashMap<Key, Arc<OtlpCounter>>>,
+    /// The registered gauges.
+    gauges: Mutex<HashMap<Key, Arc<OtlpGauge>>>,
+    /// The registered histograms.
+    histograms: Mutex<HashMap<Key, Arc<OtlpHistogram>>>,
+}
+
+impl fmt::Debug for OtlpRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtlpRecorder").finish_non_exhaustive()
+    }
+}
+
+impl OtlpRecorder {
+    /// Creates a new [`OtlpRecorder`] backed by the given [`Meter`].
+    pub fn new(meter: Meter) -> Self {
+        Self {
+            meter,
+            descriptions: Default::default(),
+            counters: Default::default(),
+            gauges: Default::default(),
+            histograms: Default::default(),
+        }
+    }
+
+    fn describe(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        if let Ok(mut descriptions) = self.descriptions.lock() {
+            descriptions.insert(key.as_str().to_string(), (description, unit));
+        }
+    }
+
+    /// Returns the description and unit of the given metric, if it was described.
+    fn description(&self, name: &str) -> (String, Option<&'static str>) {
+        self.descriptions
+            .lock()
+            .ok()
+            .and_then(|d| {
+                d.get(name)
+                    .map(|(desc, unit)| (desc.to_string(), unit.map(|u| u.as_canonical_label())))
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Recorder for OtlpRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description);
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description);
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description);
+    }
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let Ok(mut counters) = self.counters.lock() else {
+            return Counter::noop();
+        };
+        let counter = counters.entry(key.clone()).or_insert_with(|| {
+            let (description, unit) = self.description(key.name());
+            let mut builder =
+                self.meter.u64_counter(key.name().to_string()).with_description(description);
+            if let Some(unit) = unit {
+                builder = builder.with_unit(unit);
+            }
+            Arc::new(OtlpCounter {
+                counter: builder.build(),
+                attributes: attributes(key),
+                value: AtomicU64::new(0),
+            })
+        });
+        Counter::from_arc(Arc::clone(counter))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        let Ok(mut gauges) = self.gauges.lock() else {
+            return Gauge::noop();
+        };
+        let gauge = gauges.entry(key.clone()).or_insert_with(|| {
+            let (description, unit) = self.description(key.name());
+            let mut builder =
+                self.meter.f64_gauge(key.name().to_string()).with_description(description);
+            if let Some(unit) = unit {
+                builder = builder.with_unit(unit);
+            }
+            Arc::new(OtlpGauge {
+                gauge: builder.build(),
+                attributes: attributes(key),
+                value: AtomicU64::new(0f64.to_bits()),
+            })
+        });
+        Gauge::from_arc(Arc::clone(gauge))
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        let Ok(mut histograms) = self.histograms.lock() else {
+            return Histogram::noop();
+        };
+        let histogram = histograms.entry(key.clone()).or_insert_with(|| {
+            let (description, unit) = self.description(key.name());
+            let mut builder =
+                self.meter.f64_histogram(key.name().to_string()).with_description(description);
+            if let Some(unit) = unit {
+                builder = builder.with_unit(unit);
+            }
+            Arc::new(OtlpHistogram { histogram: builder.build(), attributes: attributes(key) })
+        });
+        Histogram::from_arc(Arc::clone(histogram))
+    }
+}
+
+/// A monotonic counter backed by an OpenTelemetry counter.
+struct OtlpCounter {
+    counter: opentelemetry::metrics::Counter<u64>,
+    attributes: Vec<KeyValue>,
+    /// The current value, used to translate absolute values into increments.
+    value: AtomicU64,
+}
+
+impl CounterFn for OtlpCounter {
+    fn increment(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+        self.counter.add(value, &self.attributes);
+    }
+
+    fn absolute(&self, value: u64) {
+        let previous = self.value.fetch_max(value, Ordering::Relaxed);
+        if value > previous {
+            self.counter.add(value - previous, &self.attributes);
+        }
+    }
+}
+
+/// A gauge backed by an OpenTelemetry gauge.
+struct OtlpGauge {
+    gauge: opentelemetry::metrics::Gauge<f64>,
+    attributes: Vec<KeyValue>,
+    /// The bits of the current `f64` value, used to translate increments into absolute values.
+    value: AtomicU64,
+}
+
+impl OtlpGauge {
+    fn update(&self, f: impl Fn(f64) -> f64) {
+        let mut current = self.value.load(Ordering::Relaxed);
+        loop {
+            let new = f(f64::from_bits(current));
+            match self.value.compare_exchange_weak(
+                current,
+                new.to_bits(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    self.gauge.record(new, &self.attributes);
+                    return;
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl GaugeFn for OtlpGauge {
+    fn increment(&self, value: f64) {
+        self.update(|current| current + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update(|current| current - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.update(|_| value);
+    }
+}
+
+/// A histogram backed by an OpenTelemetry histogram.
+struct OtlpHistogram {
+    histogram: opentelemetry::metrics::Histogram<f64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl HistogramFn for OtlpHistogram {
+    fn record(&self, value: f64) {
+        self.histogram.record(value, &self.attributes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    /// Spawns an in-process OTLP/HTTP collector stub that acknowledges every request and forwards
+    /// the request path and body size over the returned channel.
+    fn collector_stub() -> (String, mpsc::Receiver<(String, usize)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default().to_string();
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') &&
+                        name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap_or_default();
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+                let _ = tx.send((path, content_length));
+            }
+        });
+        (endpoint, rx)
+    }
+
+    #[test]
+    fn test_signal_endpoint() {
+        assert_eq!(
+            signal_endpoint("http://localhost:4318/", METRICS_PATH),
+            "http://localhost:4318/v1/metrics"
+        );
+        assert_eq!(
+            signal_endpoint("http://localhost:4318", TRACES_PATH),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_otlp_metrics_export() {
+        let (endpoint, rx) = collector_stub();
+        let provider = otlp_meter_provider(&endpoint, Duration::from_secs(3600)).unwrap();
+        let recorder = OtlpRecorder::new(provider.meter(SCOPE));
+
+        metrics::with_local_recorder(&recorder, || {
+            metrics::describe_gauge!("kona_test_gauge", "A test gauge");
+            metrics::counter!("kona_test_counter", "type" => "test").increment(2);
+            metrics::gauge!("kona_test_gauge").increment(3.0);
+            metrics::gauge!("kona_test_gauge").decrement(1.0);
+            metrics::histogram!("kona_test_histogram").record(0.5);
+        });
+
+        let gauge = recorder.gauges.lock().unwrap();
+        let value = gauge.values().next().unwrap().value.load(Ordering::Relaxed);
+        assert_eq!(f64::from_bits(value), 2.0);
+        drop(gauge);
+
+        provider.force_flush().unwrap();
+        let (path, len) = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(path, METRICS_PATH);
+        assert!(len > 0);
+    }
+
+    #[test]
+    fn test_otlp_counter_absolute() {
+        let provider = SdkMeterProvider::builder().build();
+        let recorder = OtlpRecorder::new(provider.meter(SCOPE));
+
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("kona_test_absolute").absolute(5);
+            metrics::counter!("kona_test_absolute").absolute(3);
+            metrics::counter!("kona_test_absolute").increment(1);
+        });
+
+        let counters = recorder.counters.lock().unwrap();
+        assert_eq!(counters.values().next().unwrap().value.load(Ordering::Relaxed), 6);
+    }
+}
@@ -16,7 +16,20 @@ pub fn init_prometheus_server(addr: IpAddr, metrics_port: u16) -> Result<(), Bui
 
     builder.install()?;
 
-    // Initialise collector for system metrics e.g. CPU, memory, etc.
+    spawn_process_collector();
+
+    info!(
+        target: "prometheus",
+        "Serving metrics at: http://{}",
+        prometheus_addr
+    );
+
+    Ok(())
+}
+
+/// Spawns a thread that periodically collects system metrics e.g. CPU, memory, etc. into the
+/// installed metrics recorder.
+pub(crate) fn spawn_process_collector() {
     let collector = Collector::default();
     collector.describe();
 
@@ -26,12 +39,4 @@ pub fn init_prometheus_server(addr: IpAddr, metrics_port: u16) -> Result<(), Bui
             sleep(Duration::from_secs(60));
         }
     });
-
-    info!(
-        target: "prometheus",
-        "Serving metrics at: http://{}",
-        prometheus_addr
-    );
-
-    Ok(())
 }
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use tracing_subscriber::EnvFilter;
 
-use crate::{LogConfig, LogRotation};
+use crate::{LogConfig, LogRotation, otlp_tracer};
 
 /// The format of the logs.
 #[derive(
@@ -79,6 +79,9 @@ impl LogConfig {
     /// * `verbosity_level` - The verbosity level (0-5). If `0`, no logs are printed.
     /// * `env_filter` - Optional environment filter for the subscriber.
     ///
+    /// If an OTLP endpoint is configured, spans are also exported to the OpenTelemetry collector.
+    /// Failing to set up the exporter is not fatal, and is logged once the subscriber is installed.
+    ///
     /// # Returns
     /// * `Result<()>` - Ok if successful, Err otherwise.
     pub fn init_tracing_subscriber(
@@ -127,6 +130,12 @@ impl LogConfig {
             }
         });
 
+        let (otlp_tracer, otlp_error) = match self.otlp_endpoint.as_deref().map(otlp_tracer) {
+            Some(Ok(tracer)) => (Some(tracer), None),
+            Some(Err(e)) => (None, Some(e)),
+            None => (None, None),
+        };
+
         let env_filter = env_filter
             .unwrap_or(EnvFilter::from_default_env())
             .add_directive(self.global_level.into());
@@ -135,8 +144,13 @@ impl LogConfig {
             .with(env_filter)
             .with(file_layer)
             .with(stdout_layer)
+            .with(otlp_tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)))
             .try_init()?;
 
+        if let Some(e) = otlp_error {
+            tracing::warn!(target: "otlp", "Tracing spans are not exported: {e}");
+        }
+
         Ok(())
     }
 }
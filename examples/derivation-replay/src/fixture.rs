@@ -0,0 +1,132 @@
+//! The recorded fixture that the derivation pipeline is replayed against.
+
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::{B256, map::HashMap};
+use anyhow::{Result, anyhow};
+use kona_derive::test_utils::{TestBlobProvider, TestChainProvider, TestL2ChainProvider};
+use kona_genesis::{L1ChainConfig, RollupConfig, SystemConfig};
+use kona_protocol::{BlockInfo, L2BlockInfo};
+use kona_registry::L1_CONFIGS;
+use op_alloy_consensus::OpBlock;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A recorded L1 block, holding everything the pipeline reads from the L1 chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1BlockFixture {
+    /// The block header.
+    pub header: Header,
+    /// The transactions in the block. Batcher transactions are read from here.
+    #[serde(default)]
+    pub transactions: Vec<TxEnvelope>,
+    /// The receipts of the block. Only required for epoch origins, where deposits and system
+    /// config updates are read from.
+    #[serde(default)]
+    pub receipts: Vec<Receipt>,
+}
+
+impl L1BlockFixture {
+    /// Returns the [`BlockInfo`] for the recorded block.
+    pub fn block_info(&self) -> BlockInfo {
+        BlockInfo::new(
+            self.header.hash_slow(),
+            self.header.number,
+            self.header.parent_hash,
+            self.header.timestamp,
+        )
+    }
+}
+
+/// A recorded range of L1 blocks together with the L2 chain state needed to replay derivation
+/// over it, without access to a live RPC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFixture {
+    /// The rollup config of the chain.
+    pub rollup_config: RollupConfig,
+    /// The L1 chain config. If absent, it is looked up in the registry by the rollup config's
+    /// L1 chain ID.
+    #[serde(default)]
+    pub l1_config: Option<L1ChainConfig>,
+    /// The L2 safe head to start deriving from.
+    pub l2_safe_head: L2BlockInfo,
+    /// The L1 block to reset the pipeline to.
+    pub l1_origin: BlockInfo,
+    /// The system config at the L2 safe head.
+    pub system_config: SystemConfig,
+    /// The recorded L1 blocks, starting at the L1 origin.
+    pub l1_blocks: Vec<L1BlockFixture>,
+    /// The canonical L2 blocks following the safe head. These are used to advance the cursor
+    /// after each set of derived attributes.
+    pub l2_blocks: Vec<L2BlockInfo>,
+    /// Full L2 blocks, needed when span batches overlap the safe head.
+    #[serde(default)]
+    pub l2_payloads: Vec<OpBlock>,
+    /// Blobs referenced by batcher transactions, keyed by versioned hash.
+    #[serde(default)]
+    pub blobs: HashMap<B256, Blob>,
+}
+
+impl ReplayFixture {
+    /// Reads a fixture from a JSON file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    /// Returns the L1 chain config for the fixture.
+    pub fn l1_config(&self) -> Result<L1ChainConfig> {
+        if let Some(config) = self.l1_config.as_ref() {
+            return Ok(config.clone());
+        }
+        let chain_id = self.rollup_config.l1_chain_id;
+        L1_CONFIGS
+            .get(&chain_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("No L1 config in fixture or registry for chain {chain_id}"))
+    }
+
+    /// Returns the number of the last recorded L1 block.
+    pub fn last_l1_block(&self) -> Option<u64> {
+        self.l1_blocks.last().map(|b| b.header.number)
+    }
+
+    /// Returns the recorded L2 block with the given number.
+    pub fn l2_block(&self, number: u64) -> Option<L2BlockInfo> {
+        self.l2_blocks.iter().find(|b| b.block_info.number == number).copied()
+    }
+
+    /// Builds an in-memory L1 chain provider serving the recorded L1 blocks.
+    pub fn chain_provider(&self) -> TestChainProvider {
+        let mut provider = TestChainProvider::default();
+        for block in &self.l1_blocks {
+            let info = block.block_info();
+            provider.insert_block_with_transactions(info.number, info, block.transactions.clone());
+            provider.insert_header(info.hash, block.header.clone());
+            provider.insert_receipts(info.hash, block.receipts.clone());
+        }
+        provider
+    }
+
+    /// Builds an in-memory L2 chain provider serving the recorded L2 blocks.
+    ///
+    /// The fixture's system config is served for the safe head and every recorded L2 block.
+    pub fn l2_chain_provider(&self) -> TestL2ChainProvider {
+        let mut blocks = self.l2_blocks.clone();
+        blocks.push(self.l2_safe_head);
+        let system_configs = blocks
+            .iter()
+            .map(|b| (b.block_info.number, self.system_config))
+            .collect::<HashMap<_, _>>();
+        TestL2ChainProvider::new(blocks, self.l2_payloads.clone(), system_configs)
+    }
+
+    /// Builds an in-memory blob provider serving the recorded blobs.
+    pub fn blob_provider(&self) -> TestBlobProvider {
+        let mut provider = TestBlobProvider::default();
+        for (hash, blob) in &self.blobs {
+            provider.insert_blob(*hash, *blob);
+        }
+        provider
+    }
+}
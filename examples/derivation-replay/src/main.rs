@@ -0,0 +1,371 @@
+//! Replays a recorded range of L1 blocks through the derivation pipeline, offline.
+//!
+//! Every artifact produced along the way is dumped as a JSON line, so that a derivation
+//! divergence can be pinned to the exact stage that introduced it by diffing against op-node's
+//! logs for the same range:
+//!
+//! - `frame`: Every frame loaded by the `FrameQueue`, including its data.
+//! - `channel`: The compressed data of every channel that is ready for decompression.
+//! - `batch`: Every batch decoded from a channel, including its transactions.
+//! - `verdict`: Every batch validity check in the `BatchStream`, `BatchQueue` or `BatchValidator`,
+//!   with the verdict and, for batches that are not accepted, the reason.
+//! - `attributes`: The final `OpAttributesWithParent` produced by the pipeline.
+//!
+//! ## Usage
+//!
+//! ```sh
+//! cargo run --release -p derivation-replay -- --fixture <path> [--output <path>]
+//! ```
+//!
+//! ## Inputs
+//!
+//! - `-v` or `--verbosity`: Verbosity level (0-5) of the logs printed to stderr.
+//! - `-f` or `--fixture`: The JSON replay fixture. See [`ReplayFixture`] for its layout.
+//! - `-o` or `--output`: (Optional) The file to write the artifacts to. If not provided, the
+//!   artifacts are written to stdout.
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use kona_cli::{LogArgs, LogConfig};
+use kona_derive::{
+    AttributesQueue, BatchProvider, BatchStream, ChannelProvider, ChannelReader,
+    DerivationPipeline, EthereumDataSource, FrameQueue, L1Retrieval, OriginProvider, Pipeline,
+    PipelineErrorKind, PollingTraversal, ResetSignal, SignalReceiver, StatefulAttributesBuilder,
+    StepResult,
+};
+use kona_protocol::L2BlockInfo;
+use std::{io::Write, path::PathBuf, sync::Arc};
+use tracing::{info, trace};
+use tracing_subscriber::{Layer, filter::filter_fn, layer::SubscriberExt, util::SubscriberInitExt};
+
+mod fixture;
+use fixture::ReplayFixture;
+
+mod recorder;
+use recorder::ArtifactRecorder;
+
+mod stages;
+use stages::Recorded;
+
+/// The number of consecutive temporary step failures after which the replay is considered
+/// stalled, typically because the fixture is missing data the pipeline needs.
+const MAX_STALLED_STEPS: usize = 4096;
+
+/// The derivation replay command.
+#[derive(Parser, Debug, Clone)]
+#[command(about = "Replays a recorded L1 range through the derivation pipeline, offline")]
+pub struct DerivationReplayCommand {
+    #[command(flatten)]
+    pub v: LogArgs,
+    /// The JSON replay fixture.
+    #[arg(long, short = 'f')]
+    pub fixture: PathBuf,
+    /// The file to write the artifacts to. Defaults to stdout.
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = DerivationReplayCommand::parse();
+
+    let writer: Box<dyn Write + Send> = match cli.output.as_ref() {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::stdout()),
+    };
+    let recorder = ArtifactRecorder::new(writer);
+
+    // Logs go to stderr at the configured verbosity, while the recorder always receives the
+    // stage events regardless of the verbosity.
+    let level = LogConfig::new(cli.v).global_level;
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr).with_filter(level))
+        .with(recorder.clone().with_filter(filter_fn(recorder::is_recorded)))
+        .try_init()?;
+
+    let fixture = ReplayFixture::from_file(&cli.fixture)?;
+    let summary = replay(&fixture, &recorder).await?;
+    recorder.finish()?;
+
+    info!(
+        target: "derivation_replay",
+        derived = summary.derived,
+        l2_head = summary.l2_head.block_info.number,
+        "Finished replaying derivation"
+    );
+    Ok(())
+}
+
+/// The outcome of a replay.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplaySummary {
+    /// The number of payload attributes derived.
+    pub derived: u64,
+    /// The L2 block the last attributes were derived on top of.
+    pub l2_head: L2BlockInfo,
+}
+
+/// Replays the fixture through a derivation pipeline, recording its artifacts.
+///
+/// The stage stack matches `PipelineBuilder::build_polled`, with [`Recorded`] wrappers slotted
+/// between the stages that hand frames, channels and batches to each other.
+pub async fn replay(fixture: &ReplayFixture, recorder: &ArtifactRecorder) -> Result<ReplaySummary> {
+    let Some(last_l1_block) = fixture.last_l1_block() else {
+        bail!("Fixture contains no L1 blocks");
+    };
+
+    let rollup_config = Arc::new(fixture.rollup_config.clone());
+    let l1_config = Arc::new(fixture.l1_config()?);
+    let chain_provider = fixture.chain_provider();
+    let l2_chain_provider = fixture.l2_chain_provider();
+    let dap = EthereumDataSource::new_from_parts(
+        chain_provider.clone(),
+        fixture.blob_provider(),
+        &rollup_config,
+    );
+    let builder = StatefulAttributesBuilder::new(
+        rollup_config.clone(),
+        l1_config,
+        l2_chain_provider.clone(),
+        chain_provider.clone(),
+    );
+
+    let mut l1_traversal = PollingTraversal::new(chain_provider, rollup_config.clone());
+    l1_traversal.block = Some(fixture.l1_origin);
+    let l1_retrieval = L1Retrieval::new(l1_traversal, dap);
+    let frame_queue = FrameQueue::new(l1_retrieval, rollup_config.clone());
+    let frames = Recorded::new(frame_queue, recorder.clone());
+    let channel_provider = ChannelProvider::new(rollup_config.clone(), frames);
+    let channels = Recorded::new(channel_provider, recorder.clone());
+    let channel_reader = ChannelReader::new(channels, rollup_config.clone());
+    let batches = Recorded::new(channel_reader, recorder.clone());
+    let batch_stream = BatchStream::new(batches, rollup_config.clone(), l2_chain_provider.clone());
+    let batch_provider =
+        BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+    let attributes = AttributesQueue::new(rollup_config.clone(), batch_provider, builder);
+    let mut pipeline = DerivationPipeline::new(attributes, rollup_config, l2_chain_provider);
+
+    let mut cursor = fixture.l2_safe_head;
+    pipeline
+        .signal(
+            ResetSignal {
+                l2_safe_head: cursor,
+                l1_origin: fixture.l1_origin,
+                system_config: Some(fixture.system_config),
+            }
+            .signal(),
+        )
+        .await?;
+
+    info!(
+        target: "derivation_replay",
+        l2_safe_head = cursor.block_info.number,
+        l1_origin = fixture.l1_origin.number,
+        last_l1_block,
+        "Replaying derivation"
+    );
+
+    let mut derived = 0;
+    let mut stalled = 0;
+    loop {
+        recorder.set_origin(pipeline.origin());
+        match pipeline.step(cursor).await {
+            StepResult::PreparedAttributes => {
+                stalled = 0;
+                let Some(attributes) = pipeline.next() else { continue };
+                recorder.record_attributes(&attributes)?;
+                derived += 1;
+
+                let next = cursor.block_info.number + 1;
+                let Some(next_cursor) = fixture.l2_block(next) else {
+                    info!(target: "derivation_replay", "No L2 block #{next} in fixture, stopping");
+                    break;
+                };
+                cursor = next_cursor;
+            }
+            StepResult::AdvancedOrigin => stalled = 0,
+            StepResult::OriginAdvanceErr(_)
+                if pipeline.origin().is_some_and(|o| o.number >= last_l1_block) =>
+            {
+                info!(target: "derivation_replay", "Reached the end of the recorded L1 range");
+                break;
+            }
+            StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => match e {
+                PipelineErrorKind::Temporary(_) => {
+                    trace!(target: "derivation_replay", "Temporary step failure: {e:?}");
+                    stalled += 1;
+                    if stalled >= MAX_STALLED_STEPS {
+                        bail!("Derivation stalled, the fixture is likely missing data: {e:?}");
+                    }
+                }
+                PipelineErrorKind::Reset(e) => bail!("Pipeline reset during replay: {e:?}"),
+                PipelineErrorKind::Critical(e) => bail!("Critical pipeline error: {e:?}"),
+            },
+        }
+    }
+
+    Ok(ReplaySummary { derived, l2_head: cursor })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{
+        Header, SignableTransaction, TxEip1559, TxEnvelope, transaction::SignerRecoverable,
+    };
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::{Address, B256, Bytes, Signature, TxKind, address};
+    use fixture::L1BlockFixture;
+    use kona_genesis::{ChainGenesis, L1ChainConfig, RollupConfig, SystemConfig};
+    use kona_protocol::{Batch, BlockInfo, DERIVATION_VERSION_0, Frame, SingleBatch};
+    use serde_json::Value;
+    use std::sync::Mutex;
+
+    const BATCH_INBOX: Address = address!("ff00000000000000000000000000000000000010");
+
+    /// An in-memory writer that the test reads the recorded artifacts back from.
+    #[derive(Debug, Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Builds a fixture with two L1 blocks, the second of which carries a batcher transaction
+    /// holding a single-frame channel with one batch on top of the L2 safe head.
+    fn fixture() -> ReplayFixture {
+        let origin = Header { number: 100, timestamp: 1000, ..Default::default() };
+        let origin_hash = origin.hash_slow();
+        let l2_safe_head = L2BlockInfo {
+            block_info: BlockInfo {
+                hash: B256::repeat_byte(0x22),
+                number: 0,
+                parent_hash: B256::ZERO,
+                timestamp: 1000,
+            },
+            l1_origin: BlockNumHash { number: 100, hash: origin_hash },
+            seq_num: 0,
+        };
+
+        // Encode the batch into a channel, and the channel into a single frame.
+        let batch = Batch::Single(SingleBatch {
+            parent_hash: l2_safe_head.block_info.hash,
+            epoch_num: 100,
+            epoch_hash: origin_hash,
+            timestamp: 1002,
+            transactions: vec![],
+        });
+        let mut encoded = Vec::new();
+        batch.encode(&mut encoded).unwrap();
+        let channel = kona_comp::compress_zlib(&alloy_rlp::encode(Bytes::from(encoded)));
+        let frame = Frame { id: [0xaa; 16], number: 0, data: channel, is_last: true };
+        let mut calldata = vec![DERIVATION_VERSION_0];
+        calldata.extend(frame.encode());
+
+        let tx = TxEip1559 {
+            chain_id: 1,
+            to: TxKind::Call(BATCH_INBOX),
+            input: calldata.into(),
+            ..Default::default()
+        };
+        let tx = TxEnvelope::from(tx.into_signed(Signature::test_signature()));
+        let batcher_address = tx.recover_signer().unwrap();
+        let system_config =
+            SystemConfig { batcher_address, gas_limit: 30_000_000, ..Default::default() };
+
+        let inclusion =
+            Header { number: 101, timestamp: 1012, parent_hash: origin_hash, ..Default::default() };
+
+        ReplayFixture {
+            rollup_config: RollupConfig {
+                genesis: ChainGenesis {
+                    l1: BlockNumHash { number: 100, hash: origin_hash },
+                    l2: BlockNumHash { number: 0, hash: l2_safe_head.block_info.hash },
+                    l2_time: 1000,
+                    system_config: Some(system_config),
+                },
+                block_time: 2,
+                max_sequencer_drift: 600,
+                seq_window_size: 10,
+                channel_timeout: 300,
+                l1_chain_id: 1,
+                batch_inbox_address: BATCH_INBOX,
+                ..Default::default()
+            },
+            l1_config: Some(L1ChainConfig::default()),
+            l2_safe_head,
+            l1_origin: BlockInfo::new(origin_hash, 100, B256::ZERO, 1000),
+            system_config,
+            l1_blocks: vec![
+                L1BlockFixture { header: origin, transactions: vec![], receipts: vec![] },
+                L1BlockFixture { header: inclusion, transactions: vec![tx], receipts: vec![] },
+            ],
+            l2_blocks: vec![],
+            l2_payloads: vec![],
+            blobs: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_records_artifacts() {
+        // Round-trip the fixture through its file format.
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.json");
+        std::fs::write(&path, serde_json::to_vec(&fixture()).unwrap()).unwrap();
+        let fixture = ReplayFixture::from_file(&path).unwrap();
+
+        let buffer = SharedBuffer::default();
+        let recorder = ArtifactRecorder::new(Box::new(buffer.clone()));
+        let _guard = tracing_subscriber::registry()
+            .with(recorder.clone().with_filter(filter_fn(recorder::is_recorded)))
+            .set_default();
+
+        let summary = replay(&fixture, &recorder).await.unwrap();
+        recorder.finish().unwrap();
+        assert_eq!(summary.derived, 1);
+        assert_eq!(summary.l2_head, fixture.l2_safe_head);
+
+        let output = buffer.0.lock().unwrap().clone();
+        let records = output
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        let of_kind =
+            |kind: &str| records.iter().filter(|r| r["kind"] == kind).cloned().collect::<Vec<_>>();
+
+        let frames = of_kind("frame");
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0]["l1_origin"], 101);
+        assert_eq!(frames[0]["channel_id"], format!("0x{}", "aa".repeat(16)));
+        assert_eq!(frames[0]["is_last"], true);
+
+        let channels = of_kind("channel");
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0]["data"], frames[0]["data"]);
+
+        let batches = of_kind("batch");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0]["batch_type"], "single");
+        assert_eq!(batches[0]["timestamp"], 1002);
+        assert_eq!(batches[0]["epoch_num"], 100);
+
+        let verdicts = of_kind("verdict");
+        assert!(!verdicts.is_empty());
+        let accepted = verdicts.last().unwrap();
+        assert_eq!(accepted["validity"], "Accept");
+        assert_eq!(accepted["timestamp"], 1002);
+        assert_eq!(accepted["reason"], Value::Null);
+
+        let attributes = of_kind("attributes");
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes[0]["block_number"], 1);
+    }
+}
@@ -0,0 +1,232 @@
+//! Records the artifacts produced by the derivation pipeline stages as JSON lines.
+//!
+//! Frames, channels and batches are captured by the [`Recorded`] stage wrappers as they are
+//! handed from one stage to the next. Batch validity verdicts are captured by the recorder's
+//! tracing [`Layer`], from the structured `validity` events emitted by the batch stages. Batch
+//! validation logs the reason for a dropped batch before the stage reports the verdict, so the
+//! most recent reason is attached to the next recorded verdict.
+//!
+//! [`Recorded`]: crate::stages::Recorded
+
+use alloy_primitives::hex;
+use kona_protocol::{Batch, BlockInfo, Frame, OpAttributesWithParent};
+use serde_json::{Map, Value, json};
+use std::{
+    fmt::Debug,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{Layer, layer::Context};
+
+/// The stages that report batch validity verdicts.
+pub const VERDICT_TARGETS: [&str; 3] = ["batch_stream", "batch_queue", "batch_validator"];
+
+/// The targets that batch validation logs drop reasons under.
+pub const REASON_TARGETS: [&str; 2] = ["single_batch", "batch_span"];
+
+/// Returns whether the recorder is interested in events with the given metadata.
+pub fn is_recorded(metadata: &Metadata<'_>) -> bool {
+    VERDICT_TARGETS.contains(&metadata.target()) || REASON_TARGETS.contains(&metadata.target())
+}
+
+/// Records derivation artifacts as JSON lines.
+#[derive(Clone)]
+pub struct ArtifactRecorder {
+    state: Arc<Mutex<RecorderState>>,
+}
+
+impl Debug for ArtifactRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArtifactRecorder").finish_non_exhaustive()
+    }
+}
+
+struct RecorderState {
+    writer: Box<dyn Write + Send>,
+    origin: Option<u64>,
+    reason: Option<String>,
+    error: Option<std::io::Error>,
+}
+
+impl RecorderState {
+    fn write(&mut self, kind: &str, l1_origin: Option<u64>, fields: Map<String, Value>) {
+        if self.error.is_some() {
+            return;
+        }
+        let mut record = Map::new();
+        record.insert("kind".into(), kind.into());
+        record.insert("l1_origin".into(), l1_origin.into());
+        record.extend(fields);
+
+        let result = serde_json::to_writer(&mut self.writer, &record)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        if let Err(e) = result {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl ArtifactRecorder {
+    /// Creates a new [`ArtifactRecorder`] writing to the given writer.
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(RecorderState {
+                writer,
+                origin: None,
+                reason: None,
+                error: None,
+            })),
+        }
+    }
+
+    /// Sets the L1 origin that subsequently recorded verdicts and attributes are attributed to.
+    pub fn set_origin(&self, origin: Option<BlockInfo>) {
+        self.lock().origin = origin.map(|o| o.number);
+    }
+
+    /// Records a frame loaded by the frame queue.
+    pub fn record_frame(&self, origin: Option<BlockInfo>, frame: &Frame) {
+        let fields = json!({
+            "channel_id": hex::encode_prefixed(frame.id),
+            "frame_number": frame.number,
+            "is_last": frame.is_last,
+            "data": hex::encode_prefixed(&frame.data),
+        });
+        self.write("frame", origin, fields);
+    }
+
+    /// Records the compressed data of a channel that is ready for decompression.
+    pub fn record_channel(&self, origin: Option<BlockInfo>, data: &[u8]) {
+        let fields = json!({
+            "size": data.len(),
+            "data": hex::encode_prefixed(data),
+        });
+        self.write("channel", origin, fields);
+    }
+
+    /// Records a batch decoded from a channel.
+    pub fn record_batch(&self, origin: Option<BlockInfo>, batch: &Batch) {
+        let fields = match batch {
+            Batch::Single(single) => json!({
+                "batch_type": "single",
+                "parent_hash": single.parent_hash,
+                "epoch_num": single.epoch_num,
+                "epoch_hash": single.epoch_hash,
+                "timestamp": single.timestamp,
+                "transactions": single.transactions,
+            }),
+            Batch::Span(span) => json!({
+                "batch_type": "span",
+                "parent_check": span.parent_check,
+                "l1_origin_check": span.l1_origin_check,
+                "starting_timestamp": span.starting_timestamp(),
+                "final_timestamp": span.final_timestamp(),
+                "elements": span.batches.iter().map(|element| json!({
+                    "epoch_num": element.epoch_num,
+                    "timestamp": element.timestamp,
+                    "transactions": element.transactions,
+                })).collect::<Vec<_>>(),
+            }),
+        };
+        self.write("batch", origin, fields);
+    }
+
+    /// Records a set of derived payload attributes.
+    pub fn record_attributes(&self, attributes: &OpAttributesWithParent) -> anyhow::Result<()> {
+        let mut fields = into_map(serde_json::to_value(attributes)?);
+        fields.insert("block_number".into(), attributes.block_number().into());
+        let mut state = self.lock();
+        let origin = state.origin;
+        state.write("attributes", origin, fields);
+        Ok(())
+    }
+
+    /// Flushes the writer, returning the first error hit while recording.
+    pub fn finish(&self) -> std::io::Result<()> {
+        let mut state = self.lock();
+        if let Some(e) = state.error.take() {
+            return Err(e);
+        }
+        state.writer.flush()
+    }
+
+    fn write(&self, kind: &str, origin: Option<BlockInfo>, fields: Value) {
+        self.lock().write(kind, origin.map(|o| o.number), into_map(fields));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RecorderState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ArtifactRecorder {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut state = self.lock();
+        if REASON_TARGETS.contains(&metadata.target()) {
+            state.reason = visitor.message;
+            return;
+        }
+        if !visitor.fields.contains_key("validity") {
+            return;
+        }
+
+        let reason = state.reason.take();
+        let accepted = visitor.fields.get("validity").is_some_and(|v| v == "Accept");
+        let mut fields = visitor.fields;
+        fields.insert("stage".into(), metadata.target().into());
+        fields.insert("reason".into(), reason.filter(|_| !accepted).into());
+        let origin = state.origin;
+        state.write("verdict", origin, fields);
+    }
+}
+
+/// Unwraps a JSON object into its fields.
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    }
+}
+
+/// Collects the fields of an event into a JSON object.
+#[derive(Debug, Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        let value = format!("{value:?}");
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.fields.insert(field.name().into(), value.into());
+        }
+    }
+}
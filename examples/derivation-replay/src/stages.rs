@@ -0,0 +1,83 @@
+//! A pass-through stage that records the artifacts handed from one pipeline stage to the next.
+
+use crate::recorder::ArtifactRecorder;
+use alloy_primitives::Bytes;
+use async_trait::async_trait;
+use kona_derive::{
+    BatchStreamProvider, ChannelReaderProvider, NextFrameProvider, OriginAdvancer, OriginProvider,
+    PipelineResult, Signal, SignalReceiver,
+};
+use kona_protocol::{Batch, BlockInfo, Frame};
+
+/// Wraps a pipeline stage, recording everything the next stage pulls out of it.
+///
+/// The wrapper is slotted between the `FrameQueue` and the `ChannelProvider` to record frames,
+/// between the `ChannelProvider` and the `ChannelReader` to record channels, and between the
+/// `ChannelReader` and the `BatchStream` to record batches.
+#[derive(Debug)]
+pub struct Recorded<P> {
+    /// The wrapped stage.
+    pub inner: P,
+    /// The recorder the artifacts are written to.
+    pub recorder: ArtifactRecorder,
+}
+
+impl<P> Recorded<P> {
+    /// Wraps the given stage.
+    pub const fn new(inner: P, recorder: ArtifactRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+#[async_trait]
+impl<P: NextFrameProvider + OriginProvider + Send> NextFrameProvider for Recorded<P> {
+    async fn next_frame(&mut self) -> PipelineResult<Frame> {
+        let frame = self.inner.next_frame().await?;
+        self.recorder.record_frame(self.inner.origin(), &frame);
+        Ok(frame)
+    }
+}
+
+#[async_trait]
+impl<P: ChannelReaderProvider + OriginProvider + Send> ChannelReaderProvider for Recorded<P> {
+    async fn next_data(&mut self) -> PipelineResult<Option<Bytes>> {
+        let data = self.inner.next_data().await?;
+        if let Some(data) = data.as_ref() {
+            self.recorder.record_channel(self.inner.origin(), data);
+        }
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl<P: BatchStreamProvider + OriginProvider + Send> BatchStreamProvider for Recorded<P> {
+    async fn next_batch(&mut self) -> PipelineResult<Batch> {
+        let batch = self.inner.next_batch().await?;
+        self.recorder.record_batch(self.inner.origin(), &batch);
+        Ok(batch)
+    }
+
+    fn flush(&mut self) {
+        self.inner.flush()
+    }
+}
+
+impl<P: OriginProvider> OriginProvider for Recorded<P> {
+    fn origin(&self) -> Option<BlockInfo> {
+        self.inner.origin()
+    }
+}
+
+#[async_trait]
+impl<P: OriginAdvancer + Send> OriginAdvancer for Recorded<P> {
+    async fn advance_origin(&mut self) -> PipelineResult<()> {
+        self.inner.advance_origin().await
+    }
+}
+
+#[async_trait]
+impl<P: SignalReceiver + Send> SignalReceiver for Recorded<P> {
+    async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+        self.inner.signal(signal).await
+    }
+}
@@ -13,13 +13,16 @@
 //! - `-v` or `--verbosity`: Verbosity level (0-2)
 //! - `-r` or `--l2-rpc`: The L2 execution layer RPC URL to use. Must be archival.
 //! - `-b` or `--block-number`: L2 block number to execute for the fixture.
+//! - `-e` or `--end-block`: (Optional) The last L2 block number to execute. If provided, a corpus
+//!   of the consecutive blocks `block-number..=end-block` is created instead of a single-block
+//!   fixture, deduplicating the trie nodes shared between the blocks.
 //! - `-o` or `--output-dir`: (Optional) The output directory for the fixture. If not provided,
-//!   defaults to `kona-executor`'s `testdata` directory.
+//!   defaults to `kona-executor`'s `testdata` directory, or `testdata/corpus` for a corpus.
 
 use anyhow::{Result, anyhow};
 use clap::Parser;
 use kona_cli::{LogArgs, LogConfig};
-use kona_executor::test_utils::ExecutorTestFixtureCreator;
+use kona_executor::test_utils::{ExecutorCorpusCreator, ExecutorTestFixtureCreator};
 use std::path::PathBuf;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
@@ -37,6 +40,9 @@ pub struct ExecutionFixtureCommand {
     /// L2 block number to execute.
     #[arg(long, short = 'b')]
     pub block_number: u64,
+    /// The last L2 block number to execute, creating a multi-block corpus.
+    #[arg(long, short = 'e')]
+    pub end_block: Option<u64>,
     /// The output directory for the fixture.
     #[arg(long, short = 'o')]
     pub output_dir: Option<PathBuf>,
@@ -47,6 +53,7 @@ async fn main() -> Result<()> {
     let cli = ExecutionFixtureCommand::parse();
     LogConfig::new(cli.v).init_tracing_subscriber(None::<EnvFilter>)?;
 
+    let custom_output_dir = cli.output_dir.is_some();
     let output_dir = if let Some(output_dir) = cli.output_dir {
         output_dir
     } else {
@@ -65,6 +72,22 @@ async fn main() -> Result<()> {
             .join("crates/proof/executor/testdata")
     };
 
+    if let Some(end_block) = cli.end_block {
+        if end_block < cli.block_number {
+            return Err(anyhow!("End block must not be before the start block"));
+        }
+
+        // Corpora live next to, but separate from, the single-block fixtures.
+        let output_dir = if custom_output_dir { output_dir } else { output_dir.join("corpus") };
+        std::fs::create_dir_all(&output_dir)?;
+        ExecutorCorpusCreator::new(cli.l2_rpc.as_str(), cli.block_number, end_block, output_dir)
+            .create_static_corpus()
+            .await;
+
+        info!(target: "execution_fixture", start_block = cli.block_number, end_block, "Successfully created static test corpus");
+        return Ok(());
+    }
+
     ExecutorTestFixtureCreator::new(cli.l2_rpc.as_str(), cli.block_number, output_dir)
         .create_static_fixture()
         .await;
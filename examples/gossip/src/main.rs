@@ -99,6 +99,7 @@ impl GossipCommand {
                 topic_scoring: Default::default(),
                 monitor_peers: Default::default(),
                 bootstore: None,
+                peer_db: None,
                 gater_config: Default::default(),
                 bootnodes: Default::default(),
                 rollup_config: rollup_config.clone(),